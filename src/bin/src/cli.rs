@@ -0,0 +1,129 @@
+//! # Command Line
+//!
+//! Flags launching the application into a given scenario. They override the
+//! `gol.toml` configuration file for the current run.
+
+use bevy::prelude::World;
+use clap::Parser;
+use gol_config::{DisplayConfig, MAX_PERIOD, MIN_PERIOD, SimulationConfig, StartupPattern};
+use gol_simulation::{RuleSet, SimRng};
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+/// Conway's Game of Life
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Cli {
+    /// Content of the grid at startup: `empty`, `soup:<seed>`,
+    /// `file:<path>` or the name of a built-in pattern
+    #[arg(long, value_name = "PATTERN", value_parser = parse_start, conflicts_with = "pattern")]
+    pub start: Option<StartupPattern>,
+    /// Pattern file loaded at startup, in any supported format
+    #[arg(long, value_name = "FILE")]
+    pub pattern: Option<PathBuf>,
+    /// Rule of the simulation, such as B3/S23
+    #[arg(long, value_parser = parse_rule)]
+    pub rule: Option<String>,
+    /// Speed of the simulation, in generations per second
+    #[arg(long, value_name = "GPS", value_parser = parse_speed)]
+    pub speed: Option<f32>,
+    /// Starts with the simulation paused
+    #[arg(long)]
+    pub paused: bool,
+    /// Seed of the random soups and of the noise
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Size of the window, in logical pixels
+    #[arg(long, value_name = "WxH", value_parser = parse_window_size)]
+    pub window_size: Option<(u32, u32)>,
+    /// Runs the simulation without any window and writes the final board
+    #[arg(long)]
+    pub headless: bool,
+    /// Number of generations computed in headless mode
+    #[arg(long, value_name = "N", default_value_t = 100, requires = "headless")]
+    pub generations: u64,
+    /// RLE file receiving the final board in headless mode, the standard
+    /// output by default
+    #[arg(long, value_name = "FILE", requires = "headless")]
+    pub out: Option<PathBuf>,
+    /// Runs the simulation without any window and streams it over
+    /// WebSocket on the given port, taking commands from the clients
+    #[arg(long, value_name = "PORT", conflicts_with = "headless")]
+    pub serve: Option<u16>,
+    /// Address the server listens on; anyone reaching it can control the
+    /// simulation
+    #[arg(
+        long,
+        value_name = "ADDR",
+        default_value = "127.0.0.1",
+        requires = "serve"
+    )]
+    pub bind: IpAddr,
+}
+
+impl Cli {
+    /// Startup pattern given by `--start` or `--pattern`, if any
+    pub fn startup_pattern(&self) -> Option<StartupPattern> {
+        self.pattern
+            .clone()
+            .map(StartupPattern::File)
+            .or_else(|| self.start.clone())
+    }
+
+    /// Applies the flags to the configuration resources
+    pub fn apply(&self, world: &mut World) {
+        self.apply_simulation(&mut world.resource_mut::<SimulationConfig>());
+        if let Some(seed) = self.seed {
+            world.resource_mut::<DisplayConfig>().random_seed = seed;
+            world.resource_mut::<SimRng>().reseed(seed);
+        }
+    }
+
+    /// Applies the flags to the simulation configuration
+    pub fn apply_simulation(&self, config: &mut SimulationConfig) {
+        if let Some(pattern) = self.startup_pattern() {
+            config.startup_pattern = pattern;
+        }
+        if let Some(rule) = &self.rule {
+            config.rule = rule.clone();
+        }
+        if let Some(speed) = self.speed {
+            config.set_generations_per_second(speed);
+        }
+        if self.paused {
+            config.running = false;
+        }
+    }
+}
+
+fn parse_start(spec: &str) -> Result<StartupPattern, String> {
+    StartupPattern::parse(spec).map_err(|e| e.to_string())
+}
+
+fn parse_rule(rule: &str) -> Result<String, String> {
+    RuleSet::parse(rule)
+        .map(|_| rule.to_string())
+        .map_err(|e| e.to_string())
+}
+
+fn parse_speed(speed: &str) -> Result<f32, String> {
+    let speed: f32 = speed
+        .parse()
+        .map_err(|_| format!("not a number: {speed}"))?;
+    let (min, max) = (1.0 / MAX_PERIOD, 1.0 / MIN_PERIOD);
+    if !(min..=max).contains(&speed) {
+        return Err(format!("must be between {min:.2} and {max}"));
+    }
+    Ok(speed)
+}
+
+fn parse_window_size(size: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("expected WIDTHxHEIGHT, such as 1280x720, got {size}");
+    let (width, height) = size.split_once(['x', 'X']).ok_or_else(invalid)?;
+    let width = width.trim().parse().map_err(|_| invalid())?;
+    let height = height.trim().parse().map_err(|_| invalid())?;
+    if width == 0 || height == 0 {
+        return Err(invalid());
+    }
+    Ok((width, height))
+}
@@ -0,0 +1,102 @@
+//! # Headless Mode
+//!
+//! Runs the simulation without any window or rendering, for batch
+//! experiments and regression tests on patterns, then writes the final
+//! board as RLE.
+//!
+//! The configuration file and the command line flags are honored, the
+//! board always being advanced one generation at a time as by the sparse
+//! backend.
+
+use crate::cli::Cli;
+use gol_config::{CONFIG_FILE, ConfigFile, SimulationConfig};
+use gol_core::{Board, Cell, SplitMix64, Step, encode_rle};
+use gol_simulation::{DEFAULT_SEED, PatternRegistry, RuleSet, startup_cells};
+use std::path::Path;
+
+/// Simulation stepped without any window, by headless mode and by the
+/// streaming server
+pub struct HeadlessSimulation {
+    pub config: SimulationConfig,
+    pub rule: RuleSet,
+    pub board: Board,
+    /// Generation reached by the board
+    pub generation: u64,
+    rng: SplitMix64,
+}
+
+impl HeadlessSimulation {
+    /// Startup board of the configuration file and the command line flags
+    pub fn new(cli: &Cli) -> Result<Self, String> {
+        let mut config = ConfigFile::load(Path::new(CONFIG_FILE))
+            .map_err(|e| e.to_string())?
+            .simulation;
+        cli.apply_simulation(&mut config);
+        let rule = RuleSet::parse(&config.rule)
+            .map_err(|e| format!("Invalid rule {}: {e}", config.rule))?;
+        let cells = startup_cells(&config.startup_pattern, &PatternRegistry::default())
+            .map_err(|e| e.to_string())?;
+        let mut simulation = Self {
+            config,
+            rule,
+            board: Board::new(),
+            generation: 0,
+            rng: SplitMix64::new(cli.seed.unwrap_or(DEFAULT_SEED)),
+        };
+        simulation.replace(&cells);
+        Ok(simulation)
+    }
+
+    /// Replaces the board with the given living cells, back at generation 0
+    pub fn replace(&mut self, cells: &[(i32, i32)]) {
+        self.board.clear();
+        for &(x, y) in cells {
+            self.board.set(Cell::new(x as isize, y as isize), true);
+        }
+        self.generation = 0;
+    }
+
+    /// Advances the board by one generation, returning its changes
+    pub fn step(&mut self) -> Step {
+        let step = self
+            .board
+            .compute_step_in(&self.rule, &self.config.topology);
+        let step = self
+            .config
+            .noise
+            .perturb(&self.board, step, self.rng.next_u64());
+        self.board.apply(&step);
+        self.generation += 1;
+        step
+    }
+
+    /// Living cells of the board
+    pub fn cells(&self) -> Vec<(i32, i32)> {
+        self.board
+            .iter()
+            .map(|cell| (cell.x as i32, cell.y as i32))
+            .collect()
+    }
+}
+
+/// Advances the startup pattern by the requested number of generations and
+/// writes the result
+pub fn run(cli: &Cli) -> Result<(), String> {
+    let mut simulation = HeadlessSimulation::new(cli)?;
+    for _ in 0..cli.generations {
+        simulation.step();
+    }
+
+    let rle = encode_rle(&simulation.cells(), Some(&simulation.config.rule));
+    match &cli.out {
+        Some(path) => std::fs::write(path, rle)
+            .map_err(|e| format!("Could not write {}: {e}", path.display()))?,
+        None => print!("{rle}"),
+    }
+    eprintln!(
+        "Generation {}: {} cells",
+        simulation.generation,
+        simulation.board.len()
+    );
+    Ok(())
+}
@@ -2,8 +2,31 @@
 //!
 //! This is the entry point for the Conway's Game of Life application.
 //! It sets up a Bevy app with the necessary plugins for simulation and GUI.
+//!
+//! The command line flags, listed by `--help`, launch the application into
+//! a given scenario: startup pattern, rule, speed, seed and window size.
+//! Without them, the `gol.toml` configuration file is used.
+//!
+//! In the browser, the query parameters of the page play the part of the
+//! flags, so links can open into a given pattern.
+//!
+//! With `--headless`, the simulation runs without any window for
+//! `--generations` generations and the final board is written as RLE. With
+//! `--serve <port>`, it runs without any window too and is streamed over
+//! WebSocket.
+
+mod cli;
+mod headless;
+#[cfg(not(target_arch = "wasm32"))]
+mod serve;
+#[cfg(target_arch = "wasm32")]
+mod web;
 
 use bevy::prelude::{App, DefaultPlugins, PluginGroup, Window, WindowPlugin};
+use clap::Parser;
+use cli::Cli;
+#[cfg(target_arch = "wasm32")]
+use gol_config::SimulationConfig;
 use gol_config::{ColorPlugin, ConfigPlugin};
 use gol_rendering::RenderingPlugin;
 use gol_simulation::SimulationPlugin;
@@ -16,19 +39,54 @@ use gol_utils::UtilsPlugin;
 /// - Default Bevy plugins for rendering and input
 /// - Custom window configuration suitable for web and desktop
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                title: "Conway's Game of Life".into(),
-                fit_canvas_to_parent: true,
-                ..Default::default()
-            }),
-            ..Default::default()
-        }))
-        .add_plugins(ConfigPlugin)
-        .add_plugins(ColorPlugin)
-        .add_plugins(SimulationPlugin)
-        .add_plugins(RenderingPlugin)
+    // The simulation worker loads this module too, without the application
+    #[cfg(target_arch = "wasm32")]
+    if gol_simulation::is_worker() {
+        gol_simulation::run_worker();
+        return;
+    }
+
+    let cli = Cli::parse();
+    if cli.headless {
+        if let Err(e) = headless::run(&cli) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(port) = cli.serve {
+        if let Err(e) = serve::run(&cli, port) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut window = Window {
+        title: "Conway's Game of Life".into(),
+        fit_canvas_to_parent: true,
+        ..Default::default()
+    };
+    if let Some(size) = cli.window_size {
+        window.resolution = size.into();
+    }
+
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(window),
+        ..Default::default()
+    }))
+    .add_plugins(ConfigPlugin)
+    .add_plugins(ColorPlugin)
+    .add_plugins(SimulationPlugin);
+
+    // Overrides the configuration file before the interface reads it
+    cli.apply(app.world_mut());
+    #[cfg(target_arch = "wasm32")]
+    web::apply_query_parameters(&mut app.world_mut().resource_mut::<SimulationConfig>());
+
+    app.add_plugins(RenderingPlugin)
         .add_plugins(UiPlugin)
         .add_plugins(UtilsPlugin)
         .run();
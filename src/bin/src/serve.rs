@@ -0,0 +1,298 @@
+//! # Streaming Server
+//!
+//! With `--serve <port>`, the headless simulation is shared over WebSocket
+//! for remote viewers and web dashboards. Every message is a JSON text.
+//!
+//! A client first receives the whole board, then the changes of each
+//! generation:
+//!
+//! ```json
+//! {"type":"board","generation":0,"rule":"B3/S23","cells":[[0,1],[1,1]]}
+//! {"type":"step","generation":1,"born":[[1,2]],"died":[[0,1]],"population":2}
+//! ```
+//!
+//! and may send commands, whose effects are broadcast to every client:
+//!
+//! ```json
+//! {"command":"load","pattern":"bo$2bo$3o!"}
+//! {"command":"step","generations":10}
+//! {"command":"rule","rule":"B36/S23"}
+//! {"command":"play"}
+//! {"command":"pause"}
+//! {"command":"speed","generations_per_second":20}
+//! ```
+//!
+//! Invalid commands are answered with `{"type":"error","message":"..."}`.
+//!
+//! Clients are not authenticated, so the server only listens on the loopback
+//! interface unless another address is given with `--bind`.
+
+use crate::cli::Cli;
+use crate::headless::HeadlessSimulation;
+use gol_config::{MAX_PERIOD, MIN_PERIOD};
+use gol_core::{Cell, MAX_PATTERN_CELLS, parse_pattern};
+use gol_simulation::RuleSet;
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+use tungstenite::{Message, WebSocket};
+
+/// Longest wait for new clients and commands between two generations
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+/// Most generations requested by a single step command
+const MAX_STEP_GENERATIONS: u64 = 10_000;
+/// Longest wait for each read and write of a client's handshake, so a silent
+/// connection cannot hold a thread for long
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Command sent by a client
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+enum Command {
+    /// Replaces the board with a pattern in any supported format
+    Load {
+        pattern: String,
+    },
+    /// Computes generations at once, the simulation running or not
+    Step {
+        #[serde(default = "one")]
+        generations: u64,
+    },
+    /// Changes the rule
+    Rule {
+        rule: String,
+    },
+    Play,
+    Pause,
+    /// Changes the running speed
+    Speed {
+        generations_per_second: f32,
+    },
+}
+
+fn one() -> u64 {
+    1
+}
+
+/// Message sent to the clients
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum Update {
+    /// Whole board, sent on connection and when it or the rule is replaced
+    Board {
+        generation: u64,
+        rule: String,
+        cells: Vec<(i32, i32)>,
+    },
+    /// Changes of one generation
+    Step {
+        generation: u64,
+        born: Vec<(isize, isize)>,
+        died: Vec<(isize, isize)>,
+        population: usize,
+    },
+    /// A command could not be applied
+    Error { message: String },
+}
+
+/// Runs the simulation and serves it on `port` until the process is
+/// stopped
+pub fn run(cli: &Cli, port: u16) -> Result<(), String> {
+    let mut simulation = HeadlessSimulation::new(cli)?;
+    let listener = TcpListener::bind((cli.bind, port))
+        .map_err(|e| format!("Could not listen on {}:{port}: {e}", cli.bind))?;
+    eprintln!(
+        "Serving the simulation on ws://{}",
+        listener.local_addr().map_err(|e| e.to_string())?
+    );
+    let connections = accept_clients(listener);
+
+    let mut clients: Vec<WebSocket<TcpStream>> = Vec::new();
+    let mut next_generation = Instant::now();
+    loop {
+        for mut client in connections.try_iter() {
+            send(&mut client, &board_update(&simulation));
+            clients.push(client);
+        }
+
+        let mut commands = Vec::new();
+        clients.retain_mut(|client| {
+            loop {
+                match client.read() {
+                    Ok(Message::Text(text)) => commands.push(serde_json::from_str(&text)),
+                    Ok(Message::Close(_)) => return false,
+                    Ok(_) => {}
+                    Err(tungstenite::Error::Io(e)) if e.kind() == ErrorKind::WouldBlock => {
+                        return true;
+                    }
+                    Err(_) => return false,
+                }
+            }
+        });
+        for command in commands {
+            let updates = match command {
+                Ok(command) => apply(&mut simulation, command),
+                Err(e) => vec![Update::Error {
+                    message: e.to_string(),
+                }],
+            };
+            broadcast(&mut clients, &updates);
+        }
+
+        let now = Instant::now();
+        if simulation.config.running && now >= next_generation {
+            next_generation = now + simulation.config.period;
+            let update = step(&mut simulation);
+            broadcast(&mut clients, &[update]);
+        }
+        std::thread::sleep(if simulation.config.running {
+            next_generation
+                .saturating_duration_since(Instant::now())
+                .min(POLL_INTERVAL)
+        } else {
+            POLL_INTERVAL
+        });
+    }
+}
+
+/// Accepts new clients in the background, each handshake on a thread of its
+/// own so that neither the generations nor the other clients wait for it,
+/// and hands them over once connected
+fn accept_clients(listener: TcpListener) -> Receiver<WebSocket<TcpStream>> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                let address = stream
+                    .peer_addr()
+                    .map_or_else(|_| "A client".to_string(), |address| address.to_string());
+                match accept(stream) {
+                    Ok(client) => {
+                        eprintln!("{address} connected");
+                        // Fails only once the server has stopped
+                        let _ = sender.send(client);
+                    }
+                    Err(e) => eprintln!("{address} could not connect: {e}"),
+                }
+            });
+        }
+    });
+    receiver
+}
+
+/// Completes the WebSocket handshake of a new client, within
+/// [`HANDSHAKE_TIMEOUT`], whose messages are then read without blocking
+fn accept(stream: TcpStream) -> Result<WebSocket<TcpStream>, String> {
+    stream
+        .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    stream
+        .set_write_timeout(Some(HANDSHAKE_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    let client = tungstenite::accept(stream).map_err(|e| e.to_string())?;
+    client
+        .get_ref()
+        .set_nonblocking(true)
+        .map_err(|e| e.to_string())?;
+    Ok(client)
+}
+
+/// Applies a command, returning the updates to broadcast
+fn apply(simulation: &mut HeadlessSimulation, command: Command) -> Vec<Update> {
+    let error = |message: String| vec![Update::Error { message }];
+    match command {
+        Command::Load { pattern } => match parse_pattern(&pattern) {
+            // The RLE and macrocell parsers cap their own output
+            Ok(pattern) if pattern.cells.len() > MAX_PATTERN_CELLS => error(format!(
+                "Invalid pattern: more than {MAX_PATTERN_CELLS} living cells"
+            )),
+            Ok(pattern) => {
+                let rule = pattern.rule.map(|rule| set_rule(simulation, rule));
+                if let Some(Err(e)) = rule {
+                    return error(e);
+                }
+                simulation.replace(&pattern.cells);
+                vec![board_update(simulation)]
+            }
+            Err(e) => error(format!("Invalid pattern: {e}")),
+        },
+        Command::Step { generations } => (0..generations.min(MAX_STEP_GENERATIONS))
+            .map(|_| step(simulation))
+            .collect(),
+        Command::Rule { rule } => {
+            set_rule(simulation, rule).map_or_else(error, |()| vec![board_update(simulation)])
+        }
+        Command::Play => {
+            simulation.config.running = true;
+            vec![]
+        }
+        Command::Pause => {
+            simulation.config.running = false;
+            vec![]
+        }
+        Command::Speed {
+            generations_per_second,
+        } => {
+            let (min, max) = (1.0 / MAX_PERIOD, 1.0 / MIN_PERIOD);
+            if (min..=max).contains(&generations_per_second) {
+                simulation
+                    .config
+                    .set_generations_per_second(generations_per_second);
+                vec![]
+            } else {
+                error(format!("Speed must be between {min:.2} and {max}"))
+            }
+        }
+    }
+}
+
+/// Sets the rule of the simulation, unless the rulestring is invalid
+fn set_rule(simulation: &mut HeadlessSimulation, rule: String) -> Result<(), String> {
+    simulation.rule = RuleSet::parse(&rule).map_err(|e| format!("Invalid rule {rule}: {e}"))?;
+    simulation.config.rule = rule;
+    Ok(())
+}
+
+/// Advances the simulation by one generation
+fn step(simulation: &mut HeadlessSimulation) -> Update {
+    let step = simulation.step();
+    let coordinates = |cells: Vec<Cell>| cells.into_iter().map(|cell| (cell.x, cell.y)).collect();
+    Update::Step {
+        generation: simulation.generation,
+        born: coordinates(step.born),
+        died: coordinates(step.died),
+        population: simulation.board.len(),
+    }
+}
+
+/// Whole board of the simulation
+fn board_update(simulation: &HeadlessSimulation) -> Update {
+    Update::Board {
+        generation: simulation.generation,
+        rule: simulation.config.rule.clone(),
+        cells: simulation.cells(),
+    }
+}
+
+/// Sends an update to a client, returning whether it is still connected.
+///
+/// Updates a slow client cannot take yet stay buffered until the next one.
+fn send(client: &mut WebSocket<TcpStream>, update: &Update) -> bool {
+    let Ok(text) = serde_json::to_string(update) else {
+        return true;
+    };
+    match client.send(Message::text(text)) {
+        Ok(()) => true,
+        Err(tungstenite::Error::Io(e)) if e.kind() == ErrorKind::WouldBlock => true,
+        Err(_) => false,
+    }
+}
+
+/// Sends updates to every client, forgetting the disconnected ones
+fn broadcast(clients: &mut Vec<WebSocket<TcpStream>>, updates: &[Update]) {
+    clients.retain_mut(|client| updates.iter().all(|update| send(client, update)));
+}
@@ -0,0 +1,225 @@
+//! # Conway's Game of Life - Terminal Front-End
+//!
+//! Runs the simulation in a terminal, for servers and SSH sessions. Each
+//! character shows two cells stacked vertically with half block characters,
+//! or more of them once zoomed out.
+//!
+//! Built with the `tui` feature: `cargo run --features tui --bin gol-tui`.
+//!
+//! Keys: `space` pauses, `n` steps while paused, arrows or `hjkl` pan,
+//! `+`/`-` zoom, `[`/`]` change the speed, `c` centers the pattern and `q`
+//! quits.
+
+use clap::Parser;
+use gol_config::{CONWAY_RULE, MAX_PERIOD, MIN_PERIOD, StartupPattern};
+use gol_core::{Board, Cell};
+use gol_simulation::{PatternRegistry, RuleSet, startup_cells};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::{DefaultTerminal, Frame};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Largest number of cells per character column
+const MAX_ZOOM: isize = 64;
+
+/// Conway's Game of Life in the terminal
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// Content of the grid at startup: `empty`, `soup:<seed>`,
+    /// `file:<path>` or the name of a built-in pattern
+    #[arg(long, value_name = "PATTERN", default_value = "r-pentomino", value_parser = parse_start)]
+    start: StartupPattern,
+    /// Pattern file loaded at startup, in any supported format
+    #[arg(long, value_name = "FILE", conflicts_with = "start")]
+    pattern: Option<PathBuf>,
+    /// Rule of the simulation, such as B3/S23
+    #[arg(long, default_value = CONWAY_RULE, value_parser = parse_rule)]
+    rule: RuleSet,
+    /// Speed of the simulation, in generations per second
+    #[arg(long, value_name = "GPS", default_value_t = 10.0, value_parser = parse_speed)]
+    speed: f32,
+    /// Starts with the simulation paused
+    #[arg(long)]
+    paused: bool,
+}
+
+fn parse_start(spec: &str) -> Result<StartupPattern, String> {
+    StartupPattern::parse(spec).map_err(|e| e.to_string())
+}
+
+fn parse_rule(rule: &str) -> Result<RuleSet, String> {
+    RuleSet::parse(rule).map_err(|e| e.to_string())
+}
+
+fn parse_speed(speed: &str) -> Result<f32, String> {
+    match speed.parse() {
+        Ok(speed) if speed > 0.0 => Ok(speed),
+        _ => Err(format!("not a positive number: {speed}")),
+    }
+}
+
+/// State of the terminal front-end
+struct Tui {
+    board: Board,
+    rule: RuleSet,
+    generation: u64,
+    running: bool,
+    period: Duration,
+    /// Board cell shown at the center of the terminal
+    center: (isize, isize),
+    /// Cells per character column, a character row covering twice as many
+    zoom: isize,
+}
+
+impl Tui {
+    fn new(args: Args) -> Result<Self, String> {
+        let pattern = args.pattern.map(StartupPattern::File).unwrap_or(args.start);
+        let cells =
+            startup_cells(&pattern, &PatternRegistry::default()).map_err(|e| e.to_string())?;
+        let mut board = Board::new();
+        for (x, y) in cells {
+            board.set(Cell::new(x as isize, y as isize), true);
+        }
+        let mut tui = Self {
+            board,
+            rule: args.rule,
+            generation: 0,
+            running: !args.paused,
+            // Clamped before the conversion, which panics on huge periods
+            period: Duration::from_secs_f32((1.0 / args.speed).clamp(MIN_PERIOD, MAX_PERIOD)),
+            center: (0, 0),
+            zoom: 1,
+        };
+        tui.center_on_pattern();
+        Ok(tui)
+    }
+
+    fn step(&mut self) {
+        self.board.advance(&self.rule);
+        self.generation += 1;
+    }
+
+    fn center_on_pattern(&mut self) {
+        if let Some((min, max)) = self.board.bounding_box() {
+            self.center = ((min.x + max.x) / 2, (min.y + max.y) / 2);
+        }
+    }
+
+    /// Handles a key press, returning false to quit
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        let pan = 8 * self.zoom;
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char(' ') => self.running = !self.running,
+            KeyCode::Char('n') if !self.running => self.step(),
+            KeyCode::Left | KeyCode::Char('h') => self.center.0 -= pan,
+            KeyCode::Right | KeyCode::Char('l') => self.center.0 += pan,
+            KeyCode::Up | KeyCode::Char('k') => self.center.1 -= pan,
+            KeyCode::Down | KeyCode::Char('j') => self.center.1 += pan,
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom = (self.zoom / 2).max(1),
+            KeyCode::Char('-') => self.zoom = (self.zoom * 2).min(MAX_ZOOM),
+            KeyCode::Char('[') => {
+                self.period = (self.period * 2).min(Duration::from_secs_f32(MAX_PERIOD));
+            }
+            KeyCode::Char(']') => {
+                self.period = (self.period / 2).max(Duration::from_secs_f32(MIN_PERIOD));
+            }
+            KeyCode::Char('c') => self.center_on_pattern(),
+            _ => {}
+        }
+        true
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [board_area, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        frame.render_widget(Paragraph::new(self.board_lines(board_area)), board_area);
+        let status = format!(
+            " Generation {} | {} cells | {:.1} gen/s | 1:{} | {}",
+            self.generation,
+            self.board.len(),
+            1.0 / self.period.as_secs_f32(),
+            self.zoom,
+            if self.running { "running" } else { "paused" },
+        );
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+
+    /// Lines of half block characters showing the board in `area`
+    fn board_lines(&self, area: Rect) -> Vec<Line<'static>> {
+        let (width, height) = (area.width as isize, area.height as isize);
+        let left = self.center.0 - width * self.zoom / 2;
+        let top = self.center.1 - height * self.zoom;
+        // Upper and lower halves of each character, as two bits
+        let mut halves = vec![0u8; (width * height) as usize];
+        for cell in self.board.iter() {
+            let column = (cell.x - left).div_euclid(self.zoom);
+            let half_row = (cell.y - top).div_euclid(self.zoom);
+            if !(0..width).contains(&column) || !(0..height * 2).contains(&half_row) {
+                continue;
+            }
+            halves[(half_row / 2 * width + column) as usize] |= 1 << (half_row % 2);
+        }
+        halves
+            .chunks(width.max(1) as usize)
+            .map(|row| {
+                row.iter()
+                    .map(|half| match half {
+                        1 => '▀',
+                        2 => '▄',
+                        3 => '█',
+                        _ => ' ',
+                    })
+                    .collect::<String>()
+                    .into()
+            })
+            .collect()
+    }
+}
+
+fn run(mut terminal: DefaultTerminal, mut tui: Tui) -> std::io::Result<()> {
+    let mut last_step = Instant::now();
+    loop {
+        terminal.draw(|frame| tui.draw(frame))?;
+        let timeout = if tui.running {
+            tui.period.saturating_sub(last_step.elapsed())
+        } else {
+            Duration::from_millis(250)
+        };
+        if event::poll(timeout)? {
+            match event::read()? {
+                Event::Key(key)
+                    if key.kind == KeyEventKind::Press && !tui.handle_key(key.code) =>
+                {
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        if tui.running && last_step.elapsed() >= tui.period {
+            tui.step();
+            last_step = Instant::now();
+        }
+    }
+}
+
+fn main() {
+    let tui = match Tui::new(Args::parse()) {
+        Ok(tui) => tui,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
+    let terminal = ratatui::init();
+    let result = run(terminal, tui);
+    ratatui::restore();
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,88 @@
+//! # Web
+//!
+//! Query parameters of the page, launching the web build into a given
+//! scenario the way the flags do on desktop, so a shared link opens into a
+//! pattern:
+//!
+//! - `rle`: the pattern, in any supported format, either url-encoded or
+//!   base64-encoded. Its rule applies unless `rule` is given
+//! - `rule`: rule of the simulation, such as `B3/S23`
+//! - `speed`: speed of the simulation, in generations per second
+//!
+//! Invalid parameters are reported in the console and ignored.
+
+use bevy::prelude::warn;
+use gol_config::{MAX_PERIOD, MIN_PERIOD, SimulationConfig, StartupPattern};
+use gol_core::parse_pattern;
+use gol_simulation::RuleSet;
+use web_sys::UrlSearchParams;
+
+/// Applies the query parameters of the page to the simulation configuration
+pub fn apply_query_parameters(config: &mut SimulationConfig) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Some(parameters) = window
+        .location()
+        .search()
+        .ok()
+        .and_then(|search| UrlSearchParams::new_with_str(&search).ok())
+    else {
+        return;
+    };
+
+    if let Some(value) = parameters.get("rle") {
+        match decode_pattern(&window, &value) {
+            Some((content, rule)) => {
+                if let Some(rule) = rule {
+                    set_rule(config, rule);
+                }
+                config.startup_pattern = StartupPattern::Text(content);
+            }
+            None => warn!("Ignoring the rle parameter, not a supported pattern"),
+        }
+    }
+    if let Some(rule) = parameters.get("rule") {
+        set_rule(config, rule);
+    }
+    if let Some(speed) = parameters.get("speed") {
+        let (min, max) = (1.0 / MAX_PERIOD, 1.0 / MIN_PERIOD);
+        match speed.parse::<f32>() {
+            Ok(speed) if (min..=max).contains(&speed) => config.set_generations_per_second(speed),
+            _ => warn!("Ignoring the speed parameter '{speed}', expected {min:.2} to {max}"),
+        }
+    }
+}
+
+/// Pattern text of a parameter and the rule it declares, the parameter
+/// holding the text itself or its base64 encoding
+fn decode_pattern(window: &web_sys::Window, value: &str) -> Option<(String, Option<String>)> {
+    // Patterns hold `!`, `$`, `.` or newlines, which base64 never does
+    let base64 = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '-' | '_' | '=' | ' '));
+    let content = if base64 {
+        // The query decodes `+` as a space, and url-safe base64 uses `-_`
+        let standard: String = value
+            .chars()
+            .map(|c| match c {
+                ' ' | '-' => '+',
+                '_' => '/',
+                c => c,
+            })
+            .collect();
+        window.atob(&standard).ok()?
+    } else {
+        value.to_string()
+    };
+    let pattern = parse_pattern(&content).ok()?;
+    Some((content, pattern.rule))
+}
+
+/// Sets the rule of the simulation, unless the rulestring is invalid
+fn set_rule(config: &mut SimulationConfig, rule: String) {
+    match RuleSet::parse(&rule) {
+        Ok(_) => config.rule = rule,
+        Err(e) => warn!("Ignoring the rule parameter '{rule}': {e}"),
+    }
+}
@@ -0,0 +1,382 @@
+# English messages of the interface.
+# Each line is `id = text`, `{ $name }` being replaced by a value.
+
+## Common
+common-all-files = All files
+common-cancel = Cancel
+common-close = Close
+common-no = No
+common-yes = Yes
+
+## Control panel
+controls-title = Game of Life
+controls-clear-grid = Clear Grid
+controls-random = Random…
+controls-soup-search = Soup Search…
+controls-analyze = Analyze…
+controls-statistics = Statistics…
+controls-save-slots = Save Slots…
+controls-save-session = Save Session…
+controls-open-session = Open Session…
+controls-store-session = Save in Browser
+controls-restore-session = Load from Browser
+controls-settings = Settings…
+controls-screenshot = Screenshot
+controls-export-image = Export Image…
+controls-export-svg = Export SVG…
+controls-import-image = Import Image…
+controls-record = Record GIF…
+controls-draw = Draw:
+controls-paint = Paint
+controls-erase = Erase
+controls-state = State
+controls-color = Color
+controls-speed = Speed ({ $speed } gen/s)
+controls-camera-distance = Camera Distance
+controls-start = Start
+controls-pause = Pause
+controls-next-generation = Next Generation
+controls-step-back = Step Back
+controls-play-backwards = ◀ Play Backwards
+controls-stop-backwards = Stop
+controls-explain-steps = Explain steps
+controls-explain-steps-hover = Next Generation first shows the cells about to die (red) and be born (green), then applies them
+controls-gens-suffix = { " " }gens
+controls-warp = Warp
+controls-cancel-warp = Cancel Warp
+controls-run-until-stable = Run until stable
+controls-rule = Rule: { $rule }
+controls-rule-preset = Rule: { $rule } ({ $name })
+controls-presets = Presets
+controls-custom = Custom
+controls-apply = Apply
+controls-table = Table…
+controls-neighborhood = Neighborhood
+controls-noise = Noise
+controls-birth-chance = Birth chance
+controls-survival-chance = Survival chance
+controls-deterministic = Deterministic
+controls-backend = Backend
+controls-hashlife-unsupported = HashLife needs a deterministic two-state Moore rule without B0, on the infinite grid
+controls-backend-elementary = Elementary (1D)
+controls-backend-margolus = Margolus (2×2 blocks)
+controls-hashlife-step = Step (2^n gens)
+controls-elementary-rule = Rule{ " " }
+controls-seed = Seed
+controls-block-rule = Block rule
+controls-partition-odd = Partition: odd, blocks shifted by (1, 1)
+controls-partition-even = Partition: even, blocks at (0, 0)
+controls-topology = Topology
+controls-topology-infinite = Infinite
+controls-topology-bounded = Bounded
+controls-topology-torus = Torus
+controls-go-to = Go to:
+controls-go = Go
+controls-help-click = Click on the grid when simulation is paused!
+controls-help-camera = Use arrow keys to move the camera, { $jump } to jump!
+controls-help-drag = Right-drag to erase, Shift-drag to select!
+controls-help-cursor = { $keys } moves the keyboard cursor, { $toggle } toggles a cell!
+controls-help-shortcuts = Press ? or F1 for all the shortcuts!
+
+## Status bar
+status-cell = Cell: { $cell }
+status-zoom = Zoom: { $zoom }%
+status-population = Population: { $population }
+status-generation = Generation: { $generation }
+status-rate = { $rate } gen/s
+timeline-label = Timeline
+timeline-hover = Drag to go back and forth through the recorded generations
+
+## Settings
+settings-title = Settings
+settings-language = Language
+settings-theme = Theme
+settings-ui-scale = Interface scale
+settings-fps-cap = Frame rate cap:
+settings-fps-cap-hover = 0 for no limit. Lower values save battery and GPU time
+settings-reduce-motion = Reduce motion
+settings-reduce-motion-hover = Turns off the interface animations, the death trails and the fading notifications
+theme-classic = Classic
+theme-dark = Dark
+theme-solarized = Solarized
+theme-high-contrast = High contrast
+theme-colorblind = Colorblind safe
+settings-tab-simulation = Simulation
+settings-tab-display = Display
+settings-tab-colors = Colors
+settings-tab-camera = Camera
+settings-tab-keys = Keys
+settings-saved-in = Saved in { $file } on exit
+settings-restore-defaults = Restore defaults
+settings-tick-rate = Tick rate (Hz)
+settings-pause-unfocused = Pause in the background
+settings-pause-unfocused-hover = Pauses while the window is unfocused or minimized, and resumes when it comes back
+settings-history-depth = { " " }generations kept for stepping back
+settings-show-grid = Show Grid
+settings-grid-major = Major lines every
+settings-grid-major-cells = { " " }cells
+settings-grid-major-hover = Thicker lines to count cells, 0 for none
+settings-grid-fade = Fade from
+settings-grid-fade-to = to
+settings-grid-fade-hover = Cell sizes on screen over which the grid fades out when zooming out
+settings-grid-axes = Highlight the axes
+settings-grid-labels = Coordinates on major lines
+settings-show-minimap = Show Minimap
+settings-neighbor-counts = Show neighbor counts
+settings-neighbor-counts-hover = Around the mouse cursor, while paused
+settings-onion-skin = Show previous generation
+settings-onion-skin-hover = Outlines the cells of the previous generation, from the history
+settings-cells = Cells:
+settings-sprites = Sprites
+settings-textures = Textures
+settings-textures-hover = Faster for very large populations
+settings-sound-muted = Mute sounds
+settings-sound-muted-hover = Each generation plays a tone, higher when the population grows and lower when it shrinks
+settings-sound-volume = Volume
+settings-bloom = Glow
+settings-bloom-hover = Living cells glow against the background, best with a dark theme
+settings-bloom-intensity = Glow intensity
+settings-density-view = Density view when zoomed far out
+settings-density-view-hover = Once cells are smaller than a pixel, each pixel shows how many of them are alive
+settings-cell-shape = Cell shape:
+settings-cell-roundness = Corner roundness
+settings-cell-size = Cell size
+settings-cell-size-hover = Below 1, leaves a gap between neighboring cells
+settings-death-trail = Death trail (gens)
+settings-animate-cells = Animate births and deaths
+settings-animate-cells-hover = Cells grow when born and shrink when dying, within a generation
+settings-state-shapes = Tell states apart by shape
+settings-state-shapes-hover = Circles, diamonds and triangles for the states after the first, besides their color
+settings-background = Background:
+settings-grid-color = Grid:
+settings-grid-color-hover = The opacity sets how much the lines show
+settings-state-color = State { $state }:
+settings-state-color-hover = Used by multi-state rules such as QuadLife
+settings-zoom-limits = Zoom limits:
+settings-zoom-closest = Closest:
+settings-zoom-farthest = Farthest:
+settings-keys-help = Click a key, then press the new one. Escape cancels.
+settings-press-key = Press a key…
+settings-key-conflict = Also bound to another action
+settings-arrow-keys = The arrow keys always move the camera as well
+
+## Key bindings
+key-move-left = Move camera left
+key-move-right = Move camera right
+key-move-up = Move camera up
+key-move-down = Move camera down
+key-zoom-in = Zoom in
+key-zoom-out = Zoom out
+key-pause = Start / pause
+key-step = Next generation
+key-step-back = Step back
+key-clear = Clear grid
+key-jump-to = Go to cell
+key-cursor-left = Keyboard cursor left
+key-cursor-right = Keyboard cursor right
+key-cursor-up = Keyboard cursor up
+key-cursor-down = Keyboard cursor down
+key-toggle-cell = Toggle cell under cursor
+key-diagnostics = Show diagnostics
+key-screenshot = Take a screenshot
+
+## Help
+help-title = Keyboard Shortcuts
+help-arrow-keys = Arrow keys
+help-move-camera = Move camera
+help-shift = Shift
+help-move-camera-faster = Move camera faster
+help-left-click = Left click / drag
+help-draw-cells = Draw cells, while paused
+help-right-drag = Right drag
+help-erase-cells = Erase cells
+help-shift-drag = Shift drag
+help-select-cells = Select cells
+help-ctrl-v = Ctrl+V
+help-paste-pattern = Paste a pattern
+help-escape = Escape
+help-close-dialogs = Close dialogs, clear the selection
+help-help-keys = ? / F1
+help-show-help = Show this help
+help-rebind = Shortcuts can be changed in Settings › Keys
+
+## Dialogs
+modal-reset-title = ⚠ Kill all cells!
+modal-reset-question = Are you sure you want to kill all cells?
+modal-random-title = Random Generation
+modal-random-question = Fill the grid with random cells?
+modal-random-centered = Centered on the view
+modal-random-size = Size:{ " " }
+modal-random-cells = { " " }cells
+random-density = density
+random-seed = Seed:{ " " }
+random-new-seed = Pick a new seed
+shape-square = Square
+shape-disc = Disc
+shape-ring = Ring
+navigation-title = Go to cell
+
+## Patterns
+pattern-patterns = Patterns:
+pattern-search = Search
+pattern-load = Load Pattern
+pattern-load-title = Load Pattern
+pattern-click-to-place = Click to place: { $name }
+pattern-paste-prompt = Paste your pattern (RLE, Life 1.05/1.06, plaintext .cells or Golly .mc):
+pattern-paste-hint = Example: bo$2bo$3o!
+pattern-open-file = Open file…
+pattern-empty = Please enter RLE content
+pattern-invalid = Invalid pattern: { $error }
+pattern-unsupported-rule = Unsupported rule '{ $rule }': { $error }
+pattern-open-title = Open pattern
+pattern-filter = Patterns
+
+## Selection
+selection-title = Selection
+selection-size = { $width } × { $height } cells
+selection-none = No selection
+selection-copy = Copy
+selection-cut = Cut
+selection-paste = Paste
+selection-move = Move
+selection-invert = Invert
+selection-clear = Clear
+selection-analyze = Analyze
+selection-clear-outside = Clear outside
+selection-fill-randomly = Fill randomly
+selection-deselect = Deselect
+selection-too-large = Selections of more than { $cells } cells cannot be inverted or filled
+
+## Analysis
+analysis-title = Pattern Analysis
+analysis-running = Running the pattern…
+analysis-whole-pattern = Whole pattern
+analysis-selection = Selection
+analysis-analyze-pattern = Analyze pattern
+analysis-analyze-selection = Analyze selection
+
+## Rule table
+rule-table-title = Rule Table
+rule-table-rule = Rule: { $rule }
+rule-table-neighbors = Neighbors
+rule-table-birth = Birth
+rule-table-survival = Survival
+
+## Images
+image-export-title = Export Image
+image-export-hashlife = Not available with the HashLife backend
+image-export-scale = Cell size:{ " " }
+image-export-save = Save PNG…
+image-export-empty = The board is empty
+image-export-too-large = The image would be too large, lower the cell size
+image-import-title = Import Image
+image-import-dark = Dark pixels
+image-import-color = Pixels of a color
+image-import-threshold = Luminance threshold
+image-import-tolerance = Tolerance
+image-import-open = Open image…
+image-import-filter = Images
+image-import-label = image
+image-import-empty = No pixel of the image matches
+image-import-too-large = Images are limited to { $size } pixels per side
+svg-export-title = Export SVG
+svg-export-empty = No cell in view to export
+recording-title = Record GIF
+recording-hashlife = Not available with the HashLife backend
+recording-frames = Frames
+recording-frame-skip = Generations per frame
+recording-scale = Cell size
+recording-delay = Frame duration
+recording-start = Start recording
+recording-stop = Stop and save
+recording-progress = Recording: { $frames } / { $total } frames
+recording-too-short = The recording needs at least two frames
+
+## Sessions and save slots
+session-invalid = Invalid session file: { $error }
+session-unsupported-rule = Unsupported rule in session: { $error }
+session-save-title = Save session
+session-open-title = Open session
+session-filter = Sessions
+slots-title = Save Slots
+slots-hashlife = Not available with the HashLife backend
+slots-snapshot = Generation { $generation }, { $cells } cells
+slots-empty = Empty
+slots-save = Save
+slots-load = Load
+slots-clear = Clear
+
+## Soup search
+soup-title = Soup Search
+soup-start = Start
+soup-stop = Stop
+soup-seed-prefix = Seed:{ " " }
+soup-give-up = Give up after:{ " " }
+soup-keep-from = Keep from:{ " " }
+soup-progress = { $soups } soups run, { $kept } kept
+soup-census = Census
+soup-results = Results
+soup-export = Export CSV
+soup-clear = Clear
+soup-seed = Seed
+soup-lifespan = Lifespan
+soup-period = Period
+soup-cells = Cells
+soup-load = Load
+soup-export-title = Export soups
+
+## Run until stable
+stability-title = Run until stable
+stability-searching = Looking for a cycle…
+stability-stable = Stable
+stability-cycle = Period { $period } cycle
+stability-found = { $kind } from generation { $start }, reached at generation { $generation }
+stability-inexact = Only the population repeats, spaceships may be escaping
+stability-not-found = No cycle found, stopped at generation { $generation }
+
+## Statistics
+stats-title = Statistics
+stats-pause = Pause
+stats-resume = Resume
+stats-last-prefix = Last{ " " }
+stats-last-suffix = { " " }generations
+stats-clear = Clear
+stats-generation = Generation
+stats-population = Population
+stats-births = Births
+stats-deaths = Deaths
+stats-plot-help = Scroll to zoom, drag to pan, double-click to reset the view
+
+## Diagnostics
+diagnostics-title = Diagnostics
+diagnostics-fps = FPS: { $fps }
+diagnostics-alive-cells = Alive cells: { $count }
+diagnostics-generation = Generation: { $generation }
+diagnostics-population = Population: { $population }
+diagnostics-births-deaths = Births / Deaths: { $births } / { $deaths }
+diagnostics-active-chunks = Active chunks: { $count }
+diagnostics-generation-time = Generation time: { $time }ms (max { $max }ms)
+diagnostics-generation-time-unknown = Generation time: N/A
+diagnostics-not-available = N/A
+diagnostics-simulation-bound = Simulation-bound
+diagnostics-render-bound = Render-bound
+
+## Notifications
+toast-pattern-placed = Pattern placed at ({ $x }, { $y })
+toast-pattern-pasted = Pattern pasted, click to place it
+toast-pattern-loaded = Pattern loaded, click to place it
+toast-session-hashlife = Sessions cannot be saved with the HashLife backend
+toast-session-save-failed = Could not save the session: { $error }
+toast-session-saved = Session saved
+toast-session-stored = Session saved in the browser
+toast-session-not-stored = No session saved in the browser
+toast-session-open-failed = Could not open the session: { $error }
+toast-screenshot = Screenshot saved as { $file }
+toast-image-saved = Image saved
+toast-image-failed = Could not export the image: { $error }
+toast-image-import-failed = Could not import the image: { $error }
+toast-recording-saved = Recording saved
+toast-recording-failed = Could not save the recording: { $error }
+toast-svg-saved = SVG saved
+toast-svg-failed = Could not save the SVG: { $error }
+toast-session-opened = Session opened at generation { $generation }
@@ -0,0 +1,382 @@
+# Messages français de l'interface.
+# Chaque ligne est `id = texte`, `{ $nom }` étant remplacé par une valeur.
+
+## Commun
+common-all-files = Tous les fichiers
+common-cancel = Annuler
+common-close = Fermer
+common-no = Non
+common-yes = Oui
+
+## Panneau de contrôle
+controls-title = Jeu de la vie
+controls-clear-grid = Vider la grille
+controls-random = Aléatoire…
+controls-soup-search = Recherche de soupes…
+controls-analyze = Analyser…
+controls-statistics = Statistiques…
+controls-save-slots = Emplacements…
+controls-save-session = Enregistrer la session…
+controls-open-session = Ouvrir une session…
+controls-store-session = Enregistrer dans le navigateur
+controls-restore-session = Charger depuis le navigateur
+controls-settings = Paramètres…
+controls-screenshot = Capture d'écran
+controls-export-image = Exporter une image…
+controls-export-svg = Exporter en SVG…
+controls-import-image = Importer une image…
+controls-record = Enregistrer un GIF…
+controls-draw = Dessin :
+controls-paint = Peindre
+controls-erase = Effacer
+controls-state = État
+controls-color = Couleur
+controls-speed = Vitesse ({ $speed } gén/s)
+controls-camera-distance = Distance de la caméra
+controls-start = Démarrer
+controls-pause = Pause
+controls-next-generation = Génération suivante
+controls-step-back = Revenir en arrière
+controls-play-backwards = ◀ Lecture arrière
+controls-stop-backwards = Arrêter
+controls-explain-steps = Expliquer les étapes
+controls-explain-steps-hover = Génération suivante montre d'abord les cellules sur le point de mourir (rouge) et de naître (vert), puis les applique
+controls-gens-suffix = { " " }gén
+controls-warp = Avancer
+controls-cancel-warp = Annuler l'avance
+controls-run-until-stable = Lancer jusqu'à stabilité
+controls-rule = Règle : { $rule }
+controls-rule-preset = Règle : { $rule } ({ $name })
+controls-presets = Préréglages
+controls-custom = Personnalisée
+controls-apply = Appliquer
+controls-table = Table…
+controls-neighborhood = Voisinage
+controls-noise = Bruit
+controls-birth-chance = Chance de naissance
+controls-survival-chance = Chance de survie
+controls-deterministic = Déterministe
+controls-backend = Moteur
+controls-hashlife-unsupported = HashLife demande une règle déterministe à deux états sur le voisinage de Moore, sans B0, sur la grille infinie
+controls-backend-elementary = Élémentaire (1D)
+controls-backend-margolus = Margolus (blocs 2×2)
+controls-hashlife-step = Pas (2^n gén)
+controls-elementary-rule = Règle{ " " }
+controls-seed = Semer
+controls-block-rule = Règle des blocs
+controls-partition-odd = Partition : impaire, blocs décalés de (1, 1)
+controls-partition-even = Partition : paire, blocs en (0, 0)
+controls-topology = Topologie
+controls-topology-infinite = Infinie
+controls-topology-bounded = Bornée
+controls-topology-torus = Tore
+controls-go-to = Aller à :
+controls-go = Aller
+controls-help-click = Cliquez sur la grille quand la simulation est en pause !
+controls-help-camera = Les flèches déplacent la caméra, { $jump } pour sauter !
+controls-help-drag = Clic droit glissé pour effacer, Maj glissé pour sélectionner !
+controls-help-cursor = { $keys } déplace le curseur clavier, { $toggle } bascule une cellule !
+controls-help-shortcuts = Appuyez sur ? ou F1 pour tous les raccourcis !
+
+## Barre d'état
+status-cell = Cellule : { $cell }
+status-zoom = Zoom : { $zoom } %
+status-population = Population : { $population }
+status-generation = Génération : { $generation }
+status-rate = { $rate } gén/s
+timeline-label = Chronologie
+timeline-hover = Glissez pour parcourir les générations enregistrées
+
+## Paramètres
+settings-title = Paramètres
+settings-language = Langue
+settings-theme = Thème
+settings-ui-scale = Taille de l'interface
+settings-fps-cap = Images par seconde au plus :
+settings-fps-cap-hover = 0 pour aucune limite. Des valeurs plus basses économisent la batterie et le GPU
+settings-reduce-motion = Réduire les animations
+settings-reduce-motion-hover = Désactive les animations de l'interface, les traînées des mortes et le fondu des notifications
+theme-classic = Classique
+theme-dark = Sombre
+theme-solarized = Solarized
+theme-high-contrast = Contraste élevé
+theme-colorblind = Adapté aux daltoniens
+settings-tab-simulation = Simulation
+settings-tab-display = Affichage
+settings-tab-colors = Couleurs
+settings-tab-camera = Caméra
+settings-tab-keys = Touches
+settings-saved-in = Enregistré dans { $file } à la fermeture
+settings-restore-defaults = Valeurs par défaut
+settings-tick-rate = Fréquence (Hz)
+settings-pause-unfocused = Pause en arrière-plan
+settings-pause-unfocused-hover = Met en pause quand la fenêtre n'a plus le focus ou est réduite, et reprend à son retour
+settings-history-depth = { " " }générations gardées pour revenir en arrière
+settings-show-grid = Afficher la grille
+settings-grid-major = Lignes principales toutes les
+settings-grid-major-cells = { " " }cellules
+settings-grid-major-hover = Lignes plus épaisses pour compter les cellules, 0 pour aucune
+settings-grid-fade = Estomper de
+settings-grid-fade-to = à
+settings-grid-fade-hover = Tailles des cellules à l'écran sur lesquelles la grille s'estompe en dézoomant
+settings-grid-axes = Mettre les axes en évidence
+settings-grid-labels = Coordonnées sur les lignes principales
+settings-show-minimap = Afficher la minicarte
+settings-neighbor-counts = Afficher le nombre de voisins
+settings-neighbor-counts-hover = Autour du curseur de la souris, en pause
+settings-onion-skin = Afficher la génération précédente
+settings-onion-skin-hover = Entoure les cellules de la génération précédente, d'après l'historique
+settings-cells = Cellules :
+settings-sprites = Sprites
+settings-textures = Textures
+settings-textures-hover = Plus rapide pour les très grandes populations
+settings-sound-muted = Couper le son
+settings-sound-muted-hover = Chaque génération joue une note, plus aiguë quand la population croît et plus grave quand elle décroît
+settings-sound-volume = Volume
+settings-bloom = Halo lumineux
+settings-bloom-hover = Les cellules vivantes brillent sur le fond, surtout avec un thème sombre
+settings-bloom-intensity = Intensité du halo
+settings-density-view = Vue de densité en dézoom extrême
+settings-density-view-hover = Quand les cellules sont plus petites qu'un pixel, chaque pixel montre combien d'entre elles sont vivantes
+settings-cell-shape = Forme des cellules :
+settings-cell-roundness = Arrondi des coins
+settings-cell-size = Taille des cellules
+settings-cell-size-hover = En dessous de 1, laisse un espace entre les cellules voisines
+settings-death-trail = Traînée des mortes (gén)
+settings-animate-cells = Animer naissances et morts
+settings-animate-cells-hover = Les cellules grandissent en naissant et rétrécissent en mourant, le temps d'une génération
+settings-state-shapes = Distinguer les états par leur forme
+settings-state-shapes-hover = Cercles, losanges et triangles pour les états après le premier, en plus de leur couleur
+settings-background = Fond :
+settings-grid-color = Grille :
+settings-grid-color-hover = L'opacité règle la visibilité des lignes
+settings-state-color = État { $state } :
+settings-state-color-hover = Utilisé par les règles à plusieurs états comme QuadLife
+settings-zoom-limits = Limites du zoom :
+settings-zoom-closest = Plus proche :
+settings-zoom-farthest = Plus loin :
+settings-keys-help = Cliquez sur une touche, puis appuyez sur la nouvelle. Échap annule.
+settings-press-key = Appuyez sur une touche…
+settings-key-conflict = Aussi associée à une autre action
+settings-arrow-keys = Les flèches déplacent toujours la caméra aussi
+
+## Raccourcis
+key-move-left = Caméra à gauche
+key-move-right = Caméra à droite
+key-move-up = Caméra en haut
+key-move-down = Caméra en bas
+key-zoom-in = Zoomer
+key-zoom-out = Dézoomer
+key-pause = Démarrer / pause
+key-step = Génération suivante
+key-step-back = Revenir en arrière
+key-clear = Vider la grille
+key-jump-to = Aller à une cellule
+key-cursor-left = Curseur clavier à gauche
+key-cursor-right = Curseur clavier à droite
+key-cursor-up = Curseur clavier en haut
+key-cursor-down = Curseur clavier en bas
+key-toggle-cell = Basculer la cellule sous le curseur
+key-diagnostics = Afficher les diagnostics
+key-screenshot = Faire une capture d'écran
+
+## Aide
+help-title = Raccourcis clavier
+help-arrow-keys = Flèches
+help-move-camera = Déplacer la caméra
+help-shift = Maj
+help-move-camera-faster = Déplacer la caméra plus vite
+help-left-click = Clic gauche / glissé
+help-draw-cells = Dessiner des cellules, en pause
+help-right-drag = Clic droit glissé
+help-erase-cells = Effacer des cellules
+help-shift-drag = Maj glissé
+help-select-cells = Sélectionner des cellules
+help-ctrl-v = Ctrl+V
+help-paste-pattern = Coller un motif
+help-escape = Échap
+help-close-dialogs = Fermer les fenêtres, vider la sélection
+help-help-keys = ? / F1
+help-show-help = Afficher cette aide
+help-rebind = Les raccourcis se changent dans Paramètres › Touches
+
+## Fenêtres de dialogue
+modal-reset-title = ⚠ Tuer toutes les cellules !
+modal-reset-question = Voulez-vous vraiment tuer toutes les cellules ?
+modal-random-title = Génération aléatoire
+modal-random-question = Remplir la grille de cellules aléatoires ?
+modal-random-centered = Centrée sur la vue
+modal-random-size = Taille :{ " " }
+modal-random-cells = { " " }cellules
+random-density = densité
+random-seed = Graine :{ " " }
+random-new-seed = Choisir une nouvelle graine
+shape-square = Carré
+shape-disc = Disque
+shape-ring = Anneau
+navigation-title = Aller à une cellule
+
+## Motifs
+pattern-patterns = Motifs :
+pattern-search = Rechercher
+pattern-load = Charger un motif
+pattern-load-title = Charger un motif
+pattern-click-to-place = Cliquez pour placer : { $name }
+pattern-paste-prompt = Collez votre motif (RLE, Life 1.05/1.06, texte .cells ou Golly .mc) :
+pattern-paste-hint = Exemple : bo$2bo$3o!
+pattern-open-file = Ouvrir un fichier…
+pattern-empty = Veuillez saisir un contenu RLE
+pattern-invalid = Motif invalide : { $error }
+pattern-unsupported-rule = Règle '{ $rule }' non prise en charge : { $error }
+pattern-open-title = Ouvrir un motif
+pattern-filter = Motifs
+
+## Sélection
+selection-title = Sélection
+selection-size = { $width } × { $height } cellules
+selection-none = Aucune sélection
+selection-copy = Copier
+selection-cut = Couper
+selection-paste = Coller
+selection-move = Déplacer
+selection-invert = Inverser
+selection-clear = Vider
+selection-analyze = Analyser
+selection-clear-outside = Vider autour
+selection-fill-randomly = Remplir au hasard
+selection-deselect = Désélectionner
+selection-too-large = Les sélections de plus de { $cells } cellules ne peuvent être ni inversées ni remplies
+
+## Analyse
+analysis-title = Analyse du motif
+analysis-running = Exécution du motif…
+analysis-whole-pattern = Motif entier
+analysis-selection = Sélection
+analysis-analyze-pattern = Analyser le motif
+analysis-analyze-selection = Analyser la sélection
+
+## Table de la règle
+rule-table-title = Table de la règle
+rule-table-rule = Règle : { $rule }
+rule-table-neighbors = Voisins
+rule-table-birth = Naissance
+rule-table-survival = Survie
+
+## Images
+image-export-title = Exporter une image
+image-export-hashlife = Indisponible avec le moteur HashLife
+image-export-scale = Taille des cellules :{ " " }
+image-export-save = Enregistrer en PNG…
+image-export-empty = La grille est vide
+image-export-too-large = L'image serait trop grande, réduisez la taille des cellules
+image-import-title = Importer une image
+image-import-dark = Pixels sombres
+image-import-color = Pixels d'une couleur
+image-import-threshold = Seuil de luminance
+image-import-tolerance = Tolérance
+image-import-open = Ouvrir une image…
+image-import-filter = Images
+image-import-label = image
+image-import-empty = Aucun pixel de l'image ne correspond
+image-import-too-large = Les images sont limitées à { $size } pixels de côté
+svg-export-title = Exporter en SVG
+svg-export-empty = Aucune cellule visible à exporter
+recording-title = Enregistrer un GIF
+recording-hashlife = Indisponible avec le moteur HashLife
+recording-frames = Images
+recording-frame-skip = Générations par image
+recording-scale = Taille des cellules
+recording-delay = Durée d'une image
+recording-start = Démarrer l'enregistrement
+recording-stop = Arrêter et enregistrer
+recording-progress = Enregistrement : { $frames } / { $total } images
+recording-too-short = L'enregistrement demande au moins deux images
+
+## Sessions et emplacements
+session-invalid = Fichier de session invalide : { $error }
+session-unsupported-rule = Règle de la session non prise en charge : { $error }
+session-save-title = Enregistrer la session
+session-open-title = Ouvrir une session
+session-filter = Sessions
+slots-title = Emplacements de sauvegarde
+slots-hashlife = Indisponible avec le moteur HashLife
+slots-snapshot = Génération { $generation }, { $cells } cellules
+slots-empty = Vide
+slots-save = Enregistrer
+slots-load = Charger
+slots-clear = Vider
+
+## Recherche de soupes
+soup-title = Recherche de soupes
+soup-start = Démarrer
+soup-stop = Arrêter
+soup-seed-prefix = Graine :{ " " }
+soup-give-up = Abandon après :{ " " }
+soup-keep-from = Garder à partir de :{ " " }
+soup-progress = { $soups } soupes lancées, { $kept } gardées
+soup-census = Recensement
+soup-results = Résultats
+soup-export = Exporter en CSV
+soup-clear = Vider
+soup-seed = Graine
+soup-lifespan = Durée de vie
+soup-period = Période
+soup-cells = Cellules
+soup-load = Charger
+soup-export-title = Exporter les soupes
+
+## Lancer jusqu'à stabilité
+stability-title = Lancer jusqu'à stabilité
+stability-searching = Recherche d'un cycle…
+stability-stable = Stable
+stability-cycle = Cycle de période { $period }
+stability-found = { $kind } depuis la génération { $start }, atteint à la génération { $generation }
+stability-inexact = Seule la population se répète, des vaisseaux s'échappent peut-être
+stability-not-found = Aucun cycle trouvé, arrêt à la génération { $generation }
+
+## Statistiques
+stats-title = Statistiques
+stats-pause = Pause
+stats-resume = Reprendre
+stats-last-prefix = Les{ " " }
+stats-last-suffix = { " " }dernières générations
+stats-clear = Vider
+stats-generation = Génération
+stats-population = Population
+stats-births = Naissances
+stats-deaths = Morts
+stats-plot-help = Molette pour zoomer, glisser pour déplacer, double-clic pour réinitialiser la vue
+
+## Diagnostics
+diagnostics-title = Diagnostics
+diagnostics-fps = IPS : { $fps }
+diagnostics-alive-cells = Cellules vivantes : { $count }
+diagnostics-generation = Génération : { $generation }
+diagnostics-population = Population : { $population }
+diagnostics-births-deaths = Naissances / morts : { $births } / { $deaths }
+diagnostics-active-chunks = Blocs actifs : { $count }
+diagnostics-generation-time = Temps par génération : { $time } ms (max { $max } ms)
+diagnostics-generation-time-unknown = Temps par génération : N/D
+diagnostics-not-available = N/D
+diagnostics-simulation-bound = Limité par la simulation
+diagnostics-render-bound = Limité par le rendu
+
+## Notifications
+toast-pattern-placed = Motif placé en ({ $x }, { $y })
+toast-pattern-pasted = Motif collé, cliquez pour le placer
+toast-pattern-loaded = Motif chargé, cliquez pour le placer
+toast-session-hashlife = Les sessions ne peuvent pas être enregistrées avec le moteur HashLife
+toast-session-save-failed = Impossible d'enregistrer la session : { $error }
+toast-session-saved = Session enregistrée
+toast-session-stored = Session enregistrée dans le navigateur
+toast-session-not-stored = Aucune session enregistrée dans le navigateur
+toast-session-open-failed = Impossible d'ouvrir la session : { $error }
+toast-screenshot = Capture d'écran enregistrée sous { $file }
+toast-image-saved = Image enregistrée
+toast-image-failed = Impossible d'exporter l'image : { $error }
+toast-image-import-failed = Impossible d'importer l'image : { $error }
+toast-recording-saved = Enregistrement sauvegardé
+toast-recording-failed = Impossible d'enregistrer le clip : { $error }
+toast-svg-saved = SVG enregistré
+toast-svg-failed = Impossible d'enregistrer le SVG : { $error }
+toast-session-opened = Session ouverte à la génération { $generation }
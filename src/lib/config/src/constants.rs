@@ -14,14 +14,58 @@ pub const CELL_COLOR: Color = Color::srgb(0.0, 0.0, 0.0);
 
 /// Default camera scale (zoomed out view)
 pub const DEFAULT_SCALE: f32 = 1.0 / 40.0;
-/// Maximum camera scale (zoomed in view)
+/// Camera scale at which one cell covers one pixel
 pub const MAX_SCALE: f32 = 1.0;
+/// Default zoom-out limit, where one pixel covers many cells
+pub const ZOOM_OUT_LIMIT: f32 = 64.0;
 
-/// Minimum time period between generations (fastest speed)
-pub const MIN_PERIOD: Seconds = 0.01;
+/// Smallest scale of the interface, relative to the size chosen by the system
+pub const MIN_UI_SCALE: f32 = 0.75;
+/// Largest scale of the interface, relative to the size chosen by the system
+pub const MAX_UI_SCALE: f32 = 2.0;
+
+/// Minimum time period between generations (fastest speed, 1000 generations/s)
+pub const MIN_PERIOD: Seconds = 0.001;
 /// Maximum time period between generations (slowest speed)
 pub const MAX_PERIOD: Seconds = 1.5;
 
+/// Generations between two keyframes of the history, replayed from by the timeline
+pub const KEYFRAME_INTERVAL: u64 = 64;
+/// Most keyframes kept, the oldest ones being dropped
+pub const MAX_KEYFRAMES: usize = 256;
+
+/// Upper bound on generations computed in a single simulation tick, to avoid stalling rendering
+pub const MAX_GENERATIONS_PER_TICK: u32 = 64;
+
+/// Default number of simulation ticks per second on the fixed timestep
+pub const DEFAULT_TICK_RATE: f64 = 60.0;
+
+/// Time spent computing generations each frame while warping
+pub const WARP_FRAME_BUDGET: Seconds = 0.012;
+/// Time a computation of generations may take on the web before leaving the
+/// rest to the next one, so that the web worker shows slow generations as
+/// they come, and the tab stays responsive when they run on the main thread
+pub const WEB_STEP_BUDGET: Seconds = 0.012;
+
+/// Generations after which "Run until stable" gives up looking for a cycle
+pub const MAX_STABILITY_GENERATIONS: u64 = 100_000;
+
+/// Generations the pattern analyzer waits for a pattern to come back
+pub const MAX_ANALYSIS_PERIOD: u64 = 1_000;
+
+/// Number of save slots keeping boards in memory
+pub const SAVE_SLOT_COUNT: usize = 5;
+
+/// Default number of generations shown on the statistics graph
+pub const POPULATION_HISTORY_LENGTH: usize = 500;
+
+/// Default number of dead cell entities kept around for reuse
+pub const DEAD_POOL_CAPACITY: usize = 10_000;
+
+/// Chunks of cell entities kept on each side of the view, so that panning
+/// back and forth over a chunk border does not despawn and respawn them
+pub const CULL_MARGIN_CHUNKS: isize = 1;
+
 /// Zoom step factor for keyboard zoom controls
 pub const ZOOM_STEP: f32 = 0.1;
 
@@ -29,3 +73,22 @@ pub const ZOOM_STEP: f32 = 0.1;
 pub const BASE_SPEED: f32 = 25.0;
 /// Maximum movement speed for camera in turbo mode
 pub const MAX_SPEED: f32 = 125.0;
+
+/// Largest width or height of an exported board image, in pixels
+pub const MAX_EXPORT_SIZE: u32 = 16384;
+/// Largest width or height of an imported picture, one cell per pixel
+pub const MAX_IMPORT_SIZE: u32 = 2048;
+/// Largest selection inverted or filled randomly, in cells, as every cell of
+/// it is visited
+pub const MAX_EDITED_AREA: u64 = 4_000_000;
+/// Most frames of a recorded clip, all kept in memory until it is encoded
+pub const MAX_RECORDING_FRAMES: u32 = 1000;
+
+/// Time during which a notification stays on screen
+pub const TOAST_DURATION: Seconds = 4.0;
+/// Number of notifications shown at once, the oldest ones being dropped
+pub const MAX_TOASTS: usize = 5;
+
+/// Time between two saves of the settings and the session to the browser
+/// storage, on the web
+pub const AUTOSAVE_PERIOD: Seconds = 10.0;
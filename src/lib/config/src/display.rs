@@ -2,56 +2,387 @@
 //!
 //! Configuration parameters for visual display and camera behavior.
 
+use crate::constants::{DEFAULT_SCALE, ZOOM_OUT_LIMIT};
+use crate::i18n::{Language, tr};
 use bevy::prelude::{Color, Resource};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How alive cells are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CellRenderMode {
+    /// One sprite entity per cell
+    #[default]
+    Sprites,
+    /// Cells written into chunk textures, one pixel per cell, or a few for
+    /// shaped cells
+    Chunks,
+}
+
+/// Outline of the living cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CellShape {
+    /// Square, its corners rounded by [`DisplayConfig::cell_roundness`]
+    #[default]
+    Square,
+    Disc,
+}
+
+impl CellShape {
+    /// Every shape, in the order they are offered
+    pub const ALL: [Self; 2] = [Self::Square, Self::Disc];
+
+    /// Whether a point belongs to the shape, the cell spanning -1 to 1 on
+    /// both axes. `roundness` is the radius of the square corners, from 0
+    /// for sharp corners to 1 for a disc
+    pub fn contains(self, roundness: f32, u: f32, v: f32) -> bool {
+        match self {
+            Self::Square => {
+                let radius = roundness.clamp(0.0, 1.0);
+                // Distance past the straight part of the edges
+                let du = (u.abs() - (1.0 - radius)).max(0.0);
+                let dv = (v.abs() - (1.0 - radius)).max(0.0);
+                u.abs() <= 1.0 && v.abs() <= 1.0 && du * du + dv * dv <= radius * radius
+            }
+            Self::Disc => u * u + v * v <= 1.0,
+        }
+    }
+}
+
+impl fmt::Display for CellShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = match self {
+            Self::Square => "shape-square",
+            Self::Disc => "shape-disc",
+        };
+        write!(f, "{}", tr(id))
+    }
+}
+
+/// Shape of the area filled with random cells, inscribed in its bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RandomShape {
+    #[default]
+    Square,
+    Disc,
+    /// Disc with a hole of half its radius
+    Ring,
+}
+
+impl RandomShape {
+    /// Every shape, in the order they are offered
+    pub const ALL: [Self; 3] = [Self::Square, Self::Disc, Self::Ring];
+
+    /// Whether a point belongs to the shape, given relative to the center
+    /// of the bounds, the bounds spanning -1 to 1 on both axes
+    pub fn contains(self, u: f64, v: f64) -> bool {
+        let distance = u * u + v * v;
+        match self {
+            Self::Square => true,
+            Self::Disc => distance <= 1.0,
+            Self::Ring => (0.25..=1.0).contains(&distance),
+        }
+    }
+}
+
+impl fmt::Display for RandomShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = match self {
+            Self::Square => "shape-square",
+            Self::Disc => "shape-disc",
+            Self::Ring => "shape-ring",
+        };
+        write!(f, "{}", tr(id))
+    }
+}
+
+/// Look of the interface, pairing the egui visuals with a color preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    /// Black cells on a light background
+    #[default]
+    Classic,
+    Dark,
+    Solarized,
+    HighContrast,
+    /// Okabe-Ito colors, told apart with any color vision
+    Colorblind,
+}
+
+impl Theme {
+    /// Every theme, in the order they are offered
+    pub const ALL: [Self; 5] = [
+        Self::Classic,
+        Self::Dark,
+        Self::Solarized,
+        Self::HighContrast,
+        Self::Colorblind,
+    ];
+
+    /// Colors of the grid matching the theme
+    pub fn colors(self) -> ColorConfig {
+        match self {
+            Self::Classic => ColorConfig::default(),
+            Self::Dark => ColorConfig {
+                cell_color: Color::srgb(0.9, 0.9, 0.9),
+                background_color: Color::srgb(0.08, 0.08, 0.1),
+                grid_color: Color::srgba(0.45, 0.45, 0.5, 0.6),
+                state_colors: vec![
+                    Color::srgb(0.95, 0.35, 0.35),
+                    Color::srgb(0.4, 0.6, 1.0),
+                    Color::srgb(0.35, 0.85, 0.45),
+                ],
+            },
+            Self::Solarized => ColorConfig {
+                cell_color: Color::srgb_u8(0xee, 0xe8, 0xd5), // base2
+                background_color: Color::srgb_u8(0x00, 0x2b, 0x36), // base03
+                grid_color: Color::srgba_u8(0x58, 0x6e, 0x75, 0xc0), // base01
+                state_colors: vec![
+                    Color::srgb_u8(0xdc, 0x32, 0x2f), // red
+                    Color::srgb_u8(0x26, 0x8b, 0xd2), // blue
+                    Color::srgb_u8(0x85, 0x99, 0x00), // green
+                ],
+            },
+            Self::HighContrast => ColorConfig {
+                cell_color: Color::WHITE,
+                background_color: Color::BLACK,
+                grid_color: Color::srgb(0.6, 0.6, 0.6),
+                state_colors: vec![
+                    Color::srgb(1.0, 1.0, 0.0),
+                    Color::srgb(0.0, 1.0, 1.0),
+                    Color::srgb(1.0, 0.0, 1.0),
+                ],
+            },
+            Self::Colorblind => ColorConfig {
+                cell_color: Color::BLACK,
+                background_color: Color::srgb(0.95, 0.95, 0.95),
+                grid_color: Color::srgba(0.5, 0.5, 0.5, 0.8),
+                state_colors: vec![
+                    Color::srgb_u8(0xe6, 0x9f, 0x00), // orange
+                    Color::srgb_u8(0x56, 0xb4, 0xe9), // sky blue
+                    Color::srgb_u8(0x00, 0x9e, 0x73), // bluish green
+                ],
+            },
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = match self {
+            Self::Classic => "theme-classic",
+            Self::Dark => "theme-dark",
+            Self::Solarized => "theme-solarized",
+            Self::HighContrast => "theme-high-contrast",
+            Self::Colorblind => "theme-colorblind",
+        };
+        write!(f, "{}", tr(id))
+    }
+}
 
 /// GUI-specific configuration parameters.
 ///
 /// Contains settings for the user interface that don't directly
 /// affect the simulation logic but control display options.
-#[derive(Resource, Debug)]
+#[derive(Resource, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DisplayConfig {
-    /// Width of the grid for random cell generation
-    pub random_grid_width: u16,
+    /// Percentage of living cells when filling the selection randomly
+    pub random_density: u8,
+    /// Seed of the random soups, so the same soup can be generated again.
+    /// Not saved, as large seeds do not fit in the configuration file
+    #[serde(skip)]
+    pub random_seed: u64,
+    /// Shape of the random fills
+    pub random_shape: RandomShape,
+    /// Side of the area filled by the Random action, in cells
+    pub random_size: u32,
     /// Whether to display the grid overlay
     pub grid_visible: bool,
+    /// Cells between two thicker major grid lines, 0 for none
+    pub grid_major_spacing: u32,
+    /// Whether to highlight the x and y axes through the origin
+    pub grid_axes: bool,
+    /// Whether to write the coordinates of the major grid lines
+    pub grid_labels: bool,
+    /// Size of a cell on screen, in pixels, below which the grid lines start
+    /// fading out
+    pub grid_fade_start: f32,
+    /// Size of a cell on screen, in pixels, below which the grid lines are
+    /// hidden. Major lines fade the same way, by the size of their squares
+    pub grid_fade_end: f32,
+    /// Whether to display the population minimap
+    pub minimap_visible: bool,
+    /// How alive cells are drawn
+    pub render_mode: CellRenderMode,
+    /// Whether the cells glow against the background, through an HDR camera
+    /// with bloom
+    pub bloom: bool,
+    /// Strength of the glow, from 0 to 1
+    pub bloom_intensity: f32,
+    /// Whether cells smaller than a pixel give way to a view of their
+    /// density, rather than aliasing into noise
+    pub density_view: bool,
+    /// Outline of the living cells
+    pub cell_shape: CellShape,
+    /// Radius of the corners of square cells, from 0 for sharp corners to 1
+    /// for discs
+    pub cell_roundness: f32,
+    /// Side of a cell relative to the grid spacing, below 1 leaving a gap
+    /// between neighboring cells
+    pub cell_size: f32,
+    /// Generations over which dead cells fade out in sprite mode, 0 for none
+    pub death_trail: u8,
+    /// Whether cells grow when born and shrink when dying, in sprite mode
+    pub animate_cells: bool,
+    /// Whether to write the neighbor counts around the cursor while paused
+    pub show_neighbor_counts: bool,
+    /// Whether to outline the cells of the previous generation
+    pub onion_skin: bool,
+    /// State given to painted cells when the rule has several, such as a QuadLife color
+    pub paint_state: u8,
+    /// Number of generations requested by the Warp control
+    pub warp_generations: u64,
+    /// Language of the interface
+    pub language: Language,
+    /// Look of the interface
+    pub theme: Theme,
+    /// Whether the living states after the first are drawn with their own
+    /// shape in sprite mode, besides their color
+    pub state_shapes: bool,
+    /// Scale of the interface text and widgets, on top of the system scale
+    pub ui_scale: f32,
+    /// Most frames drawn per second, 0 for no limit
+    pub fps_cap: u32,
+    /// Whether to turn off the interface animations and the fading effects
+    pub reduce_motion: bool,
+    /// Whether the tones following the population are silenced
+    pub sound_muted: bool,
+    /// Volume of the tones, from 0 to 1
+    pub sound_volume: f32,
 }
 
 impl Default for DisplayConfig {
     fn default() -> Self {
         Self {
-            random_grid_width: 50u16,
+            random_density: 20,
+            random_seed: 0,
+            random_shape: RandomShape::default(),
+            random_size: 50,
             grid_visible: true,
+            grid_major_spacing: 10,
+            grid_axes: true,
+            grid_labels: false,
+            grid_fade_start: 12.0,
+            grid_fade_end: 3.0,
+            minimap_visible: true,
+            render_mode: CellRenderMode::default(),
+            bloom: false,
+            bloom_intensity: 0.3,
+            density_view: true,
+            cell_shape: CellShape::default(),
+            cell_roundness: 0.0,
+            cell_size: 1.0,
+            death_trail: 0,
+            animate_cells: false,
+            show_neighbor_counts: false,
+            onion_skin: false,
+            paint_state: 1,
+            warp_generations: 1000,
+            language: Language::default(),
+            theme: Theme::default(),
+            state_shapes: false,
+            ui_scale: 1.0,
+            fps_cap: 0,
+            reduce_motion: false,
+            sound_muted: true,
+            sound_volume: 0.5,
         }
     }
 }
 
+impl DisplayConfig {
+    /// Whether cells are drawn as plain squares filling their place, the
+    /// cheapest way to draw them
+    pub fn plain_cells(&self) -> bool {
+        self.cell_shape == CellShape::Square && self.cell_roundness <= 0.0 && self.cell_size >= 1.0
+    }
+
+    /// Whether a point of a cell place, spanning -1 to 1 on both axes, is
+    /// covered by the cell, after its shape and the gap around it
+    pub fn cell_covers(&self, u: f32, v: f32) -> bool {
+        let size = self.cell_size.clamp(0.1, 1.0);
+        self.cell_shape
+            .contains(self.cell_roundness, u / size, v / size)
+    }
+
+    /// Opacity factor of grid lines `pixels` apart on screen.
+    ///
+    /// Lines are fully shown down to [`Self::grid_fade_start`] pixels apart
+    /// and hidden from [`Self::grid_fade_end`], easing in between with a
+    /// smoothstep, flat at both ends so the fade is hardly noticed.
+    pub fn grid_fade(&self, pixels: f32) -> f32 {
+        let range = self.grid_fade_start - self.grid_fade_end;
+        if range <= 0.0 {
+            return if pixels >= self.grid_fade_end {
+                1.0
+            } else {
+                0.0
+            };
+        }
+        let t = ((pixels - self.grid_fade_end) / range).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+}
+
 /// Camera movement and control configuration
-#[derive(Resource, Default)]
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CameraConfig {
     /// Whether turbo mode (faster movement) is enabled
     pub turbo_mode: bool,
+    /// Smallest camera scale (closest zoom)
+    pub min_scale: f32,
+    /// Largest camera scale (farthest zoom)
+    pub max_scale: f32,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            turbo_mode: false,
+            min_scale: DEFAULT_SCALE,
+            max_scale: ZOOM_OUT_LIMIT,
+        }
+    }
+}
+
+impl CameraConfig {
+    /// Restricts a camera scale to the configured zoom limits
+    pub fn clamp_scale(&self, scale: f32) -> f32 {
+        scale.clamp(self.min_scale, self.max_scale)
+    }
 }
 
 /// FPS display configuration
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct FpsConfig {
     /// Whether FPS counter is visible
     pub visible: bool,
 }
 
-impl Default for FpsConfig {
-    fn default() -> Self {
-        Self { visible: false }
-    }
-}
-
 /// Config for colors in game
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ColorConfig {
     /// Color cells
     pub cell_color: Color,
     /// Color of grid background
     pub background_color: Color,
+    /// Color of the grid lines, their alpha setting how much they show
+    pub grid_color: Color,
+    /// Colors of the living states after the first, in multi-state rules
+    pub state_colors: Vec<Color>,
 }
 
 impl Default for ColorConfig {
@@ -59,6 +390,26 @@ impl Default for ColorConfig {
         Self {
             cell_color: Color::srgb(0.0, 0.0, 0.0),       // Black default
             background_color: Color::srgb(0.9, 0.9, 0.9), // Light Grey default
+            grid_color: Color::srgba(0.5, 0.5, 0.5, 0.8),
+            state_colors: vec![
+                Color::srgb(0.85, 0.15, 0.15), // Red
+                Color::srgb(0.15, 0.35, 0.85), // Blue
+                Color::srgb(0.1, 0.6, 0.2),    // Green
+            ],
+        }
+    }
+}
+
+impl ColorConfig {
+    /// Color of a living cell in the given state, state 1 being `cell_color`
+    pub fn state_color(&self, state: u8) -> Color {
+        match state {
+            0 | 1 => self.cell_color,
+            _ => self
+                .state_colors
+                .get(state as usize - 2)
+                .copied()
+                .unwrap_or(self.cell_color),
         }
     }
 }
@@ -0,0 +1,119 @@
+//! # Configuration File
+//!
+//! Preferences kept in a TOML file next to the application: loaded at
+//! startup over the defaults and written back on exit.
+//!
+//! Every setting is optional in the file, the missing ones keep their
+//! default value. On the web, the file is kept in the browser storage
+//! instead, see [`crate::storage`].
+
+use crate::display::{CameraConfig, ColorConfig, DisplayConfig};
+use crate::keys::KeyBindings;
+use crate::simulation::SimulationConfig;
+use crate::storage::{read_storage, storage_available};
+use bevy::prelude::{AppExit, MessageReader, Res, warn};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Path of the configuration file, relative to the working directory
+pub const CONFIG_FILE: &str = "gol.toml";
+
+/// Configuration resources, as read from the configuration file
+#[derive(Default, Deserialize)]
+#[serde(default)]
+pub struct ConfigFile {
+    pub simulation: SimulationConfig,
+    pub display: DisplayConfig,
+    pub colors: ColorConfig,
+    pub camera: CameraConfig,
+    pub keys: KeyBindings,
+}
+
+/// Configuration resources, borrowed to be written in the configuration file
+#[derive(Serialize)]
+pub struct ConfigFileRef<'a> {
+    pub simulation: &'a SimulationConfig,
+    pub display: &'a DisplayConfig,
+    pub colors: &'a ColorConfig,
+    pub camera: &'a CameraConfig,
+    pub keys: &'a KeyBindings,
+}
+
+impl ConfigFileRef<'_> {
+    /// Writes the configuration as TOML
+    pub fn to_toml(&self) -> Result<String, ConfigFileError> {
+        toml::to_string_pretty(self).map_err(|e| ConfigFileError::Format(e.to_string()))
+    }
+}
+
+impl ConfigFile {
+    /// Reads the configuration written by [`ConfigFileRef::to_toml`]
+    pub fn from_toml(content: &str) -> Result<Self, ConfigFileError> {
+        toml::from_str(content).map_err(|e| ConfigFileError::Format(e.to_string()))
+    }
+
+    /// Loads the configuration file at `path`, falling back to the defaults
+    /// when it does not exist. On the web, it is read from the browser
+    /// storage
+    pub fn load(path: &Path) -> Result<Self, ConfigFileError> {
+        if storage_available() {
+            return read_storage(CONFIG_FILE)
+                .map_or(Ok(Self::default()), |content| Self::from_toml(&content));
+        }
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            std::fs::read_to_string(path).map_err(|e| ConfigFileError::Io(e.to_string()))?;
+        Self::from_toml(&content)
+    }
+}
+
+/// Error returned when the configuration file cannot be read or written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigFileError {
+    /// The file cannot be accessed
+    Io(String),
+    /// The file is not a valid configuration
+    Format(String),
+}
+
+impl fmt::Display for ConfigFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Cannot access {CONFIG_FILE}: {e}"),
+            Self::Format(e) => write!(f, "Invalid {CONFIG_FILE}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigFileError {}
+
+/// Writes the configuration resources back to the configuration file when
+/// the application exits
+pub fn save_config_on_exit(
+    mut exits: MessageReader<AppExit>,
+    simulation: Res<SimulationConfig>,
+    display: Res<DisplayConfig>,
+    colors: Res<ColorConfig>,
+    camera: Res<CameraConfig>,
+    keys: Res<KeyBindings>,
+) {
+    if exits.read().last().is_none() || cfg!(target_arch = "wasm32") {
+        return;
+    }
+    let config = ConfigFileRef {
+        simulation: &simulation,
+        display: &display,
+        colors: &colors,
+        camera: &camera,
+        keys: &keys,
+    };
+    let result = config.to_toml().and_then(|content| {
+        std::fs::write(CONFIG_FILE, content).map_err(|e| ConfigFileError::Io(e.to_string()))
+    });
+    if let Err(e) = result {
+        warn!("Could not save the configuration: {e}");
+    }
+}
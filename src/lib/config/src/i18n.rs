@@ -0,0 +1,113 @@
+//! # Localization
+//!
+//! Translations of the interface, read from the Fluent-style resources of
+//! the `locales` directory. Each line of a resource is `id = text`, the
+//! text holding `{ $name }` placeholders for the values given by the caller,
+//! and `{ " " }` for the spaces at its ends.
+//!
+//! The current language is global, so that any system can translate its
+//! labels without an extra parameter. It follows [`DisplayConfig::language`].
+//!
+//! [`DisplayConfig::language`]: crate::DisplayConfig::language
+
+use bevy::prelude::{DetectChanges, Res};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::display::DisplayConfig;
+
+/// Language of the interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+}
+
+impl Language {
+    /// Every language, in the order they are offered
+    pub const ALL: [Self; 2] = [Self::English, Self::French];
+
+    /// Translations of the language, parsed on first use
+    fn messages(self) -> &'static HashMap<&'static str, String> {
+        static ENGLISH: LazyLock<HashMap<&str, String>> =
+            LazyLock::new(|| parse_resource(include_str!("../locales/en.ftl")));
+        static FRENCH: LazyLock<HashMap<&str, String>> =
+            LazyLock::new(|| parse_resource(include_str!("../locales/fr.ftl")));
+        match self {
+            Self::English => &ENGLISH,
+            Self::French => &FRENCH,
+        }
+    }
+}
+
+/// Name of the language, in that language
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::English => write!(f, "English"),
+            Self::French => write!(f, "Français"),
+        }
+    }
+}
+
+/// Index of the current language in [`Language::ALL`]
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Language the interface is currently shown in
+pub fn language() -> Language {
+    Language::ALL[CURRENT.load(Ordering::Relaxed) as usize]
+}
+
+/// Shows the interface in another language
+pub fn set_language(language: Language) {
+    let index = Language::ALL
+        .iter()
+        .position(|&l| l == language)
+        .unwrap_or(0);
+    CURRENT.store(index as u8, Ordering::Relaxed);
+}
+
+/// Text of a message in the current language.
+///
+/// Falls back to English when the message is not translated, and to its
+/// id when it does not exist at all.
+pub fn tr(id: &'static str) -> &'static str {
+    language()
+        .messages()
+        .get(id)
+        .or_else(|| Language::English.messages().get(id))
+        .map_or(id, String::as_str)
+}
+
+/// Text of a message in the current language, with its placeholders
+/// replaced by the given values
+pub fn tr_args(id: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut text = tr(id).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{ ${name} }}"), &value.to_string());
+    }
+    text
+}
+
+/// Parses the `id = text` lines of a resource, skipping blank lines and
+/// `#` comments
+fn parse_resource(source: &'static str) -> HashMap<&'static str, String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(id, text)| (id.trim(), text.trim().replace("{ \" \" }", " ")))
+        .collect()
+}
+
+/// Switches the language when it is changed in the display configuration
+pub fn apply_language(display_config: Res<DisplayConfig>) {
+    if display_config.is_changed() {
+        set_language(display_config.language);
+    }
+}
@@ -0,0 +1,209 @@
+//! # Key Bindings
+//!
+//! Keyboard shortcuts of the application, editable from the settings and
+//! saved in the configuration file.
+
+use bevy::prelude::{KeyCode, Resource};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::i18n::tr;
+
+/// Action triggered by a keyboard shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    ZoomIn,
+    ZoomOut,
+    Pause,
+    Step,
+    StepBack,
+    Clear,
+    JumpTo,
+    CursorLeft,
+    CursorRight,
+    CursorUp,
+    CursorDown,
+    ToggleCell,
+    Diagnostics,
+    Screenshot,
+}
+
+impl KeyAction {
+    /// Every action, in the order they are listed to the user
+    pub const ALL: [Self; 18] = [
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::ZoomIn,
+        Self::ZoomOut,
+        Self::Pause,
+        Self::Step,
+        Self::StepBack,
+        Self::Clear,
+        Self::JumpTo,
+        Self::CursorLeft,
+        Self::CursorRight,
+        Self::CursorUp,
+        Self::CursorDown,
+        Self::ToggleCell,
+        Self::Diagnostics,
+        Self::Screenshot,
+    ];
+
+    /// Description of the action, in the current language
+    pub fn label(self) -> &'static str {
+        tr(match self {
+            Self::MoveLeft => "key-move-left",
+            Self::MoveRight => "key-move-right",
+            Self::MoveUp => "key-move-up",
+            Self::MoveDown => "key-move-down",
+            Self::ZoomIn => "key-zoom-in",
+            Self::ZoomOut => "key-zoom-out",
+            Self::Pause => "key-pause",
+            Self::Step => "key-step",
+            Self::StepBack => "key-step-back",
+            Self::Clear => "key-clear",
+            Self::JumpTo => "key-jump-to",
+            Self::CursorLeft => "key-cursor-left",
+            Self::CursorRight => "key-cursor-right",
+            Self::CursorUp => "key-cursor-up",
+            Self::CursorDown => "key-cursor-down",
+            Self::ToggleCell => "key-toggle-cell",
+            Self::Diagnostics => "key-diagnostics",
+            Self::Screenshot => "key-screenshot",
+        })
+    }
+}
+
+/// Keys bound to each action.
+///
+/// The arrow keys always move the camera as well.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub move_left: KeyCode,
+    pub move_right: KeyCode,
+    pub move_up: KeyCode,
+    pub move_down: KeyCode,
+    pub zoom_in: KeyCode,
+    pub zoom_out: KeyCode,
+    pub pause: KeyCode,
+    pub step: KeyCode,
+    pub step_back: KeyCode,
+    pub clear: KeyCode,
+    pub jump_to: KeyCode,
+    pub cursor_left: KeyCode,
+    pub cursor_right: KeyCode,
+    pub cursor_up: KeyCode,
+    pub cursor_down: KeyCode,
+    pub toggle_cell: KeyCode,
+    pub diagnostics: KeyCode,
+    pub screenshot: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            move_left: KeyCode::KeyH,
+            move_right: KeyCode::KeyL,
+            move_up: KeyCode::KeyK,
+            move_down: KeyCode::KeyJ,
+            zoom_in: KeyCode::KeyI,
+            zoom_out: KeyCode::KeyO,
+            pause: KeyCode::Space,
+            step: KeyCode::KeyN,
+            step_back: KeyCode::KeyB,
+            clear: KeyCode::KeyR,
+            jump_to: KeyCode::KeyG,
+            cursor_left: KeyCode::KeyA,
+            cursor_right: KeyCode::KeyD,
+            cursor_up: KeyCode::KeyW,
+            cursor_down: KeyCode::KeyS,
+            toggle_cell: KeyCode::Enter,
+            diagnostics: KeyCode::F3,
+            screenshot: KeyCode::F12,
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Key bound to an action
+    pub fn key(&self, action: KeyAction) -> KeyCode {
+        match action {
+            KeyAction::MoveLeft => self.move_left,
+            KeyAction::MoveRight => self.move_right,
+            KeyAction::MoveUp => self.move_up,
+            KeyAction::MoveDown => self.move_down,
+            KeyAction::ZoomIn => self.zoom_in,
+            KeyAction::ZoomOut => self.zoom_out,
+            KeyAction::Pause => self.pause,
+            KeyAction::Step => self.step,
+            KeyAction::StepBack => self.step_back,
+            KeyAction::Clear => self.clear,
+            KeyAction::JumpTo => self.jump_to,
+            KeyAction::CursorLeft => self.cursor_left,
+            KeyAction::CursorRight => self.cursor_right,
+            KeyAction::CursorUp => self.cursor_up,
+            KeyAction::CursorDown => self.cursor_down,
+            KeyAction::ToggleCell => self.toggle_cell,
+            KeyAction::Diagnostics => self.diagnostics,
+            KeyAction::Screenshot => self.screenshot,
+        }
+    }
+
+    /// Binds a key to an action
+    pub fn set(&mut self, action: KeyAction, key: KeyCode) {
+        let slot = match action {
+            KeyAction::MoveLeft => &mut self.move_left,
+            KeyAction::MoveRight => &mut self.move_right,
+            KeyAction::MoveUp => &mut self.move_up,
+            KeyAction::MoveDown => &mut self.move_down,
+            KeyAction::ZoomIn => &mut self.zoom_in,
+            KeyAction::ZoomOut => &mut self.zoom_out,
+            KeyAction::Pause => &mut self.pause,
+            KeyAction::Step => &mut self.step,
+            KeyAction::StepBack => &mut self.step_back,
+            KeyAction::Clear => &mut self.clear,
+            KeyAction::JumpTo => &mut self.jump_to,
+            KeyAction::CursorLeft => &mut self.cursor_left,
+            KeyAction::CursorRight => &mut self.cursor_right,
+            KeyAction::CursorUp => &mut self.cursor_up,
+            KeyAction::CursorDown => &mut self.cursor_down,
+            KeyAction::ToggleCell => &mut self.toggle_cell,
+            KeyAction::Diagnostics => &mut self.diagnostics,
+            KeyAction::Screenshot => &mut self.screenshot,
+        };
+        *slot = key;
+    }
+
+    /// Actions sharing their key with another one
+    pub fn conflicts(&self) -> Vec<KeyAction> {
+        KeyAction::ALL
+            .into_iter()
+            .filter(|&action| {
+                KeyAction::ALL
+                    .iter()
+                    .any(|&other| other != action && self.key(other) == self.key(action))
+            })
+            .collect()
+    }
+}
+
+/// Short name of a key, such as `H` for [`KeyCode::KeyH`]
+pub struct KeyName(pub KeyCode);
+
+impl fmt::Display for KeyName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = format!("{:?}", self.0);
+        let short = name
+            .strip_prefix("Key")
+            .or_else(|| name.strip_prefix("Digit"))
+            .unwrap_or(&name);
+        write!(f, "{short}")
+    }
+}
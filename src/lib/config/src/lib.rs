@@ -5,22 +5,45 @@
 pub mod color;
 pub mod constants;
 pub mod display;
+pub mod file;
+pub mod i18n;
+pub mod keys;
 pub mod simulation;
+pub mod storage;
 
 pub use color::*;
 pub use constants::*;
 pub use display::*;
+pub use file::*;
+pub use i18n::*;
+pub use keys::*;
 pub use simulation::*;
+pub use storage::*;
 
-use bevy::prelude::{App, Plugin};
+use bevy::prelude::{App, Last, Plugin, PreUpdate, warn};
+use std::path::Path;
 
-/// Plugin for configuration resources
+/// Plugin for configuration resources.
+///
+/// The resources start from the configuration file, when there is one, and
+/// are saved back to it on exit. On the web, the browser storage holds the
+/// file instead, saved every [`AUTOSAVE_PERIOD`].
 pub struct ConfigPlugin;
 
 impl Plugin for ConfigPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<SimulationConfig>()
-            .init_resource::<DisplayConfig>()
-            .init_resource::<CameraConfig>();
+        let config = ConfigFile::load(Path::new(CONFIG_FILE)).unwrap_or_else(|e| {
+            warn!("{e}, using the default configuration");
+            ConfigFile::default()
+        });
+        set_language(config.display.language);
+        app.insert_resource(config.simulation)
+            .insert_resource(config.display)
+            .insert_resource(config.colors)
+            .insert_resource(config.camera)
+            .insert_resource(config.keys)
+            .add_systems(PreUpdate, apply_language)
+            .add_systems(Last, save_config_on_exit)
+            .add_systems(Last, autosave_config_system);
     }
 }
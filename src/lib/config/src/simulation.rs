@@ -2,21 +2,196 @@
 //!
 //! Configuration parameters for the Game of Life simulation behavior.
 
+use crate::constants::{DEFAULT_TICK_RATE, MAX_PERIOD, MIN_PERIOD};
 use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
 use std::time::Duration;
 
+pub use gol_core::{GridTopology, MargolusRule, Noise};
+
+/// Rulestring of Conway's original Game of Life
+pub const CONWAY_RULE: &str = "B3/S23";
+
+/// Well-known Life-like rules offered as presets, by name.
+///
+/// Immigration and QuadLife are Conway's rule with two and four cell colors,
+/// and the last ones are Generations rules where cells take a few
+/// generations to die.
+pub const RULE_PRESETS: &[(&str, &str)] = &[
+    ("Conway's Life", CONWAY_RULE),
+    ("HighLife", "B36/S23"),
+    ("Day & Night", "B3678/S34678"),
+    ("Seeds", "B2/S"),
+    ("Life without Death", "B3/S012345678"),
+    ("Replicator", "B1357/S1357"),
+    ("Maze", "B3/S12345"),
+    ("2x2", "B36/S125"),
+    ("Immigration", "Immigration"),
+    ("QuadLife", "QuadLife"),
+    ("Brian's Brain", "B2/S/C3"),
+    ("Frogs", "B34/S12/C3"),
+];
+
+/// Block rules offered by the Margolus backend, by name
+pub const MARGOLUS_PRESETS: &[(&str, MargolusRule)] = &[
+    ("Critters", MargolusRule::CRITTERS),
+    ("Tron", MargolusRule::TRON),
+    ("Billiard Balls", MargolusRule::BILLIARD_BALLS),
+];
+
+/// Algorithm used to compute new generations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SimulationBackend {
+    /// Neighbor counting over the living cells, one generation per step
+    #[default]
+    Sparse,
+    /// Quadtree-based HashLife, suited for huge and highly regular patterns
+    HashLife,
+    /// One-dimensional Wolfram automaton, drawing each generation as a new
+    /// row below the previous one
+    Elementary,
+    /// Block automaton on alternating 2×2 partitions of the grid
+    Margolus,
+}
+
+/// Content of the grid when the application starts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StartupPattern {
+    /// Nothing, the grid starts empty
+    Empty,
+    /// Pattern of the registry, by name
+    Named(String),
+    /// Random soup grown from the given seed
+    Soup(u64),
+    /// Pattern file in any supported format
+    File(PathBuf),
+    /// Pattern given as text in any supported format, such as a shared link
+    /// of the web build
+    Text(String),
+}
+
+impl Default for StartupPattern {
+    fn default() -> Self {
+        Self::Named("r-pentomino".to_string())
+    }
+}
+
+impl StartupPattern {
+    /// Parses a startup pattern given on the command line: `empty`,
+    /// `soup:<seed>`, `file:<path>` or the name of a pattern.
+    ///
+    /// A plain `soup` grows the soup of seed 0.
+    pub fn parse(spec: &str) -> Result<Self, StartupPatternError> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            return Err(StartupPatternError::Empty);
+        }
+        if spec == "empty" {
+            return Ok(Self::Empty);
+        }
+        if spec == "soup" {
+            return Ok(Self::Soup(0));
+        }
+        if let Some(seed) = spec.strip_prefix("soup:") {
+            return seed
+                .parse()
+                .map(Self::Soup)
+                .map_err(|_| StartupPatternError::InvalidSeed(seed.to_string()));
+        }
+        if let Some(path) = spec.strip_prefix("file:") {
+            return Ok(Self::File(PathBuf::from(path)));
+        }
+        Ok(Self::Named(spec.to_string()))
+    }
+}
+
+/// Error returned when a startup pattern cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartupPatternError {
+    /// Nothing was given
+    Empty,
+    /// The soup seed is not a number
+    InvalidSeed(String),
+}
+
+impl fmt::Display for StartupPatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty startup pattern"),
+            Self::InvalidSeed(seed) => write!(f, "Invalid soup seed: {seed}"),
+        }
+    }
+}
+
+impl std::error::Error for StartupPatternError {}
+
 /// Configuration parameters for the Game of Life simulation.
 ///
 /// This resource controls the behavior of the simulation including
 /// whether it's running automatically and at what speed.
-#[derive(Resource, Debug)]
+///
+/// Requests and the running state are not saved in the configuration file.
+#[derive(Resource, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SimulationConfig {
     /// Whether the simulation is currently running automatically
+    #[serde(skip)]
     pub running: bool,
     /// Time delay between each generation update
     pub period: Duration,
+    /// Simulation ticks per second, independent of the rendering framerate
+    pub tick_rate: f64,
     /// Flag to trigger a single step calculation when the simulation is paused
+    #[serde(skip)]
     pub calculate_next_gen: bool,
+    /// Flag to rewind one generation from the history when the simulation is paused
+    #[serde(skip)]
+    pub step_back: bool,
+    /// Whether the history is played backwards at the running speed
+    #[serde(skip)]
+    pub playing_backwards: bool,
+    /// Generation picked on the timeline, shown once from the history
+    #[serde(skip)]
+    pub scrub_to: Option<u64>,
+    /// Flag to advance the board until it settles into a cycle
+    #[serde(skip)]
+    pub run_until_stable: bool,
+    /// Whether single steps first show the cells about to change, applying
+    /// them on the next request
+    pub explain_steps: bool,
+    /// Number of past generations kept for stepping backwards (0 disables history)
+    pub history_depth: usize,
+    /// Active rule in B/S notation (e.g. "B3/S23" or "B36/S23")
+    pub rule: String,
+    /// Probabilities of the births and survivals allowed by the rule, only
+    /// honored by the sparse backend. HashLife falls back to the sparse
+    /// backend while the noise is active.
+    pub noise: Noise,
+    /// Algorithm used to advance the board
+    pub backend: SimulationBackend,
+    /// Shape of the grid, only honored by the sparse backend. HashLife falls
+    /// back to the sparse backend on a finite grid, and the elementary and
+    /// Margolus backends always run on the infinite grid.
+    pub topology: GridTopology,
+    /// With the HashLife backend, each step advances 2^n generations
+    pub hashlife_step_log2: u8,
+    /// Wolfram number of the rule run by the elementary backend
+    pub elementary_rule: u8,
+    /// Block rule run by the Margolus backend
+    pub margolus_rule: MargolusRule,
+    /// Generations left to compute in warp mode
+    #[serde(skip)]
+    pub warp_remaining: u64,
+    /// Total number of generations requested by the current warp
+    #[serde(skip)]
+    pub warp_total: u64,
+    /// Content of the grid at startup
+    pub startup_pattern: StartupPattern,
+    /// Whether to pause while the window is unfocused or minimized, resuming
+    /// when it comes back
+    pub pause_when_unfocused: bool,
 }
 
 impl Default for SimulationConfig {
@@ -24,7 +199,72 @@ impl Default for SimulationConfig {
         Self {
             running: true,
             period: Duration::from_secs(1),
+            tick_rate: DEFAULT_TICK_RATE,
             calculate_next_gen: false,
+            step_back: false,
+            playing_backwards: false,
+            scrub_to: None,
+            run_until_stable: false,
+            explain_steps: false,
+            history_depth: 64,
+            rule: CONWAY_RULE.to_string(),
+            noise: Noise::NONE,
+            backend: SimulationBackend::default(),
+            topology: GridTopology::default(),
+            hashlife_step_log2: 0,
+            elementary_rule: 110,
+            margolus_rule: MargolusRule::default(),
+            warp_remaining: 0,
+            warp_total: 0,
+            startup_pattern: StartupPattern::default(),
+            // A background browser tab should not drain the battery
+            pause_when_unfocused: cfg!(target_arch = "wasm32"),
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Target number of generations per second while running
+    pub fn generations_per_second(&self) -> f32 {
+        1.0 / self.period.as_secs_f32()
+    }
+
+    /// Sets the running speed as a number of generations per second,
+    /// clamped to the `MIN_PERIOD..=MAX_PERIOD` range of periods
+    pub fn set_generations_per_second(&mut self, generations_per_second: f32) {
+        let period = 1.0 / generations_per_second;
+        // A NaN speed would make `Duration::from_secs_f32` panic
+        let period = if period.is_nan() {
+            MAX_PERIOD
+        } else {
+            period.clamp(MIN_PERIOD, MAX_PERIOD)
+        };
+        self.period = Duration::from_secs_f32(period);
+    }
+
+    /// Requests `generations` generations to be computed as fast as possible,
+    /// regardless of the frame timer.
+    pub fn warp(&mut self, generations: u64) {
+        self.warp_total = generations;
+        self.warp_remaining = generations;
+    }
+
+    /// Stops the current warp, keeping the generations computed so far
+    pub fn cancel_warp(&mut self) {
+        self.warp_total = 0;
+        self.warp_remaining = 0;
+    }
+
+    /// Whether a warp is in progress
+    pub fn is_warping(&self) -> bool {
+        self.warp_remaining > 0
+    }
+
+    /// Fraction of the current warp already computed (0.0 to 1.0)
+    pub fn warp_progress(&self) -> f32 {
+        if self.warp_total == 0 {
+            return 1.0;
         }
+        (self.warp_total - self.warp_remaining) as f32 / self.warp_total as f32
     }
 }
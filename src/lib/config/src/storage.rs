@@ -0,0 +1,94 @@
+//! # Storage Module
+//!
+//! Browser `localStorage`, standing in for the files of the desktop build
+//! on the web: the configuration is kept under [`CONFIG_FILE`] and the
+//! session under [`SESSION_KEY`]. Elsewhere nothing is stored.
+
+use bevy::prelude::{Local, Res, Time, warn};
+
+use crate::constants::AUTOSAVE_PERIOD;
+use crate::display::{CameraConfig, ColorConfig, DisplayConfig};
+use crate::file::{CONFIG_FILE, ConfigFileRef};
+use crate::keys::KeyBindings;
+use crate::simulation::SimulationConfig;
+
+/// Key of the session in the browser storage
+pub const SESSION_KEY: &str = "gol.session";
+
+/// Whether the browser storage is available, which is only the case on
+/// the web
+pub const fn storage_available() -> bool {
+    cfg!(target_arch = "wasm32")
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Result<web_sys::Storage, String> {
+    web_sys::window()
+        .ok_or("no window")?
+        .local_storage()
+        .map_err(|e| format!("{e:?}"))?
+        .ok_or_else(|| "storage disabled".to_string())
+}
+
+/// Value stored under `key`, if any
+#[cfg(target_arch = "wasm32")]
+pub fn read_storage(key: &str) -> Option<String> {
+    local_storage().ok()?.get_item(key).ok().flatten()
+}
+
+/// Value stored under `key`, never any outside the web
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_storage(_key: &str) -> Option<String> {
+    None
+}
+
+/// Stores `value` under `key`, failing when the storage is unavailable or
+/// full
+#[cfg(target_arch = "wasm32")]
+pub fn write_storage(key: &str, value: &str) -> Result<(), String> {
+    local_storage()?
+        .set_item(key, value)
+        .map_err(|e| format!("{e:?}"))
+}
+
+/// Stores `value` under `key`, always failing outside the web
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_storage(_key: &str, _value: &str) -> Result<(), String> {
+    Err("no browser storage".to_string())
+}
+
+/// Saves the configuration resources to the browser storage every
+/// [`AUTOSAVE_PERIOD`], when they changed, since the application is never
+/// told when its tab closes
+pub fn autosave_config_system(
+    time: Res<Time>,
+    simulation: Res<SimulationConfig>,
+    display: Res<DisplayConfig>,
+    colors: Res<ColorConfig>,
+    camera: Res<CameraConfig>,
+    keys: Res<KeyBindings>,
+    mut last_save: Local<(f32, String)>,
+) {
+    let now = time.elapsed_secs();
+    if !storage_available() || now - last_save.0 < AUTOSAVE_PERIOD {
+        return;
+    }
+    last_save.0 = now;
+    let config = ConfigFileRef {
+        simulation: &simulation,
+        display: &display,
+        colors: &colors,
+        camera: &camera,
+        keys: &keys,
+    };
+    let Ok(content) = config.to_toml() else {
+        return;
+    };
+    if content == last_save.1 {
+        return;
+    }
+    if let Err(e) = write_storage(CONFIG_FILE, &content) {
+        warn!("Could not save the configuration: {e}");
+    }
+    last_save.1 = content;
+}
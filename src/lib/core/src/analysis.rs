@@ -0,0 +1,119 @@
+//! # Analysis Module
+//!
+//! Classification of a pattern as a still life, an oscillator or a spaceship,
+//! by running it until its shape comes back, wherever it moved.
+
+use crate::board::{Board, Cell};
+use crate::rule::Rule;
+use std::fmt;
+
+/// Long-term behavior of a pattern
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Behavior {
+    /// There was nothing to analyze
+    Empty,
+    /// Every cell was dead at the given generation
+    Died { generation: u64 },
+    /// The pattern never changes
+    StillLife,
+    /// The pattern comes back in place after `period` generations
+    Oscillator { period: u64 },
+    /// The pattern comes back moved by (`dx`, `dy`) after `period` generations
+    Spaceship { period: u64, dx: isize, dy: isize },
+    /// The shape did not come back within the generations tried
+    Unknown { generations: u64 },
+}
+
+impl Behavior {
+    /// Speed of a spaceship in the usual notation, such as "c/4 diagonal"
+    /// or "2c/5 orthogonal"
+    pub fn speed(&self) -> Option<String> {
+        let Self::Spaceship { period, dx, dy } = *self else {
+            return None;
+        };
+        let (dx, dy) = (dx.unsigned_abs(), dy.unsigned_abs());
+        let direction = if dx == 0 || dy == 0 {
+            "orthogonal"
+        } else if dx == dy {
+            "diagonal"
+        } else {
+            "oblique"
+        };
+        // Speeds are written as reduced fractions of c, a period-4 shift
+        // by two cells being c/2
+        let distance = dx.max(dy) as u64;
+        let divisor = gcd(distance, period);
+        let speed = match (distance / divisor, period / divisor) {
+            (1, period) => format!("c/{period}"),
+            (distance, period) => format!("{distance}c/{period}"),
+        };
+        Some(format!("{speed} {direction}"))
+    }
+}
+
+impl fmt::Display for Behavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty pattern"),
+            Self::Died { generation } => write!(f, "Dies out at generation {generation}"),
+            Self::StillLife => write!(f, "Still life"),
+            Self::Oscillator { period } => write!(f, "Oscillator of period {period}"),
+            Self::Spaceship { dx, dy, .. } => write!(
+                f,
+                "Spaceship moving {} by ({dx}, {dy}) per period",
+                self.speed().unwrap_or_default()
+            ),
+            Self::Unknown { generations } => {
+                write!(f, "No period found within {generations} generations")
+            }
+        }
+    }
+}
+
+/// Runs the pattern for up to `max_period` generations and classifies it
+/// from the first generation at which its shape, including cell states,
+/// comes back.
+///
+/// Patterns that only become periodic after a while, such as the ones
+/// leaving debris behind, are reported as unknown.
+pub fn analyze(board: &Board, rule: &Rule, max_period: u64) -> Behavior {
+    let Some((start_shape, start_corner)) = shape(board) else {
+        return Behavior::Empty;
+    };
+    let mut current = board.clone();
+    for generation in 1..=max_period {
+        current.advance(rule);
+        let Some((current_shape, corner)) = shape(&current) else {
+            return Behavior::Died { generation };
+        };
+        if current_shape != start_shape {
+            continue;
+        }
+        let (dx, dy) = (corner.x - start_corner.x, corner.y - start_corner.y);
+        return match (generation, dx, dy) {
+            (1, 0, 0) => Behavior::StillLife,
+            (period, 0, 0) => Behavior::Oscillator { period },
+            (period, dx, dy) => Behavior::Spaceship { period, dx, dy },
+        };
+    }
+    Behavior::Unknown {
+        generations: max_period,
+    }
+}
+
+/// Cells and states of a board relative to its bottom-left corner, sorted,
+/// along with that corner
+fn shape(board: &Board) -> Option<(Vec<(Cell, u8)>, Cell)> {
+    let (min, _) = board.bounding_box()?;
+    let mut cells: Vec<(Cell, u8)> = board
+        .iter()
+        .map(|cell| (Cell::new(cell.x - min.x, cell.y - min.y), board.state(cell)))
+        .collect();
+    cells.sort_unstable();
+    Some((cells, min))
+}
+
+/// Greatest common divisor
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
@@ -0,0 +1,406 @@
+//! # Bitboard Module
+//!
+//! Living cells packed in 64×64 chunks, one 64-bit word per row, stepped
+//! with bitwise adders: a single pass over a word counts the neighbors of 64
+//! cells at once, where the sparse board counts them one hash lookup at a time.
+//!
+//! Only two-state totalistic rules over the Moore neighborhood can be
+//! stepped this way, on the unbounded grid. [`Board`] falls back to neighbor
+//! counting for the others.
+//!
+//! A board kept across generations remembers which chunks changed during
+//! the last one. A chunk whose whole neighborhood stayed the same cannot
+//! change either, so still lifes and empty regions are skipped.
+
+use crate::board::{Board, Cell, Step};
+use crate::rule::{Neighborhood, Rule, StateModel};
+use crate::topology::GridTopology;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Side length of a chunk, in cells, one bit per cell of a row
+pub const BIT_CHUNK_SIZE: isize = 64;
+
+/// Rows of a chunk, bit `i` of row `j` standing for the cell at offset (i, j)
+type Chunk = [u64; BIT_CHUNK_SIZE as usize];
+
+const EMPTY: Chunk = [0; BIT_CHUNK_SIZE as usize];
+
+/// Set of living cells stored as chunks of bit rows.
+///
+/// Chunks without any living cell are not stored.
+#[derive(Debug, Default, Clone, Eq)]
+pub struct BitBoard {
+    chunks: FxHashMap<(isize, isize), Chunk>,
+    /// Chunks whose cells changed since the last generation
+    changed: FxHashSet<(isize, isize)>,
+    /// Number of chunks stepped during the last generation
+    active: usize,
+}
+
+impl PartialEq for BitBoard {
+    fn eq(&self, other: &Self) -> bool {
+        self.chunks == other.chunks
+    }
+}
+
+impl FromIterator<Cell> for BitBoard {
+    fn from_iter<I: IntoIterator<Item = Cell>>(iter: I) -> Self {
+        let mut board = Self::default();
+        for cell in iter {
+            board.set(cell, true);
+        }
+        board
+    }
+}
+
+impl BitBoard {
+    /// Creates an empty board
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of living cells
+    pub fn len(&self) -> usize {
+        self.chunks
+            .values()
+            .flatten()
+            .map(|row| row.count_ones() as usize)
+            .sum()
+    }
+
+    /// Whether no cell is alive
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Number of chunks holding at least one living cell
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Number of chunks stepped during the last generation, the others
+    /// lying in regions that did not change
+    pub fn active_chunks(&self) -> usize {
+        self.active
+    }
+
+    /// Whether a rule can be stepped on a bitboard on the given topology
+    pub fn supports(rule: &Rule, topology: &GridTopology) -> bool {
+        rule.states == StateModel::TwoState
+            && rule.transitions.is_none()
+            && rule.neighborhood == Neighborhood::Moore
+            && *topology == GridTopology::Infinite
+    }
+
+    /// Whether the given cell is alive
+    pub fn contains(&self, cell: &Cell) -> bool {
+        let (key, x, y) = locate(*cell);
+        self.chunks
+            .get(&key)
+            .is_some_and(|chunk| (chunk[y] >> x) & 1 == 1)
+    }
+
+    /// Brings a cell to life or kills it
+    pub fn set(&mut self, cell: Cell, alive: bool) {
+        let (key, x, y) = locate(cell);
+        if alive {
+            self.chunks.entry(key).or_insert(EMPTY)[y] |= 1 << x;
+        } else if let Some(chunk) = self.chunks.get_mut(&key) {
+            chunk[y] &= !(1 << x);
+            if chunk.iter().all(|&row| row == 0) {
+                self.chunks.remove(&key);
+            }
+        } else {
+            return;
+        }
+        self.changed.insert(key);
+    }
+
+    /// Iterates over the living cells, chunk by chunk
+    pub fn iter(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.chunks
+            .iter()
+            .flat_map(|(&key, chunk)| chunk_cells(key, chunk, |word, _| word))
+    }
+
+    /// Computes which cells change during the next generation of a two-state
+    /// totalistic rule over the Moore neighborhood.
+    ///
+    /// Births without any living neighbor are ignored, as on the sparse
+    /// board, so that the empty plane stays empty.
+    pub fn compute_step(&self, rule: &Rule) -> Step {
+        self.step_chunks(&self.active_keys(), rule)
+    }
+
+    /// Advances the board by one generation and returns what changed
+    pub fn advance(&mut self, rule: &Rule) -> Step {
+        let keys = self.active_keys();
+        let step = self.step_chunks(&keys, rule);
+        self.active = keys.len();
+        self.changed.clear();
+        for cell in &step.died {
+            self.set(*cell, false);
+        }
+        for cell in &step.born {
+            self.set(*cell, true);
+        }
+        step
+    }
+
+    /// Cells changing during the next generation inside the given chunks
+    fn step_chunks(&self, keys: &[(isize, isize)], rule: &Rule) -> Step {
+        let mut step = Step::default();
+        for &key in keys {
+            let old = self.chunks.get(&key).unwrap_or(&EMPTY);
+            let new = self.next_chunk(key, rule);
+            step.born
+                .extend(chunk_cells(key, &new, |word, row| word & !old[row]));
+            step.died
+                .extend(chunk_cells(key, old, |word, row| word & !new[row]));
+        }
+        step
+    }
+
+    /// Chunks that may change next generation: those next to a chunk that
+    /// changed during the last one, when they hold living cells or border
+    /// cells of a neighbor can spill over to them
+    fn active_keys(&self) -> Vec<(isize, isize)> {
+        let mut keys = Vec::with_capacity(self.changed.len() * 9);
+        for &(cx, cy) in &self.changed {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    keys.push((cx + dx, cy + dy));
+                }
+            }
+        }
+        keys.sort_unstable();
+        keys.dedup();
+        keys.retain(|&key| self.may_hold_cells(key));
+        keys
+    }
+
+    /// Whether the chunk at `key` holds living cells or one of its neighbors
+    /// has living cells on the border they share
+    fn may_hold_cells(&self, (cx, cy): (isize, isize)) -> bool {
+        let last = BIT_CHUNK_SIZE as usize - 1;
+        (-1..=1).any(|dy: isize| {
+            (-1..=1).any(|dx: isize| {
+                let Some(chunk) = self.chunks.get(&(cx + dx, cy + dy)) else {
+                    return false;
+                };
+                match (dx, dy) {
+                    (0, 0) => true,
+                    // The neighbor to the west spills over with its east column
+                    (-1, 0) => chunk.iter().any(|row| row >> 63 != 0),
+                    (1, 0) => chunk.iter().any(|row| row & 1 != 0),
+                    (0, -1) => chunk[last] != 0,
+                    (0, 1) => chunk[0] != 0,
+                    // Corner neighbors only spill over with their corner cell
+                    _ => {
+                        let x = if dx == -1 { 63 } else { 0 };
+                        let y = if dy == -1 { last } else { 0 };
+                        (chunk[y] >> x) & 1 == 1
+                    }
+                }
+            })
+        })
+    }
+
+    /// Next generation of the chunk at `key`, from its own rows and the
+    /// border cells of its eight neighbors
+    fn next_chunk(&self, (cx, cy): (isize, isize), rule: &Rule) -> Chunk {
+        let chunk = |dx: isize, dy: isize| self.chunks.get(&(cx + dx, cy + dy)).unwrap_or(&EMPTY);
+        let last = BIT_CHUNK_SIZE as usize - 1;
+        // Rows -1 to 64 of the west, middle and east columns of chunks
+        let padded = |dx: isize| {
+            let mut rows = [0u64; BIT_CHUNK_SIZE as usize + 2];
+            rows[0] = chunk(dx, -1)[last];
+            rows[1..=BIT_CHUNK_SIZE as usize].copy_from_slice(chunk(dx, 0));
+            rows[BIT_CHUNK_SIZE as usize + 1] = chunk(dx, 1)[0];
+            rows
+        };
+        let (west, middle, east) = (padded(-1), padded(0), padded(1));
+        // Cells one step west and east of each cell of the row
+        let shifted = |i: usize| {
+            (
+                (middle[i] << 1) | (west[i] >> 63),
+                (middle[i] >> 1) | (east[i] << 63),
+            )
+        };
+
+        let mut next = EMPTY;
+        for (row, word) in next.iter_mut().enumerate() {
+            let (below, center, above) = (row, row + 1, row + 2);
+            let (below_w, below_e) = shifted(below);
+            let (center_w, center_e) = shifted(center);
+            let (above_w, above_e) = shifted(above);
+            let counts = count_neighbors([
+                below_w,
+                middle[below],
+                below_e,
+                center_w,
+                center_e,
+                above_w,
+                middle[above],
+                above_e,
+            ]);
+            *word = apply_rule(rule, middle[center], counts);
+        }
+        next
+    }
+}
+
+impl From<&Board> for BitBoard {
+    fn from(board: &Board) -> Self {
+        board.iter().copied().collect()
+    }
+}
+
+/// Chunk holding a cell, with the column and row of the cell inside it
+fn locate(cell: Cell) -> ((isize, isize), usize, usize) {
+    (
+        (
+            cell.x.div_euclid(BIT_CHUNK_SIZE),
+            cell.y.div_euclid(BIT_CHUNK_SIZE),
+        ),
+        cell.x.rem_euclid(BIT_CHUNK_SIZE) as usize,
+        cell.y.rem_euclid(BIT_CHUNK_SIZE) as usize,
+    )
+}
+
+/// Cells of the set bits of a chunk, after passing each row and its index
+/// through `select`
+fn chunk_cells<'a>(
+    (cx, cy): (isize, isize),
+    chunk: &'a Chunk,
+    select: impl Fn(u64, usize) -> u64 + 'a,
+) -> impl Iterator<Item = Cell> + 'a {
+    chunk.iter().enumerate().flat_map(move |(row, &word)| {
+        let mut bits = select(word, row);
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let x = bits.trailing_zeros() as isize;
+            bits &= bits - 1;
+            Some(Cell::new(
+                cx * BIT_CHUNK_SIZE + x,
+                cy * BIT_CHUNK_SIZE + row as isize,
+            ))
+        })
+    })
+}
+
+/// Sum and carry of three one-bit numbers, for 64 of them at once
+fn full_add(a: u64, b: u64, c: u64) -> (u64, u64) {
+    let partial = a ^ b;
+    (partial ^ c, (a & b) | (partial & c))
+}
+
+/// Number of set bits among the eight words at each bit position, as the
+/// four bits of a count from 0 to 8, lowest first
+fn count_neighbors(n: [u64; 8]) -> [u64; 4] {
+    let (sum_a, carry_a) = full_add(n[0], n[1], n[2]);
+    let (sum_b, carry_b) = full_add(n[3], n[4], n[5]);
+    let (sum_c, carry_c) = (n[6] ^ n[7], n[6] & n[7]);
+    let (ones, carry_d) = full_add(sum_a, sum_b, sum_c);
+    let (twos_sum, twos_carry) = full_add(carry_a, carry_b, carry_c);
+    let (twos, carry_e) = (twos_sum ^ carry_d, twos_sum & carry_d);
+    let (fours, eights) = (twos_carry ^ carry_e, twos_carry & carry_e);
+    [ones, twos, fours, eights]
+}
+
+/// Next row of cells from their current row and their neighbor counts
+fn apply_rule(rule: &Rule, alive: u64, counts: [u64; 4]) -> u64 {
+    let mut next = 0;
+    for count in 0..=8usize {
+        let matches = (0..4).fold(!0u64, |mask, bit| {
+            let plane = counts[bit];
+            mask & if (count >> bit) & 1 == 1 {
+                plane
+            } else {
+                !plane
+            }
+        });
+        if count > 0 && rule.is_birth(count) {
+            next |= matches & !alive;
+        }
+        if rule.is_survival(count) {
+            next |= matches & alive;
+        }
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps `cells` on a sparse board and on a bitboard, checking that both
+    /// agree after every generation
+    fn assert_matches_sparse(cells: &[(isize, isize)], generations: usize) -> BitBoard {
+        let mut board: Board = cells.iter().map(|&(x, y)| Cell::new(x, y)).collect();
+        let mut bits = BitBoard::from(&board);
+        for generation in 1..=generations {
+            board.advance(&Rule::CONWAY);
+            bits.advance(&Rule::CONWAY);
+            let mut expected: Vec<Cell> = board.iter().copied().collect();
+            let mut actual: Vec<Cell> = bits.iter().collect();
+            expected.sort_unstable();
+            actual.sort_unstable();
+            assert_eq!(actual, expected, "generation {generation}");
+        }
+        bits
+    }
+
+    #[test]
+    fn blinker_spills_over_chunk_borders() {
+        // Vertical blinkers centered on the corners of four chunks
+        for (x, y) in [(63, 63), (64, 64), (0, 0), (-1, -1)] {
+            assert_matches_sparse(&[(x, y - 1), (x, y), (x, y + 1)], 4);
+        }
+    }
+
+    #[test]
+    fn glider_crosses_chunks() {
+        // Heads towards positive x and negative y, through the chunk at the origin
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let start: Vec<_> = glider.iter().map(|&(x, y)| (x + 60, y - 4)).collect();
+        let bits = assert_matches_sparse(&start, 40);
+        assert_eq!(bits.len(), 5);
+    }
+
+    #[test]
+    fn stable_chunks_are_skipped() {
+        // A block far from a blinker keeps its chunk out of the stepping
+        let mut bits: BitBoard = [
+            (0, 0),
+            (1, 0),
+            (0, 1),
+            (1, 1),
+            (500, 499),
+            (500, 500),
+            (500, 501),
+        ]
+        .into_iter()
+        .map(|(x, y)| Cell::new(x, y))
+        .collect();
+        bits.advance(&Rule::CONWAY);
+        let first = bits.active_chunks();
+        bits.advance(&Rule::CONWAY);
+        assert!(bits.active_chunks() < first);
+        assert_eq!(bits.len(), 7);
+    }
+
+    #[test]
+    fn supported_rules() {
+        assert!(BitBoard::supports(&Rule::CONWAY, &GridTopology::Infinite));
+        let torus = GridTopology::Torus {
+            width: 64,
+            height: 64,
+        };
+        assert!(!BitBoard::supports(&Rule::CONWAY, &torus));
+        let von_neumann = Rule::parse("B2/S013V").unwrap();
+        assert!(!BitBoard::supports(&von_neumann, &GridTopology::Infinite));
+    }
+}
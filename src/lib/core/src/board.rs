@@ -0,0 +1,381 @@
+//! # Board Module
+//!
+//! Sparse set of living cells on an unbounded grid and single-generation stepping.
+
+use crate::bitboard::BitBoard;
+use crate::rule::{NEIGHBORS, Neighborhood, Rule, StateModel};
+use crate::topology::GridTopology;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Coordinates of a cell on the grid.
+///
+/// Uses signed integers so the grid can expand in all directions.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Cell {
+    /// The x-coordinate of the cell
+    pub x: isize,
+    /// The y-coordinate of the cell
+    pub y: isize,
+}
+
+impl Cell {
+    /// Creates a cell at (x, y)
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Cells that changed state during one generation
+#[derive(Debug, Default, Clone)]
+pub struct Step {
+    /// Dead cells that came alive
+    pub born: Vec<Cell>,
+    /// Living cells that died
+    pub died: Vec<Cell>,
+    /// New states of born cells that are not in state 1 and of living
+    /// cells whose state changed
+    pub states: Vec<(Cell, u8)>,
+}
+
+impl Step {
+    /// Whether no cell changed, as in a still life
+    pub fn is_empty(&self) -> bool {
+        self.born.is_empty() && self.died.is_empty() && self.states.is_empty()
+    }
+}
+
+/// Set of living cells.
+///
+/// Living cells are in state 1 unless the rule gives them more states, such
+/// as the colors of QuadLife. State 0 stands for a dead cell.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Board {
+    cells: FxHashSet<Cell>,
+    /// States of the living cells that are not in state 1
+    states: FxHashMap<Cell, u8>,
+}
+
+impl FromIterator<Cell> for Board {
+    fn from_iter<I: IntoIterator<Item = Cell>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+            states: FxHashMap::default(),
+        }
+    }
+}
+
+impl Board {
+    /// Creates an empty board
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of living cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether no cell is alive
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether the given cell is alive
+    pub fn contains(&self, cell: &Cell) -> bool {
+        self.cells.contains(cell)
+    }
+
+    /// Sets the state of a cell
+    pub fn set(&mut self, cell: Cell, alive: bool) {
+        self.set_state(cell, alive as u8);
+    }
+
+    /// State of a cell, 0 when dead
+    pub fn state(&self, cell: &Cell) -> u8 {
+        if !self.cells.contains(cell) {
+            return 0;
+        }
+        self.states.get(cell).copied().unwrap_or(1)
+    }
+
+    /// Sets the state of a cell, 0 killing it
+    pub fn set_state(&mut self, cell: Cell, state: u8) {
+        if state == 0 {
+            self.cells.remove(&cell);
+        } else {
+            self.cells.insert(cell);
+        }
+        if state > 1 {
+            self.states.insert(cell, state);
+        } else {
+            self.states.remove(&cell);
+        }
+    }
+
+    /// Kills every cell
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.states.clear();
+    }
+
+    /// Iterates over the living cells in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &Cell> + '_ {
+        self.cells.iter()
+    }
+
+    /// Smallest rectangle containing every living cell, as (min, max) corners
+    pub fn bounding_box(&self) -> Option<(Cell, Cell)> {
+        let mut cells = self.cells.iter();
+        let first = *cells.next()?;
+        Some(cells.fold((first, first), |(min, max), cell| {
+            (
+                Cell::new(min.x.min(cell.x), min.y.min(cell.y)),
+                Cell::new(max.x.max(cell.x), max.y.max(cell.y)),
+            )
+        }))
+    }
+
+    /// Computes which cells change during the next generation without applying it
+    pub fn compute_step(&self, rule: &Rule) -> Step {
+        self.compute_step_in(rule, &GridTopology::Infinite)
+    }
+
+    /// Computes the next generation on the given grid topology.
+    ///
+    /// Cells lying outside a finite grid die.
+    pub fn compute_step_in(&self, rule: &Rule, topology: &GridTopology) -> Step {
+        if let StateModel::Generations(count) = rule.states {
+            return self.compute_generations_step(rule, topology, count);
+        }
+        let mut step = if rule.transitions.is_some() {
+            self.compute_configuration_step(rule, topology)
+        } else {
+            self.compute_counting_step(rule, topology)
+        };
+        if let StateModel::Colors(colors) = rule.states {
+            step.states = step
+                .born
+                .iter()
+                .map(|cell| (*cell, self.majority_color(*cell, rule, topology, colors)))
+                .filter(|&(_, color)| color != 1)
+                .collect();
+        }
+        step
+    }
+
+    /// Next generation of a totalistic rule, from the number of living neighbors.
+    ///
+    /// The Moore neighborhood on the unbounded grid is counted on a
+    /// [`BitBoard`], 64 cells at a time.
+    fn compute_counting_step(&self, rule: &Rule, topology: &GridTopology) -> Step {
+        if rule.neighborhood == Neighborhood::Moore && *topology == GridTopology::Infinite {
+            return BitBoard::from(self).compute_step(rule);
+        }
+        let neighbor_counts =
+            calculate_neighbor_counts_in(self.cells.iter().copied(), rule.neighborhood, topology);
+
+        let died = self
+            .cells
+            .iter()
+            .filter(|cell| {
+                !topology.contains(**cell)
+                    || !rule.is_survival(neighbor_counts.get(*cell).copied().unwrap_or(0))
+            })
+            .copied()
+            .collect();
+        let born = neighbor_counts
+            .iter()
+            .filter(|(cell, count)| rule.is_birth(**count) && !self.cells.contains(*cell))
+            .map(|(cell, _)| *cell)
+            .collect();
+
+        Step {
+            born,
+            died,
+            states: Vec::new(),
+        }
+    }
+
+    /// Next generation of a non-totalistic rule, which looks at which
+    /// neighbors are alive rather than how many
+    fn compute_configuration_step(&self, rule: &Rule, topology: &GridTopology) -> Step {
+        let neighbor = |cell: &Cell, (dx, dy): (isize, isize)| {
+            topology.wrap(Cell::new(cell.x + dx, cell.y + dy))
+        };
+        let configuration = |cell: &Cell| -> u8 {
+            NEIGHBORS
+                .iter()
+                .enumerate()
+                .filter(|&(_, &offset)| {
+                    neighbor(cell, offset).is_some_and(|n| self.cells.contains(&n))
+                })
+                .fold(0, |mask, (i, _)| mask | 1 << i)
+        };
+
+        let died = self
+            .cells
+            .iter()
+            .filter(|cell| {
+                !topology.contains(**cell) || !rule.next_state(true, configuration(cell))
+            })
+            .copied()
+            .collect();
+        let candidates: FxHashSet<Cell> = self
+            .cells
+            .iter()
+            .flat_map(|cell| {
+                NEIGHBORS
+                    .iter()
+                    .filter_map(|&offset| neighbor(cell, offset))
+            })
+            .filter(|cell| !self.cells.contains(cell))
+            .collect();
+        let born = candidates
+            .into_iter()
+            .filter(|cell| rule.next_state(false, configuration(cell)))
+            .collect();
+
+        Step {
+            born,
+            died,
+            states: Vec::new(),
+        }
+    }
+
+    /// Next generation of a Generations rule with `count` states.
+    ///
+    /// Births and survivals follow the two-state rule over the cells in
+    /// state 1, then every other living cell moves on to the next state.
+    fn compute_generations_step(&self, rule: &Rule, topology: &GridTopology, count: u8) -> Step {
+        let firing: Board = self
+            .cells
+            .iter()
+            .filter(|cell| self.state(cell) == 1)
+            .copied()
+            .collect();
+        let two_state = Rule {
+            states: StateModel::TwoState,
+            ..*rule
+        };
+        let firing_step = firing.compute_step_in(&two_state, topology);
+
+        let mut step = Step {
+            // Dying cells are not dead yet and cannot be born again
+            born: firing_step
+                .born
+                .into_iter()
+                .filter(|cell| !self.cells.contains(cell))
+                .collect(),
+            ..Step::default()
+        };
+        for cell in firing_step.died {
+            if topology.contains(cell) {
+                step.states.push((cell, 2));
+            } else {
+                step.died.push(cell);
+            }
+        }
+        for (&cell, &state) in &self.states {
+            if state < count - 1 && topology.contains(cell) {
+                step.states.push((cell, state + 1));
+            } else {
+                step.died.push(cell);
+            }
+        }
+        step
+    }
+
+    /// Color of a cell born among the given living neighbors.
+    ///
+    /// The most common color among the parents wins. When they are tied, a
+    /// single color missing from them is picked, so that three parents of
+    /// different colors give birth to the fourth color of QuadLife, and the
+    /// lowest tied color otherwise.
+    fn majority_color(&self, cell: Cell, rule: &Rule, topology: &GridTopology, colors: u8) -> u8 {
+        let mut counts = vec![0usize; colors as usize + 1];
+        for &(dx, dy) in rule.neighborhood.offsets() {
+            let Some(neighbor) = topology.wrap(Cell::new(cell.x + dx, cell.y + dy)) else {
+                continue;
+            };
+            let state = self.state(&neighbor) as usize;
+            if (1..counts.len()).contains(&state) {
+                counts[state] += 1;
+            }
+        }
+
+        let max = counts[1..].iter().copied().max().unwrap_or(0);
+        let tied: Vec<usize> = (1..counts.len()).filter(|&c| counts[c] == max).collect();
+        let missing: Vec<usize> = (1..counts.len()).filter(|&c| counts[c] == 0).collect();
+        match (tied.as_slice(), missing.as_slice()) {
+            ([color], _) => *color as u8,
+            (_, [color]) => *color as u8,
+            _ => tied.first().copied().unwrap_or(1) as u8,
+        }
+    }
+
+    /// Applies a previously computed step
+    pub fn apply(&mut self, step: &Step) {
+        for cell in &step.died {
+            self.set_state(*cell, 0);
+        }
+        for cell in &step.born {
+            self.set_state(*cell, 1);
+        }
+        for &(cell, state) in &step.states {
+            self.set_state(cell, state);
+        }
+    }
+
+    /// Advances the board by one generation and returns what changed
+    pub fn advance(&mut self, rule: &Rule) -> Step {
+        self.advance_in(rule, &GridTopology::Infinite)
+    }
+
+    /// Advances the board by one generation on the given grid topology
+    pub fn advance_in(&mut self, rule: &Rule, topology: &GridTopology) -> Step {
+        let step = self.compute_step_in(rule, topology);
+        self.apply(&step);
+        step
+    }
+}
+
+/// Calculates neighbor counts for all relevant positions
+///
+/// Returns a map of positions to their neighbor counts, including both
+/// alive cells and their neighboring empty positions that might become alive.
+pub fn calculate_neighbor_counts<I>(alive_cells: I) -> FxHashMap<Cell, usize>
+where
+    I: Iterator<Item = Cell> + Clone,
+{
+    calculate_neighbor_counts_in(alive_cells, Neighborhood::Moore, &GridTopology::Infinite)
+}
+
+/// Calculates neighbor counts over the given neighborhood and grid topology
+///
+/// Neighbors past the edge of a bounded grid are ignored and those of a
+/// torus wrap around to the opposite edge.
+pub fn calculate_neighbor_counts_in<I>(
+    alive_cells: I,
+    neighborhood: Neighborhood,
+    topology: &GridTopology,
+) -> FxHashMap<Cell, usize>
+where
+    I: Iterator<Item = Cell> + Clone,
+{
+    let cell_count = alive_cells.clone().count();
+    let mut neighbors: FxHashMap<Cell, usize> = FxHashMap::with_capacity_and_hasher(
+        cell_count * (neighborhood.size() + 1),
+        Default::default(),
+    );
+
+    for cell in alive_cells {
+        for &(dx, dy) in neighborhood.offsets() {
+            let Some(neighbor) = topology.wrap(Cell::new(cell.x + dx, cell.y + dy)) else {
+                continue;
+            };
+            *neighbors.entry(neighbor).or_insert(0) += 1;
+        }
+    }
+
+    neighbors
+}
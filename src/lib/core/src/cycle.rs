@@ -0,0 +1,72 @@
+//! # Cycle Module
+//!
+//! Detection of the generation from which a board repeats itself, by
+//! remembering a fingerprint of every board state seen. Boards sending
+//! spaceships away never repeat, so a population that keeps cycling is
+//! accepted as well.
+
+use crate::board::Board;
+use crate::noise::SplitMix64;
+use crate::rule::Rule;
+use crate::soup::{MAX_SETTLED_PERIOD, SETTLED_WINDOW, settled_period};
+use crate::topology::GridTopology;
+use rustc_hash::FxHashMap;
+
+/// Periodic behavior reached by a board
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// Generations between two repetitions, 1 for a still board
+    pub period: u64,
+    /// Generation, counted from the start, at which the cycle was entered
+    pub start: u64,
+    /// Whether the whole board repeats, rather than only its population
+    pub exact: bool,
+}
+
+/// Hash of the living cells and their states, independent of the order in
+/// which the board stores them
+pub fn fingerprint(board: &Board) -> u64 {
+    board.iter().fold(0u64, |hash, cell| {
+        let position = (cell.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ cell.y as u64;
+        let state = u64::from(board.state(cell)) << 56;
+        hash.wrapping_add(SplitMix64::mix(SplitMix64::mix(position) ^ state))
+    })
+}
+
+/// Advances the board until one of its states comes back, or for at most
+/// `max_generations`.
+///
+/// Returns the number of generations computed and the cycle found, if any.
+/// The board is left at the first repetition, one period after the start of
+/// the cycle.
+pub fn run_until_cycle(
+    board: &mut Board,
+    rule: &Rule,
+    topology: &GridTopology,
+    max_generations: u64,
+) -> (u64, Option<Cycle>) {
+    let mut seen: FxHashMap<u64, u64> = FxHashMap::default();
+    seen.insert(fingerprint(board), 0);
+    let mut populations = vec![board.len()];
+    for generation in 1..=max_generations {
+        board.advance_in(rule, topology);
+        if let Some(start) = seen.insert(fingerprint(board), generation) {
+            let cycle = Cycle {
+                period: generation - start,
+                start,
+                exact: true,
+            };
+            return (generation, Some(cycle));
+        }
+        populations.push(board.len());
+        if let Some(period) = settled_period(&populations, MAX_SETTLED_PERIOD) {
+            let cycle = Cycle {
+                period: period as u64,
+                start: generation.saturating_sub((SETTLED_WINDOW + period) as u64),
+                exact: false,
+            };
+            return (generation, Some(cycle));
+        }
+    }
+    (max_generations, None)
+}
@@ -0,0 +1,58 @@
+//! # Elementary Module
+//!
+//! Wolfram's one-dimensional elementary automata, where each cell of a row
+//! looks at itself and its two neighbors to decide its state in the next row.
+
+use rustc_hash::FxHashSet;
+use std::fmt;
+
+/// Elementary rule, numbered from 0 to 255 after Wolfram.
+///
+/// Bit `n` of the number is the next state of a cell whose left, own and
+/// right states read `n` in binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ElementaryRule(pub u8);
+
+impl ElementaryRule {
+    /// Rule 110, which is Turing complete
+    pub const RULE_110: Self = Self(110);
+
+    /// Whether a cell is alive in the next row, given its neighborhood
+    pub fn next_state(&self, left: bool, center: bool, right: bool) -> bool {
+        let pattern = (left as u8) << 2 | (center as u8) << 1 | right as u8;
+        self.0 & (1 << pattern) != 0
+    }
+
+    /// Living cells of the next row, from the x coordinates of the living
+    /// cells of a row, in increasing order.
+    ///
+    /// The background stays dead, so only the cells next to a living one are
+    /// computed, even for rules where three dead cells give birth.
+    pub fn next_row(&self, row: &FxHashSet<isize>) -> Vec<isize> {
+        let candidates: FxHashSet<isize> = row.iter().flat_map(|&x| x - 1..=x + 1).collect();
+        let mut next: Vec<isize> = candidates
+            .into_iter()
+            .filter(|x| {
+                self.next_state(
+                    row.contains(&(x - 1)),
+                    row.contains(x),
+                    row.contains(&(x + 1)),
+                )
+            })
+            .collect();
+        next.sort_unstable();
+        next
+    }
+}
+
+impl Default for ElementaryRule {
+    fn default() -> Self {
+        Self::RULE_110
+    }
+}
+
+impl fmt::Display for ElementaryRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rule {}", self.0)
+    }
+}
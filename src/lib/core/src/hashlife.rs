@@ -0,0 +1,542 @@
+//! # HashLife Module
+//!
+//! Implementation of Gosper's HashLife algorithm.
+//!
+//! The board is stored as a hash-consed quadtree whose nodes memoize their
+//! future, so highly regular patterns can be advanced by millions of
+//! generations at once.
+//!
+//! Rules with birth on 0 neighbors (B0) are not supported.
+
+use crate::bitboard::BitBoard;
+use crate::rule::{NEIGHBORS, Rule};
+use crate::topology::GridTopology;
+use rustc_hash::FxHashMap;
+
+/// Number of nodes after which the node arena is compacted
+const MAX_NODES: usize = 1 << 22;
+/// Largest step, so the padded root (level step_log2 + 3) keeps i64 coordinates
+pub const MAX_STEP_LOG2: u8 = 60;
+
+/// Index of a node in the [`Universe`] arena
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// Level 0 dead cell
+    const DEAD: Self = Self(0);
+    /// Level 0 living cell
+    const ALIVE: Self = Self(1);
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Quadtree node covering a 2^level × 2^level square
+struct Node {
+    level: u8,
+    population: u64,
+    /// Quadrants in `[nw, ne, sw, se]` order (unused for level 0 leaves)
+    children: [NodeId; 4],
+    /// Memoized center of this node advanced by the universe step
+    result: Option<NodeId>,
+}
+
+/// Hash-consed quadtree holding an entire board.
+///
+/// The root is centered on the origin: a root of level `k` covers
+/// `[-2^(k-1), 2^(k-1))` on both axes.
+pub struct Universe {
+    nodes: Vec<Node>,
+    cache: FxHashMap<[NodeId; 4], NodeId>,
+    empty: Vec<NodeId>,
+    root: NodeId,
+    rule: Rule,
+    step_log2: u8,
+    generation: u64,
+}
+
+impl Universe {
+    /// Whether a rule can be advanced by HashLife on the given topology.
+    ///
+    /// The quadtree counts the Moore neighbors of two-state cells like the
+    /// bitboard does, and an empty node must stay empty.
+    pub fn supports(rule: &Rule, topology: &GridTopology) -> bool {
+        BitBoard::supports(rule, topology) && !rule.is_birth(0)
+    }
+
+    /// Creates an empty universe using the given rule
+    pub fn new(rule: Rule) -> Self {
+        let leaf = |population| Node {
+            level: 0,
+            population,
+            children: [NodeId::DEAD; 4],
+            result: None,
+        };
+        let mut universe = Self {
+            nodes: vec![leaf(0), leaf(1)],
+            cache: FxHashMap::default(),
+            empty: vec![NodeId::DEAD],
+            root: NodeId::DEAD,
+            rule,
+            step_log2: 0,
+            generation: 0,
+        };
+        universe.root = universe.empty(3);
+        universe
+    }
+
+    /// Creates a universe containing the given living cells
+    pub fn from_cells(rule: Rule, cells: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        let mut universe = Self::new(rule);
+        for (x, y) in cells {
+            universe.set_cell(x, y, true);
+        }
+        universe
+    }
+
+    /// Number of living cells
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root.index()].population
+    }
+
+    /// Number of generations computed since creation
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Number of nodes currently allocated in the arena
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Changes the rule, invalidating memoized results if needed.
+    ///
+    /// Only radius-1 neighborhoods are supported, counts beyond are ignored.
+    pub fn set_rule(&mut self, rule: Rule) {
+        if self.rule != rule {
+            self.rule = rule;
+            self.clear_results();
+        }
+    }
+
+    /// Sets how many generations a single [`Universe::step`] computes
+    /// (2^step_log2), up to 2^[`MAX_STEP_LOG2`]
+    pub fn set_step_log2(&mut self, step_log2: u8) {
+        let step_log2 = step_log2.min(MAX_STEP_LOG2);
+        if self.step_log2 != step_log2 {
+            self.step_log2 = step_log2;
+            self.clear_results();
+        }
+    }
+
+    /// Kills every cell, keeping the rule, the step size and the generation
+    /// count
+    pub fn clear(&mut self) {
+        self.root = self.empty(3);
+    }
+
+    /// Sets the state of the cell at (x, y)
+    pub fn set_cell(&mut self, x: i64, y: i64, alive: bool) {
+        loop {
+            let half = 1i64 << (self.level(self.root) - 1);
+            if (-half..half).contains(&x) && (-half..half).contains(&y) {
+                break;
+            }
+            self.expand();
+        }
+        self.root = self.set_rec(self.root, x, y, alive);
+    }
+
+    /// Advances the universe by 2^step_log2 generations
+    pub fn step(&mut self) {
+        // Pad the root until the pattern sits in its central sixteenth, so
+        // nothing can escape the result area during the step.
+        loop {
+            let root = self.root;
+            if self.level(root) >= self.step_log2 + 3 {
+                let inner = self.centre(root);
+                let inner = self.centre(inner);
+                if self.nodes[inner.index()].population == self.population() {
+                    break;
+                }
+            }
+            self.expand();
+        }
+        self.root = self.successor(self.root);
+        self.generation += 1 << self.step_log2;
+
+        if self.nodes.len() > MAX_NODES {
+            self.compact();
+        }
+    }
+
+    /// Returns the living cells, optionally restricted to an inclusive rectangle
+    pub fn cells(&self, bounds: Option<((i64, i64), (i64, i64))>) -> Vec<(i64, i64)> {
+        let mut cells = Vec::new();
+        let half = 1i64 << (self.level(self.root) - 1);
+        self.collect_cells(self.root, -half, -half, bounds, &mut cells);
+        cells
+    }
+
+    /// Replaces the board with the quadtree rooted at `root`, centered on the origin
+    pub(crate) fn set_root(&mut self, root: NodeId) {
+        self.root = root;
+        while self.level(self.root) < 3 {
+            self.expand();
+        }
+    }
+
+    /// Returns the level 0 node of a dead or living cell
+    pub(crate) fn leaf(alive: bool) -> NodeId {
+        if alive { NodeId::ALIVE } else { NodeId::DEAD }
+    }
+
+    pub(crate) fn level(&self, id: NodeId) -> u8 {
+        self.nodes[id.index()].level
+    }
+
+    fn children(&self, id: NodeId) -> [NodeId; 4] {
+        self.nodes[id.index()].children
+    }
+
+    /// Returns the canonical node with the given quadrants
+    pub(crate) fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+        if let Some(&id) = self.cache.get(&children) {
+            return id;
+        }
+        let id = NodeId(self.nodes.len() as u32);
+        let level = self.level(children[0]) + 1;
+        let population = children
+            .iter()
+            .map(|child| self.nodes[child.index()].population)
+            .sum();
+        self.nodes.push(Node {
+            level,
+            population,
+            children,
+            result: None,
+        });
+        self.cache.insert(children, id);
+        id
+    }
+
+    /// Returns the empty node of the given level
+    pub(crate) fn empty(&mut self, level: u8) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let below = self.empty[self.empty.len() - 1];
+            let node = self.join([below; 4]);
+            self.empty.push(node);
+        }
+        self.empty[level as usize]
+    }
+
+    /// Doubles the size of the root, keeping the pattern centered
+    fn expand(&mut self) {
+        let [nw, ne, sw, se] = self.children(self.root);
+        let e = self.empty(self.level(self.root) - 1);
+        let children = [
+            self.join([e, e, e, nw]),
+            self.join([e, e, ne, e]),
+            self.join([e, sw, e, e]),
+            self.join([se, e, e, e]),
+        ];
+        self.root = self.join(children);
+    }
+
+    /// Returns the central half of a node (one level below)
+    fn centre(&mut self, id: NodeId) -> NodeId {
+        let [nw, ne, sw, se] = self.children(id);
+        self.join([
+            self.children(nw)[3],
+            self.children(ne)[2],
+            self.children(sw)[1],
+            self.children(se)[0],
+        ])
+    }
+
+    fn set_rec(&mut self, id: NodeId, x: i64, y: i64, alive: bool) -> NodeId {
+        let level = self.level(id);
+        if level == 0 {
+            return if alive { NodeId::ALIVE } else { NodeId::DEAD };
+        }
+        let mut children = self.children(id);
+        let quadrant = usize::from(y >= 0) * 2 + usize::from(x >= 0);
+        children[quadrant] = if level == 1 {
+            self.set_rec(children[quadrant], 0, 0, alive)
+        } else {
+            let half = 1i64 << (level - 2);
+            let x = if x < 0 { x + half } else { x - half };
+            let y = if y < 0 { y + half } else { y - half };
+            self.set_rec(children[quadrant], x, y, alive)
+        };
+        self.join(children)
+    }
+
+    fn collect_cells(
+        &self,
+        id: NodeId,
+        x0: i64,
+        y0: i64,
+        bounds: Option<((i64, i64), (i64, i64))>,
+        cells: &mut Vec<(i64, i64)>,
+    ) {
+        let node = &self.nodes[id.index()];
+        if node.population == 0 {
+            return;
+        }
+        let size = 1i64 << node.level;
+        if let Some(((min_x, min_y), (max_x, max_y))) = bounds
+            && (x0 > max_x || y0 > max_y || x0 + size <= min_x || y0 + size <= min_y)
+        {
+            return;
+        }
+        if node.level == 0 {
+            cells.push((x0, y0));
+            return;
+        }
+        let half = size / 2;
+        let [nw, ne, sw, se] = node.children;
+        self.collect_cells(nw, x0, y0, bounds, cells);
+        self.collect_cells(ne, x0 + half, y0, bounds, cells);
+        self.collect_cells(sw, x0, y0 + half, bounds, cells);
+        self.collect_cells(se, x0 + half, y0 + half, bounds, cells);
+    }
+
+    /// Computes the 2×2 center of a 4×4 node one generation later
+    fn base_case(&mut self, id: NodeId) -> NodeId {
+        let mut grid = [[false; 4]; 4];
+        for (quadrant, (ox, oy)) in
+            self.children(id)
+                .into_iter()
+                .zip([(0, 0), (2, 0), (0, 2), (2, 2)])
+        {
+            for (i, leaf) in self.children(quadrant).into_iter().enumerate() {
+                grid[oy + i / 2][ox + i % 2] = leaf == NodeId::ALIVE;
+            }
+        }
+
+        let mut result = [NodeId::DEAD; 4];
+        for (i, (x, y)) in [(1, 1), (2, 1), (1, 2), (2, 2)].into_iter().enumerate() {
+            // Only radius-1 neighborhoods fit in the 4×4 base case
+            let config = NEIGHBORS
+                .iter()
+                .enumerate()
+                .filter(|&(_, &(dx, dy))| {
+                    grid[(y as isize + dy) as usize][(x as isize + dx) as usize]
+                })
+                .fold(0, |mask, (i, _)| mask | 1 << i);
+            if self.rule.next_state(grid[y][x], config) {
+                result[i] = NodeId::ALIVE;
+            }
+        }
+        self.join(result)
+    }
+
+    /// Returns the center of a node advanced by `2^min(step_log2, level - 2)` generations
+    fn successor(&mut self, id: NodeId) -> NodeId {
+        let node = &self.nodes[id.index()];
+        let level = node.level;
+        if node.population == 0 {
+            return self.empty(level - 1);
+        }
+        if let Some(result) = node.result {
+            return result;
+        }
+
+        let result = if level == 2 {
+            self.base_case(id)
+        } else {
+            let [a, b, c, d] = node.children;
+            let [_, a_ne, a_sw, a_se] = self.children(a);
+            let [b_nw, _, b_sw, b_se] = self.children(b);
+            let [c_nw, c_ne, _, c_se] = self.children(c);
+            let [d_nw, d_ne, d_sw, _] = self.children(d);
+
+            // Nine overlapping sub-squares, one level below this node
+            let nine = [
+                a,
+                self.join([a_ne, b_nw, a_se, b_sw]),
+                b,
+                self.join([a_sw, a_se, c_nw, c_ne]),
+                self.join([a_se, b_sw, c_ne, d_nw]),
+                self.join([b_sw, b_se, d_nw, d_ne]),
+                c,
+                self.join([c_ne, d_nw, c_se, d_sw]),
+                d,
+            ];
+
+            // At full speed both halves of the step advance time, otherwise
+            // the first half only re-centers the sub-squares.
+            let r = if self.step_log2 >= level - 2 {
+                nine.map(|n| self.successor(n))
+            } else {
+                nine.map(|n| self.centre(n))
+            };
+
+            let quadrants = [
+                self.join([r[0], r[1], r[3], r[4]]),
+                self.join([r[1], r[2], r[4], r[5]]),
+                self.join([r[3], r[4], r[6], r[7]]),
+                self.join([r[4], r[5], r[7], r[8]]),
+            ];
+            let advanced = quadrants.map(|q| self.successor(q));
+            self.join(advanced)
+        };
+
+        self.nodes[id.index()].result = Some(result);
+        result
+    }
+
+    fn clear_results(&mut self) {
+        for node in &mut self.nodes {
+            node.result = None;
+        }
+    }
+
+    /// Rebuilds the arena keeping only the nodes reachable from the root
+    fn compact(&mut self) {
+        let mut fresh = Self::new(self.rule);
+        fresh.step_log2 = self.step_log2;
+        fresh.generation = self.generation;
+        let mut mapping = FxHashMap::default();
+        fresh.root = fresh.copy_node(self, self.root, &mut mapping);
+        *self = fresh;
+    }
+
+    fn copy_node(
+        &mut self,
+        other: &Universe,
+        id: NodeId,
+        mapping: &mut FxHashMap<NodeId, NodeId>,
+    ) -> NodeId {
+        if id == NodeId::DEAD || id == NodeId::ALIVE {
+            return id;
+        }
+        if let Some(&copied) = mapping.get(&id) {
+            return copied;
+        }
+        let children = other
+            .children(id)
+            .map(|child| self.copy_node(other, child, mapping));
+        let copied = self.join(children);
+        mapping.insert(id, copied);
+        copied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::{Board, Cell};
+
+    const GLIDER: [(i64, i64); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+    fn sorted(mut cells: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+        cells.sort_unstable();
+        cells
+    }
+
+    /// Living cells of `cells` after `generations`, stepped on a sparse board
+    fn sparse(cells: &[(i64, i64)], generations: usize) -> Vec<(i64, i64)> {
+        let mut board: Board = cells
+            .iter()
+            .map(|&(x, y)| Cell::new(x as isize, y as isize))
+            .collect();
+        for _ in 0..generations {
+            board.advance(&Rule::CONWAY);
+        }
+        sorted(
+            board
+                .iter()
+                .map(|cell| (cell.x as i64, cell.y as i64))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn glider_matches_sparse_and_bitboard() {
+        let mut bits: BitBoard = GLIDER
+            .iter()
+            .map(|&(x, y)| Cell::new(x as isize, y as isize))
+            .collect();
+        let mut universe = Universe::from_cells(Rule::CONWAY, GLIDER);
+        universe.set_step_log2(2);
+        for period in 1..=8 {
+            universe.step();
+            for _ in 0..4 {
+                bits.advance(&Rule::CONWAY);
+            }
+            let expected = sparse(&GLIDER, 4 * period);
+            // A glider moves by one cell diagonally every 4 generations
+            let moved: Vec<_> = GLIDER
+                .iter()
+                .map(|&(x, y)| (x + period as i64, y + period as i64))
+                .collect();
+            assert_eq!(expected, sorted(moved));
+            assert_eq!(sorted(universe.cells(None)), expected);
+            let bits: Vec<_> = bits
+                .iter()
+                .map(|cell| (cell.x as i64, cell.y as i64))
+                .collect();
+            assert_eq!(sorted(bits), expected);
+        }
+        assert_eq!(universe.generation(), 32);
+    }
+
+    #[test]
+    fn large_steps_match_single_steps() {
+        let blinker = [(0, -1), (0, 0), (0, 1)];
+        let mut universe = Universe::from_cells(Rule::CONWAY, GLIDER.into_iter().chain(blinker));
+        universe.set_step_log2(5);
+        universe.step();
+        assert_eq!(universe.generation(), 32);
+        let start: Vec<_> = GLIDER.into_iter().chain(blinker).collect();
+        assert_eq!(sorted(universe.cells(None)), sparse(&start, 32));
+    }
+
+    #[test]
+    fn cells_are_filtered_by_bounds() {
+        let universe = Universe::from_cells(Rule::CONWAY, GLIDER);
+        assert_eq!(sorted(universe.cells(Some(((0, 0), (1, 1))))), vec![(1, 0)]);
+        assert_eq!(universe.population(), 5);
+    }
+
+    #[test]
+    fn clearing_keeps_the_step_size() {
+        let mut universe = Universe::from_cells(Rule::CONWAY, GLIDER);
+        universe.set_step_log2(2);
+        universe.step();
+        universe.clear();
+        assert_eq!(universe.population(), 0);
+        universe.set_cell(0, 0, true);
+        universe.set_cell(1, 0, true);
+        universe.set_cell(2, 0, true);
+        universe.step();
+        assert_eq!(universe.generation(), 8);
+        assert_eq!(sorted(universe.cells(None)), vec![(0, 0), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn step_is_capped() {
+        let mut universe = Universe::new(Rule::CONWAY);
+        universe.set_step_log2(u8::MAX);
+        universe.set_cell(0, 0, true);
+        universe.step();
+        assert_eq!(universe.generation(), 1 << MAX_STEP_LOG2);
+        assert_eq!(universe.population(), 0);
+    }
+
+    #[test]
+    fn supported_rules() {
+        assert!(Universe::supports(&Rule::CONWAY, &GridTopology::Infinite));
+        let b0 = Rule::parse("B013/S23").unwrap();
+        assert!(!Universe::supports(&b0, &GridTopology::Infinite));
+        let bounded = GridTopology::Bounded {
+            width: 32,
+            height: 32,
+        };
+        assert!(!Universe::supports(&Rule::CONWAY, &bounded));
+    }
+}
@@ -0,0 +1,263 @@
+//! # Hensel Module
+//!
+//! Isotropic non-totalistic rules in Hensel notation ("B2-a/S12"), where a
+//! letter after a neighbor count selects configurations of that many living
+//! neighbors up to rotation and reflection.
+
+use crate::rule::{NEIGHBORS, RuleParseError};
+
+/// Letters of each neighbor count, for counts 0 to 4 (5 to 8 mirror 3 to 0)
+const LETTERS: [&str; 5] = ["", "ce", "ceaikn", "ceaiknjqry", "ceaiknjqrtwyz"];
+
+/// One representative configuration per letter, in the order of [`LETTERS`].
+///
+/// Bit `row * 3 + col` of the 3×3 block is set for each living neighbor,
+/// the center being bit 4, as in Golly.
+const REPRESENTATIVES: [&[u16]; 5] = [
+    &[0],
+    &[1, 2],
+    &[5, 10, 3, 40, 33, 68],
+    &[69, 42, 11, 7, 98, 13, 14, 70, 41, 97],
+    &[325, 170, 15, 45, 99, 71, 106, 102, 43, 101, 105, 78, 108],
+];
+
+/// Set of neighbor configurations, indexed by 8-bit masks where bit `i`
+/// stands for the neighbor at [`NEIGHBORS`]`[i]`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ConfigSet([u64; 4]);
+
+impl ConfigSet {
+    /// Whether the configuration belongs to the set
+    pub fn contains(&self, config: u8) -> bool {
+        self.0[config as usize / 64] & (1 << (config % 64)) != 0
+    }
+
+    /// Adds a configuration to the set
+    pub fn insert(&mut self, config: u8) {
+        self.0[config as usize / 64] |= 1 << (config % 64);
+    }
+
+    /// Every configuration with `count` living neighbors
+    pub fn with_count(count: usize) -> Self {
+        Self::from_counts(1 << count)
+    }
+
+    /// Every configuration whose number of living neighbors is in the bitmask
+    pub fn from_counts(mask: u32) -> Self {
+        let mut set = Self::default();
+        for config in 0..=u8::MAX {
+            if mask & (1 << config.count_ones()) != 0 {
+                set.insert(config);
+            }
+        }
+        set
+    }
+
+    /// Bitmask of the neighbor counts with at least one configuration in the set
+    pub fn counts(&self) -> u32 {
+        (0..=u8::MAX)
+            .filter(|&config| self.contains(config))
+            .fold(0, |mask, config| mask | 1 << config.count_ones())
+    }
+
+    /// Configurations of either set
+    pub fn union(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] | other.0[i]))
+    }
+
+    /// Configurations of this set missing from the other
+    pub fn difference(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] & !other.0[i]))
+    }
+
+    /// Whether the set has no configuration
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|&word| word == 0)
+    }
+}
+
+/// Birth and survival conditions of an isotropic non-totalistic rule
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Transitions {
+    /// Configurations around a dead cell that bring it to life
+    pub birth: ConfigSet,
+    /// Configurations around a living cell that keep it alive
+    pub survival: ConfigSet,
+}
+
+/// Whether the conditions use letters and thus need the Hensel parser
+pub fn is_hensel(conditions: &str) -> bool {
+    conditions
+        .chars()
+        .any(|c| c.is_ascii_alphabetic() || c == '-')
+}
+
+/// Parses conditions such as "2-a3ce" into a set of configurations
+pub fn parse_conditions(conditions: &str) -> Result<ConfigSet, RuleParseError> {
+    let mut set = ConfigSet::default();
+    let mut chars = conditions.chars().peekable();
+    while let Some(c) = chars.next() {
+        let count = c
+            .to_digit(10)
+            .filter(|&n| n <= 8)
+            .ok_or(RuleParseError::InvalidCount(c))? as usize;
+        let negate = chars.next_if_eq(&'-').is_some();
+
+        let mut selected = ConfigSet::default();
+        let mut has_letters = false;
+        while let Some(letter) = chars.next_if(char::is_ascii_alphabetic) {
+            let letter_set =
+                letter_configs(count, letter).ok_or(RuleParseError::InvalidLetter(letter))?;
+            selected = selected.union(&letter_set);
+            has_letters = true;
+        }
+
+        let all = ConfigSet::with_count(count);
+        set = set.union(&match (has_letters, negate) {
+            (false, false) => all,
+            (false, true) => return Err(RuleParseError::InvalidCount('-')),
+            (true, false) => selected,
+            (true, true) => all.difference(&selected),
+        });
+    }
+    Ok(set)
+}
+
+/// Formats a set of configurations in Hensel notation
+pub fn format_conditions(set: &ConfigSet) -> String {
+    let mut text = String::new();
+    for count in 0..=8 {
+        let letters = letters(count);
+        let present: String = letters
+            .chars()
+            .filter(|&letter| {
+                letter_configs(count, letter)
+                    .is_some_and(|configs| configs.difference(set).is_empty())
+            })
+            .collect();
+        let all = ConfigSet::with_count(count);
+        if all.difference(set) == all {
+            continue;
+        }
+        text.push(char::from(b'0' + count as u8));
+        if all.difference(set).is_empty() {
+            continue;
+        }
+        // Pick the shorter of the included and the excluded letters
+        if present.len() * 2 > letters.len() {
+            text.push('-');
+            text.extend(letters.chars().filter(|letter| !present.contains(*letter)));
+        } else {
+            text.push_str(&present);
+        }
+    }
+    text
+}
+
+/// Letters available for a neighbor count
+fn letters(count: usize) -> &'static str {
+    LETTERS[count.min(8 - count)]
+}
+
+/// Configurations named by a letter, with every rotation and reflection
+fn letter_configs(count: usize, letter: char) -> Option<ConfigSet> {
+    let index = letters(count).find(letter)?;
+    let representative = REPRESENTATIVES[count.min(8 - count)][index];
+    let mut offsets: Vec<(isize, isize)> = (0..9)
+        .filter(|bit| representative & (1u16 << bit) != 0)
+        .map(|bit| (bit % 3 - 1, bit / 3 - 1))
+        .collect();
+    // Counts above 4 are the complements of the counts below
+    if count > 4 {
+        offsets = NEIGHBORS
+            .iter()
+            .copied()
+            .filter(|offset| !offsets.contains(offset))
+            .collect();
+    }
+
+    let mut set = ConfigSet::default();
+    for reflect in [false, true] {
+        let mut transformed: Vec<(isize, isize)> = offsets
+            .iter()
+            .map(|&(dx, dy)| if reflect { (-dx, dy) } else { (dx, dy) })
+            .collect();
+        for _ in 0..4 {
+            set.insert(configuration(&transformed));
+            for offset in &mut transformed {
+                *offset = (-offset.1, offset.0);
+            }
+        }
+    }
+    Some(set)
+}
+
+/// Mask of the given living neighbor offsets
+fn configuration(offsets: &[(isize, isize)]) -> u8 {
+    NEIGHBORS
+        .iter()
+        .enumerate()
+        .filter(|(_, offset)| offsets.contains(offset))
+        .fold(0, |mask, (i, _)| mask | 1 << i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letters_partition_each_count() {
+        for count in 0..=8 {
+            let mut union = ConfigSet::default();
+            for letter in letters(count).chars() {
+                let set = letter_configs(count, letter).unwrap();
+                assert!(set.difference(&union) == set, "{count}{letter} overlaps");
+                union = union.union(&set);
+            }
+            if !letters(count).is_empty() {
+                assert_eq!(union, ConfigSet::with_count(count), "count {count}");
+            }
+        }
+        let classes: usize = (0..=8).map(|count| letters(count).len().max(1)).sum();
+        assert_eq!(classes, 51);
+    }
+
+    #[test]
+    fn conditions_round_trip() {
+        for conditions in ["2-a3ce", "2a", "3", "1e2ik4-jq8", ""] {
+            let set = parse_conditions(conditions).unwrap();
+            assert_eq!(format_conditions(&set), conditions);
+        }
+        assert_eq!(
+            parse_conditions("23").unwrap(),
+            ConfigSet::from_counts(0b1100)
+        );
+    }
+
+    #[test]
+    fn invalid_conditions_are_rejected() {
+        assert_eq!(
+            parse_conditions("1a"),
+            Err(RuleParseError::InvalidLetter('a'))
+        );
+        assert_eq!(
+            parse_conditions("2x"),
+            Err(RuleParseError::InvalidLetter('x'))
+        );
+        assert_eq!(
+            parse_conditions("3-"),
+            Err(RuleParseError::InvalidCount('-'))
+        );
+        assert_eq!(
+            parse_conditions("9"),
+            Err(RuleParseError::InvalidCount('9'))
+        );
+    }
+
+    #[test]
+    fn detection() {
+        assert!(is_hensel("2-a"));
+        assert!(is_hensel("3ce"));
+        assert!(!is_hensel("23"));
+    }
+}
@@ -0,0 +1,41 @@
+//! # Core Module
+//!
+//! Bevy-free implementation of the automaton: board state, rules, stepping
+//! and pattern parsing. It can be reused by CLI tools, tests and servers,
+//! while the Bevy plugins act as thin adapters around it.
+
+pub mod analysis;
+pub mod bitboard;
+pub mod board;
+pub mod cycle;
+pub mod elementary;
+pub mod hashlife;
+pub mod hensel;
+pub mod life;
+pub mod macrocell;
+pub mod margolus;
+pub mod noise;
+pub mod pattern;
+pub mod plaintext;
+pub mod rle;
+pub mod rule;
+pub mod soup;
+pub mod topology;
+
+pub use analysis::*;
+pub use bitboard::*;
+pub use board::*;
+pub use cycle::*;
+pub use elementary::*;
+pub use hashlife::*;
+pub use hensel::*;
+pub use life::*;
+pub use macrocell::*;
+pub use margolus::*;
+pub use noise::*;
+pub use pattern::*;
+pub use plaintext::*;
+pub use rle::*;
+pub use rule::*;
+pub use soup::*;
+pub use topology::*;
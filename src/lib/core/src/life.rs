@@ -0,0 +1,201 @@
+//! # Life Module
+//!
+//! Parsing of the Life 1.05 (`#P` blocks of `.`/`*` rows) and Life 1.06
+//! (one `x y` coordinate pair per line) pattern formats.
+
+use crate::pattern::ParsedPattern;
+use std::fmt;
+
+/// Header line of Life 1.05 files
+pub const LIFE_105_HEADER: &str = "#Life 1.05";
+/// Header line of Life 1.06 files
+pub const LIFE_106_HEADER: &str = "#Life 1.06";
+
+/// Kind of problem found while parsing a Life 1.05/1.06 file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LifeErrorKind {
+    /// The `#Life 1.05` / `#Life 1.06` header is missing
+    MissingHeader,
+    /// A `#P` line without two integer coordinates
+    InvalidBlock(String),
+    /// A line that is not an `x y` coordinate pair
+    InvalidCoordinates(String),
+    /// A character other than `.` or `*` inside a cell block
+    UnexpectedChar(char),
+}
+
+/// Error returned when a Life 1.05/1.06 file cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LifeError {
+    /// Line of the error (1-based)
+    pub line: usize,
+    /// What went wrong
+    pub kind: LifeErrorKind,
+}
+
+impl fmt::Display for LifeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "missing '#Life 1.05' or '#Life 1.06' header"),
+            Self::InvalidBlock(line) => write!(f, "invalid block position '{line}'"),
+            Self::InvalidCoordinates(line) => write!(f, "invalid coordinates '{line}'"),
+            Self::UnexpectedChar(c) => write!(f, "unexpected character '{c}'"),
+        }
+    }
+}
+
+impl fmt::Display for LifeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for LifeError {}
+
+/// Parses two whitespace-separated integers
+fn parse_pair(text: &str) -> Option<(i32, i32)> {
+    let mut parts = text.split_whitespace();
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+    parts.next().is_none().then_some((x, y))
+}
+
+/// Parses a Life 1.05 file.
+///
+/// Cell rows are read relative to the last `#P x y` block position.
+/// `#N` selects Conway's rule and `#R s/b` declares a custom one.
+pub fn parse_life105(content: &str) -> Result<ParsedPattern, LifeError> {
+    let mut lines = content.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim().starts_with(LIFE_105_HEADER) => {}
+        _ => {
+            return Err(LifeError {
+                line: 1,
+                kind: LifeErrorKind::MissingHeader,
+            });
+        }
+    }
+
+    let mut pattern = ParsedPattern::default();
+    let (mut block_x, mut row) = (0, 0);
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let line = line.trim();
+        if let Some(position) = line.strip_prefix("#P") {
+            let (x, y) = parse_pair(position).ok_or_else(|| LifeError {
+                line: line_number,
+                kind: LifeErrorKind::InvalidBlock(line.to_string()),
+            })?;
+            block_x = x;
+            row = y;
+        } else if line == "#N" {
+            pattern.rule = Some("B3/S23".to_string());
+        } else if let Some(rule) = line.strip_prefix("#R") {
+            pattern.rule = Some(rule.trim().to_string());
+        } else if line.starts_with('#') || line.is_empty() {
+            continue;
+        } else {
+            for (dx, c) in line.chars().enumerate() {
+                match c {
+                    '*' => pattern.cells.push((block_x + dx as i32, row)),
+                    '.' => {}
+                    c => {
+                        return Err(LifeError {
+                            line: line_number,
+                            kind: LifeErrorKind::UnexpectedChar(c),
+                        });
+                    }
+                }
+            }
+            row += 1;
+        }
+    }
+    Ok(pattern)
+}
+
+/// Parses a Life 1.06 file (one `x y` pair of living cell per line)
+pub fn parse_life106(content: &str) -> Result<ParsedPattern, LifeError> {
+    let mut lines = content.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim().starts_with(LIFE_106_HEADER) => {}
+        _ => {
+            return Err(LifeError {
+                line: 1,
+                kind: LifeErrorKind::MissingHeader,
+            });
+        }
+    }
+
+    let mut pattern = ParsedPattern::default();
+    for (index, line) in lines {
+        let line = line.trim();
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+        let cell = parse_pair(line).ok_or_else(|| LifeError {
+            line: index + 1,
+            kind: LifeErrorKind::InvalidCoordinates(line.to_string()),
+        })?;
+        pattern.cells.push(cell);
+    }
+    Ok(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn life105_blocks_are_offset() {
+        let pattern =
+            parse_life105("#Life 1.05\n#D Glider\n#N\n#P -1 -1\n.*.\n..*\n***\n#P 10 0\n*\n")
+                .unwrap();
+        assert_eq!(pattern.rule.as_deref(), Some("B3/S23"));
+        assert_eq!(
+            pattern.cells,
+            vec![(0, -1), (1, 0), (-1, 1), (0, 1), (1, 1), (10, 0)]
+        );
+    }
+
+    #[test]
+    fn life105_custom_rule() {
+        let pattern = parse_life105("#Life 1.05\n#R 23/36\n*").unwrap();
+        assert_eq!(pattern.rule.as_deref(), Some("23/36"));
+        assert_eq!(pattern.cells, vec![(0, 0)]);
+    }
+
+    #[test]
+    fn life106_reads_coordinates() {
+        let pattern = parse_life106("#Life 1.06\n0 -1\n# comment\n\n1 0\n-1 1\n").unwrap();
+        assert_eq!(pattern.cells, vec![(0, -1), (1, 0), (-1, 1)]);
+    }
+
+    #[test]
+    fn errors_report_their_line() {
+        assert_eq!(
+            parse_life106("0 0").unwrap_err().kind,
+            LifeErrorKind::MissingHeader
+        );
+        assert_eq!(
+            parse_life106("#Life 1.06\n0 0\n1 x").unwrap_err(),
+            LifeError {
+                line: 3,
+                kind: LifeErrorKind::InvalidCoordinates("1 x".into()),
+            }
+        );
+        assert_eq!(
+            parse_life105("#Life 1.05\n#P 0\n").unwrap_err(),
+            LifeError {
+                line: 2,
+                kind: LifeErrorKind::InvalidBlock("#P 0".into()),
+            }
+        );
+        assert_eq!(
+            parse_life105("#Life 1.05\n.*o").unwrap_err(),
+            LifeError {
+                line: 2,
+                kind: LifeErrorKind::UnexpectedChar('o'),
+            }
+        );
+    }
+}
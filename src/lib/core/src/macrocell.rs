@@ -0,0 +1,258 @@
+//! # Macrocell Module
+//!
+//! Reader for Golly's macrocell (`.mc`) format, which stores a pattern as a
+//! deduplicated quadtree. Huge patterns such as the OTCA metapixel are
+//! loaded straight into a HashLife [`Universe`] without expanding them.
+
+use crate::hashlife::{NodeId, Universe};
+use crate::rle::MAX_PATTERN_CELLS;
+use crate::rule::Rule;
+use std::fmt;
+
+/// Header line of two-state macrocell files
+pub const MACROCELL_HEADER: &str = "[M2]";
+
+/// Kind of problem found while reading a macrocell file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacrocellErrorKind {
+    /// The `[M2]` header is missing
+    MissingHeader,
+    /// The file describes no node
+    Empty,
+    /// A character other than `.`, `*` or `$` in an 8×8 leaf line
+    InvalidLeaf(char),
+    /// A node line that is not `level nw ne sw se`
+    InvalidNode(String),
+    /// A node referring to a node that is not defined yet or has the wrong level
+    InvalidReference(usize),
+    /// The `#R` rule line could not be parsed
+    UnsupportedRule(String),
+    /// The pattern has too many living cells to be listed one by one
+    TooManyCells,
+}
+
+/// Error returned when a macrocell file cannot be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacrocellError {
+    /// Line of the error (1-based)
+    pub line: usize,
+    /// What went wrong
+    pub kind: MacrocellErrorKind,
+}
+
+impl fmt::Display for MacrocellErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "missing '[M2]' header"),
+            Self::Empty => write!(f, "no node defined"),
+            Self::InvalidLeaf(c) => write!(f, "unexpected character '{c}' in leaf"),
+            Self::InvalidNode(line) => write!(f, "invalid node '{line}'"),
+            Self::InvalidReference(index) => write!(f, "invalid reference to node {index}"),
+            Self::UnsupportedRule(rule) => write!(f, "unsupported rule '{rule}'"),
+            Self::TooManyCells => write!(f, "more than {MAX_PATTERN_CELLS} living cells"),
+        }
+    }
+}
+
+impl fmt::Display for MacrocellError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for MacrocellError {}
+
+/// Macrocell file loaded into a HashLife universe
+pub struct Macrocell {
+    /// Universe holding the pattern, centered on the origin like in Golly
+    pub universe: Universe,
+    /// Rulestring declared by the `#R` line, if any
+    pub rule: Option<String>,
+}
+
+/// Reads a two-state macrocell file.
+///
+/// Leaf lines describe 8×8 blocks (`.` dead, `*` alive, `$` end of row) and
+/// node lines `level nw ne sw se` reference earlier lines by their 1-based
+/// index, `0` standing for an empty node. The last node is the root.
+pub fn parse_macrocell(content: &str) -> Result<Macrocell, MacrocellError> {
+    let mut lines = content.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim().starts_with(MACROCELL_HEADER) => {}
+        _ => {
+            return Err(MacrocellError {
+                line: 1,
+                kind: MacrocellErrorKind::MissingHeader,
+            });
+        }
+    }
+
+    let mut rule_text = None;
+    let mut universe = Universe::new(Rule::default());
+    let mut nodes: Vec<NodeId> = Vec::new();
+
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let error = |kind| MacrocellError {
+            line: line_number,
+            kind,
+        };
+        let line = line.trim();
+
+        if let Some(rule) = line.strip_prefix("#R") {
+            let rule = rule.trim();
+            let parsed = Rule::parse(rule)
+                .map_err(|_| error(MacrocellErrorKind::UnsupportedRule(rule.to_string())))?;
+            universe.set_rule(parsed);
+            rule_text = Some(rule.to_string());
+        } else if line.starts_with('#') || line.is_empty() {
+            continue;
+        } else if line.starts_with(['.', '*', '$']) {
+            let mut grid = [[false; 8]; 8];
+            let (mut x, mut y) = (0, 0);
+            for c in line.chars() {
+                match c {
+                    '.' => x += 1,
+                    '*' => {
+                        if x >= 8 || y >= 8 {
+                            return Err(error(MacrocellErrorKind::InvalidLeaf(c)));
+                        }
+                        grid[y][x] = true;
+                        x += 1;
+                    }
+                    '$' => {
+                        x = 0;
+                        y += 1;
+                    }
+                    c => return Err(error(MacrocellErrorKind::InvalidLeaf(c))),
+                }
+            }
+            nodes.push(build_leaf(&mut universe, &grid, 0, 0, 8));
+        } else {
+            let fields: Vec<usize> = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|_| error(MacrocellErrorKind::InvalidNode(line.to_string())))?;
+            let &[level, nw, ne, sw, se] = fields.as_slice() else {
+                return Err(error(MacrocellErrorKind::InvalidNode(line.to_string())));
+            };
+            if !(4..64).contains(&level) {
+                return Err(error(MacrocellErrorKind::InvalidNode(line.to_string())));
+            }
+
+            let child_level = level as u8 - 1;
+            let mut children = [Universe::leaf(false); 4];
+            for (child, reference) in children.iter_mut().zip([nw, ne, sw, se]) {
+                *child = if reference == 0 {
+                    universe.empty(child_level)
+                } else {
+                    nodes
+                        .get(reference - 1)
+                        .copied()
+                        .filter(|node| universe.level(*node) == child_level)
+                        .ok_or_else(|| error(MacrocellErrorKind::InvalidReference(reference)))?
+                };
+            }
+            nodes.push(universe.join(children));
+        }
+    }
+
+    let Some(&root) = nodes.last() else {
+        return Err(MacrocellError {
+            line: content.lines().count(),
+            kind: MacrocellErrorKind::Empty,
+        });
+    };
+    universe.set_root(root);
+    Ok(Macrocell {
+        universe,
+        rule: rule_text,
+    })
+}
+
+/// Builds the quadtree node of a square area of an 8×8 leaf block
+fn build_leaf(
+    universe: &mut Universe,
+    grid: &[[bool; 8]; 8],
+    x: usize,
+    y: usize,
+    size: usize,
+) -> NodeId {
+    if size == 1 {
+        return Universe::leaf(grid[y][x]);
+    }
+    let half = size / 2;
+    let children = [
+        build_leaf(universe, grid, x, y, half),
+        build_leaf(universe, grid, x + half, y, half),
+        build_leaf(universe, grid, x, y + half, half),
+        build_leaf(universe, grid, x + half, y + half, half),
+    ];
+    universe.join(children)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &str = "[M2] (golly 4.2)\n#R B3/S23\n$.*$..*$***$\n4 1 0 0 0\n";
+
+    #[test]
+    fn glider_is_centered_and_runs() {
+        let mut macrocell = parse_macrocell(GLIDER).unwrap();
+        assert_eq!(macrocell.rule.as_deref(), Some("B3/S23"));
+        let mut cells = macrocell.universe.cells(None);
+        cells.sort_unstable();
+        assert_eq!(
+            cells,
+            vec![(-8, -5), (-7, -7), (-7, -5), (-6, -6), (-6, -5)]
+        );
+
+        for _ in 0..4 {
+            macrocell.universe.step();
+        }
+        let mut moved = macrocell.universe.cells(None);
+        moved.sort_unstable();
+        let shifted: Vec<_> = cells.iter().map(|&(x, y)| (x + 1, y + 1)).collect();
+        assert_eq!(moved, shifted);
+    }
+
+    #[test]
+    fn shared_nodes_are_reused() {
+        let macrocell = parse_macrocell("[M2]\n*\n4 1 1 0 1\n5 2 2 2 2\n").unwrap();
+        assert_eq!(macrocell.universe.population(), 12);
+    }
+
+    #[test]
+    fn errors_report_their_line() {
+        let error = |content| parse_macrocell(content).err().unwrap();
+        assert_eq!(error("*").kind, MacrocellErrorKind::MissingHeader);
+        assert_eq!(error("[M2]\n#C nothing").kind, MacrocellErrorKind::Empty);
+        assert_eq!(
+            error("[M2]\n.*x"),
+            MacrocellError {
+                line: 2,
+                kind: MacrocellErrorKind::InvalidLeaf('x'),
+            }
+        );
+        assert_eq!(
+            error("[M2]\n*\n4 1 2 0 0"),
+            MacrocellError {
+                line: 3,
+                kind: MacrocellErrorKind::InvalidReference(2),
+            }
+        );
+        assert_eq!(
+            error("[M2]\n*\n4 1 0 0"),
+            MacrocellError {
+                line: 3,
+                kind: MacrocellErrorKind::InvalidNode("4 1 0 0".into()),
+            }
+        );
+        assert_eq!(
+            error("[M2]\n#R nonsense\n*").kind,
+            MacrocellErrorKind::UnsupportedRule("nonsense".into())
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! # Margolus Module
+//!
+//! Block automata on the Margolus neighborhood: the grid is cut into 2×2
+//! blocks, each block is replaced as a whole, and the partition shifts by
+//! one cell diagonally every generation. Rules such as Critters are
+//! reversible.
+
+use crate::board::{Board, Cell, Step};
+use rustc_hash::FxHashMap;
+use std::fmt;
+
+/// Bit of the top-left cell of a block, y growing upwards
+const TOP_LEFT: u8 = 1;
+/// Bit of the top-right cell of a block
+const TOP_RIGHT: u8 = 2;
+/// Bit of the bottom-left cell of a block
+const BOTTOM_LEFT: u8 = 4;
+/// Bit of the bottom-right cell of a block
+const BOTTOM_RIGHT: u8 = 8;
+
+/// Every bit of a block
+const FULL: u8 = 15;
+
+/// Block rule, given as the new contents of each of the 16 possible blocks.
+///
+/// Blocks are numbered as in MCell, with 1 for the top-left cell, 2 for the
+/// top-right one, 4 for the bottom-left one and 8 for the bottom-right one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MargolusRule(pub [u8; 16]);
+
+impl MargolusRule {
+    /// Critters: blocks of two cells are kept, the others are inverted and
+    /// blocks of three cells are also rotated by a half turn
+    pub const CRITTERS: Self = Self([15, 14, 13, 3, 11, 5, 6, 1, 7, 9, 10, 2, 12, 4, 8, 0]);
+
+    /// Tron: full and empty blocks are inverted, the others are kept
+    pub const TRON: Self = Self([15, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 0]);
+
+    /// Billiard ball machine: single cells cross their block diagonally and
+    /// diagonal pairs turn by a quarter, so colliding balls bounce
+    pub const BILLIARD_BALLS: Self = Self([0, 8, 4, 3, 2, 5, 9, 7, 1, 6, 10, 11, 12, 13, 14, 15]);
+
+    /// Parses a rule in MCell notation ("MS,D15;14;13;3;11;5;6;1;7;9;10;2;12;4;8;0"),
+    /// the prefix being optional.
+    ///
+    /// Rules turning empty blocks into something else are only accepted when
+    /// they also empty full blocks, as the background then merely blinks.
+    pub fn parse(rulestring: &str) -> Result<Self, MargolusParseError> {
+        let rulestring = rulestring.trim();
+        if rulestring.is_empty() {
+            return Err(MargolusParseError::Empty);
+        }
+        let table = rulestring
+            .strip_prefix("MS,D")
+            .or_else(|| rulestring.strip_prefix("ms,d"))
+            .unwrap_or(rulestring);
+
+        let values = table
+            .split([';', ','])
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|&block| block <= FULL)
+                    .ok_or_else(|| MargolusParseError::InvalidBlock(value.trim().to_string()))
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        let table: [u8; 16] = values
+            .as_slice()
+            .try_into()
+            .map_err(|_| MargolusParseError::WrongLength(values.len()))?;
+
+        let rule = Self(table);
+        if table[0] != 0 && !rule.is_strobing() {
+            return Err(MargolusParseError::LivingBackground);
+        }
+        Ok(rule)
+    }
+
+    /// Whether empty blocks fill and full blocks empty, making the
+    /// background blink from one generation to the next.
+    ///
+    /// Such rules are run on the complement of the board every other
+    /// generation, which keeps the background dead on screen.
+    pub fn is_strobing(&self) -> bool {
+        self.0[0] == FULL && self.0[usize::from(FULL)] == 0
+    }
+
+    /// New contents of a block, on the even or the odd partition
+    pub fn next_block(&self, block: u8, odd: bool) -> u8 {
+        let block = usize::from(block & FULL);
+        match (self.is_strobing(), odd) {
+            (false, _) => self.0[block],
+            (true, false) => self.0[block] ^ FULL,
+            (true, true) => self.0[block ^ usize::from(FULL)],
+        }
+    }
+
+    /// Cells that change over one generation.
+    ///
+    /// Even generations use blocks whose bottom-left cell has even
+    /// coordinates, odd ones the blocks shifted by one cell diagonally.
+    pub fn compute_step(&self, board: &Board, odd: bool) -> Step {
+        let offset = isize::from(odd);
+        let mut blocks: FxHashMap<Cell, u8> = FxHashMap::default();
+        for cell in board.iter() {
+            let x = (cell.x - offset).div_euclid(2) * 2 + offset;
+            let y = (cell.y - offset).div_euclid(2) * 2 + offset;
+            let bit = match (cell.x - x, cell.y - y) {
+                (0, 1) => TOP_LEFT,
+                (1, 1) => TOP_RIGHT,
+                (0, 0) => BOTTOM_LEFT,
+                _ => BOTTOM_RIGHT,
+            };
+            *blocks.entry(Cell::new(x, y)).or_default() |= bit;
+        }
+
+        let mut step = Step::default();
+        for (origin, block) in blocks {
+            let next = self.next_block(block, odd);
+            for (bit, dx, dy) in [
+                (TOP_LEFT, 0, 1),
+                (TOP_RIGHT, 1, 1),
+                (BOTTOM_LEFT, 0, 0),
+                (BOTTOM_RIGHT, 1, 0),
+            ] {
+                let cell = Cell::new(origin.x + dx, origin.y + dy);
+                match (block & bit != 0, next & bit != 0) {
+                    (false, true) => step.born.push(cell),
+                    (true, false) => step.died.push(cell),
+                    _ => {}
+                }
+            }
+        }
+        step
+    }
+
+    /// Advances the board by one generation, returning the changes
+    pub fn advance(&self, board: &mut Board, odd: bool) -> Step {
+        let step = self.compute_step(board, odd);
+        board.apply(&step);
+        step
+    }
+}
+
+impl Default for MargolusRule {
+    fn default() -> Self {
+        Self::CRITTERS
+    }
+}
+
+impl fmt::Display for MargolusRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MS,D")?;
+        for (i, block) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+            write!(f, "{block}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when a Margolus rule cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MargolusParseError {
+    /// The rulestring is empty
+    Empty,
+    /// The table does not hold exactly 16 blocks
+    WrongLength(usize),
+    /// A block is not a number from 0 to 15
+    InvalidBlock(String),
+    /// Empty blocks come alive while full blocks survive, which would fill
+    /// the infinite background
+    LivingBackground,
+}
+
+impl fmt::Display for MargolusParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty rulestring"),
+            Self::WrongLength(len) => write!(f, "expected 16 blocks, found {len}"),
+            Self::InvalidBlock(block) => write!(f, "invalid block '{block}'"),
+            Self::LivingBackground => write!(f, "empty blocks must stay empty"),
+        }
+    }
+}
+
+impl std::error::Error for MargolusParseError {}
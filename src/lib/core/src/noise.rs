@@ -0,0 +1,114 @@
+//! # Noise Module
+//!
+//! Stochastic variant of the rules, where births and survivals only happen
+//! with some probability, driven by a small seeded random number generator
+//! so noisy runs can be replayed.
+
+use crate::board::{Board, Cell, Step};
+use rustc_hash::FxHashSet;
+
+/// SplitMix64 pseudo-random generator, small and reproducible on every
+/// platform
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator from a seed
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Next random 64-bit value
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        Self::mix(self.state)
+    }
+
+    /// Next random value in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        unit(self.next_u64())
+    }
+
+    /// Whether an event of probability `p` happens
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.next_f64() < f64::from(p)
+    }
+
+    /// Scrambles a value, the output function of SplitMix64
+    pub const fn mix(value: u64) -> u64 {
+        let mut z = value;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Probabilities for the births and survivals allowed by the rule to happen
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Noise {
+    /// Probability that a cell meeting the birth condition comes alive
+    pub birth: f32,
+    /// Probability that a cell meeting the survival condition stays alive
+    pub survival: f32,
+}
+
+impl Noise {
+    /// Deterministic rule, every allowed birth and survival happening
+    pub const NONE: Self = Self {
+        birth: 1.0,
+        survival: 1.0,
+    };
+
+    /// Whether some outcome is left to chance
+    pub fn is_active(&self) -> bool {
+        self.birth < 1.0 || self.survival < 1.0
+    }
+
+    /// Drops births and survivals of a step computed from `board` at random.
+    ///
+    /// Each cell draws from its coordinates and `key`, so the outcome does not
+    /// depend on the order in which cells are stored. Deaths required by the
+    /// rule and state changes of aging cells are kept.
+    pub fn perturb(&self, board: &Board, mut step: Step, key: u64) -> Step {
+        if !self.is_active() {
+            return step;
+        }
+        let roll = |cell: &Cell, p: f32| -> bool {
+            let position = (cell.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ cell.y as u64;
+            unit(SplitMix64::mix(key ^ SplitMix64::mix(position))) < f64::from(p)
+        };
+
+        let mut dropped: FxHashSet<Cell> = FxHashSet::default();
+        step.born.retain(|cell| {
+            let born = roll(cell, self.birth);
+            if !born {
+                dropped.insert(*cell);
+            }
+            born
+        });
+
+        let died: FxHashSet<Cell> = step.died.iter().copied().collect();
+        let changed: FxHashSet<Cell> = step.states.iter().map(|(cell, _)| *cell).collect();
+        for cell in board.iter() {
+            if !died.contains(cell) && !changed.contains(cell) && !roll(cell, self.survival) {
+                step.died.push(*cell);
+            }
+        }
+        step.states.retain(|(cell, _)| !dropped.contains(cell));
+        step
+    }
+}
+
+impl Default for Noise {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Maps 64 random bits to `[0, 1)`
+fn unit(bits: u64) -> f64 {
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
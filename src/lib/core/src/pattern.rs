@@ -0,0 +1,215 @@
+//! # Pattern Module
+//!
+//! Format-independent pattern representation and format dispatch.
+
+use crate::life::{LIFE_105_HEADER, LIFE_106_HEADER, LifeError, parse_life105, parse_life106};
+use crate::macrocell::{MACROCELL_HEADER, MacrocellError, MacrocellErrorKind, parse_macrocell};
+use crate::plaintext::{PlaintextError, looks_like_plaintext, parse_plaintext};
+use crate::rle::{MAX_PATTERN_CELLS, RleError, parse_rle_pattern};
+use std::fmt;
+
+/// Pattern decoded from one of the supported file formats
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedPattern {
+    /// Coordinates of the living cells
+    pub cells: Vec<(i32, i32)>,
+    /// Rulestring declared by the file, if any
+    pub rule: Option<String>,
+    /// Pattern name declared by the file, if any
+    pub name: Option<String>,
+}
+
+/// Supported pattern file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternFormat {
+    /// Run Length Encoded (`.rle`)
+    Rle,
+    /// Life 1.05 (`.lif`, `.life`)
+    Life105,
+    /// Life 1.06 (`.lif`, `.life`)
+    Life106,
+    /// Plaintext (`.cells`)
+    Plaintext,
+    /// Golly macrocell (`.mc`)
+    Macrocell,
+}
+
+impl PatternFormat {
+    /// Guesses the format of some pattern content
+    pub fn detect(content: &str) -> Self {
+        let first_line = content.trim_start().lines().next().unwrap_or_default().trim();
+        if first_line.starts_with(MACROCELL_HEADER) {
+            Self::Macrocell
+        } else if first_line.starts_with(LIFE_105_HEADER) {
+            Self::Life105
+        } else if first_line.starts_with(LIFE_106_HEADER) {
+            Self::Life106
+        } else if looks_like_plaintext(content) {
+            Self::Plaintext
+        } else {
+            Self::Rle
+        }
+    }
+}
+
+/// Error returned when pattern content cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    /// Invalid RLE content
+    Rle(RleError),
+    /// Invalid Life 1.05/1.06 content
+    Life(LifeError),
+    /// Invalid plaintext content
+    Plaintext(PlaintextError),
+    /// Invalid macrocell content
+    Macrocell(MacrocellError),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rle(e) => write!(f, "RLE {e}"),
+            Self::Life(e) => write!(f, "Life {e}"),
+            Self::Plaintext(e) => write!(f, "plaintext {e}"),
+            Self::Macrocell(e) => write!(f, "macrocell {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl PatternError {
+    /// Line of the error (1-based)
+    pub fn line(&self) -> usize {
+        match self {
+            Self::Rle(e) => e.line,
+            Self::Life(e) => e.line,
+            Self::Plaintext(e) => e.line,
+            Self::Macrocell(e) => e.line,
+        }
+    }
+
+    fn line_mut(&mut self) -> &mut usize {
+        match self {
+            Self::Rle(e) => &mut e.line,
+            Self::Life(e) => &mut e.line,
+            Self::Plaintext(e) => &mut e.line,
+            Self::Macrocell(e) => &mut e.line,
+        }
+    }
+}
+
+impl From<RleError> for PatternError {
+    fn from(e: RleError) -> Self {
+        Self::Rle(e)
+    }
+}
+
+impl From<LifeError> for PatternError {
+    fn from(e: LifeError) -> Self {
+        Self::Life(e)
+    }
+}
+
+impl From<PlaintextError> for PatternError {
+    fn from(e: PlaintextError) -> Self {
+        Self::Plaintext(e)
+    }
+}
+
+impl From<MacrocellError> for PatternError {
+    fn from(e: MacrocellError) -> Self {
+        Self::Macrocell(e)
+    }
+}
+
+/// Parses pattern content in any supported format, detected from the content
+pub fn parse_pattern(content: &str) -> Result<ParsedPattern, PatternError> {
+    let trimmed = content.trim_start();
+    // Report lines of the original content, leading blank lines included
+    let skipped = content[..content.len() - trimmed.len()].matches('\n').count();
+    parse_trimmed(trimmed).map_err(|mut e| {
+        *e.line_mut() += skipped;
+        e
+    })
+}
+
+fn parse_trimmed(content: &str) -> Result<ParsedPattern, PatternError> {
+    Ok(match PatternFormat::detect(content) {
+        PatternFormat::Rle => parse_rle_pattern(content)?,
+        PatternFormat::Life105 => parse_life105(content)?,
+        PatternFormat::Life106 => parse_life106(content)?,
+        PatternFormat::Plaintext => parse_plaintext(content)?,
+        PatternFormat::Macrocell => {
+            let macrocell = parse_macrocell(content)?;
+            // A few lines of nodes can describe billions of cells
+            if macrocell.universe.population() > MAX_PATTERN_CELLS as u64 {
+                return Err(PatternError::Macrocell(MacrocellError {
+                    line: 1,
+                    kind: MacrocellErrorKind::TooManyCells,
+                }));
+            }
+            ParsedPattern {
+                cells: macrocell
+                    .universe
+                    .cells(None)
+                    .into_iter()
+                    .map(|(x, y)| (x as i32, y as i32))
+                    .collect(),
+                rule: macrocell.rule,
+                name: None,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_are_detected() {
+        assert_eq!(
+            PatternFormat::detect("x = 1, y = 1\no!"),
+            PatternFormat::Rle
+        );
+        assert_eq!(
+            PatternFormat::detect("#Life 1.05\n*"),
+            PatternFormat::Life105
+        );
+        assert_eq!(
+            PatternFormat::detect("#Life 1.06\n0 0"),
+            PatternFormat::Life106
+        );
+        assert_eq!(
+            PatternFormat::detect("!Name: x\nO"),
+            PatternFormat::Plaintext
+        );
+        assert_eq!(PatternFormat::detect("[M2]\n*"), PatternFormat::Macrocell);
+    }
+
+    #[test]
+    fn error_lines_count_leading_blank_lines() {
+        let error = parse_pattern("\n\n#Life 1.06\n0 0\nx").unwrap_err();
+        assert_eq!(error.line(), 5);
+        assert!(matches!(error, PatternError::Life(_)));
+    }
+
+    #[test]
+    fn huge_macrocells_are_not_listed() {
+        // Each level doubles the side of a full square, up to 2^24 cells
+        let mut content = format!("[M2]\n{}\n", "********$".repeat(8));
+        for level in 4..=12 {
+            let node = level - 3;
+            content += &format!("{level} {node} {node} {node} {node}\n");
+        }
+        let error = parse_pattern(&content).unwrap_err();
+        assert!(matches!(
+            error,
+            PatternError::Macrocell(MacrocellError {
+                kind: MacrocellErrorKind::TooManyCells,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+//! # Plaintext Module
+//!
+//! Parsing of the plaintext `.cells` format: `O` for living cells, `.` for
+//! dead ones and `!` comment lines.
+
+use crate::pattern::ParsedPattern;
+use std::fmt;
+
+/// Error returned when a `.cells` file cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaintextError {
+    /// Line of the error (1-based)
+    pub line: usize,
+    /// Column of the error (1-based)
+    pub column: usize,
+    /// The offending character
+    pub found: char,
+}
+
+impl fmt::Display for PlaintextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: unexpected character '{}'",
+            self.line, self.column, self.found
+        )
+    }
+}
+
+impl std::error::Error for PlaintextError {}
+
+/// Whether content looks like a plaintext pattern.
+///
+/// True when it starts with a `!` comment, or when every line only
+/// contains cell characters.
+pub fn looks_like_plaintext(content: &str) -> bool {
+    let mut lines = content.lines().map(str::trim).filter(|line| !line.is_empty());
+    match lines.next() {
+        Some(first) if first.starts_with('!') => true,
+        Some(first) => std::iter::once(first)
+            .chain(lines)
+            .all(|line| line.chars().all(|c| matches!(c, '.' | 'O' | '*'))),
+        None => false,
+    }
+}
+
+/// Parses a plaintext `.cells` pattern.
+///
+/// `*` is accepted as an alias of `O`, as found in some older files.
+pub fn parse_plaintext(content: &str) -> Result<ParsedPattern, PlaintextError> {
+    let mut pattern = ParsedPattern::default();
+    let mut y = 0;
+    for (index, line) in content.lines().enumerate() {
+        if let Some(name) = line.strip_prefix("!Name:") {
+            pattern.name = Some(name.trim().to_string()).filter(|name| !name.is_empty());
+            continue;
+        }
+        if line.starts_with('!') {
+            continue;
+        }
+        for (x, c) in line.trim_end().chars().enumerate() {
+            match c {
+                'O' | '*' => pattern.cells.push((x as i32, y)),
+                '.' => {}
+                found => {
+                    return Err(PlaintextError {
+                        line: index + 1,
+                        column: x + 1,
+                        found,
+                    });
+                }
+            }
+        }
+        y += 1;
+    }
+    Ok(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &str = "!Name: Glider\n!A comment\n.O.\n..O\nOOO\n";
+
+    #[test]
+    fn cells_and_name_are_read() {
+        let pattern = parse_plaintext(GLIDER).unwrap();
+        assert_eq!(pattern.name.as_deref(), Some("Glider"));
+        assert_eq!(pattern.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn empty_lines_are_dead_rows() {
+        let pattern = parse_plaintext("O\n\n*.O").unwrap();
+        assert_eq!(pattern.cells, vec![(0, 0), (0, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn detection() {
+        assert!(looks_like_plaintext(GLIDER));
+        assert!(looks_like_plaintext(".O.\nOOO"));
+        assert!(!looks_like_plaintext("x = 3, y = 3\nbo$2bo$3o!"));
+        assert!(!looks_like_plaintext(""));
+    }
+
+    #[test]
+    fn unexpected_characters_are_located() {
+        assert_eq!(
+            parse_plaintext("!Name: x\n.O.\n..X").unwrap_err(),
+            PlaintextError {
+                line: 3,
+                column: 3,
+                found: 'X',
+            }
+        );
+    }
+}
@@ -0,0 +1,369 @@
+//! # RLE Module
+//!
+//! Parsing of the Run Length Encoded pattern format.
+
+use crate::pattern::ParsedPattern;
+use std::fmt;
+
+/// Kind of problem found while parsing RLE content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RleErrorKind {
+    /// The content has no pattern data
+    Empty,
+    /// A character that is not part of the RLE alphabet
+    UnexpectedChar(char),
+    /// A header field that is not `x`, `y` or `rule`, or is malformed
+    InvalidHeader(String),
+    /// A run count too large to be meaningful
+    CountTooLarge,
+    /// The runs reach past the representable coordinates
+    PatternTooLarge,
+    /// The pattern has more living cells than [`MAX_PATTERN_CELLS`]
+    TooManyCells,
+    /// A run count not followed by `b`, `o` or `$`
+    DanglingCount,
+    /// The terminating `!` is missing
+    MissingTerminator,
+}
+
+/// Error returned when RLE content cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RleError {
+    /// Line of the error (1-based)
+    pub line: usize,
+    /// Column of the error (1-based)
+    pub column: usize,
+    /// What went wrong
+    pub kind: RleErrorKind,
+}
+
+impl fmt::Display for RleErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no pattern data"),
+            Self::UnexpectedChar(c) => write!(f, "unexpected character '{c}'"),
+            Self::InvalidHeader(field) => write!(f, "invalid header field '{field}'"),
+            Self::CountTooLarge => write!(f, "run count too large"),
+            Self::PatternTooLarge => write!(f, "pattern too large"),
+            Self::TooManyCells => write!(f, "more than {MAX_PATTERN_CELLS} living cells"),
+            Self::DanglingCount => write!(f, "run count not followed by b, o or $"),
+            Self::MissingTerminator => write!(f, "missing terminating '!'"),
+        }
+    }
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl std::error::Error for RleError {}
+
+/// Largest accepted run count
+const MAX_RUN: i32 = 1_000_000;
+
+/// Largest accepted number of living cells in a pattern
+pub const MAX_PATTERN_CELLS: usize = 5_000_000;
+
+/// Parses RLE content, reporting the position of the first error.
+///
+/// Comment lines (`#N`, `#C`...) are skipped and the optional
+/// `x = .., y = .., rule = ..` header is read for its rule.
+pub fn parse_rle_pattern(rle: &str) -> Result<ParsedPattern, RleError> {
+    let mut pattern = ParsedPattern::default();
+    let (mut x, mut y) = (0i32, 0i32);
+    let mut num: Option<i32> = None;
+    let mut seen_data = false;
+    let mut last_position = (1, 1);
+
+    for (line_index, line) in rle.lines().enumerate() {
+        let line_number = line_index + 1;
+        let trimmed = line.trim_start();
+        if let Some(name) = trimmed.strip_prefix("#N") {
+            pattern.name = Some(name.trim().to_string()).filter(|name| !name.is_empty());
+            continue;
+        }
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        if !seen_data && trimmed.starts_with('x') {
+            pattern.rule = parse_header(trimmed).map_err(|kind| RleError {
+                line: line_number,
+                column: line.len() - trimmed.len() + 1,
+                kind,
+            })?;
+            continue;
+        }
+
+        for (char_index, c) in line.chars().enumerate() {
+            let error = |kind| RleError {
+                line: line_number,
+                column: char_index + 1,
+                kind,
+            };
+            last_position = (line_number, char_index + 1);
+            match c {
+                '0'..='9' => {
+                    let digit = c as i32 - '0' as i32;
+                    let value = num
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(digit))
+                        .filter(|&value| value <= MAX_RUN)
+                        .ok_or(error(RleErrorKind::CountTooLarge))?;
+                    num = Some(value);
+                }
+                'b' | '.' => {
+                    x = x
+                        .checked_add(num.take().unwrap_or(1))
+                        .ok_or(error(RleErrorKind::PatternTooLarge))?;
+                    seen_data = true;
+                }
+                'o' => {
+                    let count = num.take().unwrap_or(1);
+                    let end = x
+                        .checked_add(count)
+                        .ok_or(error(RleErrorKind::PatternTooLarge))?;
+                    if pattern.cells.len() + count as usize > MAX_PATTERN_CELLS {
+                        return Err(error(RleErrorKind::TooManyCells));
+                    }
+                    pattern.cells.extend((x..end).map(|cell_x| (cell_x, y)));
+                    x = end;
+                    seen_data = true;
+                }
+                '$' => {
+                    y = y
+                        .checked_add(num.take().unwrap_or(1))
+                        .ok_or(error(RleErrorKind::PatternTooLarge))?;
+                    x = 0;
+                    seen_data = true;
+                }
+                '!' => {
+                    if num.is_some() {
+                        return Err(error(RleErrorKind::DanglingCount));
+                    }
+                    return Ok(pattern);
+                }
+                c if c.is_whitespace() => {
+                    if num.is_some() {
+                        return Err(error(RleErrorKind::DanglingCount));
+                    }
+                }
+                c => return Err(error(RleErrorKind::UnexpectedChar(c))),
+            }
+        }
+    }
+
+    let (line, column) = last_position;
+    Err(RleError {
+        line,
+        column,
+        kind: if seen_data {
+            RleErrorKind::MissingTerminator
+        } else {
+            RleErrorKind::Empty
+        },
+    })
+}
+
+/// Parses RLE content known to be valid, such as embedded assets.
+///
+/// # Panics
+///
+/// Panics if the content is not valid RLE.
+pub fn parse_rle(rle: &str) -> Vec<(i32, i32)> {
+    match parse_rle_pattern(rle) {
+        Ok(pattern) => pattern.cells,
+        Err(e) => panic!("invalid embedded RLE pattern: {e}"),
+    }
+}
+
+/// Maximum length of an encoded RLE line, as recommended by the format
+const LINE_LENGTH: usize = 70;
+
+/// Encodes cells as RLE, with the top-left of their bounding box as origin
+pub fn encode_rle(cells: &[(i32, i32)], rule: Option<&str>) -> String {
+    let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let width = cells.iter().map(|&(x, _)| x - min_x + 1).max().unwrap_or(0);
+    let height = cells.iter().map(|&(_, y)| y - min_y + 1).max().unwrap_or(0);
+
+    let mut header = format!("x = {width}, y = {height}");
+    if let Some(rule) = rule {
+        header.push_str(&format!(", rule = {rule}"));
+    }
+
+    // Row-major order, as runs are read left to right then top to bottom
+    let mut sorted: Vec<(i32, i32)> = cells
+        .iter()
+        .map(|&(x, y)| (y - min_y, x - min_x))
+        .collect();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let mut runs: Vec<(i32, char)> = Vec::new();
+    let mut push = |count: i32, tag: char| match runs.last_mut() {
+        Some((last_count, last_tag)) if *last_tag == tag => *last_count += count,
+        _ => runs.push((count, tag)),
+    };
+    let (mut x, mut y) = (0, 0);
+    for (cell_y, cell_x) in sorted {
+        if cell_y > y {
+            push(cell_y - y, '$');
+            y = cell_y;
+            x = 0;
+        }
+        if cell_x > x {
+            push(cell_x - x, 'b');
+        }
+        push(1, 'o');
+        x = cell_x + 1;
+    }
+    push(1, '!');
+
+    let mut rle = header;
+    let mut line = String::new();
+    for (count, tag) in runs {
+        let run = if count == 1 {
+            tag.to_string()
+        } else {
+            format!("{count}{tag}")
+        };
+        if line.len() + run.len() > LINE_LENGTH {
+            rle.push('\n');
+            rle.push_str(&line);
+            line.clear();
+        }
+        line.push_str(&run);
+    }
+    rle.push('\n');
+    rle.push_str(&line);
+    rle.push('\n');
+    rle
+}
+
+/// Reads the rule from a `x = 3, y = 3, rule = B3/S23` header line.
+///
+/// The rule runs to the end of the line, as Golly rules such as
+/// `B3/S23:T100,100` contain commas.
+fn parse_header(header: &str) -> Result<Option<String>, RleErrorKind> {
+    let mut rest = header;
+    loop {
+        let Some((key, value)) = rest.split_once('=') else {
+            return Err(RleErrorKind::InvalidHeader(rest.trim().to_string()));
+        };
+        let (key, value, next) = match key.trim() {
+            "rule" => return Ok(Some(value.trim().to_string())),
+            key => match value.split_once(',') {
+                Some((value, next)) => (key, value.trim(), Some(next)),
+                None => (key, value.trim(), None),
+            },
+        };
+        if !matches!(key, "x" | "y") || value.parse::<u64>().is_err() {
+            return Err(RleErrorKind::InvalidHeader(format!("{key} = {value}")));
+        }
+        match next {
+            Some(next) => rest = next,
+            None => return Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut cells: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
+        cells.sort_unstable();
+        cells
+    }
+
+    #[test]
+    fn multi_digit_runs_and_row_counts() {
+        let pattern = parse_rle_pattern("x = 13, y = 12\n12bo$o10$2o!").unwrap();
+        assert_eq!(
+            sorted(pattern.cells),
+            vec![(0, 1), (0, 11), (1, 11), (12, 0)]
+        );
+    }
+
+    #[test]
+    fn header_and_name_are_read() {
+        let pattern =
+            parse_rle_pattern("#N Glider\n#C A comment\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!")
+                .unwrap();
+        assert_eq!(pattern.name.as_deref(), Some("Glider"));
+        assert_eq!(pattern.rule.as_deref(), Some("B3/S23"));
+        assert_eq!(pattern.cells.len(), 5);
+    }
+
+    #[test]
+    fn golly_rule_keeps_its_commas() {
+        let pattern = parse_rle_pattern("x = 1, y = 1, rule = B3/S23:T100,100\no!").unwrap();
+        assert_eq!(pattern.rule.as_deref(), Some("B3/S23:T100,100"));
+    }
+
+    #[test]
+    fn encoding_round_trips() {
+        let cells = vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2), (40, 9)];
+        let rle = encode_rle(&cells, Some("B3/S23"));
+        let pattern = parse_rle_pattern(&rle).unwrap();
+        assert_eq!(sorted(pattern.cells), sorted(cells));
+        assert_eq!(pattern.rule.as_deref(), Some("B3/S23"));
+    }
+
+    #[test]
+    fn errors_report_their_position() {
+        let error = |rle| parse_rle_pattern(rle).unwrap_err();
+        assert_eq!(
+            error("x = 3, y = 3\nbo$2bz!"),
+            RleError {
+                line: 2,
+                column: 6,
+                kind: RleErrorKind::UnexpectedChar('z'),
+            }
+        );
+        assert_eq!(
+            error("x = 3, z = 3\no!").kind,
+            RleErrorKind::InvalidHeader("z = 3".into())
+        );
+        assert_eq!(error("9999999o!").kind, RleErrorKind::CountTooLarge);
+        assert_eq!(error("3!").kind, RleErrorKind::DanglingCount);
+        assert_eq!(error("bo$o").kind, RleErrorKind::MissingTerminator);
+        assert_eq!(error("#C only a comment").kind, RleErrorKind::Empty);
+    }
+
+    #[test]
+    fn coordinate_overflow_is_an_error() {
+        let rle = "1000000b".repeat(3000) + "o!";
+        assert_eq!(
+            parse_rle_pattern(&rle).unwrap_err(),
+            RleError {
+                line: 1,
+                column: 2148 * 8,
+                kind: RleErrorKind::PatternTooLarge,
+            }
+        );
+        let rle = "1000000$".repeat(3000) + "o!";
+        assert_eq!(
+            parse_rle_pattern(&rle).unwrap_err().kind,
+            RleErrorKind::PatternTooLarge
+        );
+    }
+
+    #[test]
+    fn living_cells_are_capped() {
+        let rle = "1000000o$".repeat(5) + "!";
+        assert_eq!(parse_rle_pattern(&rle).unwrap().cells.len(), 5_000_000);
+        let rle = "1000000o$".repeat(6) + "!";
+        assert_eq!(
+            parse_rle_pattern(&rle).unwrap_err(),
+            RleError {
+                line: 1,
+                column: 5 * 9 + 8,
+                kind: RleErrorKind::TooManyCells,
+            }
+        );
+    }
+}
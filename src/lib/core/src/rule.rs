@@ -0,0 +1,666 @@
+//! # Rule Module
+//!
+//! Birth/survival rules of Life-like automata, the neighborhoods they count
+//! over, and their rulestring notation.
+
+use crate::hensel::{ConfigSet, Transitions, format_conditions, is_hensel, parse_conditions};
+use std::fmt;
+use std::str::FromStr;
+
+/// The eight neighboring positions relative to any cell.
+/// These offsets represent the Moore neighborhood (all adjacent cells).
+pub static NEIGHBORS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// The four orthogonal neighbors of the von Neumann neighborhood
+pub static VON_NEUMANN_NEIGHBORS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// The 24 cells of the 5×5 square around a cell, for Moore neighborhoods of radius 2
+pub static MOORE_RADIUS_2_NEIGHBORS: [(isize, isize); 24] = [
+    (-2, -2),
+    (-1, -2),
+    (0, -2),
+    (1, -2),
+    (2, -2),
+    (-2, -1),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (2, -1),
+    (-2, 0),
+    (-1, 0),
+    (1, 0),
+    (2, 0),
+    (-2, 1),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (2, 1),
+    (-2, 2),
+    (-1, 2),
+    (0, 2),
+    (1, 2),
+    (2, 2),
+];
+
+/// Set of cells whose living count decides the fate of a cell
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    /// The eight surrounding cells
+    #[default]
+    Moore,
+    /// The four orthogonally adjacent cells
+    VonNeumann,
+    /// The 24 cells within a distance of two, as in Larger than Life
+    MooreRadius2,
+}
+
+impl Neighborhood {
+    /// Every neighborhood, in display order
+    pub const ALL: [Self; 3] = [Self::Moore, Self::VonNeumann, Self::MooreRadius2];
+
+    /// Offsets of the neighbors relative to the cell
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Moore => &NEIGHBORS,
+            Self::VonNeumann => &VON_NEUMANN_NEIGHBORS,
+            Self::MooreRadius2 => &MOORE_RADIUS_2_NEIGHBORS,
+        }
+    }
+
+    /// Number of neighbors, which is also the highest possible count
+    pub fn size(&self) -> usize {
+        self.offsets().len()
+    }
+
+    /// Largest distance between a cell and one of its neighbors
+    pub fn radius(&self) -> usize {
+        match self {
+            Self::Moore | Self::VonNeumann => 1,
+            Self::MooreRadius2 => 2,
+        }
+    }
+}
+
+impl fmt::Display for Neighborhood {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Moore => write!(f, "Moore"),
+            Self::VonNeumann => write!(f, "von Neumann"),
+            Self::MooreRadius2 => write!(f, "Moore (radius 2)"),
+        }
+    }
+}
+
+/// Meaning of the states a living cell can be in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StateModel {
+    /// A single living state
+    #[default]
+    TwoState,
+    /// Living cells carry one of several colors, numbered from 1, and
+    /// newborns take the majority color of their parents
+    Colors(u8),
+    /// Generations rules with the given number of states, dead included.
+    ///
+    /// Only cells in state 1 count as neighbors. Those that do not survive
+    /// start dying through the following states instead of dying at once,
+    /// and dying cells cannot be born again until they are dead.
+    Generations(u8),
+}
+
+impl StateModel {
+    /// Two colors, as in Immigration
+    pub const IMMIGRATION: Self = Self::Colors(2);
+    /// Four colors, as in QuadLife
+    pub const QUADLIFE: Self = Self::Colors(4);
+
+    /// Generations rule with `count` states, or a two-state rule below 3
+    pub fn generations(count: u8) -> Self {
+        if count > 2 {
+            Self::Generations(count)
+        } else {
+            Self::TwoState
+        }
+    }
+
+    /// Number of states a living cell can be in, numbered from 1
+    pub fn living_states(&self) -> u8 {
+        match self {
+            Self::TwoState => 1,
+            Self::Colors(colors) => *colors,
+            Self::Generations(count) => count - 1,
+        }
+    }
+
+    /// Number of states, the dead one included
+    pub fn count(&self) -> u8 {
+        self.living_states() + 1
+    }
+
+    /// Name appended to the rulestring ("B36/S23 QuadLife")
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Self::Colors(2) => Some("Immigration"),
+            Self::Colors(4) => Some("QuadLife"),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned when a rulestring cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleParseError {
+    /// The rulestring is empty
+    Empty,
+    /// The rulestring does not contain exactly one birth and one survival part
+    MissingPart,
+    /// A neighbor count is not a digit within the neighborhood size
+    InvalidCount(char),
+    /// A Hensel letter does not name a configuration of its neighbor count
+    InvalidLetter(char),
+    /// A field of a range-based rule ("R2,C2,S5-8,B6-7,NM") is malformed
+    InvalidField(String),
+    /// The requested range and neighborhood type are not supported
+    UnsupportedNeighborhood(String),
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty rulestring"),
+            Self::MissingPart => write!(f, "expected a rule like B3/S23"),
+            Self::InvalidCount(c) => write!(f, "invalid neighbor count '{c}'"),
+            Self::InvalidLetter(c) => write!(f, "invalid neighborhood letter '{c}'"),
+            Self::InvalidField(field) => write!(f, "invalid rule field '{field}'"),
+            Self::UnsupportedNeighborhood(neighborhood) => {
+                write!(f, "unsupported neighborhood '{neighborhood}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+/// Birth/survival rule of a Life-like automaton.
+///
+/// Each count field is a bitmask where bit `n` is set when a cell with `n`
+/// living neighbors is born (resp. survives). Isotropic non-totalistic rules
+/// also carry the exact neighbor configurations, and their count fields hold
+/// every count with at least one matching configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rule {
+    /// Neighbor counts that make a dead cell come alive
+    pub birth: u32,
+    /// Neighbor counts that keep a living cell alive
+    pub survival: u32,
+    /// Cells counted as neighbors
+    pub neighborhood: Neighborhood,
+    /// Configurations of a non-totalistic Moore rule ("B2-a/S12")
+    pub transitions: Option<Transitions>,
+    /// States of living cells
+    pub states: StateModel,
+}
+
+impl Rule {
+    /// Conway's Game of Life (B3/S23)
+    pub const CONWAY: Self = Self {
+        birth: 1 << 3,
+        survival: (1 << 2) | (1 << 3),
+        neighborhood: Neighborhood::Moore,
+        transitions: None,
+        states: StateModel::TwoState,
+    };
+
+    /// Parses a rulestring.
+    ///
+    /// Accepts the B/S notation ("B3/S23", "b36/s23", "S23/B3") as well as
+    /// the older survival/birth notation ("23/3"). A trailing "V" selects the
+    /// von Neumann neighborhood ("B2/S013V"), and larger neighborhoods use the
+    /// range notation of Golly ("R2,C2,S6-9,B7-8,NM"). Moore rules may follow
+    /// each count with Hensel letters ("B2-a/S12"). The multi-color variants
+    /// are named after the rulestring ("B36/S23 QuadLife"), and alone they
+    /// stand for Conway's rule ("Immigration"). Generations rules end with
+    /// their number of states ("B2/S/C3", or "/2/3" in S/B/C order).
+    pub fn parse(rulestring: &str) -> Result<Self, RuleParseError> {
+        let rulestring = rulestring.trim();
+        if rulestring.is_empty() {
+            return Err(RuleParseError::Empty);
+        }
+        let (rulestring, states) = split_state_model(rulestring);
+        let rule = if rulestring.is_empty() {
+            Self::CONWAY
+        } else {
+            Self::parse_two_state(rulestring)?
+        };
+        // The range notation gives its number of states itself
+        if states == StateModel::TwoState {
+            return Ok(rule);
+        }
+        Ok(Self { states, ..rule })
+    }
+
+    /// Parses a rulestring without a state model name or number of states
+    fn parse_two_state(rulestring: &str) -> Result<Self, RuleParseError> {
+        if is_range_rule(rulestring) {
+            return parse_range_rule(rulestring);
+        }
+
+        let (rulestring, neighborhood) = match rulestring.strip_suffix(['V', 'v']) {
+            Some(rest) => (rest, Neighborhood::VonNeumann),
+            None => (rulestring, Neighborhood::Moore),
+        };
+        let max = neighborhood.size();
+
+        let parts: Vec<&str> = rulestring.split('/').map(str::trim).collect();
+        let &[first, second] = parts.as_slice() else {
+            return Err(RuleParseError::MissingPart);
+        };
+
+        let (mut birth, mut survival) = (None, None);
+        for part in [first, second] {
+            let mut chars = part.chars();
+            match chars.next().map(|c| c.to_ascii_uppercase()) {
+                Some('B') => birth = Some(chars.as_str()),
+                Some('S') => survival = Some(chars.as_str()),
+                _ => {}
+            }
+        }
+        let (birth, survival) = match (birth, survival) {
+            (Some(birth), Some(survival)) => (birth, survival),
+            // Classic "S/B" notation without letters
+            (None, None) => (second, first),
+            _ => return Err(RuleParseError::MissingPart),
+        };
+
+        if neighborhood == Neighborhood::Moore && (is_hensel(birth) || is_hensel(survival)) {
+            return Ok(Self::from_transitions(Transitions {
+                birth: parse_conditions(birth)?,
+                survival: parse_conditions(survival)?,
+            }));
+        }
+        Ok(Self {
+            birth: parse_counts(birth, max)?,
+            survival: parse_counts(survival, max)?,
+            neighborhood,
+            transitions: None,
+            states: StateModel::TwoState,
+        })
+    }
+
+    /// Moore rule with the given configurations, kept totalistic when every
+    /// count is either fully included or left out
+    pub fn from_transitions(transitions: Transitions) -> Self {
+        let birth = transitions.birth.counts();
+        let survival = transitions.survival.counts();
+        let totalistic = transitions.birth == ConfigSet::from_counts(birth)
+            && transitions.survival == ConfigSet::from_counts(survival);
+        Self {
+            birth,
+            survival,
+            neighborhood: Neighborhood::Moore,
+            transitions: (!totalistic).then_some(transitions),
+            states: StateModel::TwoState,
+        }
+    }
+
+    /// Whether a dead cell with `neighbor_count` living neighbors is born
+    pub fn is_birth(&self, neighbor_count: usize) -> bool {
+        neighbor_count <= self.neighborhood.size() && self.birth & (1 << neighbor_count) != 0
+    }
+
+    /// Whether a living cell with `neighbor_count` living neighbors survives
+    pub fn is_survival(&self, neighbor_count: usize) -> bool {
+        neighbor_count <= self.neighborhood.size() && self.survival & (1 << neighbor_count) != 0
+    }
+
+    /// Next state of a cell from which of its eight surrounding cells are
+    /// alive, bit `i` of `config` standing for [`NEIGHBORS`]`[i]`.
+    ///
+    /// Only fits radius-1 neighborhoods, whose neighbors all lie in the 3×3 block.
+    pub fn next_state(&self, alive: bool, config: u8) -> bool {
+        if let Some(transitions) = &self.transitions {
+            let set = if alive {
+                &transitions.survival
+            } else {
+                &transitions.birth
+            };
+            return set.contains(config);
+        }
+        let offsets = self.neighborhood.offsets();
+        let count = NEIGHBORS
+            .iter()
+            .enumerate()
+            .filter(|(i, offset)| config & (1 << i) != 0 && offsets.contains(offset))
+            .count();
+        if alive {
+            self.is_survival(count)
+        } else {
+            self.is_birth(count)
+        }
+    }
+
+    /// Same rule with a neighbor count added to or removed from the birth
+    /// (or survival) conditions, replacing any Hensel letters of that count
+    pub fn with_condition(self, survival: bool, count: usize, enabled: bool) -> Self {
+        let bit = 1u32 << count;
+        let toggle = |mask: u32| if enabled { mask | bit } else { mask & !bit };
+        let Some(mut transitions) = self.transitions else {
+            let (birth, survival) = if survival {
+                (self.birth, toggle(self.survival))
+            } else {
+                (toggle(self.birth), self.survival)
+            };
+            return Self {
+                birth,
+                survival,
+                ..self
+            };
+        };
+
+        let set = if survival {
+            &mut transitions.survival
+        } else {
+            &mut transitions.birth
+        };
+        let configs = ConfigSet::with_count(count);
+        *set = if enabled {
+            set.union(&configs)
+        } else {
+            set.difference(&configs)
+        };
+        Self {
+            states: self.states,
+            ..Self::from_transitions(transitions)
+        }
+    }
+
+    /// Same rule over another neighborhood, dropping counts it cannot reach
+    /// and the configurations that only make sense for the Moore neighborhood
+    pub fn with_neighborhood(self, neighborhood: Neighborhood) -> Self {
+        let reachable = (1u32 << (neighborhood.size() + 1)) - 1;
+        Self {
+            birth: self.birth & reachable,
+            survival: self.survival & reachable,
+            neighborhood,
+            transitions: self
+                .transitions
+                .filter(|_| neighborhood == Neighborhood::Moore),
+            states: self.states,
+        }
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::CONWAY
+    }
+}
+
+impl FromStr for Rule {
+    type Err = RuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.states.name() {
+            let rule = Self {
+                states: StateModel::TwoState,
+                ..*self
+            };
+            return if rule == Self::CONWAY {
+                write!(f, "{name}")
+            } else {
+                write!(f, "{rule} {name}")
+            };
+        }
+        if matches!(self.states, StateModel::Generations(_))
+            && self.neighborhood != Neighborhood::MooreRadius2
+        {
+            let rule = Self {
+                states: StateModel::TwoState,
+                ..*self
+            };
+            return write!(f, "{rule}/C{}", self.states.count());
+        }
+        let digits = |mask: u32| -> String {
+            (0..=8u32)
+                .filter(|&n| mask & (1 << n) != 0)
+                .map(|n| char::from(b'0' + n as u8))
+                .collect()
+        };
+        if let Some(transitions) = &self.transitions {
+            return write!(
+                f,
+                "B{}/S{}",
+                format_conditions(&transitions.birth),
+                format_conditions(&transitions.survival)
+            );
+        }
+        match self.neighborhood {
+            Neighborhood::Moore => write!(f, "B{}/S{}", digits(self.birth), digits(self.survival)),
+            Neighborhood::VonNeumann => {
+                write!(f, "B{}/S{}V", digits(self.birth), digits(self.survival))
+            }
+            Neighborhood::MooreRadius2 => write!(
+                f,
+                "R2,C{},S{},B{},NM",
+                self.states.count(),
+                count_ranges(self.survival),
+                count_ranges(self.birth)
+            ),
+        }
+    }
+}
+
+/// Parses a list of neighbor counts ("236") into a bitmask
+fn parse_counts(digits: &str, max: usize) -> Result<u32, RuleParseError> {
+    let mut mask = 0u32;
+    for c in digits.chars() {
+        match c.to_digit(10) {
+            Some(n) if n as usize <= max => mask |= 1 << n,
+            _ => return Err(RuleParseError::InvalidCount(c)),
+        }
+    }
+    Ok(mask)
+}
+
+/// Splits the name of a multi-color variant ("B36/S23 QuadLife") or the
+/// number of states of a Generations rule ("B2/S/C3") off a rulestring
+fn split_state_model(rulestring: &str) -> (&str, StateModel) {
+    for states in [StateModel::IMMIGRATION, StateModel::QUADLIFE] {
+        let Some(name) = states.name() else {
+            continue;
+        };
+        let split = rulestring.len().saturating_sub(name.len());
+        let matches = rulestring
+            .get(split..)
+            .is_some_and(|suffix| suffix.eq_ignore_ascii_case(name));
+        if matches {
+            return (rulestring[..split].trim_end(), states);
+        }
+    }
+
+    if let Some((rule, count)) = rulestring.rsplit_once('/') {
+        let count = count.trim();
+        let count = count.strip_prefix(['C', 'c']).unwrap_or(count);
+        // Birth and survival must still be there before the count
+        let count = count.parse::<u8>().ok().filter(|_| rule.contains('/'));
+        if let Some(count) = count {
+            return (rule.trim_end(), StateModel::generations(count));
+        }
+    }
+    (rulestring, StateModel::TwoState)
+}
+
+/// Whether the rulestring uses the range notation ("R2,...")
+fn is_range_rule(rulestring: &str) -> bool {
+    let mut chars = rulestring.chars();
+    matches!(chars.next(), Some('R' | 'r')) && chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
+/// Parses a rule in the range notation, e.g. "R2,C2,S6-9,B7-8,NM".
+///
+/// Counts are comma-separated numbers or inclusive ranges following `S` or
+/// `B`. `C` is the number of states, above 2 for Generations rules, and `M`
+/// (whether the cell counts itself) must be 0 when given.
+fn parse_range_rule(rulestring: &str) -> Result<Rule, RuleParseError> {
+    let mut range = None;
+    let mut neighborhood_type = 'M';
+    let mut states = StateModel::TwoState;
+    let (mut birth, mut survival) = (None, None);
+    // Count lists continue over the following comma-separated items
+    let mut list = None;
+
+    for item in rulestring.split(',').map(str::trim) {
+        let invalid = || RuleParseError::InvalidField(item.to_string());
+        let Some(first) = item.chars().next() else {
+            continue;
+        };
+        let (field, value) = if first.is_ascii_digit() {
+            (list.ok_or_else(invalid)?, item)
+        } else {
+            (first.to_ascii_uppercase(), &item[first.len_utf8()..])
+        };
+        list = None;
+        match field {
+            'R' => range = Some(value.parse::<usize>().map_err(|_| invalid())?),
+            'C' => states = StateModel::generations(value.parse().map_err(|_| invalid())?),
+            'M' if value == "0" => {}
+            'N' => {
+                let kind = value.chars().next().ok_or_else(invalid)?;
+                neighborhood_type = kind.to_ascii_uppercase();
+            }
+            'S' | 'B' => {
+                let counts = if field == 'S' {
+                    &mut survival
+                } else {
+                    &mut birth
+                };
+                let mask = counts.get_or_insert(0);
+                if !value.is_empty() {
+                    *mask |= parse_count_range(value).ok_or_else(invalid)?;
+                }
+                list = Some(field);
+            }
+            _ => return Err(invalid()),
+        }
+    }
+
+    let neighborhood = match (range, neighborhood_type) {
+        (Some(1), 'M') => Neighborhood::Moore,
+        (Some(1), 'N') => Neighborhood::VonNeumann,
+        (Some(2), 'M') => Neighborhood::MooreRadius2,
+        (range, kind) => {
+            return Err(RuleParseError::UnsupportedNeighborhood(format!(
+                "R{},N{kind}",
+                range.unwrap_or(0)
+            )));
+        }
+    };
+    let (Some(birth), Some(survival)) = (birth, survival) else {
+        return Err(RuleParseError::MissingPart);
+    };
+    let rule = Rule {
+        birth,
+        survival,
+        neighborhood,
+        transitions: None,
+        states,
+    };
+    // Counts beyond the neighborhood size can never happen
+    if rule.with_neighborhood(neighborhood) != rule {
+        return Err(RuleParseError::InvalidField(rulestring.to_string()));
+    }
+    Ok(rule)
+}
+
+/// Parses a neighbor count ("5") or an inclusive range of counts ("2-4") into a bitmask
+fn parse_count_range(item: &str) -> Option<u32> {
+    let (low, high) = item.split_once('-').unwrap_or((item, item));
+    let low: usize = low.trim().parse().ok()?;
+    let high: usize = high.trim().parse().ok()?;
+    if low > high || high > MOORE_RADIUS_2_NEIGHBORS.len() {
+        return None;
+    }
+    Some((low..=high).fold(0, |mask, n| mask | 1 << n))
+}
+
+/// Formats a bitmask of counts as comma-separated numbers and ranges ("2-3,5")
+fn count_ranges(mask: u32) -> String {
+    let mut ranges = Vec::new();
+    let mut n = 0;
+    while n < u32::BITS {
+        if mask & (1 << n) == 0 {
+            n += 1;
+            continue;
+        }
+        let start = n;
+        while n + 1 < u32::BITS && mask & (1 << (n + 1)) != 0 {
+            n += 1;
+        }
+        ranges.push(if start == n {
+            start.to_string()
+        } else {
+            format!("{start}-{n}")
+        });
+        n += 1;
+    }
+    ranges.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rulestrings_round_trip() {
+        for rulestring in [
+            "B3/S23",
+            "B36/S23",
+            "B2/S013V",
+            "B2-a/S12",
+            "B2/S/C3",
+            "B36/S23 QuadLife",
+            "Immigration",
+            "R2,C2,S6-9,B7-8,NM",
+        ] {
+            let rule = Rule::parse(rulestring).unwrap();
+            assert_eq!(rule.to_string(), rulestring);
+            assert_eq!(Rule::parse(&rule.to_string()), Ok(rule));
+        }
+    }
+
+    #[test]
+    fn alternative_notations_name_the_same_rule() {
+        for rulestring in ["B3/S23", "b3/s23", "S23/B3", "23/3", " B3/S23 "] {
+            assert_eq!(Rule::parse(rulestring), Ok(Rule::CONWAY), "{rulestring}");
+        }
+    }
+
+    #[test]
+    fn invalid_rulestrings_are_rejected() {
+        assert_eq!(Rule::parse(""), Err(RuleParseError::Empty));
+        assert_eq!(Rule::parse("B3"), Err(RuleParseError::MissingPart));
+        assert_eq!(
+            Rule::parse("B39/S23"),
+            Err(RuleParseError::InvalidCount('9'))
+        );
+        assert_eq!(
+            Rule::parse("B5/S23V"),
+            Err(RuleParseError::InvalidCount('5'))
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! # Soup Module
+//!
+//! Random soups run until their population settles into a cycle, with a
+//! census of the objects left behind, in the spirit of soup searchers such as
+//! apgsearch.
+
+use crate::board::{Board, Cell};
+use crate::noise::SplitMix64;
+use crate::rule::Rule;
+use rustc_hash::FxHashSet;
+use std::collections::BTreeMap;
+
+/// Side of the square soups, in cells
+pub const SOUP_SIZE: isize = 16;
+
+/// Longest population period recognized as settled
+pub const MAX_SETTLED_PERIOD: usize = 30;
+
+/// Number of generations the population must repeat before a soup counts
+/// as settled
+pub const SETTLED_WINDOW: usize = 120;
+
+/// Common objects of the ash, as rows of living (`o`) and dead (`.`) cells.
+///
+/// Every orientation is recognized, so each phase needs a single entry.
+const KNOWN_OBJECTS: &[(&str, &str)] = &[
+    ("block", "oo$oo"),
+    ("blinker", "ooo"),
+    ("beehive", ".oo.$o..o$.oo."),
+    ("loaf", ".oo.$o..o$.o.o$..o."),
+    ("boat", "oo.$o.o$.o."),
+    ("ship", "oo.$o.o$.oo"),
+    ("tub", ".o.$o.o$.o."),
+    ("pond", ".oo.$o..o$o..o$.oo."),
+    ("glider", ".o.$..o$ooo"),
+    ("glider", "o.o$.oo$.o."),
+    ("beacon", "oo..$oo..$..oo$..oo"),
+    ("beacon", "oo..$o...$...o$..oo"),
+    ("toad", ".ooo$ooo."),
+    ("toad", "..o.$o..o$o..o$.o.."),
+];
+
+/// Outcome of a soup run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoupResult {
+    /// Seed the soup was generated from, see [`random_soup`]
+    pub seed: u64,
+    /// Generations before the population started to cycle, or the
+    /// generation limit if it never did
+    pub lifespan: u64,
+    /// Period of the settled population, `None` when the limit was reached
+    pub period: Option<usize>,
+    /// Living cells once settled
+    pub final_population: usize,
+    /// Objects of the ash by name, with their count
+    pub census: BTreeMap<String, usize>,
+}
+
+impl SoupResult {
+    /// Whether the soup settled before the generation limit
+    pub fn settled(&self) -> bool {
+        self.period.is_some()
+    }
+
+    /// One CSV line: seed, lifespan, period, final population and census
+    pub fn to_csv_line(&self) -> String {
+        let census: Vec<String> = self
+            .census
+            .iter()
+            .map(|(name, count)| format!("{name}:{count}"))
+            .collect();
+        format!(
+            "{},{},{},{},\"{}\"",
+            self.seed,
+            self.lifespan,
+            self.period
+                .map_or(String::new(), |period| period.to_string()),
+            self.final_population,
+            census.join(" ")
+        )
+    }
+}
+
+/// Header matching [`SoupResult::to_csv_line`]
+pub const SOUP_CSV_HEADER: &str = "seed,lifespan,period,final_population,census";
+
+/// Square soup of [`SOUP_SIZE`] cells at 50% density, its bottom-left
+/// corner at the origin. The same seed always gives the same soup.
+pub fn random_soup(seed: u64) -> Board {
+    let mut rng = SplitMix64::new(seed);
+    let mut board = Board::new();
+    for y in 0..SOUP_SIZE {
+        for x in 0..SOUP_SIZE {
+            if rng.next_u64() & 1 == 1 {
+                board.set(Cell::new(x, y), true);
+            }
+        }
+    }
+    board
+}
+
+/// Runs the soup of `seed` until its population cycles or
+/// `max_generations` have passed, then takes the census of its ash
+pub fn run_soup(seed: u64, rule: &Rule, max_generations: u64) -> SoupResult {
+    let mut board = random_soup(seed);
+    let mut populations = vec![board.len()];
+    let mut settled = None;
+    for _ in 0..max_generations {
+        board.advance(rule);
+        populations.push(board.len());
+        if let Some(period) = settled_period(&populations, MAX_SETTLED_PERIOD) {
+            settled = Some(period);
+            break;
+        }
+    }
+
+    let generation = populations.len() as u64 - 1;
+    SoupResult {
+        seed,
+        lifespan: match settled {
+            Some(period) => generation.saturating_sub((SETTLED_WINDOW + period) as u64),
+            None => generation,
+        },
+        period: settled,
+        final_population: board.len(),
+        census: census(&board),
+    }
+}
+
+/// Smallest period up to `max_period` with which the last
+/// [`SETTLED_WINDOW`] populations repeat
+pub fn settled_period(populations: &[usize], max_period: usize) -> Option<usize> {
+    (1..=max_period).find(|&period| {
+        populations.len() > SETTLED_WINDOW + period
+            && (populations.len() - SETTLED_WINDOW..populations.len())
+                .all(|i| populations[i] == populations[i - period])
+    })
+}
+
+/// Counts the objects of a board, splitting it into groups of touching cells
+/// named after [`KNOWN_OBJECTS`] or, for unknown ones, after their cells
+pub fn census(board: &Board) -> BTreeMap<String, usize> {
+    let known: Vec<(&str, Vec<Cell>)> = KNOWN_OBJECTS
+        .iter()
+        .map(|(name, rows)| (*name, canonical(&parse_rows(rows))))
+        .collect();
+
+    let mut census = BTreeMap::new();
+    let mut seen: FxHashSet<Cell> = FxHashSet::default();
+    for &start in board.iter() {
+        if !seen.insert(start) {
+            continue;
+        }
+        let mut object = vec![start];
+        let mut i = 0;
+        while i < object.len() {
+            let cell = object[i];
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    let neighbor = Cell::new(cell.x + dx, cell.y + dy);
+                    if board.contains(&neighbor) && seen.insert(neighbor) {
+                        object.push(neighbor);
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        let shape = canonical(&object);
+        let name = known
+            .iter()
+            .find(|(_, cells)| *cells == shape)
+            .map_or_else(|| format_rows(&shape), |(name, _)| name.to_string());
+        *census.entry(name).or_insert(0) += 1;
+    }
+    census
+}
+
+/// Smallest of the eight orientations of a shape, moved to the origin, so
+/// that a shape and its rotations or reflections compare equal
+fn canonical(cells: &[Cell]) -> Vec<Cell> {
+    let transforms: [fn(Cell) -> Cell; 8] = [
+        |c| Cell::new(c.x, c.y),
+        |c| Cell::new(-c.y, c.x),
+        |c| Cell::new(-c.x, -c.y),
+        |c| Cell::new(c.y, -c.x),
+        |c| Cell::new(-c.x, c.y),
+        |c| Cell::new(c.y, c.x),
+        |c| Cell::new(c.x, -c.y),
+        |c| Cell::new(-c.y, -c.x),
+    ];
+    transforms
+        .iter()
+        .map(|transform| {
+            let moved: Vec<Cell> = cells.iter().map(|&cell| transform(cell)).collect();
+            let min_x = moved.iter().map(|cell| cell.x).min().unwrap_or(0);
+            let min_y = moved.iter().map(|cell| cell.y).min().unwrap_or(0);
+            let mut shape: Vec<Cell> = moved
+                .iter()
+                .map(|cell| Cell::new(cell.x - min_x, cell.y - min_y))
+                .collect();
+            shape.sort_unstable();
+            shape
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+/// Cells of rows written with `o` and `.`, separated by `$`
+fn parse_rows(rows: &str) -> Vec<Cell> {
+    rows.split('$')
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == 'o')
+                .map(move |(x, _)| Cell::new(x as isize, y as isize))
+        })
+        .collect()
+}
+
+/// Writes a shape as rows of `o` and `.`, the inverse of [`parse_rows`]
+fn format_rows(cells: &[Cell]) -> String {
+    let width = cells.iter().map(|cell| cell.x).max().unwrap_or(0) + 1;
+    let height = cells.iter().map(|cell| cell.y).max().unwrap_or(0) + 1;
+    let rows: Vec<String> = (0..height)
+        .map(|y| {
+            let row: String = (0..width)
+                .map(|x| {
+                    if cells.contains(&Cell::new(x, y)) {
+                        'o'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            row.trim_end_matches('.').to_string()
+        })
+        .collect();
+    rows.join("$")
+}
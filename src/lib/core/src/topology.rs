@@ -0,0 +1,65 @@
+//! # Topology Module
+//!
+//! Shape of the grid the automaton lives on: unbounded, a finite rectangle
+//! whose outside is always dead, or a torus where edges wrap around.
+
+use crate::board::Cell;
+
+/// Shape of the grid.
+///
+/// Finite grids are `width` x `height` cells centered on the origin, covering
+/// x in `-width / 2 .. width - width / 2` (and likewise for y).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridTopology {
+    /// Unbounded grid
+    #[default]
+    Infinite,
+    /// Finite grid surrounded by permanently dead cells
+    Bounded { width: usize, height: usize },
+    /// Finite grid whose opposite edges are joined
+    Torus { width: usize, height: usize },
+}
+
+impl GridTopology {
+    /// Inclusive (min, max) corners of a finite grid
+    pub fn bounds(&self) -> Option<(Cell, Cell)> {
+        match *self {
+            Self::Infinite => None,
+            Self::Bounded { width, height } | Self::Torus { width, height } => {
+                let min = Cell::new(-(width as isize / 2), -(height as isize / 2));
+                Some((
+                    min,
+                    Cell::new(min.x + width as isize - 1, min.y + height as isize - 1),
+                ))
+            }
+        }
+    }
+
+    /// Whether the cell lies on the grid
+    pub fn contains(&self, cell: Cell) -> bool {
+        match self.bounds() {
+            None => true,
+            Some((min, max)) => {
+                (min.x..=max.x).contains(&cell.x) && (min.y..=max.y).contains(&cell.y)
+            }
+        }
+    }
+
+    /// Maps a neighbor position onto the grid.
+    ///
+    /// Returns `None` past the edge of a bounded grid, and wraps coordinates
+    /// around on a torus.
+    pub fn wrap(&self, cell: Cell) -> Option<Cell> {
+        match *self {
+            Self::Torus { width, height } if width > 0 && height > 0 => {
+                let (min, _) = self.bounds()?;
+                Some(Cell::new(
+                    min.x + (cell.x - min.x).rem_euclid(width as isize),
+                    min.y + (cell.y - min.y).rem_euclid(height as isize),
+                ))
+            }
+            _ => self.contains(cell).then_some(cell),
+        }
+    }
+}
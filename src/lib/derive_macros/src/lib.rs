@@ -1,7 +1,12 @@
 use colored::{control, Colorize};
+use gol_core::{PatternFormat, parse_pattern};
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use syn::{Ident, LitStr, parse_macro_input};
 
 /// Information about a parsed RLE file
 struct RleFileInfo {
@@ -24,75 +29,282 @@ impl RleFileInfo {
     }
 }
 
-/// Generate all function for .rle files in a directory
+/// Generate all function for pattern files (.rle, .cells, .lif) in a directory and its subdirectories
 #[proc_macro]
 pub fn generate_pattern_functions(input: TokenStream) -> TokenStream {
     // Force colors to be enabled during compilation
     control::set_override(true);
-    let assets_path = input.to_string().trim_matches('"').to_string();
-
-    let rle_files = match find_rle_files(&assets_path) {
-        Ok(files) => files,
-        Err(e) => {
-            let error = format!(
-                "compile_error!(\"Failed to read assets directory: {}\");",
-                e
-            );
-            return error.parse().unwrap();
+    let assets_dir = parse_macro_input!(input as LitStr);
+
+    match expand_pattern_functions(&assets_dir) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => {
+            let error = error.to_compile_error();
+            let tracked = track_pattern_files(&assets_dir.value());
+            quote! { #error #tracked }.into()
         }
-    };
+    }
+}
+
+/// Includes every pattern file of the directory, so that fixing a broken
+/// asset reruns the macro instead of keeping its error
+fn track_pattern_files(dir: &str) -> TokenStream2 {
+    let mut files = Vec::new();
+    if collect_pattern_files(Path::new(dir), &mut files).is_err() {
+        return TokenStream2::new();
+    }
+    let paths = files.iter().filter_map(|file_path| include_path(file_path).ok());
+    // Associated items cannot be named `_`, hence the function
+    quote! {
+        #[allow(dead_code)]
+        fn track_pattern_files() {
+            #(const _: &str = include_str!(#paths);)*
+        }
+    }
+}
 
-    let mut functions = String::new();
+/// Absolute path of an asset, as `include_str!` resolves relative paths
+/// from the file invoking the macro
+fn include_path(file_path: &Path) -> Result<String, String> {
+    std::path::absolute(file_path)
+        .map(|path| path.to_string_lossy().into_owned())
+        .map_err(|e| format!("failed to resolve {}: {e}", file_path.display()))
+}
 
-    for (fn_name, file_path) in rle_files {
-        functions.push_str(&format!(
-            "pub fn {}() -> &'static [(i32, i32)] {{\n pattern!(file \"{}\")\n}}\n\n",
-            fn_name, file_path
-        ));
+/// Embedded pattern asset found in the assets directory
+struct PatternAsset {
+    fn_name: Ident,
+    path: String,
+    /// Cells converted at compile time, `None` for RLE files decoded at runtime
+    cells: Option<Vec<(i32, i32)>>,
+    /// Name declared by the file, or the file name
+    name: String,
+    /// Category from a `category: ...` comment line, or the subdirectory name
+    category: String,
+}
+
+fn expand_pattern_functions(assets_dir: &LitStr) -> syn::Result<TokenStream2> {
+    let assets = find_pattern_files(assets_dir)?;
+
+    let functions = assets.iter().map(|asset| {
+        let fn_name = &asset.fn_name;
+        let body = match &asset.cells {
+            // RLE files are embedded and decoded on first use
+            None => {
+                let path = &asset.path;
+                quote! { pattern!(file #path) }
+            }
+            // Other formats are converted to a cell table at compile time,
+            // the include making cargo rebuild when the file changes
+            Some(cells) => {
+                let path = &asset.path;
+                let (xs, ys): (Vec<i32>, Vec<i32>) = cells.iter().copied().unzip();
+                quote! {
+                    const _: &str = include_str!(#path);
+                    &[#((#xs, #ys)),*]
+                }
+            }
+        };
+        quote! {
+            pub fn #fn_name() -> &'static [(i32, i32)] {
+                #body
+            }
+        }
+    });
+
+    let names = assets.iter().map(|asset| {
+        let PatternAsset { fn_name, name, .. } = asset;
+        quote! { (#name, Self::#fn_name) }
+    });
+
+    // A later asset with the same name replaces the earlier one in the UI
+    let mut categories: Vec<(&str, &str)> = Vec::new();
+    for asset in &assets {
+        categories.retain(|(name, _)| *name != asset.name);
+        categories.push((&asset.name, &asset.category));
     }
-    functions.parse().unwrap()
+    let (category_names, categories): (Vec<_>, Vec<_>) = categories.into_iter().unzip();
+
+    Ok(quote! {
+        #(#functions)*
+
+        // Name table of every embedded pattern, so the UI needs no per-asset code
+        const ALL_PATTERNS: &'static [(&'static str, fn() -> &'static [(i32, i32)])] =
+            &[#(#names),*];
+
+        pub fn all_patterns() -> &'static [(&'static str, fn() -> &'static [(i32, i32)])] {
+            Self::ALL_PATTERNS
+        }
+
+        /// Category of an embedded pattern, from a `category: ...` comment
+        /// line or its subdirectory, empty when absent
+        pub fn pattern_category(name: &str) -> &'static str {
+            match name {
+                #(#category_names => #categories,)*
+                _ => "",
+            }
+        }
+    })
 }
 
-fn find_rle_files(dir: &str) -> Result<Vec<(String, String)>, std::io::Error> {
+fn find_pattern_files(assets_dir: &LitStr) -> syn::Result<Vec<PatternAsset>> {
+    let dir = assets_dir.value();
+    let path = Path::new(&dir);
+    let error = |message: String| syn::Error::new(assets_dir.span(), message);
+
+    if !path.is_dir() {
+        return Err(error(format!("directory {dir} not found")));
+    }
+
+    let mut files = Vec::new();
+    collect_pattern_files(path, &mut files)
+        .map_err(|e| error(format!("failed to read assets directory {dir}: {e}")))?;
+    // Directory iteration order is platform dependent
+    files.sort();
+
     let mut results = Vec::new();
-    let path = Path::new(dir);
+    let mut fn_names = HashSet::new();
+    let mut errors = Vec::new();
+    for file_path in files {
+        match load_pattern_file(path, &file_path, &mut fn_names) {
+            Ok(asset) => results.push(asset),
+            Err(message) => errors.push(error(message)),
+        }
+    }
 
-    if !path.exists() {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            format!("Directory {} not found", dir),
+    // Report every broken asset at once rather than one per build
+    let mut errors = errors.into_iter();
+    match errors.next() {
+        Some(mut first) => {
+            first.extend(errors);
+            Err(first)
+        }
+        None => Ok(results),
+    }
+}
+
+/// Reads and validates one pattern file
+fn load_pattern_file(
+    assets_dir: &Path,
+    file_path: &Path,
+    fn_names: &mut HashSet<String>,
+) -> Result<PatternAsset, String> {
+    let Some(file_name) = file_path.file_stem().and_then(|s| s.to_str()) else {
+        return Err(format!("{} has a non UTF-8 file name", file_path.display()));
+    };
+    let relative = file_path.strip_prefix(assets_dir).unwrap_or(file_path);
+    let directory = relative
+        .parent()
+        .map(|parent| parent.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    // Convert file name in valid function name, prefixing the subdirectory on collision
+    let mut fn_name = to_fn_name(file_name);
+    if fn_names.contains(&fn_name) && !directory.is_empty() {
+        fn_name = to_fn_name(&format!("{directory}_{file_name}"));
+    }
+    if !fn_names.insert(fn_name.clone()) {
+        return Err(format!(
+            "{} generates the duplicate function name `{fn_name}`",
+            file_path.display()
         ));
     }
 
-    for entry in fs::read_dir(path)? {
-        let entry = entry?;
-        let file_path = entry.path();
-
-        if file_path.extension().and_then(|s| s.to_str()) == Some("rle") {
-            let file_name = file_path
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("unknown");
-
-            // Convert file name in valid function name
-            let fn_name = file_name.replace(['-', ' '], "_").to_lowercase();
-            let relative_path = format!("../../../../{}", file_path.to_string_lossy());
-
-            // Get file metadata
-            let metadata = fs::metadata(&file_path).ok();
-            let size_bytes = metadata.map(|m| m.len()).unwrap_or(0);
-
-            // Display file info
-            let file_info = RleFileInfo {
-                name: file_name.to_string(),
-                path: file_path.display().to_string(),
-                size_bytes,
-            };
-            file_info.display();
-
-            results.push((fn_name, relative_path));
+    // Get file metadata
+    let metadata = fs::metadata(file_path).ok();
+    let size_bytes = metadata.map(|m| m.len()).unwrap_or(0);
+
+    // Display file info
+    let file_info = RleFileInfo {
+        name: file_name.to_string(),
+        path: file_path.display().to_string(),
+        size_bytes,
+    };
+    file_info.display();
+
+    // Validate the pattern now rather than panicking when it is first used
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| format!("failed to read {}: {e}", file_path.display()))?;
+    let parsed = parse_pattern(&content)
+        .map_err(|e| format!("invalid pattern {}:{}: {e}", file_path.display(), e.line()))?;
+    let cells = match PatternFormat::detect(content.trim_start()) {
+        PatternFormat::Rle => None,
+        _ => Some(parsed.cells),
+    };
+
+    // Read the optional category from the comments (`#C`, `#D` or `!` lines)
+    let mut category = directory
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    for line in content.lines().map(str::trim) {
+        let comment = line
+            .strip_prefix("#C")
+            .or_else(|| line.strip_prefix("#D"))
+            .or_else(|| line.strip_prefix('!'));
+        if let Some(value) = comment.and_then(|c| c.trim().strip_prefix("category:")) {
+            category = value.trim().to_string();
+        }
+    }
+
+    Ok(PatternAsset {
+        fn_name: format_ident!("{}", fn_name),
+        path: include_path(file_path)?,
+        cells,
+        name: parsed.name.unwrap_or_else(|| file_name.to_string()),
+        category,
+    })
+}
+
+/// Extensions of the pattern files embedded as assets
+const PATTERN_EXTENSIONS: &[&str] = &["rle", "cells", "lif", "life"];
+
+/// Recursively collects the pattern files below a directory
+fn collect_pattern_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let file_path = entry?.path();
+        if file_path.is_dir() {
+            collect_pattern_files(&file_path, files)?;
+        } else if file_path
+            .extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(|extension| PATTERN_EXTENSIONS.contains(&extension))
+        {
+            files.push(file_path);
         }
     }
+    Ok(())
+}
 
-    Ok(results)
+/// Functions of `Patterns` other than the pattern functions, generated by
+/// this macro or written next to it
+const RESERVED_NAMES: &[&str] = &[
+    "all_patterns",
+    "pattern_category",
+    "track_pattern_files",
+    "from_rle_string",
+    "from_string",
+];
+
+/// Turns a file name into a valid Rust function name
+fn to_fn_name(name: &str) -> String {
+    let mut fn_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if fn_name.is_empty() || fn_name.starts_with(|c: char| c.is_ascii_digit()) {
+        fn_name.insert(0, '_');
+    }
+    // Keywords and `_` do not parse as identifiers
+    if syn::parse_str::<Ident>(&fn_name).is_err() || RESERVED_NAMES.contains(&fn_name.as_str()) {
+        fn_name.push_str("_pattern");
+    }
+    fn_name
 }
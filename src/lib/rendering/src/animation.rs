@@ -0,0 +1,134 @@
+//! # Animation Module
+//!
+//! Short birth and death animations of the cell sprites: newborn cells grow
+//! from nothing and dying cells shrink and fade out, which makes slow
+//! simulations easier to follow. The animations never outlast a generation.
+//!
+//! Dying cells hand over to the death trails once their animation is over.
+
+use crate::chunks::render_mode_is;
+use crate::sprites::draw_new_cells_system;
+use crate::trails::update_trails_system;
+use bevy::color::Alpha;
+use bevy::prelude::{
+    Added, App, Commands, Component, Entity, IntoScheduleConfigs, Plugin, Query, RemovedComponents,
+    Res, Sprite, Time, Transform, Update, Vec3, Visibility, With, Without,
+};
+use gol_config::{CellRenderMode, DisplayConfig, SimulationConfig};
+use gol_simulation::{Alive, CellPosition};
+
+/// Longest a cell animation lasts, in seconds
+const CELL_ANIMATION_TIME: f32 = 0.15;
+
+/// Which way a cell is animated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellAnimation {
+    /// Growing from nothing
+    Birth,
+    /// Shrinking and fading out
+    Death,
+}
+
+/// Cell sprite being animated
+#[derive(Component, Debug)]
+pub struct Animating {
+    pub animation: CellAnimation,
+    /// Time the animation started, in seconds since startup
+    pub started: f32,
+}
+
+/// Plugin for the birth and death animations
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            animate_cells_system
+                .after(draw_new_cells_system)
+                .before(update_trails_system)
+                .run_if(render_mode_is(CellRenderMode::Sprites)),
+        );
+    }
+}
+
+/// Filter of the cells that are not animated
+type StillCell = (With<CellPosition>, Without<Animating>);
+
+/// Starts the animation of the cells born or killed this frame and plays
+/// the running ones, restoring the sprites once they are over.
+///
+/// Animations last [`CELL_ANIMATION_TIME`], or a generation when it is
+/// shorter, and are turned off with the reduced motion setting.
+#[allow(clippy::too_many_arguments)]
+pub fn animate_cells_system(
+    mut commands: Commands,
+    display_config: Res<DisplayConfig>,
+    simulation_config: Res<SimulationConfig>,
+    time: Res<Time>,
+    born: Query<Entity, (Added<Alive>, With<Sprite>)>,
+    mut died: RemovedComponents<Alive>,
+    mut q_animating: Query<(
+        Entity,
+        &Animating,
+        &mut Transform,
+        &mut Sprite,
+        &mut Visibility,
+    )>,
+    mut q_still: Query<(&mut Transform, &mut Visibility), StillCell>,
+    q_sprites: Query<(), (With<CellPosition>, With<Sprite>)>,
+) {
+    let now = time.elapsed_secs();
+    let enabled = display_config.animate_cells && !display_config.reduce_motion;
+    if enabled {
+        let start = |animation| Animating {
+            animation,
+            started: now,
+        };
+        for entity in &born {
+            commands.entity(entity).insert(start(CellAnimation::Birth));
+            // Hidden right away rather than shown whole for a frame
+            if let Ok((mut transform, _)) = q_still.get_mut(entity) {
+                transform.scale = Vec3::ZERO;
+            }
+        }
+        // Despawned entities and those without a sprite yet have nothing to show
+        for entity in died.read().filter(|entity| q_sprites.contains(*entity)) {
+            commands.entity(entity).insert(start(CellAnimation::Death));
+            // Shown until the animation is over rather than hidden for a frame
+            if let Ok((_, mut visibility)) = q_still.get_mut(entity) {
+                *visibility = Visibility::Visible;
+            }
+        }
+    } else {
+        died.clear();
+    }
+
+    let duration = CELL_ANIMATION_TIME.min(simulation_config.period.as_secs_f32());
+    for (entity, animating, mut transform, mut sprite, mut visibility) in &mut q_animating {
+        let progress = if enabled && duration > 0.0 {
+            ((now - animating.started) / duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        // Ease out, fast at first and settling smoothly
+        let eased = 1.0 - (1.0 - progress).powi(2);
+        match animating.animation {
+            CellAnimation::Birth => transform.scale = Vec3::splat(eased),
+            CellAnimation::Death => {
+                transform.scale = Vec3::splat(1.0 - eased);
+                sprite.color.set_alpha(1.0 - eased);
+                if *visibility != Visibility::Visible && progress < 1.0 {
+                    *visibility = Visibility::Visible;
+                }
+            }
+        }
+        if progress >= 1.0 {
+            transform.scale = Vec3::ONE;
+            if animating.animation == CellAnimation::Death {
+                *visibility = Visibility::Hidden;
+            }
+            commands.entity(entity).remove::<Animating>();
+        }
+    }
+}
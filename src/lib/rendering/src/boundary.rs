@@ -0,0 +1,71 @@
+//! # Boundary Module
+//!
+//! Outlines the edge of finite grid topologies.
+
+use bevy::prelude::{App, Camera, GlobalTransform, Plugin, Query, Res, Vec3};
+use bevy_egui::{
+    EguiContexts,
+    egui::{self, Color32},
+};
+use gol_config::{GridTopology, SimulationBackend, SimulationConfig};
+
+/// Plugin for grid boundary rendering
+pub struct BoundaryPlugin;
+
+impl Plugin for BoundaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(bevy_egui::EguiPrimaryContextPass, draw_boundary_system);
+    }
+}
+
+/// System that draws the edge of a bounded or toroidal grid
+pub fn draw_boundary_system(
+    mut contexts: EguiContexts,
+    simulation_config: Res<SimulationConfig>,
+    q_camera: Query<(&Camera, &GlobalTransform)>,
+) {
+    // The other backends run on the infinite grid whatever the topology
+    if simulation_config.backend != SimulationBackend::Sparse {
+        return;
+    }
+    let Some((min, max)) = simulation_config.topology.bounds() else {
+        return;
+    };
+    // Dead edges and wrapping edges are told apart by color
+    let color = match simulation_config.topology {
+        GridTopology::Torus { .. } => Color32::from_rgb(40, 110, 220),
+        _ => Color32::from_rgb(200, 40, 40),
+    };
+    let Ok((camera, camera_transform)) = q_camera.single() else {
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    // Cells are centered on integer coordinates
+    let corner = |x: f32, y: f32| {
+        camera
+            .world_to_viewport(camera_transform, Vec3::new(x, y, 0.0))
+            .ok()
+            .map(|pos| egui::pos2(pos.x, pos.y))
+    };
+    let (Some(top_left), Some(bottom_right)) = (
+        corner(min.x as f32 - 0.5, max.y as f32 + 0.5),
+        corner(max.x as f32 + 0.5, min.y as f32 - 0.5),
+    ) else {
+        return;
+    };
+
+    // The egui background lies above the cells and the grid lines, but below the windows
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("grid_boundary"),
+    ));
+    painter.rect_stroke(
+        egui::Rect::from_two_pos(top_left, bottom_right),
+        0.0,
+        egui::Stroke::new(2.0_f32, color),
+        egui::StrokeKind::Outside,
+    );
+}
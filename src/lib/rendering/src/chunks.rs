@@ -0,0 +1,281 @@
+//! # Chunks Module
+//!
+//! Texture-based cell rendering for large populations. Alive cells are written
+//! into one image per square chunk of the grid, one pixel per cell, and each
+//! chunk is drawn as a single sprite. Cell entities keep existing for
+//! interaction but carry no sprite of their own in this mode.
+//!
+//! Only the cells that changed are drawn each frame, so only the textures of
+//! their chunks are uploaded again.
+//!
+//! Cells with a configured shape or gap take [`SHAPED_CELL_PIXELS`] pixels
+//! per side instead, at the cost of larger textures.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::color::ColorToPacked;
+use bevy::image::ImageSampler;
+use bevy::prelude::{
+    Added, App, Assets, Changed, Commands, Component, DetectChanges, Entity, Handle, Image,
+    IntoScheduleConfigs, Or, Plugin, Query, RemovedComponents, Res, ResMut, Resource, Sprite,
+    Transform, Update, Vec2, With,
+};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use gol_config::{CellRenderMode, ColorConfig, DisplayConfig};
+use gol_simulation::{Alive, CellPosition, CellState, CellSyncSet};
+use std::collections::HashMap;
+
+/// Side length of a chunk, in cells
+pub const CHUNK_SIZE: isize = 256;
+
+/// Side length of a cell in the chunk textures, in pixels, when cells are
+/// not plain squares
+pub const SHAPED_CELL_PIXELS: isize = 4;
+
+/// Samples taken along each side of a pixel to find how much of it a shaped
+/// cell covers
+const COVERAGE_SAMPLES: isize = 4;
+
+/// Sprite drawing the cells of one chunk
+#[derive(Component)]
+pub struct CellChunk;
+
+/// Spawned chunk sprite with its texture
+struct Chunk {
+    entity: Entity,
+    image: Handle<Image>,
+    /// Number of cells drawn in the texture
+    cells: usize,
+}
+
+/// Chunk sprites currently spawned, by chunk coordinates, and the cell drawn
+/// by each living entity
+#[derive(Resource, Default)]
+pub struct ChunkMap {
+    chunks: HashMap<(isize, isize), Chunk>,
+    /// Position drawn for each living cell entity, erased once it dies or moves
+    drawn: HashMap<Entity, CellPosition>,
+    /// Side length of a cell in the textures, in pixels
+    cell_pixels: isize,
+    /// Coverage of the pixels of a cell, from [`cell_footprint`]
+    footprint: Vec<u8>,
+}
+
+impl ChunkMap {
+    /// Despawns every chunk sprite and forgets the drawn cells
+    fn clear(&mut self, commands: &mut Commands) {
+        for (_, chunk) in self.chunks.drain() {
+            commands.entity(chunk.entity).despawn();
+        }
+        self.drawn.clear();
+    }
+
+    /// Writes the pixels of the cell at `pos` into its chunk texture, with
+    /// the given color, or transparent ones when erasing it
+    fn draw(
+        &mut self,
+        commands: &mut Commands,
+        images: &mut Assets<Image>,
+        pos: CellPosition,
+        color: Option<[u8; 4]>,
+    ) {
+        let key = (pos.x.div_euclid(CHUNK_SIZE), pos.y.div_euclid(CHUNK_SIZE));
+        let side = CHUNK_SIZE * self.cell_pixels;
+        let chunk = match self.chunks.get_mut(&key) {
+            Some(chunk) => chunk,
+            // Nothing to erase in a chunk that was never drawn
+            None if color.is_none() => return,
+            None => {
+                let image = images.add(chunk_image(side as u32));
+                // Cells are centered on integer coordinates
+                let origin = (key.0 * CHUNK_SIZE, key.1 * CHUNK_SIZE);
+                let half = CHUNK_SIZE as f32 / 2.0 - 0.5;
+                let entity = commands
+                    .spawn((
+                        CellChunk,
+                        Sprite {
+                            image: image.clone(),
+                            custom_size: Some(Vec2::splat(CHUNK_SIZE as f32)),
+                            ..Default::default()
+                        },
+                        Transform::from_xyz(origin.0 as f32 + half, origin.1 as f32 + half, 0.0),
+                    ))
+                    .id();
+                self.chunks.entry(key).or_insert(Chunk {
+                    entity,
+                    image,
+                    cells: 0,
+                })
+            }
+        };
+        match color {
+            Some(_) => chunk.cells += 1,
+            None => chunk.cells = chunk.cells.saturating_sub(1),
+        }
+        // Only the changed texture is uploaded again
+        let Some(data) = images
+            .get_mut(&chunk.image)
+            .and_then(|image| image.data.as_mut())
+        else {
+            return;
+        };
+        // Image rows go downward while world y goes upward
+        let col = pos.x.rem_euclid(CHUNK_SIZE) * self.cell_pixels;
+        let row = (CHUNK_SIZE - 1 - pos.y.rem_euclid(CHUNK_SIZE)) * self.cell_pixels;
+        for (index, &coverage) in self.footprint.iter().enumerate() {
+            let index = index as isize;
+            let (dy, dx) = (index / self.cell_pixels, index % self.cell_pixels);
+            let offset = (((row + dy) * side + col + dx) * 4) as usize;
+            let pixel = color.map_or([0; 4], |[r, g, b, a]| {
+                [r, g, b, (u16::from(a) * u16::from(coverage) / 255) as u8]
+            });
+            data[offset..offset + 4].copy_from_slice(&pixel);
+        }
+    }
+}
+
+/// Plugin for chunked texture rendering
+pub struct ChunkPlugin;
+
+impl Plugin for ChunkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChunkMap>()
+            .add_systems(
+                Update,
+                (strip_cell_sprites_system, update_chunks_system)
+                    .after(CellSyncSet)
+                    .run_if(render_mode_is(CellRenderMode::Chunks)),
+            )
+            .add_systems(
+                Update,
+                clear_chunks_system.run_if(render_mode_is(CellRenderMode::Sprites)),
+            );
+    }
+}
+
+/// Run condition that is true while cells are drawn with the given mode
+pub fn render_mode_is(mode: CellRenderMode) -> impl Fn(Res<DisplayConfig>) -> bool {
+    move |config: Res<DisplayConfig>| config.render_mode == mode
+}
+
+/// Removes the per-cell sprites, which the chunk textures replace
+pub fn strip_cell_sprites_system(
+    mut commands: Commands,
+    query: Query<Entity, (With<CellPosition>, With<Sprite>)>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).remove::<Sprite>();
+    }
+}
+
+/// Filter of the cells that were born, moved or recolored
+type RedrawnCell = Or<(Added<Alive>, Changed<CellPosition>, Changed<CellState>)>;
+
+/// Draws the cells born, killed, moved or recolored since the last frame
+/// into the textures of their chunks.
+///
+/// Every texture is drawn again when the display or the colors change.
+#[allow(clippy::too_many_arguments)]
+pub fn update_chunks_system(
+    mut commands: Commands,
+    display_config: Res<DisplayConfig>,
+    color_config: Res<ColorConfig>,
+    changed: Query<(Entity, &CellPosition, &CellState), (RedrawnCell, With<Alive>)>,
+    mut died: RemovedComponents<Alive>,
+    alive: Query<(Entity, &CellPosition, &CellState), With<Alive>>,
+    mut images: ResMut<Assets<Image>>,
+    mut chunk_map: ResMut<ChunkMap>,
+) {
+    let rebuild = display_config.is_changed() || color_config.is_changed();
+    if rebuild {
+        chunk_map.clear(&mut commands);
+        chunk_map.cell_pixels = if display_config.plain_cells() {
+            1
+        } else {
+            SHAPED_CELL_PIXELS
+        };
+        chunk_map.footprint = cell_footprint(&display_config, chunk_map.cell_pixels);
+    }
+
+    // Erasing first, so a cell born where another died stays drawn
+    for entity in died.read() {
+        if let Some(pos) = chunk_map.drawn.remove(&entity) {
+            chunk_map.draw(&mut commands, &mut images, pos, None);
+        }
+    }
+    let cells = if rebuild {
+        alive.iter().collect::<Vec<_>>()
+    } else {
+        changed.iter().collect()
+    };
+    for (entity, &pos, state) in cells {
+        if let Some(previous) = chunk_map.drawn.insert(entity, pos) {
+            chunk_map.draw(&mut commands, &mut images, previous, None);
+        }
+        let color = color_config.state_color(state.0).to_srgba().to_u8_array();
+        chunk_map.draw(&mut commands, &mut images, pos, Some(color));
+    }
+
+    // Chunks left without any alive cell disappear
+    chunk_map.chunks.retain(|_, chunk| {
+        let keep = chunk.cells > 0;
+        if !keep {
+            commands.entity(chunk.entity).despawn();
+        }
+        keep
+    });
+}
+
+/// Despawns the chunk sprites once cells are drawn as sprites again
+pub fn clear_chunks_system(mut commands: Commands, mut chunk_map: ResMut<ChunkMap>) {
+    if chunk_map.chunks.is_empty() && chunk_map.drawn.is_empty() {
+        return;
+    }
+    chunk_map.clear(&mut commands);
+}
+
+/// How much of each pixel of a cell `cell_pixels` pixels wide the cell
+/// covers, after its configured shape and gap, row by row from the top
+fn cell_footprint(display_config: &DisplayConfig, cell_pixels: isize) -> Vec<u8> {
+    if cell_pixels == 1 {
+        return vec![255];
+    }
+    let samples = cell_pixels * COVERAGE_SAMPLES;
+    // Sample positions along a side, the cell spanning -1 to 1
+    let position = |pixel: isize, sample: isize| {
+        ((pixel * COVERAGE_SAMPLES + sample) as f32 + 0.5) / samples as f32 * 2.0 - 1.0
+    };
+    let mut footprint = Vec::with_capacity((cell_pixels * cell_pixels) as usize);
+    for row in 0..cell_pixels {
+        for column in 0..cell_pixels {
+            let mut covered = 0;
+            for sy in 0..COVERAGE_SAMPLES {
+                for sx in 0..COVERAGE_SAMPLES {
+                    if display_config.cell_covers(position(column, sx), position(row, sy)) {
+                        covered += 1;
+                    }
+                }
+            }
+            footprint.push((covered * 255 / (COVERAGE_SAMPLES * COVERAGE_SAMPLES)) as u8);
+        }
+    }
+    footprint
+}
+
+/// Builds the transparent texture of one chunk, `side` pixels wide, kept
+/// in the main world so that single cells can be drawn into it
+fn chunk_image(side: u32) -> Image {
+    let mut image = Image::new(
+        Extent3d {
+            width: side,
+            height: side,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        vec![0; (side * side * 4) as usize],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    // Keep cells crisp instead of blurring them together
+    image.sampler = ImageSampler::nearest();
+    image
+}
@@ -0,0 +1,179 @@
+//! # Density Module
+//!
+//! Aggregated view for extreme zoom-out. Once a cell is smaller than a
+//! pixel, single cells alias into noise, so the view is covered with one
+//! texture instead, each pixel blending from the background color to the
+//! cell color with the share of living cells it covers.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::color::{ColorToPacked, Mix};
+use bevy::image::ImageSampler;
+use bevy::prelude::{
+    App, Assets, Camera, Commands, Component, DetectChanges, GlobalTransform, Image,
+    IntoScheduleConfigs, Local, Plugin, Projection, Query, Res, ResMut, Sprite, Transform, Update,
+    Vec2, Visibility, With,
+};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use gol_config::{ColorConfig, DisplayConfig};
+use gol_simulation::{BoardState, CellSyncSet};
+
+/// Depth of the density view, above the cells and their chunk textures
+const DENSITY_DEPTH: f32 = 0.9;
+
+/// Sprite covering the view with the density of the living cells
+#[derive(Component)]
+pub struct DensityView;
+
+/// Plugin for the aggregated density view
+pub struct DensityPlugin;
+
+impl Plugin for DensityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_density_view_system.after(CellSyncSet));
+    }
+}
+
+/// Camera scale and the area it sees, as its size in pixels and the world
+/// position of its top-left corner
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ViewArea {
+    scale: f32,
+    width: u32,
+    height: u32,
+    top_left: Vec2,
+}
+
+/// Shows the density view while cells are smaller than a pixel, redrawing
+/// it when the board, the colors or the view change, and hides it otherwise
+#[allow(clippy::too_many_arguments)]
+fn update_density_view_system(
+    mut commands: Commands,
+    board: Res<BoardState>,
+    display_config: Res<DisplayConfig>,
+    color_config: Res<ColorConfig>,
+    mut images: ResMut<Assets<Image>>,
+    q_camera: Query<(&Camera, &Projection, &GlobalTransform)>,
+    mut q_view: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<DensityView>>,
+    mut last_area: Local<Option<ViewArea>>,
+) {
+    let area = q_camera
+        .single()
+        .ok()
+        .and_then(|(camera, projection, transform)| view_area(camera, projection, transform))
+        .filter(|area| display_config.density_view && area.scale > 1.0);
+    let Some(area) = area else {
+        for (_, _, mut visibility) in &mut q_view {
+            if *visibility != Visibility::Hidden {
+                *visibility = Visibility::Hidden;
+            }
+        }
+        *last_area = None;
+        return;
+    };
+    let unchanged = *last_area == Some(area)
+        && !board.is_changed()
+        && !color_config.is_changed()
+        && !q_view.is_empty();
+    if unchanged {
+        return;
+    }
+    *last_area = Some(area);
+
+    let image = images.add(density_image(&board, area, &color_config));
+    // The texture spans the view, centered on it
+    let size = Vec2::new(area.width as f32, area.height as f32) * area.scale;
+    let center = area.top_left + Vec2::new(size.x, -size.y) / 2.0;
+    let transform = Transform::from_xyz(center.x, center.y, DENSITY_DEPTH);
+    match q_view.single_mut() {
+        Ok((mut sprite, mut view_transform, mut visibility)) => {
+            sprite.image = image;
+            sprite.custom_size = Some(size);
+            *view_transform = transform;
+            *visibility = Visibility::Visible;
+        }
+        Err(_) => {
+            commands.spawn((
+                DensityView,
+                Sprite {
+                    image,
+                    custom_size: Some(size),
+                    ..Default::default()
+                },
+                transform,
+                Visibility::Visible,
+            ));
+        }
+    }
+}
+
+/// Area seen by an orthographic camera, its corner snapped to whole pixels
+/// of the world so that panning does not make the pixels shimmer
+fn view_area(
+    camera: &Camera,
+    projection: &Projection,
+    camera_transform: &GlobalTransform,
+) -> Option<ViewArea> {
+    let Projection::Orthographic(orthographic) = projection else {
+        return None;
+    };
+    let scale = orthographic.scale;
+    let size = camera.logical_viewport_size()?;
+    let corner = camera
+        .viewport_to_world_2d(camera_transform, Vec2::ZERO)
+        .ok()?;
+    // One extra pixel on each side covers the snapping
+    Some(ViewArea {
+        scale,
+        width: size.x.ceil() as u32 + 2,
+        height: size.y.ceil() as u32 + 2,
+        top_left: Vec2::new(
+            ((corner.x / scale).floor() - 1.0) * scale,
+            ((corner.y / scale).ceil() + 1.0) * scale,
+        ),
+    })
+}
+
+/// Draws the share of living cells covered by each pixel of the area.
+///
+/// The share goes through a square root, so that sparse regions stay
+/// visible next to dense ones.
+fn density_image(board: &BoardState, area: ViewArea, colors: &ColorConfig) -> Image {
+    let (width, height) = (area.width as usize, area.height as usize);
+    let mut counts = vec![0u32; width * height];
+    for cell in board.0.iter() {
+        // Cells are centered on integer coordinates
+        let column = ((cell.x as f32 + 0.5 - area.top_left.x) / area.scale).floor();
+        let row = ((area.top_left.y - cell.y as f32 - 0.5) / area.scale).floor();
+        if column < 0.0 || row < 0.0 || column >= width as f32 || row >= height as f32 {
+            continue;
+        }
+        counts[row as usize * width + column as usize] += 1;
+    }
+
+    let cells_per_pixel = area.scale * area.scale;
+    let (background, cell) = (
+        colors.background_color.to_srgba(),
+        colors.cell_color.to_srgba(),
+    );
+    let data = counts
+        .into_iter()
+        .flat_map(|count| {
+            let share = (count as f32 / cells_per_pixel).min(1.0).sqrt();
+            background.mix(&cell, share).to_u8_array()
+        })
+        .collect();
+    let mut image = Image::new(
+        Extent3d {
+            width: area.width,
+            height: area.height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    // One texel per pixel, kept sharp
+    image.sampler = ImageSampler::nearest();
+    image
+}
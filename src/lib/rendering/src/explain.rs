@@ -0,0 +1,70 @@
+//! # Explain Module
+//!
+//! Highlights of the explained stepping: cells about to die are tinted red
+//! and cells about to be born green, until the next generation is applied.
+
+use bevy::prelude::{
+    App, Color, Commands, Component, DetectChanges, Entity, Plugin, Query, Res, Sprite, Transform,
+    Update, Vec2, With,
+};
+use gol_simulation::ExplainPhase;
+
+/// Depth of the highlights, above the cells and below the selection
+const HIGHLIGHT_DEPTH: f32 = 0.5;
+
+/// Color of the cells about to die
+const DYING_COLOR: Color = Color::srgba(0.9, 0.1, 0.1, 0.6);
+/// Color of the cells about to be born
+const NEWBORN_COLOR: Color = Color::srgba(0.1, 0.8, 0.2, 0.6);
+
+/// Square highlighting a cell about to change
+#[derive(Component)]
+pub struct ExplainHighlight;
+
+/// Plugin for the highlights of the explained stepping
+pub struct ExplainHighlightPlugin;
+
+impl Plugin for ExplainHighlightPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_explain_highlights);
+    }
+}
+
+/// Replaces the highlights whenever the explained step changes
+pub fn update_explain_highlights(
+    mut commands: Commands,
+    phase: Res<ExplainPhase>,
+    q_highlights: Query<Entity, With<ExplainHighlight>>,
+) {
+    if !phase.is_changed() {
+        return;
+    }
+    for entity in &q_highlights {
+        commands.entity(entity).despawn();
+    }
+    let Some(step) = phase.preview() else {
+        return;
+    };
+    let highlight = |x: isize, y: isize, color: Color| {
+        (
+            ExplainHighlight,
+            Sprite {
+                color,
+                custom_size: Some(Vec2::ONE),
+                ..Default::default()
+            },
+            Transform::from_xyz(x as f32, y as f32, HIGHLIGHT_DEPTH),
+        )
+    };
+    commands.spawn_batch(
+        step.died
+            .iter()
+            .map(|cell| highlight(cell.x, cell.y, DYING_COLOR))
+            .chain(
+                step.born
+                    .iter()
+                    .map(|cell| highlight(cell.x, cell.y, NEWBORN_COLOR)),
+            )
+            .collect::<Vec<_>>(),
+    );
+}
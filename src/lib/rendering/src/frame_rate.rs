@@ -0,0 +1,38 @@
+//! # Frame Rate Module
+//!
+//! Caps the number of frames drawn per second, to spare the battery and the
+//! GPU of laptops. The cap is the longest time the event loop waits between
+//! two frames: input and repaint requests from the interface still wake it
+//! earlier. The simulation, on its fixed timestep, keeps its own pace.
+
+use bevy::prelude::{App, DetectChanges, Plugin, Res, ResMut, Update};
+use bevy::winit::{UpdateMode, WinitSettings};
+use gol_config::DisplayConfig;
+use std::time::Duration;
+
+/// Plugin applying the frame rate cap of the settings
+pub struct FrameRatePlugin;
+
+impl Plugin for FrameRatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WinitSettings>()
+            .add_systems(Update, apply_fps_cap_system);
+    }
+}
+
+/// Switches the event loop between running continuously and waiting for the
+/// next frame when the cap changes
+pub fn apply_fps_cap_system(
+    display_config: Res<DisplayConfig>,
+    mut winit_settings: ResMut<WinitSettings>,
+) {
+    if !display_config.is_changed() {
+        return;
+    }
+    let mode = match display_config.fps_cap {
+        0 => UpdateMode::Continuous,
+        fps => UpdateMode::reactive_low_power(Duration::from_secs_f64(1.0 / f64::from(fps))),
+    };
+    winit_settings.focused_mode = mode;
+    winit_settings.unfocused_mode = mode;
+}
@@ -1,153 +1,228 @@
 //! # Grid Module
 //!
 //! Handles the visual rendering of the grid overlay.
+//!
+//! The lines are drawn as Bevy gizmos, batched on the GPU, leaving the egui
+//! layer to the user interface and the coordinate labels. Every
+//! [`DisplayConfig::grid_major_spacing`] cells a thicker major line helps
+//! counting, and the axes through the origin can be highlighted. Both fade
+//! out as they get closer on screen, following [`DisplayConfig::grid_fade`].
 
-use bevy::prelude::{App, Camera, GlobalTransform, Plugin, Projection, Query, Res, Vec2, Vec3};
+use bevy::color::{Alpha, ColorToPacked};
+use bevy::prelude::{
+    App, AppGizmoBuilder, Camera, Color, GizmoConfig, GizmoConfigGroup, GizmoLineConfig, Gizmos,
+    GlobalTransform, Plugin, Projection, Query, Reflect, Res, UVec2, Update, Vec2, Vec3,
+};
 use bevy_egui::{
     EguiContexts,
     egui::{self, Color32},
 };
-use gol_config::{DEFAULT_SCALE, DisplayConfig, MAX_SCALE};
+use gol_config::{ColorConfig, DisplayConfig};
+
+/// Faintest grid worth drawing, as an opacity
+const MIN_LINE_OPACITY: f32 = 0.05;
+
+/// Closest grid lines may be drawn, in pixels, past which they would blur
+/// into a solid fill and cost a line per pixel
+const MIN_LINE_SPACING: f32 = 2.0;
+
+/// Color of the axes through the origin
+const AXIS_COLOR: Color = Color::srgb(0.9, 0.55, 0.1);
+
+/// Closest two coordinate labels may be, in points
+const MIN_LABEL_SPACING: f32 = 48.0;
+
+/// Gizmo group of the minor grid lines, one pixel wide
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct GridGizmos;
+
+/// Gizmo group of the major grid lines and the axes, two pixels wide
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct MajorGridGizmos;
 
 /// Plugin for grid rendering systems
 pub struct GridPlugin;
 
 impl Plugin for GridPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(bevy_egui::EguiPrimaryContextPass, draw_grid_system);
+        let config = |width: f32| GizmoConfig {
+            line: GizmoLineConfig {
+                width,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        app.insert_gizmo_config(GridGizmos, config(1.0))
+            .insert_gizmo_config(MajorGridGizmos, config(2.0))
+            .add_systems(Update, draw_grid_system)
+            .add_systems(bevy_egui::EguiPrimaryContextPass, draw_grid_labels_system);
     }
 }
 
-/// System that renders the grid overlay using egui
+/// Color of grid lines `spacing` cells apart, faded out as the camera zooms
+/// out, or `None` when they are too faint or too close to be drawn
+fn line_color(
+    display_config: &DisplayConfig,
+    color_config: &ColorConfig,
+    scale: f32,
+    spacing: f32,
+) -> Option<Color> {
+    let pixels = spacing / scale;
+    let color = color_config.grid_color;
+    let opacity = color.alpha() * display_config.grid_fade(pixels);
+    (pixels >= MIN_LINE_SPACING && opacity >= MIN_LINE_OPACITY).then(|| color.with_alpha(opacity))
+}
+
+/// Camera scale and visible area, as the (min, max) cells at its corners
+fn visible_cells(
+    camera: &Camera,
+    projection: &Projection,
+    camera_transform: &GlobalTransform,
+) -> Option<(f32, Vec2, Vec2)> {
+    let Projection::Orthographic(orthographic) = projection else {
+        return None;
+    };
+    let size = camera.logical_viewport_size()?;
+    let top_left = camera
+        .viewport_to_world_2d(camera_transform, Vec2::ZERO)
+        .ok()?;
+    let bottom_right = camera.viewport_to_world_2d(camera_transform, size).ok()?;
+    let min = Vec2::new(top_left.x, bottom_right.y).round();
+    let max = Vec2::new(bottom_right.x, top_left.y).round();
+    Some((orthographic.scale, min, max))
+}
+
+/// Indices of the first and last lines `spacing` cells apart around the
+/// cells from `min` to `max`, line `k` running along the left or bottom
+/// edge of cell `k * spacing`
+fn line_range(min: f32, max: f32, spacing: f32) -> (f32, f32) {
+    (
+        ((min + 0.5) / spacing).floor(),
+        ((max + 0.5) / spacing).floor() + 1.0,
+    )
+}
+
+/// System that renders the grid overlay: minor lines between every cell,
+/// thicker major lines and the axes
 pub fn draw_grid_system(
-    mut contexts: EguiContexts,
+    mut gizmos: Gizmos<GridGizmos>,
+    mut major_gizmos: Gizmos<MajorGridGizmos>,
     display_config: Res<DisplayConfig>,
+    color_config: Res<ColorConfig>,
     q_camera: Query<(&Camera, &Projection, &GlobalTransform)>,
 ) {
     if !display_config.grid_visible {
         return;
     }
-
-    // Use semi-transparent color for rows in the grid
-    const LINE_COLOR: Color32 = Color32::from_gray(128);
-    let (camera, camera_projection, camera_transform) = match q_camera.single() {
-        Ok(data) => data,
-        Err(_) => return,
+    let Ok((camera, projection, camera_transform)) = q_camera.single() else {
+        return;
     };
-
-    let camera_scale = match camera_projection {
-        Projection::Orthographic(orthographic) => orthographic.scale,
-        _ => return,
+    let Some((scale, min, max)) = visible_cells(camera, projection, camera_transform) else {
+        return;
     };
 
+    // Cells are centered on integer coordinates, lines run along their edges
+    if let Some(color) = line_color(&display_config, &color_config, scale, 1.0) {
+        let cells = UVec2::new((max.x - min.x) as u32 + 1, (max.y - min.y) as u32 + 1);
+        gizmos
+            .grid_2d((min + max) / 2.0, cells, Vec2::ONE, color)
+            .outer_edges();
+    }
+
+    let spacing = display_config.grid_major_spacing as f32;
+    let major_color = (spacing > 0.0)
+        .then(|| line_color(&display_config, &color_config, scale, spacing))
+        .flatten();
+    if let Some(color) = major_color {
+        let (x_first, x_last) = line_range(min.x, max.x, spacing);
+        let (y_first, y_last) = line_range(min.y, max.y, spacing);
+        let cells = UVec2::new((x_last - x_first) as u32, (y_last - y_first) as u32);
+        let center = Vec2::new(x_first + x_last, y_first + y_last) * spacing / 2.0 - 0.5;
+        major_gizmos
+            .grid_2d(center, cells, Vec2::splat(spacing), color)
+            .outer_edges();
+    }
+
+    // The axes run along the edges of the origin cell, and show at any zoom
+    if display_config.grid_axes {
+        let (low, high) = (min - 0.5, max + 0.5);
+        if (low.x..=high.x).contains(&-0.5) {
+            major_gizmos.line_2d(Vec2::new(-0.5, low.y), Vec2::new(-0.5, high.y), AXIS_COLOR);
+        }
+        if (low.y..=high.y).contains(&-0.5) {
+            major_gizmos.line_2d(Vec2::new(low.x, -0.5), Vec2::new(high.x, -0.5), AXIS_COLOR);
+        }
+    }
+}
+
+/// System that writes the coordinates of the major grid lines along the top
+/// and left edges of the area left free by the panels, when they are far
+/// enough apart to be read
+pub fn draw_grid_labels_system(
+    mut contexts: EguiContexts,
+    display_config: Res<DisplayConfig>,
+    color_config: Res<ColorConfig>,
+    q_camera: Query<(&Camera, &Projection, &GlobalTransform)>,
+) {
+    let spacing = display_config.grid_major_spacing as f32;
+    if !display_config.grid_visible || !display_config.grid_labels || spacing <= 0.0 {
+        return;
+    }
+    let Ok((camera, projection, camera_transform)) = q_camera.single() else {
+        return;
+    };
+    let Some((scale, min, max)) = visible_cells(camera, projection, camera_transform) else {
+        return;
+    };
+    if spacing / scale < MIN_LABEL_SPACING {
+        return;
+    }
     let Ok(ctx) = contexts.ctx_mut() else {
         return;
     };
 
-    let transparent_frame = egui::containers::Frame {
-        fill: Color32::TRANSPARENT,
-        ..Default::default()
+    let to_screen = |x: f32, y: f32| {
+        camera
+            .world_to_viewport(camera_transform, Vec3::new(x, y, 0.0))
+            .ok()
+            .map(|pos| egui::pos2(pos.x, pos.y))
     };
-    let line_width = (1.0 - (camera_scale - DEFAULT_SCALE) / (MAX_SCALE - DEFAULT_SCALE)).powi(10);
-
-    egui::CentralPanel::default()
-        .frame(transparent_frame)
-        .show(ctx, |ui| {
-            let (response, painter) = ui.allocate_painter(
-                bevy_egui::egui::Vec2::new(ui.available_width(), ui.available_height()),
-                egui::Sense::hover(),
-            );
-            let Ok(ray_top_left) =
-                camera.viewport_to_world(camera_transform, Vec2 { x: 0.0, y: 0.0 })
-            else {
-                return;
-            };
-            let visible_top_left = ray_top_left.origin.truncate();
-            let (x_min, y_max) = (
-                visible_top_left.x.round() as isize,
-                visible_top_left.y.round() as isize,
+    // Below the windows, like the cells
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("grid_labels"),
+    ));
+    let font = egui::FontId::monospace(11.0);
+    // Labels stay opaque to remain readable over the cells
+    let [r, g, b, _] = color_config.grid_color.to_srgba().to_u8_array();
+    let color = Color32::from_rgb(r, g, b);
+    let margin = egui::vec2(3.0, 2.0);
+    let free = ctx.available_rect();
+
+    let (x_first, x_last) = line_range(min.x, max.x, spacing);
+    for k in x_first as i64..=x_last as i64 {
+        let x = k as f32 * spacing;
+        if let Some(pos) = to_screen(x - 0.5, max.y + 0.5) {
+            painter.text(
+                egui::pos2(pos.x, free.top()) + margin,
+                egui::Align2::LEFT_TOP,
+                (k * i64::from(display_config.grid_major_spacing)).to_string(),
+                font.clone(),
+                color,
             );
-            let Ok(ray_bottom_right) = camera.viewport_to_world(
-                camera_transform,
-                Vec2 {
-                    x: response.rect.right(),
-                    y: response.rect.bottom(),
-                },
-            ) else {
-                return;
-            };
-            let visible_bottom_right = ray_bottom_right.origin.truncate();
-            let (x_max, y_min) = (
-                visible_bottom_right.x.round() as isize,
-                visible_bottom_right.y.round() as isize,
+        }
+    }
+    let (y_first, y_last) = line_range(min.y, max.y, spacing);
+    for k in y_first as i64..=y_last as i64 {
+        let y = k as f32 * spacing;
+        if let Some(pos) = to_screen(min.x - 0.5, y - 0.5) {
+            painter.text(
+                egui::pos2(free.left(), pos.y) + egui::vec2(margin.x, -margin.y),
+                egui::Align2::LEFT_BOTTOM,
+                (k * i64::from(display_config.grid_major_spacing)).to_string(),
+                font.clone(),
+                color,
             );
-
-            // Draw vertical lines
-            for x in x_min..=x_max {
-                let Ok(start) = camera.world_to_viewport(
-                    camera_transform,
-                    Vec3 {
-                        x: x as f32 - 0.5,
-                        y: y_min as f32 - 0.5,
-                        z: 0.0,
-                    },
-                ) else {
-                    continue;
-                };
-                let start_pos = egui::Pos2::new(start.x, start.y);
-                let Ok(end) = camera.world_to_viewport(
-                    camera_transform,
-                    Vec3 {
-                        x: x as f32 - 0.5,
-                        y: y_max as f32 + 0.5,
-                        z: 0.0,
-                    },
-                ) else {
-                    continue;
-                };
-                let end_pos = egui::Pos2::new(end.x, end.y);
-                painter.add(egui::Shape::LineSegment {
-                    points: [start_pos, end_pos],
-                    stroke: egui::Stroke {
-                        width: line_width,
-                        color: LINE_COLOR,
-                    }
-                    .into(),
-                });
-            }
-
-            // Draw horizontal lines
-            for y in y_min..=y_max {
-                let Ok(start) = camera.world_to_viewport(
-                    camera_transform,
-                    Vec3 {
-                        x: x_min as f32 - 0.5,
-                        y: y as f32 - 0.5,
-                        z: 0.0,
-                    },
-                ) else {
-                    continue;
-                };
-                let start_pos = egui::Pos2::new(start.x, start.y);
-                let Ok(end) = camera.world_to_viewport(
-                    camera_transform,
-                    Vec3 {
-                        x: x_max as f32 + 0.5,
-                        y: y as f32 - 0.5,
-                        z: 0.0,
-                    },
-                ) else {
-                    continue;
-                };
-                let end_pos = egui::Pos2::new(end.x, end.y);
-                painter.add(egui::Shape::LineSegment {
-                    points: [start_pos, end_pos],
-                    stroke: egui::Stroke {
-                        width: line_width,
-                        color: LINE_COLOR,
-                    }
-                    .into(),
-                });
-            }
-        });
+        }
+    }
 }
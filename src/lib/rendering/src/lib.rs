@@ -1,13 +1,31 @@
 //! # Rendering Module
 //!
 //! Handles all visual rendering aspects of the Game of Life,
-//! including cell sprites and grid display.
+//! including cell sprites, chunk textures and grid display.
 
+pub mod animation;
+pub mod boundary;
+pub mod chunks;
+pub mod density;
+pub mod explain;
+pub mod frame_rate;
 pub mod grid;
+pub mod onion;
+pub mod shapes;
 pub mod sprites;
+pub mod trails;
 
+pub use animation::*;
+pub use boundary::*;
+pub use chunks::*;
+pub use density::*;
+pub use explain::*;
+pub use frame_rate::*;
 pub use grid::*;
+pub use onion::*;
+pub use shapes::*;
 pub use sprites::*;
+pub use trails::*;
 
 use bevy::prelude::{App, ClearColor, Plugin};
 use gol_config::BG_COLOR;
@@ -19,6 +37,14 @@ impl Plugin for RenderingPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(ClearColor(BG_COLOR))
             .add_plugins(SpritePlugin)
-            .add_plugins(GridPlugin);
+            .add_plugins(TrailPlugin)
+            .add_plugins(ExplainHighlightPlugin)
+            .add_plugins(OnionSkinPlugin)
+            .add_plugins(GridPlugin)
+            .add_plugins(ChunkPlugin)
+            .add_plugins(BoundaryPlugin)
+            .add_plugins(FrameRatePlugin)
+            .add_plugins(AnimationPlugin)
+            .add_plugins(DensityPlugin);
     }
 }
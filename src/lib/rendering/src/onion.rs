@@ -0,0 +1,124 @@
+//! # Onion Module
+//!
+//! Onion skin: faint outlines of the cells alive at the previous generation,
+//! drawn behind the current ones so that oscillators and moving patterns
+//! show where they come from.
+//!
+//! The previous board is the last snapshot of the generation history, so
+//! nothing is drawn while the history is disabled or with the HashLife
+//! backend, which does not record it.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::color::Alpha;
+use bevy::image::ImageSampler;
+use bevy::prelude::{
+    App, Assets, Commands, Component, Entity, Handle, Image, IntoScheduleConfigs, Plugin, Query,
+    Res, ResMut, Resource, Sprite, Startup, Transform, Update, Vec2, With,
+};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use gol_config::{ColorConfig, DisplayConfig};
+use gol_simulation::{BoardHistory, CellSet, SimulationStats};
+
+/// Side length of the outline texture, in pixels, its border being one pixel wide
+const OUTLINE_TEXTURE_SIZE: u32 = 8;
+
+/// Depth of the outlines, behind the living cells and above the death trails
+const OUTLINE_DEPTH: f32 = -0.25;
+
+/// Opacity of the outlines
+const OUTLINE_OPACITY: f32 = 0.5;
+
+/// Outline texture and the generation whose outlines are shown
+#[derive(Resource)]
+pub struct OnionSkin {
+    texture: Handle<Image>,
+    generation: Option<u64>,
+}
+
+/// Outline of a cell alive at the previous generation
+#[derive(Component)]
+pub struct OnionOutline;
+
+/// Plugin for the onion skin
+pub struct OnionSkinPlugin;
+
+impl Plugin for OnionSkinPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_onion_skin)
+            .add_systems(Update, update_onion_skin.after(CellSet));
+    }
+}
+
+fn setup_onion_skin(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let size = OUTLINE_TEXTURE_SIZE;
+    let data = (0..size * size)
+        .flat_map(|i| {
+            let (x, y) = (i % size, i / size);
+            let border = x == 0 || y == 0 || x == size - 1 || y == size - 1;
+            if border { [255; 4] } else { [0; 4] }
+        })
+        .collect();
+    let mut image = Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.sampler = ImageSampler::nearest();
+    commands.insert_resource(OnionSkin {
+        texture: images.add(image),
+        generation: None,
+    });
+}
+
+/// Outlines the cells of the previous generation whenever it changes
+pub fn update_onion_skin(
+    mut commands: Commands,
+    display_config: Res<DisplayConfig>,
+    color_config: Res<ColorConfig>,
+    stats: Res<SimulationStats>,
+    history: Res<BoardHistory>,
+    mut onion: ResMut<OnionSkin>,
+    q_outlines: Query<Entity, With<OnionOutline>>,
+) {
+    let previous = stats
+        .generation
+        .checked_sub(1)
+        .filter(|_| display_config.onion_skin)
+        .and_then(|generation| history.get(generation));
+    let generation = previous.map(|snapshot| snapshot.generation);
+    if generation == onion.generation {
+        return;
+    }
+    onion.generation = generation;
+
+    for entity in &q_outlines {
+        commands.entity(entity).despawn();
+    }
+    let Some(snapshot) = previous else {
+        return;
+    };
+    let color = color_config.state_color(1).with_alpha(OUTLINE_OPACITY);
+    let outlines: Vec<_> = snapshot
+        .board
+        .iter()
+        .map(|cell| {
+            (
+                OnionOutline,
+                Sprite {
+                    image: onion.texture.clone(),
+                    color,
+                    custom_size: Some(Vec2::ONE),
+                    ..Default::default()
+                },
+                Transform::from_xyz(cell.x as f32, cell.y as f32, OUTLINE_DEPTH),
+            )
+        })
+        .collect();
+    commands.spawn_batch(outlines);
+}
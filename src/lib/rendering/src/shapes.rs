@@ -0,0 +1,135 @@
+//! # Shapes Module
+//!
+//! Masks telling the living states apart by their shape as well as their
+//! color, for colorblind users. The first living state keeps the configured
+//! cell shape, the next ones are drawn as circles, diamonds and triangles.
+//!
+//! The configured shape of the cells is a mask too, rebuilt when the
+//! [`DisplayConfig`] changes, plain squares using the default sprite image.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::{
+    Assets, DetectChanges, FromWorld, Handle, Image, Res, ResMut, Resource, World,
+};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use gol_config::{CellShape, DisplayConfig};
+
+/// Side length of a shape mask, in pixels
+const MASK_SIZE: u32 = 32;
+
+/// Shape of the cells in a living state after the first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StateShape {
+    Circle,
+    Diamond,
+    Triangle,
+}
+
+impl StateShape {
+    /// Shapes given to the states after the first, in order
+    const ALL: [Self; 3] = [Self::Circle, Self::Diamond, Self::Triangle];
+
+    /// Whether a point belongs to the shape, the cell spanning -1 to 1 on
+    /// both axes with `v` going downward
+    fn contains(self, u: f32, v: f32) -> bool {
+        match self {
+            Self::Circle => u * u + v * v <= 1.0,
+            Self::Diamond => u.abs() + v.abs() <= 1.0,
+            // Pointing up, its base along the bottom of the cell
+            Self::Triangle => u.abs() <= (v + 1.0) / 2.0,
+        }
+    }
+
+    /// White mask of the shape, tinted by the sprite color
+    fn mask(self) -> Image {
+        mask(|u, v| self.contains(u, v))
+    }
+}
+
+/// White mask of the points of a cell for which `contains` holds, the cell
+/// spanning -1 to 1 on both axes with `v` going downward
+fn mask(contains: impl Fn(f32, f32) -> bool) -> Image {
+    let mut data = Vec::with_capacity((MASK_SIZE * MASK_SIZE * 4) as usize);
+    for row in 0..MASK_SIZE {
+        for column in 0..MASK_SIZE {
+            // Sample the center of each pixel
+            let u = (column as f32 + 0.5) / MASK_SIZE as f32 * 2.0 - 1.0;
+            let v = (row as f32 + 0.5) / MASK_SIZE as f32 * 2.0 - 1.0;
+            let alpha = if contains(u, v) { 255 } else { 0 };
+            data.extend_from_slice(&[255, 255, 255, alpha]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: MASK_SIZE,
+            height: MASK_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+/// Images of the shaped cells
+#[derive(Resource)]
+pub struct CellShapes {
+    masks: Vec<Handle<Image>>,
+    /// Image of the cells in the first living state
+    cell: Handle<Image>,
+    /// Shape and roundness the cell image was drawn with
+    outline: (CellShape, f32),
+}
+
+impl FromWorld for CellShapes {
+    fn from_world(world: &mut World) -> Self {
+        let mut images = world.resource_mut::<Assets<Image>>();
+        Self {
+            masks: StateShape::ALL
+                .into_iter()
+                .map(|shape| images.add(shape.mask()))
+                .collect(),
+            cell: Handle::default(),
+            outline: (CellShape::Square, 0.0),
+        }
+    }
+}
+
+impl CellShapes {
+    /// Image of the cells in a living state: the configured cell shape for
+    /// the first one, then the shapes in turn
+    pub fn image(&self, state: u8) -> Handle<Image> {
+        match state {
+            0 | 1 => self.cell(),
+            _ => self.masks[(state as usize - 2) % self.masks.len()].clone(),
+        }
+    }
+
+    /// Image of the cells in the configured shape
+    pub fn cell(&self) -> Handle<Image> {
+        self.cell.clone()
+    }
+}
+
+/// Redraws the cell image when the configured shape or roundness changes.
+///
+/// The gap between cells is left to the sprite size, so that the image
+/// fills its sprite.
+pub fn update_cell_shape_system(
+    display_config: Res<DisplayConfig>,
+    mut shapes: ResMut<CellShapes>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let outline = (display_config.cell_shape, display_config.cell_roundness);
+    if !display_config.is_changed() || shapes.outline == outline {
+        return;
+    }
+    let (shape, roundness) = outline;
+    shapes.cell = if shape == CellShape::Square && roundness <= 0.0 {
+        Handle::default()
+    } else {
+        images.add(mask(|u, v| shape.contains(roundness, u, v)))
+    };
+    shapes.outline = outline;
+}
@@ -3,27 +3,39 @@
 //! Handles the visual representation of cells as sprites.
 
 use bevy::prelude::{
-    App, Commands, Entity, IntoScheduleConfigs, Plugin, Query, Res, Sprite, Transform, Update,
-    Vec2, With, Without,
+    App, Camera, Changed, Color, Commands, DetectChanges, Entity, Handle, Image,
+    IntoScheduleConfigs, Mut, Plugin, Projection, Query, Res, Sprite, Transform, Update, Vec2,
+    With, Without,
 };
-use gol_config::ColorConfig;
-use gol_simulation::{Alive, CellPosition, CellSet};
+use crate::chunks::render_mode_is;
+use crate::shapes::{CellShapes, update_cell_shape_system};
+use gol_config::{CellRenderMode, ColorConfig, DisplayConfig};
+use gol_simulation::{Alive, CellPosition, CellState, CellSyncSet};
 
 /// Plugin for sprite rendering systems
 pub struct SpritePlugin;
 
 impl Plugin for SpritePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.init_resource::<CellShapes>().add_systems(
             Update,
             (
-                draw_new_cells_system.before(CellSet),
-                update_cell_colors_system,
-            ),
+                update_cell_shape_system,
+                draw_new_cells_system.after(CellSyncSet),
+                update_cell_colors_system
+                    .after(update_cell_shape_system)
+                    .run_if(cell_look_changed),
+                update_changed_cell_colors_system.after(CellSyncSet),
+                update_cell_sizes_system,
+            )
+                .run_if(render_mode_is(CellRenderMode::Sprites)),
         );
     }
 }
 
+/// Filter of the living cells that have no sprite yet
+type UndrawnCell = (With<Alive>, Without<Sprite>);
+
 /// System that adds visual components to newly spawned cells.
 ///
 /// This system runs when cells are first created and adds the necessary
@@ -31,29 +43,136 @@ impl Plugin for SpritePlugin {
 pub fn draw_new_cells_system(
     mut commands: Commands,
     color_config: Res<ColorConfig>,
-    query: Query<(Entity, &CellPosition), (With<Alive>, Without<Sprite>)>,
+    display_config: Res<DisplayConfig>,
+    shapes: Res<CellShapes>,
+    q_camera: Query<&Projection, With<Camera>>,
+    query: Query<(Entity, &CellPosition, &CellState), UndrawnCell>,
 ) {
-    for (entity, pos) in query.iter() {
+    let size = q_camera
+        .single()
+        .map_or(display_config.cell_size, |projection| {
+            cell_sprite_size(projection, display_config.cell_size)
+        });
+    for (entity, pos, state) in query.iter() {
         commands
             .entity(entity)
             .insert(Sprite {
-                color: color_config.cell_color,
-                custom_size: Some(Vec2::new(1.0, 1.0)),
+                image: state_image(&display_config, &shapes, state.0),
+                color: color_config.state_color(state.0),
+                custom_size: Some(Vec2::splat(size)),
                 ..Default::default()
             })
             .insert(Transform::from_xyz(pos.x as f32, pos.y as f32, 0.0));
     }
 }
 
-/// System that updates the colors of existing cells when the color configuration changes
+/// Run condition that is true when the colors or the shapes of the cells
+/// may have changed
+pub fn cell_look_changed(
+    color_config: Res<ColorConfig>,
+    display_config: Res<DisplayConfig>,
+    shapes: Res<CellShapes>,
+) -> bool {
+    color_config.is_changed() || display_config.is_changed() || shapes.is_changed()
+}
+
+/// Filter of the living cells
+type LivingCell = (With<CellPosition>, With<Alive>);
+
+/// System that updates the colors and shapes of every living cell when the
+/// configuration changes.
+///
+/// The look of each state is worked out once, and the sprites are updated
+/// in parallel, only touching those that differ.
 pub fn update_cell_colors_system(
     color_config: Res<ColorConfig>,
+    display_config: Res<DisplayConfig>,
+    shapes: Res<CellShapes>,
+    mut query: Query<(&mut Sprite, &CellState), LivingCell>,
+) {
+    let looks: Vec<(Color, Handle<Image>)> = (0..=u8::MAX)
+        .map(|state| {
+            (
+                color_config.state_color(state),
+                state_image(&display_config, &shapes, state),
+            )
+        })
+        .collect();
+    query.par_iter_mut().for_each(|(mut sprite, state)| {
+        set_look(&mut sprite, &looks[state.0 as usize]);
+    });
+}
+
+/// Filter of the living cells whose state changed
+type RecoloredCell = (With<Alive>, Changed<CellState>);
+
+/// System that updates the colors and shapes of the cells whose state
+/// changed, including recycled entities coming back to life
+pub fn update_changed_cell_colors_system(
+    color_config: Res<ColorConfig>,
+    display_config: Res<DisplayConfig>,
+    shapes: Res<CellShapes>,
+    mut query: Query<(&mut Sprite, &CellState), RecoloredCell>,
+) {
+    for (mut sprite, state) in query.iter_mut() {
+        let look = (
+            color_config.state_color(state.0),
+            state_image(&display_config, &shapes, state.0),
+        );
+        set_look(&mut sprite, &look);
+    }
+}
+
+/// Gives a sprite the color and image of its state, leaving it untouched
+/// when they already match
+fn set_look(sprite: &mut Mut<Sprite>, (color, image): &(Color, Handle<Image>)) {
+    if sprite.color != *color {
+        sprite.color = *color;
+    }
+    if sprite.image != *image {
+        sprite.image = image.clone();
+    }
+}
+
+/// Image of a cell sprite, shaped after its state when enabled and after
+/// the configured cell shape otherwise
+fn state_image(display_config: &DisplayConfig, shapes: &CellShapes, state: u8) -> Handle<Image> {
+    if display_config.state_shapes {
+        shapes.image(state)
+    } else {
+        shapes.cell()
+    }
+}
+
+/// Side length of a cell sprite, in cells, for the given camera projection
+/// and the configured cell size.
+///
+/// Past the point where a cell is smaller than a pixel, sprites grow so that
+/// every alive cell still covers at least one pixel instead of vanishing.
+pub fn cell_sprite_size(projection: &Projection, cell_size: f32) -> f32 {
+    match projection {
+        Projection::Orthographic(orthographic) => orthographic.scale.max(cell_size),
+        _ => cell_size,
+    }
+}
+
+/// System that resizes cell sprites when zooming beyond one pixel per cell,
+/// or when the configured cell size changes
+pub fn update_cell_sizes_system(
+    display_config: Res<DisplayConfig>,
+    q_camera: Query<&Projection, With<Camera>>,
     mut query: Query<&mut Sprite, (With<CellPosition>, With<Alive>)>,
 ) {
-    // Verify and correct the cell color every frame
+    let Ok(projection) = q_camera.single() else {
+        return;
+    };
+    let size = Some(Vec2::splat(cell_sprite_size(
+        projection,
+        display_config.cell_size,
+    )));
     for mut sprite in query.iter_mut() {
-        if sprite.color != color_config.cell_color {
-            sprite.color = color_config.cell_color;
+        if sprite.custom_size != size {
+            sprite.custom_size = size;
         }
     }
 }
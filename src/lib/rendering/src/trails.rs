@@ -0,0 +1,116 @@
+//! # Trails Module
+//!
+//! Afterglow of recently dead cells in sprite mode. Killed cells keep their
+//! pooled entity and sprite, which stays visible and fades out over a few
+//! generations, so moving patterns leave a trail behind them.
+
+use crate::animation::Animating;
+use crate::chunks::render_mode_is;
+use bevy::color::Alpha;
+use bevy::prelude::{
+    App, Commands, Component, Entity, IntoScheduleConfigs, Local, Plugin, Query, Res, Sprite,
+    Transform, Update, Visibility, With, Without,
+};
+use gol_config::{CellRenderMode, ColorConfig, DisplayConfig};
+use gol_simulation::{Alive, CellPosition, CellSyncSet, SimulationStats};
+
+/// Depth of trail sprites, below the living cells
+const TRAIL_DEPTH: f32 = -0.5;
+
+/// Opacity of a trail right after the cell died
+const TRAIL_OPACITY: f32 = 0.6;
+
+/// Dead cell entity showing an afterglow
+#[derive(Component, Debug)]
+pub struct Trail {
+    /// Generations since the cell died
+    pub age: u64,
+}
+
+/// Plugin for the death trails
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            update_trails_system
+                .after(CellSyncSet)
+                .run_if(render_mode_is(CellRenderMode::Sprites)),
+        );
+    }
+}
+
+/// Filter of the dead cell sprites that have no trail yet
+type TraillessCell = (
+    With<CellPosition>,
+    With<Sprite>,
+    Without<Alive>,
+    Without<Trail>,
+);
+
+/// Filter of the trails of dead cells whose death animation is over
+type FadingTrail = (Without<Alive>, Without<Animating>);
+
+/// Starts the trail of newly dead cells, ages the existing ones as
+/// generations pass and hides them once they are fully faded.
+///
+/// Revived entities drop their trail, the spawn resetting their sprite, and
+/// trails wait for the death animation to be over.
+#[allow(clippy::too_many_arguments)]
+pub fn update_trails_system(
+    mut commands: Commands,
+    display_config: Res<DisplayConfig>,
+    color_config: Res<ColorConfig>,
+    stats: Res<SimulationStats>,
+    mut last_generation: Local<u64>,
+    mut q_dead: Query<(Entity, &mut Transform), TraillessCell>,
+    mut q_trails: Query<(&mut Trail, &mut Sprite, &mut Visibility), FadingTrail>,
+    q_revived: Query<Entity, (With<Alive>, With<Trail>)>,
+) {
+    for entity in &q_revived {
+        commands.entity(entity).remove::<Trail>();
+    }
+
+    // Going back in time, e.g. when the grid is cleared, ends every trail
+    let rewound = stats.generation < *last_generation;
+    let elapsed = stats.generation.saturating_sub(*last_generation);
+    *last_generation = stats.generation;
+
+    // Dead cells are tagged even while trails are off, so that enabling them
+    // later does not light up every pooled entity
+    let start_age = if rewound { u64::MAX } else { 0 };
+    for (entity, mut transform) in &mut q_dead {
+        transform.translation.z = TRAIL_DEPTH;
+        commands.entity(entity).insert(Trail { age: start_age });
+    }
+
+    // Reduced motion hides the trails, keeping their bookkeeping
+    let length = if display_config.reduce_motion {
+        0
+    } else {
+        u64::from(display_config.death_trail)
+    };
+    let color = color_config.state_color(1);
+    for (mut trail, mut sprite, mut visibility) in &mut q_trails {
+        trail.age = if rewound {
+            u64::MAX
+        } else {
+            trail.age.saturating_add(elapsed)
+        };
+        if trail.age >= length {
+            if *visibility != Visibility::Hidden {
+                *visibility = Visibility::Hidden;
+            }
+            continue;
+        }
+        let fade = (length - trail.age) as f32 / (length + 1) as f32;
+        let faded = color.with_alpha(TRAIL_OPACITY * fade);
+        if sprite.color != faded {
+            sprite.color = faded;
+        }
+        if *visibility != Visibility::Visible {
+            *visibility = Visibility::Visible;
+        }
+    }
+}
@@ -1,20 +1,42 @@
 //! # Cell Module
 //!
 //! Defines the basic cell types, components, and states for the Game of Life.
+//!
+//! The living cells are held by the [`BoardState`] resource, the single
+//! source of truth of the board: the simulation, the editing tools and the
+//! files all read and change it. Cell entities only display it, and are
+//! spawned, recycled and updated from it by [`sync_cell_entities`], only in
+//! the [`BIT_CHUNK_SIZE`] chunks around the view so that large boards do not
+//! cost an entity per cell. Cells scrolled far enough off screen lose their
+//! entity, and get one back when scrolled into view again.
 
 use bevy::prelude::{
-    App, Commands, Component, Entity, IntoScheduleConfigs, Plugin, Resource, Startup, SystemSet,
+    App, Commands, Component, DetectChanges, Entity, IntoScheduleConfigs, Last, Local, Plugin,
+    Query, Res, ResMut, Resource, Startup, SystemSet, Transform, Update, Visibility, With, warn,
 };
+use gol_config::{CULL_MARGIN_CHUNKS, DEAD_POOL_CAPACITY, SimulationConfig, StartupPattern};
+use gol_core::{BIT_CHUNK_SIZE, Board, Cell, PatternError, parse_pattern, random_soup};
+use rustc_hash::FxHashSet;
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::generation::SimulationViewport;
+use crate::pattern::PatternRegistry;
 
 /// System set for organizing cell-related systems in the Bevy ECS.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct CellSet;
 
+/// System set bringing the cell entities in line with the [`BoardState`],
+/// after every [`CellSet`] system of the frame.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct CellSyncSet;
+
 /// Represents the position of a cell in the Game of Life grid.
 ///
 /// Uses signed integers to allow for negative coordinates,
 /// enabling an infinite grid that can expand in all directions.
-#[derive(Clone, Copy, Component, PartialEq, Eq, Debug, Hash)]
+#[derive(Clone, Copy, Component, Default, PartialEq, Eq, Debug, Hash)]
 pub struct CellPosition {
     /// The x-coordinate of the cell
     pub x: isize,
@@ -22,14 +44,62 @@ pub struct CellPosition {
     pub y: isize,
 }
 
+impl From<Cell> for CellPosition {
+    fn from(cell: Cell) -> Self {
+        Self {
+            x: cell.x,
+            y: cell.y,
+        }
+    }
+}
+
+impl From<CellPosition> for Cell {
+    fn from(pos: CellPosition) -> Self {
+        Cell::new(pos.x, pos.y)
+    }
+}
+
 /// Marker component for cells that are currently alive
 #[derive(Component)]
+#[require(CellState)]
 pub struct Alive;
 
+/// State of a living cell, such as its color in QuadLife.
+///
+/// Plain two-state rules keep every cell in state 1.
+#[derive(Clone, Copy, Component, PartialEq, Eq, Debug, Hash)]
+pub struct CellState(pub u8);
+
+impl Default for CellState {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// Living cells of the grid, states included.
+///
+/// The source of truth of the board: changing it is enough to bring cells
+/// to life or kill them, their entities following once per frame. With the
+/// HashLife backend it only holds the cells around the view, the universe
+/// holding the others.
+#[derive(Resource, Default, Debug, Clone, PartialEq, Eq)]
+pub struct BoardState(pub Board);
+
 /// Pool of dead cell entities ready for reuse
-#[derive(Resource, Default)]
+#[derive(Resource)]
 pub struct DeadCellPool {
     pub entities: Vec<Entity>,
+    /// Number of pooled entities kept, surplus ones are despawned
+    pub capacity: usize,
+}
+
+impl Default for DeadCellPool {
+    fn default() -> Self {
+        Self {
+            entities: Vec::new(),
+            capacity: DEAD_POOL_CAPACITY,
+        }
+    }
 }
 
 /// Plugin for cell-related functionality
@@ -38,16 +108,203 @@ pub struct CellPlugin;
 impl Plugin for CellPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(DeadCellPool::default())
-            .add_systems(Startup, setup_initial_pattern.in_set(CellSet));
+            .init_resource::<BoardState>()
+            .configure_sets(Update, CellSyncSet.after(CellSet))
+            .add_systems(Startup, setup_initial_pattern.in_set(CellSet))
+            .add_systems(Update, sync_cell_entities.in_set(CellSyncSet))
+            // Despawning last keeps reclaimed entities out of every other system's queries
+            .add_systems(Last, reclaim_dead_cells_system);
     }
 }
 
-/// Sets up the initial pattern of living cells.
+/// Sets up the initial pattern of living cells, as configured by
+/// [`SimulationConfig::startup_pattern`].
 ///
-/// Spawns a simple pattern of cells to start the simulation.
-/// This creates a small glider pattern that will move across the grid.
-pub fn setup_initial_pattern(mut commands: Commands) {
-    for &(x, y) in &[(0, 0), (-1, 0), (0, -1), (0, 1), (1, 1)] {
-        commands.spawn((CellPosition { x, y }, Alive));
+/// A pattern that cannot be found or read leaves the grid empty.
+pub fn setup_initial_pattern(
+    mut board: ResMut<BoardState>,
+    config: Res<SimulationConfig>,
+    registry: Res<PatternRegistry>,
+) {
+    let cells = startup_cells(&config.startup_pattern, &registry).unwrap_or_else(|e| {
+        warn!("{e}");
+        Vec::new()
+    });
+    for (x, y) in cells {
+        board.0.set(Cell::new(x as isize, y as isize), true);
+    }
+}
+
+/// Living cells of a startup pattern
+pub fn startup_cells(
+    pattern: &StartupPattern,
+    registry: &PatternRegistry,
+) -> Result<Vec<(i32, i32)>, StartupError> {
+    match pattern {
+        StartupPattern::Empty => Ok(Vec::new()),
+        StartupPattern::Named(name) => registry
+            .get(name)
+            .map(|entry| entry.cells.clone())
+            .ok_or_else(|| StartupError::UnknownPattern(name.clone())),
+        StartupPattern::Soup(seed) => Ok(random_soup(*seed)
+            .iter()
+            .map(|cell| (cell.x as i32, cell.y as i32))
+            .collect()),
+        StartupPattern::File(path) => {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| StartupError::Read(path.clone(), e.to_string()))?;
+            parse_pattern(&content)
+                .map(|pattern| pattern.cells)
+                .map_err(|e| StartupError::Parse(path.clone(), e))
+        }
+        StartupPattern::Text(content) => parse_pattern(content)
+            .map(|pattern| pattern.cells)
+            .map_err(StartupError::ParseText),
+    }
+}
+
+/// Error returned when a startup pattern cannot be loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartupError {
+    /// No pattern of the registry has this name
+    UnknownPattern(String),
+    /// The pattern file cannot be read
+    Read(PathBuf, String),
+    /// The pattern file is not in a supported format
+    Parse(PathBuf, PatternError),
+    /// The pattern text is not in a supported format
+    ParseText(PatternError),
+}
+
+impl fmt::Display for StartupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPattern(name) => write!(f, "Unknown startup pattern: {name}"),
+            Self::Read(path, e) => write!(f, "Could not read {}: {e}", path.display()),
+            Self::Parse(path, e) => write!(f, "Could not parse {}: {e}", path.display()),
+            Self::ParseText(e) => write!(f, "Could not parse the startup pattern: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for StartupError {}
+
+/// Brings a cell to life at `pos` in the given state, reusing a pooled
+/// entity when one is available.
+fn spawn_cell_entity(
+    commands: &mut Commands,
+    dead_pool: &mut DeadCellPool,
+    pos: CellPosition,
+    state: CellState,
+) {
+    if let Some(entity) = dead_pool.entities.pop() {
+        commands
+            .entity(entity)
+            .insert(Alive)
+            .insert(state)
+            .insert(Visibility::Visible)
+            .insert(Transform::from_xyz(pos.x as f32, pos.y as f32, 0.0))
+            .insert(pos);
+    } else {
+        commands.spawn((pos, Alive, state, Visibility::Visible));
+    }
+}
+
+/// Kills the cell held by `entity` and returns the entity to the pool.
+fn kill_cell_entity(commands: &mut Commands, dead_pool: &mut DeadCellPool, entity: Entity) {
+    commands
+        .entity(entity)
+        .remove::<Alive>()
+        .insert(Visibility::Hidden);
+    dead_pool.entities.push(entity);
+}
+
+/// Despawns the oldest pooled entities once the pool grows past its capacity.
+///
+/// Keeps memory bounded after a large population dies out, while the most
+/// recently killed entities stay available for reuse.
+pub fn reclaim_dead_cells_system(mut commands: Commands, mut dead_pool: ResMut<DeadCellPool>) {
+    let surplus = dead_pool.entities.len().saturating_sub(dead_pool.capacity);
+    if surplus == 0 {
+        return;
+    }
+    for entity in dead_pool.entities.drain(..surplus) {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Area covered by the chunks overlapping the viewport, widened by `margin`
+/// chunks on each side, as its (min, max) corners
+fn chunk_area(viewport: &SimulationViewport, margin: isize) -> (Cell, Cell) {
+    let chunk = |coordinate: isize| coordinate.div_euclid(BIT_CHUNK_SIZE);
+    let min = Cell::new(
+        (chunk(viewport.min.x) - margin) * BIT_CHUNK_SIZE,
+        (chunk(viewport.min.y) - margin) * BIT_CHUNK_SIZE,
+    );
+    let max = Cell::new(
+        (chunk(viewport.max.x) + margin + 1) * BIT_CHUNK_SIZE - 1,
+        (chunk(viewport.max.y) + margin + 1) * BIT_CHUNK_SIZE - 1,
+    );
+    (min, max)
+}
+
+/// Whether the area given by its (min, max) corners lies inside another
+fn area_within(inner: (Cell, Cell), outer: (Cell, Cell)) -> bool {
+    inner.0.x >= outer.0.x
+        && inner.0.y >= outer.0.y
+        && inner.1.x <= outer.1.x
+        && inner.1.y <= outer.1.y
+}
+
+/// Makes the living entities match the board around the visible chunks,
+/// spawning, recycling and updating them as needed.
+///
+/// Entities are kept up to [`CULL_MARGIN_CHUNKS`] chunks around the view.
+/// They are only culled once the view moved a whole margin away, or zoomed
+/// in that far, so that small pans leave them alone.
+///
+/// Runs when the board changed or when the view left the covered area.
+pub fn sync_cell_entities(
+    mut commands: Commands,
+    board: Res<BoardState>,
+    viewport: Res<SimulationViewport>,
+    mut dead_pool: ResMut<DeadCellPool>,
+    alive_query: Query<(Entity, &CellPosition, &CellState), With<Alive>>,
+    mut last_area: Local<Option<(Cell, Cell)>>,
+) {
+    let visible = chunk_area(&viewport, 0);
+    let kept = last_area.filter(|&area| {
+        area_within(visible, area)
+            && area_within(area, chunk_area(&viewport, 2 * CULL_MARGIN_CHUNKS))
+    });
+    if !board.is_changed() && kept.is_some() {
+        return;
+    }
+    let area = kept.unwrap_or_else(|| chunk_area(&viewport, CULL_MARGIN_CHUNKS));
+    *last_area = Some(area);
+    let shown = |cell: &Cell| {
+        (area.0.x..=area.1.x).contains(&cell.x) && (area.0.y..=area.1.y).contains(&cell.y)
+    };
+
+    let board = &board.0;
+    let mut present = FxHashSet::default();
+    for (entity, pos, state) in &alive_query {
+        let cell = Cell::from(*pos);
+        let new_state = if shown(&cell) { board.state(&cell) } else { 0 };
+        match new_state {
+            0 => kill_cell_entity(&mut commands, &mut dead_pool, entity),
+            new_state => {
+                if new_state != state.0 {
+                    commands.entity(entity).insert(CellState(new_state));
+                }
+                present.insert(cell);
+            }
+        }
+    }
+    for cell in board.iter().filter(|cell| shown(cell)) {
+        if !present.contains(cell) {
+            let state = CellState(board.state(cell));
+            spawn_cell_entity(&mut commands, &mut dead_pool, (*cell).into(), state);
+        }
     }
 }
@@ -0,0 +1,104 @@
+//! # Elementary Module
+//!
+//! Backend running a one-dimensional Wolfram automaton. The living cells of
+//! the lowest row hold the current generation and each step draws the next
+//! one just below it, so the history of the automaton unrolls down the grid.
+
+use bevy::platform::time::Instant;
+use bevy::prelude::{App, FixedUpdate, IntoScheduleConfigs, Plugin, ResMut, Resource};
+use gol_core::{Cell, ElementaryRule};
+use rustc_hash::FxHashSet;
+
+use crate::cell::{BoardState, CellPosition, CellSet};
+use crate::events::GenerationEvents;
+use crate::generation::{GenerationClock, SimulationStats, backend_is};
+use gol_config::{MAX_GENERATIONS_PER_TICK, SimulationBackend, SimulationConfig};
+
+/// Row of the most recent generation, which the camera follows
+#[derive(Resource, Default, Debug)]
+pub struct ElementaryRow {
+    /// World y of the row, `None` while the board is empty
+    pub y: Option<isize>,
+}
+
+/// Plugin for the elementary automaton backend
+pub struct ElementaryPlugin;
+
+impl Plugin for ElementaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ElementaryRow>().add_systems(
+            FixedUpdate,
+            calculate_next_rows
+                .in_set(CellSet)
+                .run_if(backend_is(SimulationBackend::Elementary)),
+        );
+    }
+}
+
+/// Draws the rows of the generations that came due below the lowest row.
+///
+/// Warps are honored here too, a tick computing at most
+/// [`MAX_GENERATIONS_PER_TICK`] of their rows.
+pub fn calculate_next_rows(
+    mut board: ResMut<BoardState>,
+    mut stats: ResMut<SimulationStats>,
+    mut clock: GenerationClock,
+    mut config: ResMut<SimulationConfig>,
+    mut row: ResMut<ElementaryRow>,
+    mut events: GenerationEvents,
+) {
+    let steps = if config.is_warping() {
+        let steps = config.warp_remaining.min(MAX_GENERATIONS_PER_TICK as u64);
+        config.warp_remaining -= steps;
+        if !config.is_warping() {
+            config.cancel_warp();
+        }
+        steps as u32
+    } else {
+        clock.due(&mut config)
+    };
+
+    let Some(mut y) = board.0.iter().map(|cell| cell.y).min() else {
+        row.y = None;
+        return;
+    };
+    if steps == 0 {
+        if row.y != Some(y) {
+            row.y = Some(y);
+        }
+        return;
+    }
+
+    let started = Instant::now();
+    let rule = ElementaryRule(config.elementary_rule);
+    let mut cells: FxHashSet<isize> = board
+        .0
+        .iter()
+        .filter(|cell| cell.y == y)
+        .map(|cell| cell.x)
+        .collect();
+    let mut population = board.0.len();
+    let first = stats.generation;
+    for _ in 0..steps {
+        let next = rule.next_row(&cells);
+        // A dead row stays dead, as the background never comes alive
+        if next.is_empty() {
+            break;
+        }
+        y -= 1;
+        stats.generation += 1;
+        for &x in &next {
+            board.0.set(Cell::new(x, y), true);
+            events.born(CellPosition { x, y }, stats.generation);
+        }
+        stats.births = next.len();
+        population += next.len();
+        cells = next.into_iter().collect();
+    }
+    stats.deaths = 0;
+    stats.population = population;
+    stats.step_time = started.elapsed() / steps;
+    stats.active_chunks = None;
+    events.advanced(&stats, stats.generation - first);
+    row.y = Some(y);
+}
@@ -0,0 +1,106 @@
+//! # Events Module
+//!
+//! Messages sent by the simulation as it advances. They are the supported
+//! extension point for plugins reacting to the simulation, such as audio,
+//! statistics, scripting or networking, which only need to read them with a
+//! `MessageReader` instead of hooking into the simulation systems.
+//!
+//! A step computing several generations at once, as happens at high speed,
+//! reports the net change of its cells. Warps, runs until stable and the
+//! HashLife backend do not track individual cells and only send
+//! [`GenerationAdvanced`].
+
+use bevy::ecs::message::{Message, MessageWriter};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{App, Plugin};
+use gol_core::Step;
+
+use crate::cell::CellPosition;
+use crate::generation::SimulationStats;
+
+/// A cell came to life
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellBorn {
+    pub position: CellPosition,
+    /// Generation at which the cell is alive
+    pub generation: u64,
+}
+
+/// A cell died
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellDied {
+    pub position: CellPosition,
+    /// Generation at which the cell is dead
+    pub generation: u64,
+}
+
+/// The simulation computed one or more generations
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GenerationAdvanced {
+    /// Generation reached
+    pub generation: u64,
+    /// Number of generations computed by the step
+    pub generations: u64,
+    /// Cells born during the last generation
+    pub births: usize,
+    /// Cells that died during the last generation
+    pub deaths: usize,
+    /// Number of living cells
+    pub population: usize,
+}
+
+/// Writers of the simulation events, shared by the backends
+#[derive(SystemParam)]
+pub struct GenerationEvents<'w> {
+    born: MessageWriter<'w, CellBorn>,
+    died: MessageWriter<'w, CellDied>,
+    advanced: MessageWriter<'w, GenerationAdvanced>,
+}
+
+impl GenerationEvents<'_> {
+    /// Sends the births and deaths of a step ending at `generation`
+    pub fn step(&mut self, step: &Step, generation: u64) {
+        self.born.write_batch(step.born.iter().map(|cell| CellBorn {
+            position: (*cell).into(),
+            generation,
+        }));
+        self.died.write_batch(step.died.iter().map(|cell| CellDied {
+            position: (*cell).into(),
+            generation,
+        }));
+    }
+
+    /// Sends the birth of a single cell
+    pub fn born(&mut self, position: CellPosition, generation: u64) {
+        self.born.write(CellBorn {
+            position,
+            generation,
+        });
+    }
+
+    /// Sends the end of a step of `generations` generations, as recorded in
+    /// the statistics
+    pub fn advanced(&mut self, stats: &SimulationStats, generations: u64) {
+        if generations == 0 {
+            return;
+        }
+        self.advanced.write(GenerationAdvanced {
+            generation: stats.generation,
+            generations,
+            births: stats.births,
+            deaths: stats.deaths,
+            population: stats.population,
+        });
+    }
+}
+
+/// Plugin registering the simulation events
+pub struct EventsPlugin;
+
+impl Plugin for EventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<CellBorn>()
+            .add_message::<CellDied>()
+            .add_message::<GenerationAdvanced>();
+    }
+}
@@ -0,0 +1,120 @@
+//! # Explain Module
+//!
+//! Educational stepping: while enabled, asking for the next generation first
+//! shows which cells are about to die and be born, and only asking again
+//! applies the change.
+
+use bevy::prelude::{App, FixedUpdate, IntoScheduleConfigs, Plugin, Res, ResMut, Resource};
+use gol_core::{Board, Rule, Step};
+
+use crate::cell::{BoardState, CellSet};
+use crate::events::GenerationEvents;
+use crate::generation::{SimulationStats, backend_is, calculate_next_generation};
+use crate::history::{BoardHistory, BoardSnapshot};
+use crate::random::SimRng;
+use crate::rules::RuleSet;
+use gol_config::{SimulationBackend, SimulationConfig};
+
+/// Phase of the explained stepping
+#[derive(Resource, Default, Debug)]
+pub enum ExplainPhase {
+    /// Waiting for the next generation to be requested
+    #[default]
+    Idle,
+    /// Showing the changes of the next generation before applying them
+    Preview {
+        /// Board the changes were computed from
+        start: Board,
+        /// Changes leading to the next generation
+        step: Step,
+    },
+}
+
+impl ExplainPhase {
+    /// Changes on display, if any
+    pub fn preview(&self) -> Option<&Step> {
+        match self {
+            Self::Idle => None,
+            Self::Preview { step, .. } => Some(step),
+        }
+    }
+}
+
+/// Plugin for the explained stepping
+pub struct ExplainPlugin;
+
+impl Plugin for ExplainPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExplainPhase>().add_systems(
+            FixedUpdate,
+            explain_next_generation
+                .in_set(CellSet)
+                .before(calculate_next_generation)
+                .run_if(backend_is(SimulationBackend::Sparse)),
+        );
+    }
+}
+
+/// Takes over single steps while the explanation mode is on.
+///
+/// The first request computes the next generation and keeps it on display,
+/// the second one applies it. Editing the board in between computes the
+/// preview again from the edited board.
+#[allow(clippy::too_many_arguments)]
+pub fn explain_next_generation(
+    mut board_state: ResMut<BoardState>,
+    rule: Res<RuleSet>,
+    mut phase: ResMut<ExplainPhase>,
+    mut config: ResMut<SimulationConfig>,
+    mut stats: ResMut<SimulationStats>,
+    mut history: ResMut<BoardHistory>,
+    mut rng: ResMut<SimRng>,
+    mut events: GenerationEvents,
+) {
+    if !config.explain_steps || config.running || config.is_warping() {
+        if phase.preview().is_some() {
+            *phase = ExplainPhase::Idle;
+        }
+        return;
+    }
+    if !config.calculate_next_gen {
+        return;
+    }
+    config.calculate_next_gen = false;
+
+    let board = board_state.0.clone();
+    let current = std::mem::take(&mut *phase);
+    let ExplainPhase::Preview { start, step } = current else {
+        *phase = preview(board, &rule.0, &config, &mut rng);
+        return;
+    };
+    if start != board {
+        *phase = preview(board, &rule.0, &config, &mut rng);
+        return;
+    }
+
+    let mut next = start.clone();
+    next.apply(&step);
+    history.push(
+        BoardSnapshot {
+            generation: stats.generation,
+            board: start,
+        },
+        config.history_depth,
+    );
+    stats.generation += 1;
+    stats.births = step.born.len();
+    stats.deaths = step.died.len();
+    stats.population = next.len();
+    board_state.0 = next;
+    events.step(&step, stats.generation);
+    events.advanced(&stats, 1);
+}
+
+/// Computes the next generation of `board` with the configured rule,
+/// topology and noise
+fn preview(board: Board, rule: &Rule, config: &SimulationConfig, rng: &mut SimRng) -> ExplainPhase {
+    let step = board.compute_step_in(rule, &config.topology);
+    let step = config.noise.perturb(&board, step, rng.next_u64());
+    ExplainPhase::Preview { start: board, step }
+}
@@ -1,16 +1,33 @@
 //! # Generation Module
 //!
 //! Handles the main simulation loop, timing, and generation calculations.
+//!
+//! Generations are computed on the `FixedUpdate` schedule, so the simulation
+//! advances at the same pace whatever the rendering framerate. The sparse
+//! backend steps the board on a background task, or in a Web Worker on the
+//! web, and the main thread only applies the resulting diff, keeping the UI
+//! responsive during slow steps.
 
+use bevy::ecs::system::SystemParam;
+use bevy::platform::time::Instant;
 use bevy::prelude::{
-    App, Commands, DetectChanges, Entity, IntoScheduleConfigs, Plugin, Query, Res, ResMut,
-    Resource, Time, Timer, TimerMode, Transform, Update, Visibility, With,
+    App, DetectChanges, Fixed, FixedUpdate, IntoScheduleConfigs, Plugin, Res, ResMut, Resource,
+    Time, Timer, TimerMode, Update, warn,
 };
-use rustc_hash::FxHashSet;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future};
+use gol_core::{BitBoard, Board, Cell, GridTopology, Noise, Rule, SplitMix64, Step};
+use rustc_hash::FxHashMap;
+use std::time::Duration;
 
-use crate::cell::{Alive, CellPosition, CellSet, DeadCellPool};
-use crate::rules::{calculate_neighbor_counts, should_cell_be_born, should_cell_survive};
-use gol_config::SimulationConfig;
+use crate::cell::{BoardState, CellPosition, CellSet};
+use crate::events::GenerationEvents;
+use crate::history::{BoardHistory, BoardSnapshot};
+use crate::random::SimRng;
+use crate::rules::RuleSet;
+use gol_config::{
+    MAX_GENERATIONS_PER_TICK, SimulationBackend, SimulationConfig, WARP_FRAME_BUDGET,
+    WEB_STEP_BUDGET,
+};
 
 /// Timer resource that controls when to calculate the next generation.
 ///
@@ -19,6 +36,106 @@ use gol_config::SimulationConfig;
 #[derive(Resource)]
 pub struct GenerationTimer(pub Timer);
 
+/// Statistics about the running simulation.
+///
+/// Updated by the generation systems after each computed step.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct SimulationStats {
+    /// Number of generations computed since the board was last cleared
+    pub generation: u64,
+    /// Cells born during the last step
+    pub births: usize,
+    /// Cells that died during the last step
+    pub deaths: usize,
+    /// Number of living cells after the last step
+    pub population: usize,
+    /// Time spent computing each generation of the last step
+    pub step_time: Duration,
+    /// Chunks stepped during the last generation, when the rule is stepped
+    /// on a bitboard
+    pub active_chunks: Option<usize>,
+}
+
+impl SimulationStats {
+    /// Resets the statistics, e.g. when the board is cleared
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Generations computed off the main thread, ready to be applied to the board
+pub struct GenerationDiff {
+    /// Board of the last computed generation, `None` when the web worker
+    /// keeps it
+    pub board: Option<Board>,
+    /// Boards captured before each computed generation, oldest first
+    pub snapshots: Vec<BoardSnapshot>,
+    /// Net change between the start board and the last computed generation
+    pub step: Step,
+    /// Number of generations computed
+    pub generations: u64,
+    /// Generations requested but left for the next computation, once the
+    /// time budget ran out
+    pub left: u32,
+    /// Cells born during the last generation
+    pub births: usize,
+    /// Cells that died during the last generation
+    pub deaths: usize,
+    /// Number of living cells after the last generation
+    pub population: usize,
+    /// Time spent computing each generation
+    pub step_time: Duration,
+    /// Bitboard of the last computed generation, when the rule allows it
+    /// and the web worker does not keep it
+    pub bits: Option<BitBoard>,
+    /// Chunks stepped during the last generation, when stepped on a bitboard
+    pub active_chunks: Option<usize>,
+    /// Rule and topology the generations were computed with, which the
+    /// stable chunks of `bits` only hold for
+    pub stepped_with: (Rule, GridTopology),
+}
+
+/// Background computation of the next generations with the sparse backend
+#[derive(Resource, Default)]
+pub struct GenerationTask {
+    task: Option<Task<GenerationDiff>>,
+    /// Generations that came due while a computation was running
+    pending: u32,
+    /// Whether the board was edited while a computation was running
+    edited: bool,
+    /// Copy of the board left by the last applied computation, which the
+    /// next one starts from instead of cloning the board again
+    board: Option<Board>,
+    /// Whether the web worker holds the board the next computation starts
+    /// from, so that only edited boards are sent to it
+    #[cfg(target_arch = "wasm32")]
+    worker_synced: bool,
+    /// Bitboard matching the board, kept between computations so that
+    /// chunks which stopped changing stay skipped
+    bits: Option<BitBoard>,
+}
+
+/// Region of the grid currently visible on screen, in cell coordinates.
+///
+/// Cell entities only exist in the chunks overlapping this region, and the
+/// HashLife backend only mirrors the cells inside it on the board.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimulationViewport {
+    /// Bottom-left corner (inclusive)
+    pub min: CellPosition,
+    /// Top-right corner (inclusive)
+    pub max: CellPosition,
+}
+
+impl Default for SimulationViewport {
+    fn default() -> Self {
+        Self {
+            min: CellPosition { x: -128, y: -128 },
+            max: CellPosition { x: 128, y: 128 },
+        }
+    }
+}
+
 /// Plugin for generation calculation systems
 pub struct GenerationPlugin;
 
@@ -27,99 +144,417 @@ impl Plugin for GenerationPlugin {
         let config = SimulationConfig::default();
         let period = config.period;
         app.insert_resource(GenerationTimer(Timer::new(period, TimerMode::Repeating)))
-            .add_systems(Update, simulation_config_listener)
-            .add_systems(Update, calculate_next_generation.in_set(CellSet));
+            .insert_resource(Time::<Fixed>::from_hz(config.tick_rate))
+            .init_resource::<RuleSet>()
+            .init_resource::<SimulationStats>()
+            .init_resource::<SimulationViewport>()
+            .init_resource::<GenerationTask>()
+            .add_systems(
+                Update,
+                (simulation_config_listener, rule_config_listener.before(CellSet)),
+            )
+            .add_systems(
+                FixedUpdate,
+                calculate_next_generation
+                    .in_set(CellSet)
+                    .run_if(backend_is(SimulationBackend::Sparse)),
+            )
+            // Warps are bounded by a per-frame time budget rather than by ticks
+            .add_systems(
+                Update,
+                warp_generations
+                    .in_set(CellSet)
+                    .run_if(backend_is(SimulationBackend::Sparse)),
+            );
     }
 }
 
-/// Listens for changes to simulation parameters and updates the timer accordingly.
+/// Run condition that is true while the given simulation backend is selected
+pub fn backend_is(backend: SimulationBackend) -> impl Fn(Res<SimulationConfig>) -> bool {
+    move |config: Res<SimulationConfig>| config.backend == backend
+}
+
+/// Listens for changes to simulation parameters and updates the timers accordingly.
 ///
 /// When the simulation speed (period) is changed, this system updates
-/// the generation timer to use the new duration.
+/// the generation timer to use the new duration, and a new tick rate
+/// updates the fixed timestep.
 pub fn simulation_config_listener(
     config: Res<SimulationConfig>,
     mut timer: ResMut<GenerationTimer>,
+    mut fixed_time: ResMut<Time<Fixed>>,
 ) {
     if config.is_changed() {
         if config.period != timer.0.duration() {
             timer.0.set_duration(config.period);
             timer.0.reset();
         }
+        if config.tick_rate > 0.0 && fixed_time.timestep().as_secs_f64() != 1.0 / config.tick_rate
+        {
+            fixed_time.set_timestep_hz(config.tick_rate);
+        }
+    }
+}
+
+/// Listens for changes to the configured rulestring and updates the active rule.
+///
+/// Invalid rulestrings are reported and the previous rule is kept.
+pub fn rule_config_listener(config: Res<SimulationConfig>, mut rule: ResMut<RuleSet>) {
+    if !config.is_changed() {
+        return;
+    }
+    match RuleSet::parse(&config.rule) {
+        Ok(parsed) => {
+            if *rule != parsed {
+                *rule = parsed;
+            }
+        }
+        Err(e) => warn!("Ignoring invalid rule '{}': {}", config.rule, e),
     }
 }
 
-/// Main system that implements Conway's Game of Life rules.
+/// Timer counting the generations that come due, with the time it ticks with
+#[derive(SystemParam)]
+pub struct GenerationClock<'w> {
+    timer: ResMut<'w, GenerationTimer>,
+    time: Res<'w, Time>,
+}
+
+impl GenerationClock<'_> {
+    /// Returns how many generations should be computed this tick.
+    ///
+    /// Ticks the generation timer while running, so that speeds above the
+    /// tick rate run several generations per tick (up to
+    /// [`MAX_GENERATIONS_PER_TICK`]), or consumes a pending single-step
+    /// request while paused. On the `FixedUpdate` schedule the time advances
+    /// by the fixed timestep, which makes the result independent of the
+    /// framerate.
+    pub fn due(&mut self, config: &mut SimulationConfig) -> u32 {
+        if config.running {
+            self.timer.0.tick(self.time.delta());
+            self.timer
+                .0
+                .times_finished_this_tick()
+                .min(MAX_GENERATIONS_PER_TICK)
+        } else if config.calculate_next_gen {
+            config.calculate_next_gen = false;
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Main system that applies the active rule to compute the next generations.
 ///
-/// Applies Conway's rules:
+/// With Conway's rule (B3/S23):
 ///  - Live cells with 2-3 neighbors survive
 ///  - Dead cells with exactly 3 neighbors become alive
 ///  - All other cells die or stay dead
+///
+/// Generations are computed on the async compute pool. Once a computation
+/// finishes its diff is applied, unless the board was edited in the meantime,
+/// and generations that came due while it ran start the next one. The board
+/// is left untouched when nothing changed, so still lifes do not trigger
+/// change detection.
+///
+/// On the web the board is stepped in the Web Worker of the [`worker`]
+/// module, which only receives the board again after an edit. A computation
+/// stops after [`WEB_STEP_BUDGET`] and leaves its remaining generations to
+/// the next one, so heavy patterns advance steadily. Without a worker, the
+/// compute pool runs on the main thread and the budget keeps the tab
+/// responsive.
+///
+/// [`worker`]: crate::worker
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_next_generation(
-    mut commands: Commands,
-    alive_query: Query<(Entity, &CellPosition), With<Alive>>,
-    mut dead_pool: ResMut<DeadCellPool>,
-    mut timer: ResMut<GenerationTimer>,
+    mut board: ResMut<BoardState>,
+    rule: Res<RuleSet>,
+    mut stats: ResMut<SimulationStats>,
+    mut history: ResMut<BoardHistory>,
+    mut clock: GenerationClock,
     mut config: ResMut<SimulationConfig>,
-    time: Res<Time>,
+    mut generation_task: ResMut<GenerationTask>,
+    mut rng: ResMut<SimRng>,
+    mut events: GenerationEvents,
 ) {
-    if config.running {
-        timer.0.tick(time.delta());
-        if !timer.0.just_finished() {
-            return;
-        }
-    } else if !config.calculate_next_gen {
+    if config.is_warping() {
         return;
-    } else {
-        config.calculate_next_gen = false;
+    }
+    let steps = clock.due(&mut config);
+    generation_task.pending = (generation_task.pending + steps).min(MAX_GENERATIONS_PER_TICK);
+    // Edits and new rules may wake up chunks the bitboard considers stable.
+    // The board is only mutated here when applying a diff, which change
+    // detection does not report to the system making the change.
+    if board.is_changed() {
+        generation_task.edited = generation_task.task.is_some();
+        generation_task.board = None;
+        generation_task.bits = None;
+        #[cfg(target_arch = "wasm32")]
+        {
+            generation_task.worker_synced = false;
+        }
+    } else if rule.is_changed() {
+        generation_task.bits = None;
     }
 
-    let cell_count = alive_query.iter().count();
+    if let Some(task) = generation_task.task.as_mut() {
+        let Some(diff) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        generation_task.task = None;
+        generation_task.pending =
+            (generation_task.pending + diff.left).min(MAX_GENERATIONS_PER_TICK);
 
-    // Pre-allocation for performance
-    let mut cells_to_kill = Vec::with_capacity(cell_count / 2);
-    // Create set of alive positions for quick lookup
-    let alive_positions: FxHashSet<CellPosition> =
-        alive_query.iter().map(|(_, pos)| *pos).collect();
+        let applying = Instant::now();
+        // Edits made while computing would be overwritten, so the result is
+        // dropped, and a failed worker computes no generation
+        let edited = std::mem::take(&mut generation_task.edited);
+        if !edited && diff.generations > 0 {
+            for snapshot in diff.snapshots {
+                history.push(snapshot, config.history_depth);
+            }
+            stats.generation += diff.generations;
+            stats.births = diff.births;
+            stats.deaths = diff.deaths;
+            stats.population = diff.population;
+            stats.active_chunks = diff.active_chunks;
+            // Chunks stable under a rule changed meanwhile may wake up under the new one
+            if diff.stepped_with == (rule.0, config.topology) {
+                generation_task.bits = diff.bits;
+            }
 
-    // Calculate neighbor counts for all relevant positions
-    let neighbor_counts = calculate_neighbor_counts(alive_positions.iter().copied());
+            if !diff.step.is_empty() {
+                board.0.apply(&diff.step);
+            }
+            generation_task.board = diff.board;
+            // Applying the diff on the main thread is part of the step too
+            let applied = applying.elapsed().div_f64(diff.generations as f64);
+            stats.step_time = diff.step_time + applied;
+            events.step(&diff.step, stats.generation);
+            events.advanced(&stats, diff.generations);
+            return;
+        }
+    }
 
-    // Determine which cells should die
-    for (entity, cell) in &alive_query {
-        let neighbor_count = neighbor_counts.get(cell).copied().unwrap_or(0);
-        if !should_cell_survive(neighbor_count) {
-            cells_to_kill.push(entity);
+    if generation_task.pending == 0 {
+        return;
+    }
+    let steps = std::mem::take(&mut generation_task.pending);
+    let seed = rng.next_u64();
+    #[cfg(target_arch = "wasm32")]
+    {
+        let request = crate::worker::WorkerRequest {
+            cells: (!generation_task.worker_synced).then(|| crate::worker::board_cells(&board.0)),
+            rule: rule.0.to_string(),
+            topology: config.topology,
+            noise: config.noise,
+            seed,
+            steps,
+            generation: stats.generation,
+            history_depth: config.history_depth,
+        };
+        if let Some(task) = crate::worker::spawn(request, rule.0) {
+            generation_task.worker_synced = true;
+            generation_task.task = Some(task);
+            return;
         }
     }
 
-    // Determine which cells should be born
-    let mut cells_to_spawn = Vec::new();
-    for (pos, count) in &neighbor_counts {
-        if should_cell_be_born(*count) && !alive_positions.contains(pos) {
-            cells_to_spawn.push(*pos);
+    let start = generation_task
+        .board
+        .take()
+        .unwrap_or_else(|| board.0.clone());
+    let bits = generation_task.bits.take();
+    let (rule, topology, noise) = (rule.0, config.topology, config.noise);
+    let (generation, history_depth) = (stats.generation, config.history_depth);
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        compute_generations(
+            start,
+            bits,
+            GenerationJob {
+                rule,
+                topology,
+                noise,
+                rng: SplitMix64::new(seed),
+                steps,
+                generation,
+                history_depth,
+                budget: cfg!(target_arch = "wasm32")
+                    .then(|| Duration::from_secs_f32(WEB_STEP_BUDGET)),
+            },
+        )
+    });
+    generation_task.task = Some(task);
+}
+
+/// Parameters of a run of generations, computed off the main thread by
+/// [`compute_generations`]
+pub(crate) struct GenerationJob {
+    /// Rule the board is stepped with
+    pub rule: Rule,
+    /// Topology of the grid
+    pub topology: GridTopology,
+    /// Chance of random births and survivals
+    pub noise: Noise,
+    /// Source of the random births and survivals
+    pub rng: SplitMix64,
+    /// Number of generations to compute
+    pub steps: u32,
+    /// Generation the board is at
+    pub generation: u64,
+    /// Number of snapshots to keep for the history
+    pub history_depth: usize,
+    /// Time after which the generations left are given back
+    pub budget: Option<Duration>,
+}
+
+/// Advances `board` by the `steps` generations of `job` on its topology,
+/// leaving births and survivals to chance according to its noise.
+///
+/// The net step is gathered from the cells each generation changed, so the
+/// starting board needs no copy. Records a snapshot before each of the last `history_depth` generations.
+/// Rules that allow it are stepped on `bits`, built from `start` when
+/// missing. Past the `budget`, the generations left are given back in the
+/// diff, which then snapshots every generation since it cannot tell which
+/// are the last ones, keeping the last `history_depth`.
+pub(crate) fn compute_generations(
+    mut board: Board,
+    bits: Option<BitBoard>,
+    job: GenerationJob,
+) -> GenerationDiff {
+    let GenerationJob {
+        rule,
+        topology,
+        noise,
+        mut rng,
+        steps,
+        generation,
+        history_depth,
+        budget,
+    } = job;
+    let started = Instant::now();
+    let mut bits = (BitBoard::supports(&rule, &topology) && !noise.is_active())
+        .then(|| bits.unwrap_or_else(|| BitBoard::from(&board)));
+    // State of each changed cell before the first generation
+    let mut initial: FxHashMap<Cell, u8> = FxHashMap::default();
+    let mut snapshots = Vec::new();
+    let (mut births, mut deaths) = (0, 0);
+    let mut generations = 0;
+    for offset in 0..steps as u64 {
+        if history_depth > 0
+            && (budget.is_some() || (steps as u64 - offset) as usize <= history_depth)
+        {
+            if snapshots.len() == history_depth {
+                snapshots.remove(0);
+            }
+            snapshots.push(BoardSnapshot {
+                generation: generation + offset,
+                board: board.clone(),
+            });
+        }
+        let step = match bits.as_mut() {
+            Some(bits) => bits.advance(&rule),
+            None => {
+                let step = board.compute_step_in(&rule, &topology);
+                noise.perturb(&board, step, rng.next_u64())
+            }
+        };
+        let changed = step.born.iter().chain(&step.died);
+        for &cell in changed.chain(step.states.iter().map(|(cell, _)| cell)) {
+            initial.entry(cell).or_insert_with(|| board.state(&cell));
+        }
+        board.apply(&step);
+        births = step.born.len();
+        deaths = step.died.len();
+        generations += 1;
+        if budget.is_some_and(|budget| started.elapsed() >= budget) {
+            break;
         }
     }
 
-    // Kill cells
-    for entity in cells_to_kill {
-        commands
-            .entity(entity)
-            .remove::<Alive>()
-            .insert(Visibility::Hidden);
-        dead_pool.entities.push(entity);
-    }
-
-    // Spawn new cells
-    for new_pos in cells_to_spawn {
-        if let Some(entity) = dead_pool.entities.pop() {
-            commands
-                .entity(entity)
-                .insert(Alive)
-                .insert(Visibility::Visible)
-                .insert(Transform::from_xyz(new_pos.x as f32, new_pos.y as f32, 0.0))
-                .insert(new_pos);
-        } else {
-            commands.spawn((new_pos, Alive, Visibility::Visible));
+    let mut step = Step::default();
+    for (cell, before) in initial {
+        let after = board.state(&cell);
+        if before == 0 && after != 0 {
+            step.born.push(cell);
+        } else if before != 0 && after == 0 {
+            step.died.push(cell);
         }
+        // Newborns default to state 1, so only other states are recorded
+        if after != 0 && after != before.max(1) {
+            step.states.push((cell, after));
+        }
+    }
+    GenerationDiff {
+        population: board.len(),
+        board: Some(board),
+        snapshots,
+        step,
+        generations: u64::from(generations),
+        left: steps - generations,
+        births,
+        deaths,
+        step_time: started.elapsed() / generations,
+        active_chunks: bits.as_ref().map(BitBoard::active_chunks),
+        bits,
+        stepped_with: (rule, topology),
+    }
+}
+
+/// Computes as many warp generations as fit in the frame budget.
+///
+/// The board is stepped in place and entities are synced once per frame.
+pub fn warp_generations(
+    mut board: ResMut<BoardState>,
+    rule: Res<RuleSet>,
+    mut stats: ResMut<SimulationStats>,
+    mut history: ResMut<BoardHistory>,
+    mut config: ResMut<SimulationConfig>,
+    mut rng: ResMut<SimRng>,
+    mut events: GenerationEvents,
+) {
+    if !config.is_warping() {
+        return;
+    }
+
+    history.push(
+        BoardSnapshot {
+            generation: stats.generation,
+            board: board.0.clone(),
+        },
+        config.history_depth,
+    );
+
+    let budget = Duration::from_secs_f32(WARP_FRAME_BUDGET);
+    let start = Instant::now();
+    let mut generations = 0;
+    let board = &mut board.0;
+    let mut bits = (BitBoard::supports(&rule, &config.topology) && !config.noise.is_active())
+        .then(|| BitBoard::from(&*board));
+    while config.warp_remaining > 0 && start.elapsed() < budget {
+        let step = match bits.as_mut() {
+            Some(bits) => bits.advance(&rule),
+            None => {
+                let step = board.compute_step_in(&rule, &config.topology);
+                config.noise.perturb(board, step, rng.next_u64())
+            }
+        };
+        board.apply(&step);
+        stats.generation += 1;
+        stats.births = step.born.len();
+        stats.deaths = step.died.len();
+        config.warp_remaining -= 1;
+        generations += 1;
+    }
+    stats.population = board.len();
+    stats.active_chunks = bits.as_ref().map(BitBoard::active_chunks);
+    if generations > 0 {
+        stats.step_time = start.elapsed() / generations;
+    }
+    events.advanced(&stats, u64::from(generations));
+
+    if !config.is_warping() {
+        config.cancel_warp();
     }
 }
@@ -0,0 +1,263 @@
+//! # HashLife Module
+//!
+//! Alternative simulation backend built on the HashLife [`Universe`] of `gol_core`.
+//!
+//! While this backend is active, the [`HashLifeUniverse`] is the source of
+//! truth and the [`BoardState`] only mirrors the cells inside the [`SimulationViewport`].
+//! Edits replacing the whole board go through [`HashLifeUniverse::replace`],
+//! and features reading more than the view through [`HashLifeUniverse::cells_in`].
+
+use bevy::platform::time::Instant;
+use bevy::prelude::{
+    App, DetectChanges, FixedUpdate, IntoScheduleConfigs, Plugin, Res, ResMut, Resource, warn,
+};
+use gol_core::{Board, Cell, Rule, Universe};
+use std::time::Duration;
+
+use crate::cell::{BoardState, CellPosition, CellSet};
+use crate::events::GenerationEvents;
+use crate::generation::{GenerationClock, SimulationStats, SimulationViewport, backend_is};
+use crate::rules::RuleSet;
+use gol_config::{SimulationBackend, SimulationConfig, WARP_FRAME_BUDGET};
+
+/// Resource holding the HashLife board and the part of it mirrored on the [`BoardState`]
+#[derive(Resource)]
+pub struct HashLifeUniverse {
+    pub universe: Universe,
+    /// Cells last mirrored on the board
+    mirrored: Board,
+    /// Whether the universe currently owns the board
+    active: bool,
+}
+
+impl Default for HashLifeUniverse {
+    fn default() -> Self {
+        Self {
+            universe: Universe::new(RuleSet::default().0),
+            mirrored: Board::new(),
+            active: false,
+        }
+    }
+}
+
+impl HashLifeUniverse {
+    /// Whether the universe currently owns the board
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Replaces every cell of the universe with the cells of a board, for
+    /// the edits replacing the whole board rather than its visible part.
+    /// Does nothing while another backend owns the board.
+    pub fn replace(&mut self, board: &Board) {
+        if !self.active {
+            return;
+        }
+        self.universe.clear();
+        for cell in board.iter() {
+            self.universe.set_cell(cell.x as i64, cell.y as i64, true);
+        }
+        self.mirrored = board.clone();
+    }
+
+    /// Living cells inside the given inclusive bounds, or every living cell
+    /// without bounds. While the universe owns the board, they are read from
+    /// it with the edits of the board applied first.
+    pub fn cells_in(
+        &mut self,
+        board: &Board,
+        bounds: Option<(CellPosition, CellPosition)>,
+    ) -> Board {
+        if !self.active {
+            let inside = |cell: &Cell| {
+                bounds.is_none_or(|(min, max)| {
+                    (min.x..=max.x).contains(&cell.x) && (min.y..=max.y).contains(&cell.y)
+                })
+            };
+            let mut cells = Board::new();
+            for cell in board.iter().filter(|cell| inside(cell)) {
+                cells.set_state(*cell, board.state(cell));
+            }
+            return cells;
+        }
+        self.reconcile(board);
+        let bounds =
+            bounds.map(|(min, max)| ((min.x as i64, min.y as i64), (max.x as i64, max.y as i64)));
+        self.universe
+            .cells(bounds)
+            .into_iter()
+            .map(|(x, y)| Cell::new(x as isize, y as isize))
+            .collect()
+    }
+
+    /// Applies cells painted or erased on the board since the last sync
+    fn reconcile(&mut self, board: &Board) {
+        for cell in board.iter().filter(|cell| !self.mirrored.contains(cell)) {
+            self.universe.set_cell(cell.x as i64, cell.y as i64, true);
+        }
+        for cell in self.mirrored.iter().filter(|cell| !board.contains(cell)) {
+            self.universe.set_cell(cell.x as i64, cell.y as i64, false);
+        }
+        self.mirrored = board.clone();
+    }
+
+    /// Makes the board match the universe inside the given bounds
+    fn sync_board(
+        &mut self,
+        board: &mut ResMut<BoardState>,
+        bounds: Option<((i64, i64), (i64, i64))>,
+    ) {
+        let visible: Board = self
+            .universe
+            .cells(bounds)
+            .into_iter()
+            .map(|(x, y)| Cell::new(x as isize, y as isize))
+            .collect();
+        // Only a changed board has its entities synced again
+        if board.0 != visible {
+            board.0 = visible.clone();
+        }
+        self.mirrored = visible;
+    }
+}
+
+/// Plugin for the HashLife simulation backend
+pub struct HashLifePlugin;
+
+impl Plugin for HashLifePlugin {
+    fn build(&self, app: &mut App) {
+        // Runs on the fixed timestep like the sparse backend
+        app.init_resource::<HashLifeUniverse>().add_systems(
+            FixedUpdate,
+            (
+                hashlife_backend_listener,
+                calculate_next_generation_hashlife.run_if(backend_is(SimulationBackend::HashLife)),
+            )
+                .chain()
+                .in_set(CellSet),
+        );
+    }
+}
+
+/// Whether the HashLife backend can run `rule` with the given configuration.
+///
+/// HashLife ignores the topology and the noise, so only deterministic rules
+/// on the infinite grid that [`Universe::supports`] are accepted.
+pub fn hashlife_supports(rule: &Rule, config: &SimulationConfig) -> bool {
+    Universe::supports(rule, &config.topology) && !config.noise.is_active()
+}
+
+/// Moves the board between the [`BoardState`] and the HashLife universe when the backend changes.
+///
+/// However the backend was selected, HashLife falls back to the sparse
+/// backend when it cannot run the rule, topology or noise of the configuration.
+pub fn hashlife_backend_listener(
+    mut config: ResMut<SimulationConfig>,
+    rule: Res<RuleSet>,
+    mut board: ResMut<BoardState>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+) {
+    if !config.is_changed() && !rule.is_changed() {
+        return;
+    }
+    if config.backend == SimulationBackend::HashLife && !hashlife_supports(&rule.0, &config) {
+        warn!(
+            "HashLife cannot run {} with this topology and noise, using the sparse backend",
+            rule.0
+        );
+        config.backend = SimulationBackend::Sparse;
+    }
+    let enabled = config.backend == SimulationBackend::HashLife;
+
+    if enabled && !hashlife.active {
+        let cells = board.0.iter().map(|cell| (cell.x as i64, cell.y as i64));
+        hashlife.universe = Universe::from_cells(rule.0, cells);
+        hashlife.mirrored = board.0.clone();
+        hashlife.active = true;
+    } else if !enabled && hashlife.active {
+        // Materialize the whole board again for the other backends
+        hashlife.reconcile(&board.0);
+        hashlife.sync_board(&mut board, None);
+        hashlife.mirrored.clear();
+        hashlife.active = false;
+    }
+
+    // Warps pick their own step sizes and restore the configured one when done
+    if !config.is_warping() {
+        hashlife.universe.set_step_log2(config.hashlife_step_log2);
+    }
+}
+
+/// Advances the HashLife universe and mirrors the visible cells on the board
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_next_generation_hashlife(
+    mut board: ResMut<BoardState>,
+    rule: Res<RuleSet>,
+    viewport: Res<SimulationViewport>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+    mut stats: ResMut<SimulationStats>,
+    mut clock: GenerationClock,
+    mut config: ResMut<SimulationConfig>,
+    mut events: GenerationEvents,
+) {
+    let warping = config.is_warping();
+    let steps = if warping { 0 } else { clock.due(&mut config) };
+    let due = steps > 0;
+    if !warping && !due && !viewport.is_changed() {
+        return;
+    }
+
+    hashlife.reconcile(&board.0);
+    hashlife.universe.set_rule(rule.0);
+    let before = hashlife.universe.generation();
+    let started = Instant::now();
+    if warping {
+        warp_universe(&mut hashlife.universe, &mut config);
+    } else {
+        for _ in 0..steps {
+            hashlife.universe.step();
+        }
+    }
+
+    if warping || due {
+        // Individual births and deaths are not tracked across a HashLife step
+        let generations = hashlife.universe.generation() - before;
+        stats.generation += generations;
+        stats.births = 0;
+        stats.deaths = 0;
+        stats.population = hashlife.universe.population() as usize;
+        stats.active_chunks = None;
+        if generations > 0 {
+            stats.step_time = started.elapsed().div_f64(generations as f64);
+        }
+        events.advanced(&stats, generations);
+    }
+
+    let bounds = (
+        (viewport.min.x as i64, viewport.min.y as i64),
+        (viewport.max.x as i64, viewport.max.y as i64),
+    );
+    hashlife.sync_board(&mut board, Some(bounds));
+}
+
+/// Advances the universe towards the end of the current warp within the frame budget
+fn warp_universe(universe: &mut Universe, config: &mut SimulationConfig) {
+    let budget = Duration::from_secs_f32(WARP_FRAME_BUDGET);
+    let start = Instant::now();
+    while config.warp_remaining > 0 && start.elapsed() < budget {
+        // Never overshoot the requested number of generations
+        let step_log2 = config
+            .hashlife_step_log2
+            .min(config.warp_remaining.ilog2() as u8);
+        universe.set_step_log2(step_log2);
+
+        let before = universe.generation();
+        universe.step();
+        config.warp_remaining -= universe.generation() - before;
+    }
+
+    if !config.is_warping() {
+        config.cancel_warp();
+        universe.set_step_log2(config.hashlife_step_log2);
+    }
+}
@@ -0,0 +1,269 @@
+//! # History Module
+//!
+//! Keeps recent board states in a ring buffer so the simulation can be
+//! rewound generation by generation while paused.
+//!
+//! Older generations are kept as sparse keyframes, every
+//! [`KEYFRAME_INTERVAL`] generations, from which the timeline replays the
+//! generations that are no longer cached. The replay ignores the noise of
+//! noisy rules, so only deterministic runs are shown again exactly, and only
+//! happens under the sparse backend, whose rule it applies. The history is
+//! forgotten when the backend changes.
+
+use bevy::prelude::{
+    App, DetectChanges, IntoScheduleConfigs, Local, Plugin, Res, ResMut, Resource, Time, Update,
+};
+use gol_core::{Board, GridTopology, Rule};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::cell::{BoardState, CellSet};
+use crate::generation::SimulationStats;
+use crate::rules::RuleSet;
+use gol_config::{KEYFRAME_INTERVAL, MAX_KEYFRAMES, SimulationBackend, SimulationConfig};
+
+/// Board state captured before a generation was computed
+#[derive(Debug, Clone)]
+pub struct BoardSnapshot {
+    /// Generation number of the captured board
+    pub generation: u64,
+    /// Living cells at that generation
+    pub board: Board,
+}
+
+/// Ring buffer of the most recent board snapshots, and sparse keyframes of
+/// the older ones
+#[derive(Resource, Default)]
+pub struct BoardHistory {
+    snapshots: VecDeque<BoardSnapshot>,
+    keyframes: VecDeque<BoardSnapshot>,
+}
+
+impl BoardHistory {
+    /// Records a snapshot, dropping the oldest ones beyond `depth`.
+    ///
+    /// The snapshots of its generation and later ones are dropped first, as
+    /// they belong to the timeline left when going back in time.
+    pub fn push(&mut self, snapshot: BoardSnapshot, depth: usize) {
+        if depth == 0 {
+            self.clear();
+            return;
+        }
+        let generation = snapshot.generation;
+        self.snapshots.retain(|s| s.generation < generation);
+        self.keyframes.retain(|s| s.generation < generation);
+
+        let due = self
+            .keyframes
+            .back()
+            .is_none_or(|last| generation >= last.generation + KEYFRAME_INTERVAL);
+        if due {
+            if self.keyframes.len() >= MAX_KEYFRAMES {
+                self.keyframes.pop_front();
+            }
+            self.keyframes.push_back(snapshot.clone());
+        }
+        while self.snapshots.len() >= depth {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Snapshot of a generation, if it is still cached
+    pub fn get(&self, generation: u64) -> Option<&BoardSnapshot> {
+        self.snapshots.iter().find(|s| s.generation == generation)
+    }
+
+    /// Closest snapshot or keyframe at or before a generation
+    pub fn nearest(&self, generation: u64) -> Option<&BoardSnapshot> {
+        self.snapshots
+            .iter()
+            .chain(&self.keyframes)
+            .filter(|s| s.generation <= generation)
+            .max_by_key(|s| s.generation)
+    }
+
+    /// Snapshot a generation can be restored from: the closest earlier one
+    /// under the sparse backend, the generation itself under the others,
+    /// which cannot be replayed with the Life rule
+    pub fn restore_point(
+        &self,
+        generation: u64,
+        backend: SimulationBackend,
+    ) -> Option<&BoardSnapshot> {
+        if backend == SimulationBackend::Sparse {
+            self.nearest(generation)
+        } else {
+            self.get(generation)
+        }
+    }
+
+    /// Board of a generation, replayed with `rule` from its
+    /// [`restore_point`](Self::restore_point) when it is not cached
+    pub fn board_at(
+        &self,
+        generation: u64,
+        backend: SimulationBackend,
+        rule: &Rule,
+        topology: &GridTopology,
+    ) -> Option<Board> {
+        let start = self.restore_point(generation, backend)?;
+        let mut board = start.board.clone();
+        for _ in start.generation..generation {
+            let step = board.compute_step_in(rule, topology);
+            board.apply(&step);
+        }
+        Some(board)
+    }
+
+    /// Oldest and most recent generations recorded
+    pub fn range(&self) -> Option<(u64, u64)> {
+        let oldest = self
+            .keyframes
+            .front()
+            .into_iter()
+            .chain(self.snapshots.front())
+            .map(|s| s.generation)
+            .min()?;
+        let latest = self.snapshots.back()?.generation;
+        Some((oldest, latest))
+    }
+
+    /// Removes and returns the most recent snapshot
+    pub fn pop(&mut self) -> Option<BoardSnapshot> {
+        self.snapshots.pop_back()
+    }
+
+    /// Most recent snapshot, kept in the history
+    pub fn latest(&self) -> Option<&BoardSnapshot> {
+        self.snapshots.back()
+    }
+
+    /// Number of generations that can be rewound
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Whether no generation can be rewound
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Forgets every snapshot
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.keyframes.clear();
+    }
+}
+
+/// Plugin for the generation history
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BoardHistory>().add_systems(
+            Update,
+            (
+                clear_history_on_backend_change,
+                reverse_playback_system,
+                step_back_system,
+            )
+                .chain()
+                .in_set(CellSet),
+        );
+    }
+}
+
+/// Forgets the history when the backend changes, as its snapshots were
+/// recorded under other rules
+pub fn clear_history_on_backend_change(
+    config: Res<SimulationConfig>,
+    mut history: ResMut<BoardHistory>,
+    mut backend: Local<SimulationBackend>,
+) {
+    if config.is_changed() && config.backend != *backend {
+        *backend = config.backend;
+        history.clear();
+    }
+}
+
+/// Steps back at the running speed while playing backwards, until the
+/// start of the history or until the simulation runs again
+pub fn reverse_playback_system(
+    mut config: ResMut<SimulationConfig>,
+    history: Res<BoardHistory>,
+    stats: Res<SimulationStats>,
+    time: Res<Time>,
+    mut elapsed: Local<Duration>,
+) {
+    if !config.playing_backwards {
+        *elapsed = Duration::ZERO;
+        return;
+    }
+    let exhausted = stats
+        .generation
+        .checked_sub(1)
+        .and_then(|previous| history.restore_point(previous, config.backend))
+        .is_none();
+    if config.running || exhausted {
+        config.playing_backwards = false;
+        return;
+    }
+    *elapsed += time.delta();
+    if *elapsed >= config.period {
+        // A single step per frame, dropping the rest of a slow frame
+        *elapsed = (*elapsed - config.period).min(config.period);
+        config.step_back = true;
+    }
+}
+
+/// Restores the previous generation when a step back is requested while
+/// paused, or the generation picked on the timeline, pausing the simulation.
+///
+/// The current board is recorded first when it is the most recent one, so
+/// the timeline can come back to it.
+pub fn step_back_system(
+    mut board_state: ResMut<BoardState>,
+    rule: Res<RuleSet>,
+    mut config: ResMut<SimulationConfig>,
+    mut history: ResMut<BoardHistory>,
+    mut stats: ResMut<SimulationStats>,
+) {
+    // Reading first, as taking the flags would mark the config as changed
+    if !config.step_back && config.scrub_to.is_none() {
+        return;
+    }
+    let target = if std::mem::take(&mut config.step_back) {
+        if config.running {
+            return;
+        }
+        stats.generation.checked_sub(1)
+    } else {
+        config.scrub_to.take()
+    };
+    let Some(target) = target.filter(|&target| target != stats.generation) else {
+        return;
+    };
+    let Some(board) = history.board_at(target, config.backend, &rule.0, &config.topology) else {
+        return;
+    };
+    config.running = false;
+
+    let newest = history
+        .latest()
+        .is_none_or(|latest| latest.generation < stats.generation);
+    stats.births = 0;
+    stats.deaths = 0;
+    stats.population = board.len();
+    let current = std::mem::replace(&mut board_state.0, board);
+    if newest {
+        history.push(
+            BoardSnapshot {
+                generation: stats.generation,
+                board: current,
+            },
+            config.history_depth,
+        );
+    }
+    stats.generation = target;
+}
@@ -4,13 +4,42 @@
 //! It handles cell states, generation calculations, and simulation timing.
 
 pub mod cell;
+pub mod elementary;
+pub mod events;
+pub mod explain;
 pub mod generation;
+pub mod hashlife;
+pub mod history;
+pub mod margolus;
 pub mod pattern;
+pub mod population;
+pub mod queue;
+pub mod random;
 pub mod rules;
+pub mod slots;
+pub mod soup;
+pub mod stability;
+pub mod worker;
 
 pub use cell::*;
+pub use elementary::*;
+pub use events::*;
+pub use explain::*;
 pub use generation::*;
+pub use hashlife::*;
+pub use history::*;
+pub use margolus::*;
+pub use pattern::{
+    PatternCategory, PatternEntry, PatternPlugin, PatternRegistry, THUMBNAIL_SIZE, Thumbnail,
+};
+pub use population::*;
+pub use queue::*;
+pub use random::*;
 pub use rules::*;
+pub use slots::*;
+pub use soup::*;
+pub use stability::*;
+pub use worker::*;
 
 use bevy::prelude::{Plugin, App};
 
@@ -22,6 +51,20 @@ pub struct SimulationPlugin;
 
 impl Plugin for SimulationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(CellPlugin).add_plugins(GenerationPlugin);
+        app.add_plugins(CellPlugin)
+            .add_plugins(ElementaryPlugin)
+            .add_plugins(EventsPlugin)
+            .add_plugins(ExplainPlugin)
+            .add_plugins(GenerationPlugin)
+            .add_plugins(HashLifePlugin)
+            .add_plugins(HistoryPlugin)
+            .add_plugins(MargolusPlugin)
+            .add_plugins(PatternPlugin)
+            .add_plugins(PopulationPlugin)
+            .add_plugins(RandomPlugin)
+            .add_plugins(SlotsPlugin)
+            .add_plugins(SoupPlugin)
+            .add_plugins(StabilityPlugin)
+            .add_plugins(ActionQueuePlugin);
     }
 }
@@ -0,0 +1,84 @@
+//! # Margolus Module
+//!
+//! Backend running block automata such as Critters. Each generation replaces
+//! the 2×2 blocks of a partition that alternates with the generation parity,
+//! so the stepping path is separate from the neighbor counting one.
+
+use bevy::platform::time::Instant;
+use bevy::prelude::{App, FixedUpdate, IntoScheduleConfigs, Plugin, ResMut};
+
+use crate::cell::{BoardState, CellSet};
+use crate::events::GenerationEvents;
+use crate::generation::{GenerationClock, SimulationStats, backend_is};
+use crate::history::{BoardHistory, BoardSnapshot};
+use gol_config::{MAX_GENERATIONS_PER_TICK, SimulationBackend, SimulationConfig};
+
+/// Plugin for the Margolus block automaton backend
+pub struct MargolusPlugin;
+
+impl Plugin for MargolusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            calculate_next_blocks
+                .in_set(CellSet)
+                .run_if(backend_is(SimulationBackend::Margolus)),
+        );
+    }
+}
+
+/// Whether the given generation uses the odd partition, whose blocks are
+/// shifted by one cell diagonally
+pub fn margolus_parity_is_odd(generation: u64) -> bool {
+    generation % 2 == 1
+}
+
+/// Computes the generations that came due with the configured block rule.
+///
+/// The partition follows the generation counter, so stepping back through
+/// the history keeps it in phase. Warps compute at most
+/// [`MAX_GENERATIONS_PER_TICK`] generations per tick.
+pub fn calculate_next_blocks(
+    mut board: ResMut<BoardState>,
+    mut stats: ResMut<SimulationStats>,
+    mut history: ResMut<BoardHistory>,
+    mut clock: GenerationClock,
+    mut config: ResMut<SimulationConfig>,
+    mut events: GenerationEvents,
+) {
+    let steps = if config.is_warping() {
+        let steps = config.warp_remaining.min(MAX_GENERATIONS_PER_TICK as u64);
+        config.warp_remaining -= steps;
+        if !config.is_warping() {
+            config.cancel_warp();
+        }
+        steps as u32
+    } else {
+        clock.due(&mut config)
+    };
+    if steps == 0 {
+        return;
+    }
+
+    let started = Instant::now();
+    let rule = config.margolus_rule;
+    let board = &mut board.0;
+    for _ in 0..steps {
+        history.push(
+            BoardSnapshot {
+                generation: stats.generation,
+                board: board.clone(),
+            },
+            config.history_depth,
+        );
+        let step = rule.advance(board, margolus_parity_is_odd(stats.generation));
+        stats.generation += 1;
+        stats.births = step.born.len();
+        stats.deaths = step.died.len();
+        events.step(&step, stats.generation);
+    }
+    stats.population = board.len();
+    stats.step_time = started.elapsed() / steps;
+    stats.active_chunks = None;
+    events.advanced(&stats, u64::from(steps));
+}
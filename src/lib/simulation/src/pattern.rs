@@ -1,4 +1,12 @@
+//! # Pattern Module
+//!
+//! Embedded pattern assets and the runtime [`PatternRegistry`] the UI browses.
+
+use bevy::prelude::{App, Plugin, Resource};
+use gol_core::pattern::{ParsedPattern, PatternError, parse_pattern};
+use gol_core::rle::{RleError, parse_rle, parse_rle_pattern};
 use gol_macros::generate_pattern_functions;
+use std::fmt;
 use std::sync::OnceLock;
 
 macro_rules! pattern {
@@ -17,50 +25,267 @@ macro_rules! pattern {
     }};
 }
 
-fn parse_rle(rle: &str) -> Vec<(i32, i32)> {
-    let mut cells = Vec::new();
-    let mut x = 0;
-    let mut y = 0;
-    let mut num = 0;
-
-    for byte in rle.bytes() {
-        match byte {
-            // Number of iteration
-            b'0'..=b'9' => num = num * 10 + (byte - b'0') as i32,
-            b'b' | b'.' => {
-                // Cell is dead
-                x += num.max(1);
-                num = 0;
-            }
-            b'o' => {
-                // Cell living
-                let count = num.max(1);
-                for i in 0..count {
-                    cells.push((x + i, y));
-                }
-                x += count;
-                num = 0;
-            }
-            b'$' => {
-                // EOL
-                y += num.max(1);
-                x = 0;
-                num = 0;
-            }
-            b'!' => break, // EOF
-            _ => {}
-        }
-    }
-    cells
-}
-
 pub struct Patterns;
 
 impl Patterns {
     generate_pattern_functions!("assets");
 
     /// Parse RLE from string content (for dynamic loading)
-    pub fn from_rle_string(rle_content: &str) -> Vec<(i32, i32)> {
-        parse_rle(rle_content)
+    pub fn from_rle_string(rle_content: &str) -> Result<ParsedPattern, RleError> {
+        parse_rle_pattern(rle_content)
+    }
+
+    /// Parse pattern content in any supported format (RLE, Life 1.05/1.06, plaintext, macrocell)
+    pub fn from_string(content: &str) -> Result<ParsedPattern, PatternError> {
+        parse_pattern(content)
+    }
+}
+
+/// Family a pattern belongs to in the pattern browser
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PatternCategory {
+    StillLife,
+    Oscillator,
+    Spaceship,
+    Gun,
+    #[default]
+    Other,
+    /// Patterns loaded by the user at runtime
+    Loaded,
+}
+
+impl PatternCategory {
+    /// All categories, in display order
+    pub const ALL: [Self; 6] = [
+        Self::StillLife,
+        Self::Oscillator,
+        Self::Spaceship,
+        Self::Gun,
+        Self::Other,
+        Self::Loaded,
+    ];
+
+    /// Parses a category name such as `still life` or `Oscillators`
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase().replace(['-', '_', ' '], "");
+        match name.trim_end_matches('s') {
+            "stilllife" | "stilllive" => Some(Self::StillLife),
+            "oscillator" => Some(Self::Oscillator),
+            "spaceship" => Some(Self::Spaceship),
+            "gun" => Some(Self::Gun),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PatternCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::StillLife => "Still lifes",
+            Self::Oscillator => "Oscillators",
+            Self::Spaceship => "Spaceships",
+            Self::Gun => "Guns",
+            Self::Other => "Other",
+            Self::Loaded => "Loaded",
+        })
+    }
+}
+
+/// Maximum width and height of a pattern thumbnail, in thumbnail pixels
+pub const THUMBNAIL_SIZE: usize = 16;
+
+/// Downsampled preview of a pattern, a pixel being alive when any cell it covers is
+#[derive(Debug, Clone, Default)]
+pub struct Thumbnail {
+    pub width: usize,
+    pub height: usize,
+    /// Row-major pixels, the first row being the top of the pattern as placed on the grid
+    pub pixels: Vec<bool>,
+}
+
+impl Thumbnail {
+    /// Builds the thumbnail of a set of cells
+    pub fn new(cells: &[(i32, i32)]) -> Self {
+        let Some(&(first_x, first_y)) = cells.first() else {
+            return Self::default();
+        };
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (first_x, first_y, first_x, first_y);
+        for &(x, y) in cells {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        let span = (max_x - min_x).max(max_y - min_y) as usize + 1;
+        let scale = span.div_ceil(THUMBNAIL_SIZE);
+        let width = (max_x - min_x) as usize / scale + 1;
+        let height = (max_y - min_y) as usize / scale + 1;
+        let mut pixels = vec![false; width * height];
+        for &(x, y) in cells {
+            let column = (x - min_x) as usize / scale;
+            // Placed patterns grow upwards, so the largest y is the top row
+            let row = (max_y - y) as usize / scale;
+            pixels[row * width + column] = true;
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Whether the pixel at the given column and row is alive
+    pub fn get(&self, column: usize, row: usize) -> bool {
+        self.pixels[row * self.width + column]
+    }
+}
+
+/// Named pattern available for placement
+#[derive(Debug, Clone)]
+pub struct PatternEntry {
+    pub name: String,
+    pub category: PatternCategory,
+    /// Cells relative to the placement point
+    pub cells: Vec<(i32, i32)>,
+    /// Preview shown in the pattern browser
+    pub thumbnail: Thumbnail,
+}
+
+/// Built-in pattern: name, category and cells
+type Builtin = (&'static str, PatternCategory, &'static [(i32, i32)]);
+
+/// Resource listing every pattern that can be placed on the grid
+#[derive(Resource)]
+pub struct PatternRegistry {
+    entries: Vec<PatternEntry>,
+}
+
+impl Default for PatternRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            entries: Vec::new(),
+        };
+        let builtins: [Builtin; 12] = [
+            (
+                "block",
+                PatternCategory::StillLife,
+                pattern!(inline "2o$2o!"),
+            ),
+            (
+                "beehive",
+                PatternCategory::StillLife,
+                pattern!(inline "b2o$o2bo$b2o!"),
+            ),
+            (
+                "loaf",
+                PatternCategory::StillLife,
+                pattern!(inline "b2o$o2bo$bobo$2bo!"),
+            ),
+            (
+                "boat",
+                PatternCategory::StillLife,
+                pattern!(inline "2o$obo$bo!"),
+            ),
+            (
+                "blinker",
+                PatternCategory::Oscillator,
+                pattern!(inline "3o!"),
+            ),
+            (
+                "toad",
+                PatternCategory::Oscillator,
+                pattern!(inline "b3o$3o!"),
+            ),
+            (
+                "beacon",
+                PatternCategory::Oscillator,
+                pattern!(inline "2o$2o$2b2o$2b2o!"),
+            ),
+            (
+                "pentadecathlon",
+                PatternCategory::Oscillator,
+                pattern!(inline "2bo4bo$2ob4ob2o$2bo4bo!"),
+            ),
+            (
+                "glider",
+                PatternCategory::Spaceship,
+                pattern!(inline "bo$2bo$3o!"),
+            ),
+            (
+                "lwss",
+                PatternCategory::Spaceship,
+                pattern!(inline "bo2bo$o4b$o3bo$4o!"),
+            ),
+            (
+                "r-pentomino",
+                PatternCategory::Other,
+                pattern!(inline "b2o$2o$bo!"),
+            ),
+            (
+                "gosper-glider-gun",
+                PatternCategory::Gun,
+                pattern!(inline "24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$10bo5bo7bo$11bo3bo$12b2o!"),
+            ),
+        ];
+        for (name, category, cells) in builtins {
+            registry.register(name, category, cells.to_vec());
+        }
+        for &(name, cells) in Patterns::all_patterns() {
+            let category = PatternCategory::from_name(Patterns::pattern_category(name));
+            registry.register(name, category.unwrap_or_default(), cells().to_vec());
+        }
+        registry
+    }
+}
+
+impl PatternRegistry {
+    /// Adds a pattern, replacing any previous pattern with the same name
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        category: PatternCategory,
+        cells: Vec<(i32, i32)>,
+    ) {
+        let name = name.into();
+        self.entries.retain(|entry| entry.name != name);
+        self.entries.push(PatternEntry {
+            name,
+            category,
+            thumbnail: Thumbnail::new(&cells),
+            cells,
+        });
+    }
+
+    /// Looks a pattern up by name
+    pub fn get(&self, name: &str) -> Option<&PatternEntry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    /// Iterates over every registered pattern in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &PatternEntry> {
+        self.entries.iter()
+    }
+
+    /// Iterates over the patterns of a category whose name contains `filter`, ignoring case
+    pub fn search<'a>(
+        &'a self,
+        category: PatternCategory,
+        filter: &'a str,
+    ) -> impl Iterator<Item = &'a PatternEntry> {
+        let filter = filter.trim().to_lowercase();
+        self.entries.iter().filter(move |entry| {
+            entry.category == category && entry.name.to_lowercase().contains(&filter)
+        })
+    }
+}
+
+/// Plugin registering the built-in patterns
+pub struct PatternPlugin;
+
+impl Plugin for PatternPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PatternRegistry>();
     }
 }
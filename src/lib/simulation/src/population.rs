@@ -0,0 +1,98 @@
+//! # Population Module
+//!
+//! Recent history of the population, births and deaths, one sample per
+//! computed generation, for the statistics graph.
+
+use bevy::prelude::{
+    App, DetectChanges, IntoScheduleConfigs, Plugin, Res, ResMut, Resource, Update,
+};
+use std::collections::VecDeque;
+
+use crate::cell::CellSet;
+use crate::generation::SimulationStats;
+use gol_config::POPULATION_HISTORY_LENGTH;
+
+/// Statistics recorded after a generation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PopulationSample {
+    pub generation: u64,
+    pub population: usize,
+    pub births: usize,
+    pub deaths: usize,
+}
+
+/// Last generations recorded, oldest first
+#[derive(Resource, Debug)]
+pub struct PopulationHistory {
+    samples: VecDeque<PopulationSample>,
+    /// Number of generations kept
+    pub capacity: usize,
+    /// Whether recording is suspended, freezing the graph
+    pub paused: bool,
+}
+
+impl Default for PopulationHistory {
+    fn default() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            capacity: POPULATION_HISTORY_LENGTH,
+            paused: false,
+        }
+    }
+}
+
+impl PopulationHistory {
+    /// Recorded samples, oldest first
+    pub fn samples(&self) -> impl Iterator<Item = &PopulationSample> {
+        self.samples.iter()
+    }
+
+    /// Records a sample, forgetting the generations it comes back before
+    /// and the oldest ones beyond the capacity
+    pub fn record(&mut self, sample: PopulationSample) {
+        while self
+            .samples
+            .back()
+            .is_some_and(|last| last.generation >= sample.generation)
+        {
+            self.samples.pop_back();
+        }
+        self.samples.push_back(sample);
+        self.truncate();
+    }
+
+    /// Drops the oldest samples beyond the capacity
+    pub fn truncate(&mut self) {
+        while self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Forgets every sample
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// Plugin for the population history
+pub struct PopulationPlugin;
+
+impl Plugin for PopulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PopulationHistory>()
+            .add_systems(Update, record_population.after(CellSet));
+    }
+}
+
+/// Records the statistics left by the generation systems whenever they change
+pub fn record_population(stats: Res<SimulationStats>, mut history: ResMut<PopulationHistory>) {
+    if !stats.is_changed() || history.paused {
+        return;
+    }
+    history.record(PopulationSample {
+        generation: stats.generation,
+        population: stats.population,
+        births: stats.births,
+        deaths: stats.deaths,
+    });
+}
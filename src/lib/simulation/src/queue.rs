@@ -0,0 +1,165 @@
+//! # Queue Module
+//!
+//! Commands driving the simulation from other plugins. Along with the
+//! [`events`](crate::events) messages, the [`ActionQueue`] is the supported
+//! extension surface: a plugin pushes [`SimulationAction`]s onto it and they
+//! are applied in order before the next generations are computed, without
+//! the plugin touching the board, the rule or the timers itself.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::{App, IntoScheduleConfigs, Plugin, Res, ResMut, Resource, Update, warn};
+use gol_config::SimulationConfig;
+use gol_core::{Cell, parse_pattern};
+
+use crate::cell::{BoardState, CellPosition, CellSet};
+use crate::generation::{SimulationStats, rule_config_listener};
+use crate::hashlife::HashLifeUniverse;
+use crate::pattern::PatternRegistry;
+use crate::rules::RuleSet;
+
+/// Command for the simulation, applied by [`apply_actions_system`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulationAction {
+    /// Replaces the board with a pattern in any supported format, such as
+    /// RLE, applying the rule it declares and pausing the simulation
+    LoadPattern(String),
+    /// Replaces the board with a pattern of the registry, by name, pausing
+    /// the simulation
+    LoadNamed(String),
+    /// Brings the given cells to life, or kills them
+    SetCells {
+        cells: Vec<CellPosition>,
+        alive: bool,
+    },
+    /// Changes the rule, given as a rulestring such as `B3/S23`
+    SetRule(String),
+    /// Starts running the simulation
+    Play,
+    /// Pauses the simulation
+    Pause,
+    /// Sets the running speed, in generations per second, clamped to the supported range
+    SetSpeed(f32),
+    /// Computes the given number of generations as fast as possible
+    Step(u64),
+    /// Kills every cell, pauses the simulation and resets the statistics
+    Clear,
+}
+
+/// Actions waiting to be applied, in the order they were pushed
+#[derive(Resource, Default, Debug)]
+pub struct ActionQueue(pub VecDeque<SimulationAction>);
+
+impl ActionQueue {
+    /// Queues an action after the pending ones
+    pub fn push(&mut self, action: SimulationAction) {
+        self.0.push_back(action);
+    }
+}
+
+/// Plugin applying the queued simulation actions
+pub struct ActionQueuePlugin;
+
+impl Plugin for ActionQueuePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionQueue>().add_systems(
+            Update,
+            apply_actions_system
+                .before(rule_config_listener)
+                .before(CellSet),
+        );
+    }
+}
+
+/// Applies every queued action, in order.
+///
+/// Actions that cannot be applied, such as an invalid pattern or rule, are
+/// reported and skipped.
+pub fn apply_actions_system(
+    mut queue: ResMut<ActionQueue>,
+    mut board: ResMut<BoardState>,
+    mut config: ResMut<SimulationConfig>,
+    mut stats: ResMut<SimulationStats>,
+    registry: Res<PatternRegistry>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+) {
+    if queue.0.is_empty() {
+        return;
+    }
+    for action in std::mem::take(&mut queue.0) {
+        match action {
+            SimulationAction::LoadPattern(content) => match parse_pattern(&content) {
+                Ok(pattern) => {
+                    if let Some(rule) = pattern.rule {
+                        set_rule(&mut config, rule);
+                    }
+                    replace_board(
+                        &mut board,
+                        &mut hashlife,
+                        &mut config,
+                        &mut stats,
+                        &pattern.cells,
+                    );
+                }
+                Err(e) => warn!("Ignoring invalid pattern: {e}"),
+            },
+            SimulationAction::LoadNamed(name) => match registry.get(&name) {
+                Some(entry) => replace_board(
+                    &mut board,
+                    &mut hashlife,
+                    &mut config,
+                    &mut stats,
+                    &entry.cells,
+                ),
+                None => warn!("Ignoring unknown pattern '{name}'"),
+            },
+            SimulationAction::SetCells { cells, alive } => {
+                for position in cells {
+                    board.0.set(position.into(), alive);
+                }
+            }
+            SimulationAction::SetRule(rule) => set_rule(&mut config, rule),
+            SimulationAction::Play => config.running = true,
+            SimulationAction::Pause => config.running = false,
+            SimulationAction::SetSpeed(generations_per_second) => {
+                if generations_per_second > 0.0 {
+                    config.set_generations_per_second(generations_per_second);
+                } else {
+                    warn!("Ignoring invalid speed {generations_per_second}");
+                }
+            }
+            SimulationAction::Step(1) => config.calculate_next_gen = true,
+            SimulationAction::Step(generations) => config.warp(generations),
+            SimulationAction::Clear => {
+                replace_board(&mut board, &mut hashlife, &mut config, &mut stats, &[])
+            }
+        }
+    }
+}
+
+/// Sets the configured rule, which the rule listener then applies, unless
+/// the rulestring is invalid
+fn set_rule(config: &mut SimulationConfig, rule: String) {
+    match RuleSet::parse(&rule) {
+        Ok(_) => config.rule = rule,
+        Err(e) => warn!("Ignoring invalid rule '{rule}': {e}"),
+    }
+}
+
+/// Replaces the living cells of the board and of the HashLife universe,
+/// pausing the simulation
+fn replace_board(
+    board: &mut BoardState,
+    hashlife: &mut HashLifeUniverse,
+    config: &mut SimulationConfig,
+    stats: &mut SimulationStats,
+    cells: &[(i32, i32)],
+) {
+    config.running = false;
+    board.0.clear();
+    stats.reset();
+    for &(x, y) in cells {
+        board.0.set(Cell::new(x as isize, y as isize), true);
+    }
+    hashlife.replace(&board.0);
+}
@@ -0,0 +1,36 @@
+//! # Random Module
+//!
+//! Seeded random number generator shared by the simulation, so that noisy
+//! runs can be replayed from the same seed.
+
+use bevy::prelude::{App, Deref, DerefMut, Plugin, Resource};
+use gol_core::SplitMix64;
+
+/// Seed used until another one is chosen
+pub const DEFAULT_SEED: u64 = 0x5EED;
+
+/// Random number generator of the simulation
+#[derive(Resource, Clone, Copy, Debug, Deref, DerefMut)]
+pub struct SimRng(pub SplitMix64);
+
+impl SimRng {
+    /// Restarts the sequence from a seed
+    pub fn reseed(&mut self, seed: u64) {
+        self.0 = SplitMix64::new(seed);
+    }
+}
+
+impl Default for SimRng {
+    fn default() -> Self {
+        Self(SplitMix64::new(DEFAULT_SEED))
+    }
+}
+
+/// Plugin for the shared random number generator
+pub struct RandomPlugin;
+
+impl Plugin for RandomPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimRng>();
+    }
+}
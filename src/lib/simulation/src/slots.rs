@@ -0,0 +1,107 @@
+//! # Slots Module
+//!
+//! Save slots keeping whole boards in memory along with their generation, so
+//! that several experiments can branch from a common checkpoint.
+
+use bevy::prelude::{App, IntoScheduleConfigs, Plugin, ResMut, Resource, Update};
+
+use crate::cell::{BoardState, CellSet};
+use crate::generation::SimulationStats;
+use crate::history::{BoardHistory, BoardSnapshot};
+use gol_config::{SAVE_SLOT_COUNT, SimulationBackend, SimulationConfig};
+
+/// Named save slot, empty until a board is saved in it
+#[derive(Debug, Clone)]
+pub struct SaveSlot {
+    pub name: String,
+    pub snapshot: Option<BoardSnapshot>,
+}
+
+/// Operation on a save slot, given by its index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotRequest {
+    /// Saves the current board in the slot
+    Save(usize),
+    /// Replaces the current board with the one saved in the slot
+    Load(usize),
+}
+
+/// Save slots, along with the pending operation
+#[derive(Resource, Debug)]
+pub struct SaveSlots {
+    pub slots: Vec<SaveSlot>,
+    /// Operation applied on the next frame
+    pub request: Option<SlotRequest>,
+}
+
+impl Default for SaveSlots {
+    fn default() -> Self {
+        Self {
+            slots: (1..=SAVE_SLOT_COUNT)
+                .map(|i| SaveSlot {
+                    name: format!("Slot {i}"),
+                    snapshot: None,
+                })
+                .collect(),
+            request: None,
+        }
+    }
+}
+
+/// Plugin for the save slots
+pub struct SlotsPlugin;
+
+impl Plugin for SlotsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveSlots>()
+            .add_systems(Update, save_slots_system.in_set(CellSet));
+    }
+}
+
+/// Saves or loads a slot when requested.
+///
+/// Loading pauses the simulation and forgets the history, which belongs to
+/// the replaced board. The HashLife backend only mirrors the visible cells
+/// on the board, so slots are ignored while it is selected.
+pub fn save_slots_system(
+    mut board: ResMut<BoardState>,
+    mut slots: ResMut<SaveSlots>,
+    mut config: ResMut<SimulationConfig>,
+    mut stats: ResMut<SimulationStats>,
+    mut history: ResMut<BoardHistory>,
+) {
+    let Some(request) = slots.request.take() else {
+        return;
+    };
+    if config.backend == SimulationBackend::HashLife {
+        return;
+    }
+
+    match request {
+        SlotRequest::Save(index) => {
+            let snapshot = BoardSnapshot {
+                generation: stats.generation,
+                board: board.0.clone(),
+            };
+            if let Some(slot) = slots.slots.get_mut(index) {
+                slot.snapshot = Some(snapshot);
+            }
+        }
+        SlotRequest::Load(index) => {
+            let Some(snapshot) = slots
+                .slots
+                .get(index)
+                .and_then(|slot| slot.snapshot.as_ref())
+            else {
+                return;
+            };
+            config.running = false;
+            board.0 = snapshot.board.clone();
+            stats.generation = snapshot.generation;
+            stats.births = 0;
+            stats.deaths = 0;
+            stats.population = snapshot.board.len();
+            history.clear();
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! # Soup Module
+//!
+//! Automated soup search: random soups are run one after the other on the
+//! async compute pool with the active rule, a census of their ash is kept,
+//! and the long-lived ones are recorded for later inspection.
+
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource, Update, info};
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future};
+use gol_core::{SOUP_CSV_HEADER, SoupResult, run_soup};
+use std::collections::BTreeMap;
+
+use crate::rules::RuleSet;
+
+/// State and findings of the soup search
+#[derive(Resource)]
+pub struct SoupSearch {
+    /// Whether new soups are started
+    pub running: bool,
+    /// Seed of the next soup to run
+    pub next_seed: u64,
+    /// Generations after which a soup is given up on
+    pub max_generations: u64,
+    /// Lifespan from which a soup is recorded as a methuselah
+    pub methuselah_lifespan: u64,
+    /// Number of soups run so far
+    pub soups: u64,
+    /// Objects found in the ash of every soup, by name
+    pub census: BTreeMap<String, u64>,
+    /// Soups that lived long or never settled, in the order they were found
+    pub results: Vec<SoupResult>,
+    task: Option<Task<SoupResult>>,
+}
+
+impl Default for SoupSearch {
+    fn default() -> Self {
+        Self {
+            running: false,
+            next_seed: 0,
+            max_generations: 20_000,
+            methuselah_lifespan: 1_000,
+            soups: 0,
+            census: BTreeMap::new(),
+            results: Vec::new(),
+            task: None,
+        }
+    }
+}
+
+impl SoupSearch {
+    /// Whether a soup is being run
+    pub fn is_busy(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// Whether a soup is worth recording
+    pub fn is_interesting(&self, result: &SoupResult) -> bool {
+        !result.settled() || result.lifespan >= self.methuselah_lifespan
+    }
+
+    /// Forgets the census and the recorded soups
+    pub fn clear(&mut self) {
+        self.soups = 0;
+        self.census.clear();
+        self.results.clear();
+    }
+
+    /// Recorded soups as CSV, one line per soup
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(SOUP_CSV_HEADER);
+        csv.push('\n');
+        for result in &self.results {
+            csv.push_str(&result.to_csv_line());
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Adds a finished soup to the census, keeping it if interesting
+    fn record(&mut self, result: SoupResult) {
+        self.soups += 1;
+        for (name, count) in &result.census {
+            *self.census.entry(name.clone()).or_insert(0) += *count as u64;
+        }
+        if self.is_interesting(&result) {
+            info!(
+                "Soup {} lived {} generations ({} cells left)",
+                result.seed, result.lifespan, result.final_population
+            );
+            self.results.push(result);
+        }
+    }
+}
+
+/// Plugin for the soup search
+pub struct SoupPlugin;
+
+impl Plugin for SoupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoupSearch>()
+            .add_systems(Update, soup_search_system);
+    }
+}
+
+/// Collects the finished soup and starts the next one while searching
+pub fn soup_search_system(mut search: ResMut<SoupSearch>, rule: Res<RuleSet>) {
+    if let Some(task) = search.task.as_mut() {
+        let Some(result) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        search.task = None;
+        search.record(result);
+    }
+    if !search.running {
+        return;
+    }
+
+    let seed = search.next_seed;
+    search.next_seed = search.next_seed.wrapping_add(1);
+    let rule = rule.0;
+    let max_generations = search.max_generations;
+    search.task = Some(
+        AsyncComputeTaskPool::get().spawn(async move { run_soup(seed, &rule, max_generations) }),
+    );
+}
@@ -0,0 +1,128 @@
+//! # Stability Module
+//!
+//! "Run until stable": the board is advanced on the async compute pool until
+//! one of its states repeats, and the period and generation reached are
+//! reported.
+
+use bevy::prelude::{App, IntoScheduleConfigs, Plugin, Res, ResMut, Resource, Update, warn};
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future};
+use gol_core::{Board, Cycle, run_until_cycle};
+
+use crate::cell::{BoardState, CellSet};
+use crate::events::GenerationEvents;
+use crate::generation::{SimulationStats, backend_is};
+use crate::history::{BoardHistory, BoardSnapshot};
+use crate::rules::RuleSet;
+use gol_config::{MAX_STABILITY_GENERATIONS, SimulationBackend, SimulationConfig};
+
+/// Progress of the last stability search
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StabilityStatus {
+    /// No search was run, or its report was dismissed
+    #[default]
+    Idle,
+    /// The board is being advanced in the background
+    Searching,
+    /// The board settled into a cycle, reached at `generation`, the start
+    /// of the cycle being counted from the first generation too
+    Stable { cycle: Cycle, generation: u64 },
+    /// No cycle showed up before `generation`
+    Unstable { generation: u64 },
+}
+
+/// Result of a finished search: the start and final boards, the number of
+/// generations computed and the cycle found
+type StabilityOutcome = (Board, Board, u64, Option<Cycle>);
+
+/// State of the "Run until stable" search
+#[derive(Resource, Default)]
+pub struct StabilitySearch {
+    /// Progress of the last search
+    pub status: StabilityStatus,
+    task: Option<Task<StabilityOutcome>>,
+}
+
+/// Plugin for the stability search
+pub struct StabilityPlugin;
+
+impl Plugin for StabilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StabilitySearch>().add_systems(
+            Update,
+            run_until_stable
+                .in_set(CellSet)
+                .run_if(backend_is(SimulationBackend::Sparse)),
+        );
+    }
+}
+
+/// Starts a search when requested and applies its result once finished.
+///
+/// The search ignores the noise setting, as a noisy board would never repeat.
+/// Its result is dropped if the board was edited in the meantime.
+pub fn run_until_stable(
+    mut board_state: ResMut<BoardState>,
+    rule: Res<RuleSet>,
+    mut config: ResMut<SimulationConfig>,
+    mut search: ResMut<StabilitySearch>,
+    mut stats: ResMut<SimulationStats>,
+    mut history: ResMut<BoardHistory>,
+    mut events: GenerationEvents,
+) {
+    if config.run_until_stable {
+        config.run_until_stable = false;
+        if search.task.is_none() {
+            config.running = false;
+            let start = board_state.0.clone();
+            let (rule, topology) = (rule.0, config.topology);
+            search.task = Some(AsyncComputeTaskPool::get().spawn(async move {
+                let mut board = start.clone();
+                let (generations, cycle) =
+                    run_until_cycle(&mut board, &rule, &topology, MAX_STABILITY_GENERATIONS);
+                (start, board, generations, cycle)
+            }));
+            search.status = StabilityStatus::Searching;
+        }
+    }
+
+    let Some(task) = search.task.as_mut() else {
+        return;
+    };
+    let Some((start, board, generations, cycle)) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    search.task = None;
+    if board_state.0 != start {
+        warn!("The board was edited during the stability search, its result is dropped");
+        search.status = StabilityStatus::Idle;
+        return;
+    }
+
+    history.push(
+        BoardSnapshot {
+            generation: stats.generation,
+            board: start,
+        },
+        config.history_depth,
+    );
+    let first = stats.generation;
+    stats.generation += generations;
+    stats.births = 0;
+    stats.deaths = 0;
+    stats.population = board.len();
+    board_state.0 = board;
+    events.advanced(&stats, generations);
+
+    search.status = match cycle {
+        Some(cycle) => StabilityStatus::Stable {
+            cycle: Cycle {
+                start: first + cycle.start,
+                ..cycle
+            },
+            generation: stats.generation,
+        },
+        None => StabilityStatus::Unstable {
+            generation: stats.generation,
+        },
+    };
+}
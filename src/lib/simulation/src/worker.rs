@@ -0,0 +1,286 @@
+//! # Worker Module
+//!
+//! Stepping of the sparse backend in a Web Worker, so that slow generations
+//! never block the browser tab.
+//!
+//! The page loads `worker.js`, which runs the same WebAssembly module: its
+//! `main` finds itself in a worker and calls [`run_worker`] instead of
+//! starting the application. The worker keeps its own copy of the board,
+//! which is only sent again after an edit, and answers each request with
+//! the net change of the computed generations. Messages are JSON texts of a
+//! [`WorkerRequest`] and of a `Result` holding a [`WorkerReply`].
+//!
+//! When the worker cannot be started, generations are computed on the main
+//! thread instead.
+
+use gol_core::{BitBoard, Board, Cell, GridTopology, Noise, Rule, SplitMix64, Step};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::generation::{GenerationDiff, GenerationJob, compute_generations};
+use crate::history::BoardSnapshot;
+use gol_config::WEB_STEP_BUDGET;
+
+/// Living cell with its state, as sent to and from the worker
+pub type WorkerCell = (isize, isize, u8);
+
+/// Generations to compute, posted to the worker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerRequest {
+    /// Board to start from, when the worker's copy is outdated
+    pub cells: Option<Vec<WorkerCell>>,
+    /// Rulestring of the active rule
+    pub rule: String,
+    /// Shape of the grid
+    pub topology: GridTopology,
+    /// Probabilities of the births and survivals allowed by the rule
+    pub noise: Noise,
+    /// Seed of the noise of these generations
+    pub seed: u64,
+    /// Number of generations to compute
+    pub steps: u32,
+    /// Generation number of the starting board
+    pub generation: u64,
+    /// Number of snapshots to keep for stepping backwards
+    pub history_depth: usize,
+}
+
+/// Generations computed by the worker
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkerReply {
+    pub born: Vec<(isize, isize)>,
+    pub died: Vec<(isize, isize)>,
+    pub states: Vec<WorkerCell>,
+    /// Boards captured before the last generations, with their number
+    pub snapshots: Vec<(u64, Vec<WorkerCell>)>,
+    pub generations: u64,
+    pub left: u32,
+    pub births: usize,
+    pub deaths: usize,
+    pub population: usize,
+    pub step_time: Duration,
+    pub active_chunks: Option<usize>,
+}
+
+/// Living cells of a board with their state
+pub fn board_cells(board: &Board) -> Vec<WorkerCell> {
+    board
+        .iter()
+        .map(|cell| (cell.x, cell.y, board.state(cell)))
+        .collect()
+}
+
+/// Board holding the given cells
+fn cells_board(cells: &[WorkerCell]) -> Board {
+    let mut board = Board::new();
+    for &(x, y, state) in cells {
+        board.set_state(Cell::new(x, y), state);
+    }
+    board
+}
+
+impl WorkerReply {
+    /// Diff to apply on the main thread, computed with the given rule and
+    /// topology
+    pub fn into_diff(self, stepped_with: (Rule, GridTopology)) -> GenerationDiff {
+        let position = |(x, y): (isize, isize)| Cell::new(x, y);
+        GenerationDiff {
+            board: None,
+            snapshots: self
+                .snapshots
+                .into_iter()
+                .map(|(generation, cells)| BoardSnapshot {
+                    generation,
+                    board: cells_board(&cells),
+                })
+                .collect(),
+            step: Step {
+                born: self.born.into_iter().map(position).collect(),
+                died: self.died.into_iter().map(position).collect(),
+                states: self
+                    .states
+                    .into_iter()
+                    .map(|(x, y, state)| (Cell::new(x, y), state))
+                    .collect(),
+            },
+            generations: self.generations,
+            left: self.left,
+            births: self.births,
+            deaths: self.deaths,
+            population: self.population,
+            step_time: self.step_time,
+            bits: None,
+            active_chunks: self.active_chunks,
+            stepped_with,
+        }
+    }
+}
+
+/// Board kept by the worker between requests
+#[derive(Default)]
+pub struct WorkerState {
+    board: Board,
+    bits: Option<BitBoard>,
+    /// Rule and topology `bits` was stepped with
+    stepped_with: Option<(Rule, GridTopology)>,
+}
+
+impl WorkerState {
+    /// Computes the generations of a request from the kept board
+    pub fn handle(&mut self, request: WorkerRequest) -> Result<WorkerReply, String> {
+        let rule = Rule::parse(&request.rule).map_err(|e| e.to_string())?;
+        if let Some(cells) = request.cells {
+            self.board = cells_board(&cells);
+            self.bits = None;
+        }
+        // Chunks stable under another rule may wake up under this one
+        if self.stepped_with != Some((rule, request.topology)) {
+            self.bits = None;
+        }
+
+        let diff = compute_generations(
+            std::mem::take(&mut self.board),
+            self.bits.take(),
+            GenerationJob {
+                rule,
+                topology: request.topology,
+                noise: request.noise,
+                rng: SplitMix64::new(request.seed),
+                steps: request.steps,
+                generation: request.generation,
+                history_depth: request.history_depth,
+                budget: Some(Duration::from_secs_f32(WEB_STEP_BUDGET)),
+            },
+        );
+        self.board = diff.board.unwrap_or_default();
+        self.bits = diff.bits;
+        self.stepped_with = Some(diff.stepped_with);
+
+        let position = |cell: Cell| (cell.x, cell.y);
+        Ok(WorkerReply {
+            born: diff.step.born.into_iter().map(position).collect(),
+            died: diff.step.died.into_iter().map(position).collect(),
+            states: diff
+                .step
+                .states
+                .into_iter()
+                .map(|(cell, state)| (cell.x, cell.y, state))
+                .collect(),
+            snapshots: diff
+                .snapshots
+                .iter()
+                .map(|snapshot| (snapshot.generation, board_cells(&snapshot.board)))
+                .collect(),
+            generations: diff.generations,
+            left: diff.left,
+            births: diff.births,
+            deaths: diff.deaths,
+            population: diff.population,
+            step_time: diff.step_time,
+            active_chunks: diff.active_chunks,
+        })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub use web::{is_worker, run_worker, spawn};
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::{WorkerReply, WorkerRequest, WorkerState};
+    use crate::generation::GenerationDiff;
+    use bevy::prelude::warn;
+    use bevy::tasks::{AsyncComputeTaskPool, Task};
+    use gol_core::Rule;
+    use std::cell::RefCell;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{DedicatedWorkerGlobalScope, MessageEvent, Worker, WorkerOptions, WorkerType};
+
+    /// Script of the worker, next to the page
+    const WORKER_SCRIPT: &str = "./worker.js";
+
+    thread_local! {
+        /// Worker of the page, `None` once it could not start or failed
+        static WORKER: RefCell<Option<Worker>> = RefCell::new(start_worker());
+    }
+
+    fn start_worker() -> Option<Worker> {
+        let options = WorkerOptions::new();
+        options.set_type(WorkerType::Module);
+        Worker::new_with_options(WORKER_SCRIPT, &options)
+            .inspect_err(|e| warn!("Could not start the simulation worker: {e:?}"))
+            .ok()
+    }
+
+    /// Whether the module runs in a worker rather than in the page
+    pub fn is_worker() -> bool {
+        js_sys::global().is::<DedicatedWorkerGlobalScope>()
+    }
+
+    /// Answers the requests of the page, from the worker
+    pub fn run_worker() {
+        let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+        let replies = scope.clone();
+        let mut state = WorkerState::default();
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let reply = event
+                .data()
+                .as_string()
+                .ok_or_else(|| "request is not a text".to_string())
+                .and_then(|text| serde_json::from_str(&text).map_err(|e| e.to_string()))
+                .and_then(|request| state.handle(request));
+            let text = serde_json::to_string(&reply).unwrap_or_default();
+            if let Err(e) = replies.post_message(&JsValue::from_str(&text)) {
+                warn!("Could not answer the page: {e:?}");
+            }
+        });
+        scope.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        // The handler lives as long as the worker
+        on_message.forget();
+    }
+
+    /// Computes the generations of a request in the worker, or returns
+    /// `None` when there is no worker to do it
+    pub fn spawn(request: WorkerRequest, rule: Rule) -> Option<Task<GenerationDiff>> {
+        let worker = WORKER.with_borrow(Clone::clone)?;
+        let stepped_with = (rule, request.topology);
+        Some(AsyncComputeTaskPool::get().spawn(async move {
+            match post(&worker, &request).await {
+                Ok(reply) => reply.into_diff(stepped_with),
+                Err(e) => {
+                    warn!("The simulation worker failed, stepping on the page: {e}");
+                    worker.terminate();
+                    WORKER.set(None);
+                    // The generations are given back to the next computation
+                    let failed = WorkerReply {
+                        left: request.steps,
+                        ..WorkerReply::default()
+                    };
+                    failed.into_diff(stepped_with)
+                }
+            }
+        }))
+    }
+
+    /// Posts a request and waits for its reply
+    async fn post(worker: &Worker, request: &WorkerRequest) -> Result<WorkerReply, String> {
+        let text = serde_json::to_string(request).map_err(|e| e.to_string())?;
+        // Only one request is pending at a time, answered by the next message
+        let reply = js_sys::Promise::new(&mut |resolve, reject| {
+            worker.set_onmessage(Some(&resolve));
+            worker.set_onerror(Some(&reject));
+        });
+        worker
+            .post_message(&JsValue::from_str(&text))
+            .map_err(|e| format!("{e:?}"))?;
+        let event = JsFuture::from(reply).await.map_err(|e| format!("{e:?}"))?;
+        let text = event
+            .unchecked_into::<MessageEvent>()
+            .data()
+            .as_string()
+            .ok_or("reply is not a text")?;
+        serde_json::from_str::<Result<WorkerReply, String>>(&text).map_err(|e| e.to_string())?
+    }
+}
@@ -0,0 +1,90 @@
+//! # Actions Module
+//!
+//! Actions on the whole grid, sent as [`UiAction`] messages by the
+//! confirmation dialogs and the keyboard shortcuts, and applied in one place.
+
+use bevy::prelude::{
+    App, Camera, Message, MessageReader, Plugin, Query, Res, ResMut, Transform, Update, With, warn,
+};
+use gol_config::{DisplayConfig, SimulationConfig};
+use gol_simulation::{
+    BoardState, CellPosition, HashLifeUniverse, RuleSet, SimRng, SimulationStats,
+};
+
+use crate::selection::generate_random_cells;
+
+/// Action on the whole grid
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiAction {
+    /// Kills every cell and resets the statistics
+    Clear,
+    /// Replaces the grid with random cells around the center of the view,
+    /// with the density, shape, size and seed of the display configuration
+    Randomize,
+}
+
+/// Plugin applying the grid actions
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<UiAction>()
+            .add_systems(Update, ui_action_system);
+    }
+}
+
+/// Applies the last action sent this frame, pausing the simulation.
+///
+/// Earlier actions of the same frame are dropped, as the last one replaces
+/// the whole grid anyway.
+#[allow(clippy::too_many_arguments)]
+pub fn ui_action_system(
+    mut actions: MessageReader<UiAction>,
+    mut board: ResMut<BoardState>,
+    q_camera: Query<&Transform, With<Camera>>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    display_config: Res<DisplayConfig>,
+    mut stats: ResMut<SimulationStats>,
+    mut rng: ResMut<SimRng>,
+    rule: Res<RuleSet>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+) {
+    let Some(&action) = actions.read().last() else {
+        return;
+    };
+    simulation_config.running = false;
+    board.0.clear();
+    stats.reset();
+
+    if action == UiAction::Randomize {
+        let center = q_camera
+            .single()
+            .map(|transform| transform.translation.truncate().round())
+            .unwrap_or_default();
+        let size = display_config.random_size as isize;
+        let half = size / 2;
+        let min = CellPosition {
+            x: center.x as isize - half,
+            y: center.y as isize - half,
+        };
+        let max = CellPosition {
+            x: min.x + size - 1,
+            y: min.y + size - 1,
+        };
+        // The same seed gives the same soup, and the same noisy run after it
+        rng.reseed(display_config.random_seed);
+        let filled = generate_random_cells(
+            &mut board.0,
+            (min, max),
+            display_config.random_shape,
+            display_config.random_density,
+            rule.states.living_states(),
+            &mut rng,
+        );
+        if !filled {
+            warn!("The random area of {size}×{size} cells is too large");
+        }
+    }
+    // The board only holds the visible cells of a HashLife universe
+    hashlife.replace(&board.0);
+}
@@ -0,0 +1,118 @@
+//! # Analysis Module
+//!
+//! Dialog classifying the pattern on the grid, or the selected part of it,
+//! as a still life, an oscillator or a spaceship, with its period and speed.
+
+use crate::selection::Selection;
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource};
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{MAX_ANALYSIS_PERIOD, tr};
+use gol_core::{Behavior, Board, analyze};
+use gol_simulation::{BoardState, HashLifeUniverse, RuleSet};
+
+/// Cells taken into account by the analysis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnalysisScope {
+    /// Every living cell
+    Pattern,
+    /// The living cells of the selection
+    Selection,
+}
+
+/// State of the analysis dialog
+#[derive(Resource, Default)]
+pub struct AnalysisDialog {
+    pub open: bool,
+    /// Analysis to start on the next frame
+    pub requested: Option<AnalysisScope>,
+    /// Last finished analysis
+    pub result: Option<(AnalysisScope, Behavior)>,
+    task: Option<(AnalysisScope, Task<Behavior>)>,
+}
+
+/// Plugin for the pattern analysis dialog
+pub struct AnalysisPlugin;
+
+impl Plugin for AnalysisPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AnalysisDialog>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, analysis_dialog_system);
+    }
+}
+
+/// Runs requested analyses in the background and renders the dialog
+pub fn analysis_dialog_system(
+    mut contexts: EguiContexts,
+    mut dialog: ResMut<AnalysisDialog>,
+    selection: Res<Selection>,
+    rule: Res<RuleSet>,
+    board_state: Res<BoardState>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+) {
+    if let Some(scope) = dialog.requested.take() {
+        // Read from the universe under HashLife, as the board only mirrors the view
+        let board = match (scope, selection.rect) {
+            (AnalysisScope::Pattern, _) => hashlife.cells_in(&board_state.0, None),
+            (AnalysisScope::Selection, Some(rect)) => hashlife.cells_in(&board_state.0, Some(rect)),
+            (AnalysisScope::Selection, None) => Board::new(),
+        };
+        let rule = rule.0;
+        let task = AsyncComputeTaskPool::get()
+            .spawn(async move { analyze(&board, &rule, MAX_ANALYSIS_PERIOD) });
+        dialog.task = Some((scope, task));
+        dialog.open = true;
+    }
+    let finished = dialog.task.as_mut().and_then(|(scope, task)| {
+        block_on(future::poll_once(task)).map(|behavior| (*scope, behavior))
+    });
+    if finished.is_some() {
+        dialog.result = finished;
+        dialog.task = None;
+    }
+    if !dialog.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = true;
+    egui::Window::new(tr("analysis-title"))
+        .id(egui::Id::new("analysis"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            if dialog.task.is_some() {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr("analysis-running"));
+                });
+            } else if let Some((scope, behavior)) = &dialog.result {
+                let subject = match scope {
+                    AnalysisScope::Pattern => tr("analysis-whole-pattern"),
+                    AnalysisScope::Selection => tr("analysis-selection"),
+                };
+                ui.label(subject);
+                ui.strong(behavior.to_string());
+            }
+            ui.horizontal(|ui| {
+                if ui.button(tr("analysis-analyze-pattern")).clicked() {
+                    dialog.requested = Some(AnalysisScope::Pattern);
+                }
+                let selected = selection.rect.is_some();
+                if ui
+                    .add_enabled(
+                        selected,
+                        egui::Button::new(tr("analysis-analyze-selection")),
+                    )
+                    .clicked()
+                {
+                    dialog.requested = Some(AnalysisScope::Selection);
+                }
+            });
+        });
+    if !open {
+        dialog.open = false;
+    }
+}
@@ -0,0 +1,78 @@
+//! # Audio Module
+//!
+//! Optional sound feedback of the simulation. Each step reported by a
+//! [`GenerationAdvanced`] message plays a short tone: its pitch rises when
+//! the population grows and falls when it shrinks, and its loudness follows
+//! the share of cells that changed. Muted by default.
+
+use std::time::Duration;
+
+use bevy::audio::{AudioPlayer, Pitch, PlaybackSettings, Volume};
+use bevy::ecs::message::MessageReader;
+use bevy::prelude::{App, Assets, Commands, Local, Plugin, Res, ResMut, Time, Update};
+use gol_config::DisplayConfig;
+use gol_simulation::GenerationAdvanced;
+
+/// Frequency of the tone of a steady population, in hertz
+const BASE_FREQUENCY: f32 = 440.0;
+/// Length of a tone
+const TONE_DURATION: Duration = Duration::from_millis(60);
+/// Shortest time between two tones, so fast simulations do not turn into a
+/// continuous buzz
+const MIN_TONE_INTERVAL: f32 = 0.1;
+
+/// Plugin for the sound feedback
+pub struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, play_generation_tone_system);
+    }
+}
+
+/// Plays the tone of the latest step, at most once per [`MIN_TONE_INTERVAL`]
+pub fn play_generation_tone_system(
+    mut commands: Commands,
+    mut advanced: MessageReader<GenerationAdvanced>,
+    mut pitches: ResMut<Assets<Pitch>>,
+    display_config: Res<DisplayConfig>,
+    time: Res<Time>,
+    mut last_tone: Local<Option<f32>>,
+) {
+    let Some(step) = advanced.read().last() else {
+        return;
+    };
+    let now = time.elapsed_secs();
+    let too_soon = last_tone.is_some_and(|last| now - last < MIN_TONE_INTERVAL);
+    if display_config.sound_muted || display_config.sound_volume <= 0.0 || too_soon {
+        return;
+    }
+    let Some((frequency, loudness)) = generation_tone(step) else {
+        return;
+    };
+    *last_tone = Some(now);
+    commands.spawn((
+        AudioPlayer(pitches.add(Pitch::new(frequency, TONE_DURATION))),
+        PlaybackSettings::DESPAWN
+            .with_volume(Volume::Linear(display_config.sound_volume * loudness)),
+    ));
+}
+
+/// Frequency and relative loudness of the tone of a step, `None` when no
+/// cell changed
+fn generation_tone(step: &GenerationAdvanced) -> Option<(f32, f32)> {
+    let changed = step.births + step.deaths;
+    if changed == 0 {
+        return None;
+    }
+    // Growth of the population during the generation, as a share of the
+    // cells it had, moving the pitch by up to an octave either way
+    let before = (step.population + step.deaths)
+        .saturating_sub(step.births)
+        .max(1) as f32;
+    let growth = (step.births as f32 - step.deaths as f32) / before;
+    let frequency = BASE_FREQUENCY * 2.0_f32.powf(growth.clamp(-1.0, 1.0));
+    // Quiet for a few flickering cells, full once a tenth of them change
+    let loudness = (changed as f32 / before * 10.0).clamp(0.2, 1.0);
+    Some((frequency, loudness))
+}
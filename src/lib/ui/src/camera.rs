@@ -3,15 +3,29 @@
 //! Camera initialization and movement controls for the 2D Game of Life view.
 
 use bevy::camera::ScalingMode;
-use bevy::prelude::{App, Camera2d, Commands, OrthographicProjection, Plugin, Projection, Startup};
-use gol_config::DEFAULT_SCALE;
+use bevy::post_process::bloom::Bloom;
+use bevy::prelude::{
+    App, Camera, Camera2d, Commands, DetectChanges, Entity, GlobalTransform,
+    OrthographicProjection, Plugin, Projection, Query, Res, ResMut, Startup, Transform, Update,
+    Vec2, With,
+};
+use bevy::render::view::Hdr;
+use gol_config::{DEFAULT_SCALE, DisplayConfig, SimulationBackend, SimulationConfig};
+use gol_simulation::{CellPosition, ElementaryRow, SimulationViewport};
 
 /// Plugin for camera-related systems
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, init_camera);
+        app.add_systems(Startup, init_camera).add_systems(
+            Update,
+            (
+                update_simulation_viewport,
+                follow_elementary_row,
+                update_bloom,
+            ),
+        );
     }
 }
 
@@ -29,3 +43,95 @@ pub fn init_camera(mut commands: Commands) {
     });
     commands.spawn((Camera2d, projection));
 }
+
+/// Keeps the simulation viewport in sync with the area seen by the camera
+pub fn update_simulation_viewport(
+    q_camera: Query<(&Camera, &GlobalTransform)>,
+    mut viewport: ResMut<SimulationViewport>,
+) {
+    let Ok((camera, camera_transform)) = q_camera.single() else {
+        return;
+    };
+    let Some(size) = camera.logical_viewport_size() else {
+        return;
+    };
+    let (Ok(top_left), Ok(bottom_right)) = (
+        camera.viewport_to_world_2d(camera_transform, Vec2::ZERO),
+        camera.viewport_to_world_2d(camera_transform, size),
+    ) else {
+        return;
+    };
+
+    // One extra cell of margin so partially visible cells are included
+    let visible = SimulationViewport {
+        min: CellPosition {
+            x: top_left.x.floor() as isize - 1,
+            y: bottom_right.y.floor() as isize - 1,
+        },
+        max: CellPosition {
+            x: bottom_right.x.ceil() as isize + 1,
+            y: top_left.y.ceil() as isize + 1,
+        },
+    };
+    if *viewport != visible {
+        *viewport = visible;
+    }
+}
+
+/// Scrolls the camera down with the elementary automaton, keeping its newest
+/// row near the bottom of the view while the simulation runs
+pub fn follow_elementary_row(
+    row: Res<ElementaryRow>,
+    simulation_config: Res<SimulationConfig>,
+    viewport: Res<SimulationViewport>,
+    mut q_camera: Query<&mut Transform, With<Camera2d>>,
+) {
+    if simulation_config.backend != SimulationBackend::Elementary
+        || !(simulation_config.running || simulation_config.is_warping())
+        || !row.is_changed()
+    {
+        return;
+    }
+    let (Some(y), Ok(mut transform)) = (row.y, q_camera.single_mut()) else {
+        return;
+    };
+    let height = (viewport.max.y - viewport.min.y) as f32;
+    let target = y as f32 + 0.4 * height;
+    // Only scroll down, leaving the user free to pan below the newest row
+    if target < transform.translation.y {
+        transform.translation.y = target;
+    }
+}
+
+/// Turns the HDR camera and its bloom on or off with the display settings.
+///
+/// Bloom only adds a glow when the camera renders in HDR, so both are
+/// inserted and removed together.
+pub fn update_bloom(
+    mut commands: Commands,
+    display_config: Res<DisplayConfig>,
+    q_camera: Query<(Entity, Option<&Bloom>), With<Camera2d>>,
+) {
+    if !display_config.is_changed() {
+        return;
+    }
+    let Ok((camera, bloom)) = q_camera.single() else {
+        return;
+    };
+    match (display_config.bloom, bloom) {
+        (true, Some(bloom)) if bloom.intensity == display_config.bloom_intensity => {}
+        (true, _) => {
+            commands.entity(camera).insert((
+                Hdr,
+                Bloom {
+                    intensity: display_config.bloom_intensity,
+                    ..Bloom::NATURAL
+                },
+            ));
+        }
+        (false, Some(_)) => {
+            commands.entity(camera).remove::<(Hdr, Bloom)>();
+        }
+        (false, None) => {}
+    }
+}
@@ -0,0 +1,64 @@
+//! # Clipboard Module
+//!
+//! Text clipboard of the RLE copy and paste. On desktop, egui reaches the
+//! system clipboard. In the browser the page may only use the async Web
+//! Clipboard API, so copies are written and pastes read through it instead.
+//!
+//! Pattern files need nothing of the sort: the file dialogs open an
+//! `<input type=file>` picker in the browser.
+
+use bevy::prelude::Resource;
+use bevy::tasks::{Task, block_on, futures_lite::future};
+use bevy_egui::egui;
+
+/// Read of the browser clipboard, started by the paste shortcut
+#[derive(Resource, Default)]
+pub struct ClipboardRead {
+    /// Reads the text, `None` when the clipboard holds none or the page was
+    /// refused access to it
+    task: Option<Task<Option<String>>>,
+}
+
+impl ClipboardRead {
+    /// Starts reading the browser clipboard, unless a read is running
+    pub fn request(&mut self) {
+        if self.task.is_none() {
+            self.task = read_clipboard();
+        }
+    }
+
+    /// Text of the clipboard, once the read finished
+    pub fn poll(&mut self) -> Option<String> {
+        let text = block_on(future::poll_once(self.task.as_mut()?))?;
+        self.task = None;
+        text
+    }
+}
+
+/// Copies text to the clipboard, through the Web Clipboard API in the
+/// browser
+pub fn copy_text(ctx: &egui::Context, text: String) {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(window) = web_sys::window() {
+        // The write completes in the background, nothing waits for it
+        let _ = window.navigator().clipboard().write_text(&text);
+        return;
+    }
+    ctx.copy_text(text);
+}
+
+/// Reads the browser clipboard in the background
+#[cfg(target_arch = "wasm32")]
+fn read_clipboard() -> Option<Task<Option<String>>> {
+    let promise = web_sys::window()?.navigator().clipboard().read_text();
+    Some(bevy::tasks::IoTaskPool::get().spawn(async move {
+        let text = wasm_bindgen_futures::JsFuture::from(promise).await.ok()?;
+        text.as_string()
+    }))
+}
+
+/// Outside the browser, egui forwards the pastes itself
+#[cfg(not(target_arch = "wasm32"))]
+fn read_clipboard() -> Option<Task<Option<String>>> {
+    None
+}
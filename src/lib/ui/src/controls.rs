@@ -2,43 +2,114 @@
 //!
 //! Main control panel for the Game of Life simulation.
 
-use crate::pattern::{PlacementMode, RleLoader, pattern_system, rle_loader_modal};
-use bevy::prelude::{Plugin, Commands, ResMut, Projection, GlobalTransform, With, Entity, App, Query, Color, Visibility, Sprite, Vec2, Transform};
+use crate::analysis::{AnalysisDialog, AnalysisScope};
+use crate::modals::ModalState;
+use crate::image_export::ImageExportWindow;
+use crate::image_import::ImageImportWindow;
+use crate::input::DrawTool;
+use crate::navigation::JumpTarget;
+use crate::pattern::{PatternBrowser, PatternParams, pattern_system, rle_loader_modal};
+use crate::recording::RecordingWindow;
+use crate::screenshot::ScreenshotRequest;
+use crate::session::SessionFiles;
+use crate::settings::SettingsWindow;
+use crate::slots::SlotsWindow;
+use crate::soup::SoupPanel;
+use crate::stats::StatsWindow;
+use crate::svg_export::SvgExport;
+use bevy::color::ColorToPacked;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{Plugin, Res, ResMut, Resource, Projection, Transform, Camera, With, App, Query, FromWorld, World};
 use bevy_egui::{EguiContexts, egui};
-use gol_config::{ColorConfig, DisplayConfig, SimulationConfig};
-use gol_simulation::{Alive, DeadCellPool};
+use gol_config::{
+    CameraConfig, ColorConfig, DisplayConfig, KeyBindings, KeyName,
+    GridTopology, MARGOLUS_PRESETS, Noise, RULE_PRESETS, SimulationBackend, SimulationConfig,
+    storage_available, tr, tr_args,
+};
+use gol_core::Cell;
+use gol_simulation::{
+    BoardHistory, BoardState, Neighborhood, RuleSet, SimulationStats, StateModel,
+    hashlife_supports, margolus_parity_is_odd,
+};
 use gol_utils::{period_to_slider, scale_to_slider, slider_to_period, slider_to_scale};
 use std::time::Duration;
 
+/// Width and height proposed when switching to a finite grid
+const DEFAULT_GRID_SIZE: usize = 100;
+
+/// Text input state for editing the simulation rule
+#[derive(Resource)]
+pub struct RuleEditor {
+    pub text: String,
+    pub error_message: Option<String>,
+    /// Whether the rule table dialog is open
+    pub table_open: bool,
+}
+
+impl FromWorld for RuleEditor {
+    /// Starts from the configured rule, which may come from the command line
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            text: world.resource::<SimulationConfig>().rule.clone(),
+            error_message: None,
+            table_open: false,
+        }
+    }
+}
+
+/// Dialogs and panels opened from the control panel
+#[derive(SystemParam)]
+pub struct PanelParams<'w> {
+    pub jump_target: ResMut<'w, JumpTarget>,
+    pub soup: ResMut<'w, SoupPanel>,
+    pub analysis: ResMut<'w, AnalysisDialog>,
+    pub stats: ResMut<'w, StatsWindow>,
+    pub slots: ResMut<'w, SlotsWindow>,
+    pub session: ResMut<'w, SessionFiles>,
+    pub settings: ResMut<'w, SettingsWindow>,
+    pub screenshot: ResMut<'w, ScreenshotRequest>,
+    pub image_export: ResMut<'w, ImageExportWindow>,
+    pub image_import: ResMut<'w, ImageImportWindow>,
+    pub recording: ResMut<'w, RecordingWindow>,
+    pub svg_export: ResMut<'w, SvgExport>,
+    pub modals: ResMut<'w, ModalState>,
+    pub bindings: Res<'w, KeyBindings>,
+}
+
 /// Plugin for control panel systems
 pub struct ControlsPlugin;
 
 impl Plugin for ControlsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(bevy_egui::EguiPrimaryContextPass, control_panel_system);
+        app.init_resource::<RuleEditor>()
+            .init_resource::<PatternBrowser>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, control_panel_system);
     }
 }
 
 /// Main control panel system that renders the GUI controls
+#[allow(clippy::too_many_arguments)]
 pub fn control_panel_system(
-    mut commands: Commands,
     mut contexts: EguiContexts,
     mut simulation_config: ResMut<SimulationConfig>,
     mut display_config: ResMut<DisplayConfig>,
-    mut color_config: ResMut<ColorConfig>,
-    mut q_camera: Query<(&mut Projection, &GlobalTransform)>,
-    q_cells: Query<Entity, With<Alive>>,
-    mut dead_pool: ResMut<DeadCellPool>,
-    mut placement_mode: ResMut<PlacementMode>,
-    mut rle_loader: ResMut<RleLoader>,
+    color_config: Res<ColorConfig>,
+    camera_config: Res<CameraConfig>,
+    mut q_camera: Query<(&mut Projection, &mut Transform), With<Camera>>,
+    mut board: ResMut<BoardState>,
+    mut rule_editor: ResMut<RuleEditor>,
+    mut stats: ResMut<SimulationStats>,
+    history: Res<BoardHistory>,
+    mut patterns: PatternParams,
+    mut draw_tool: ResMut<DrawTool>,
+    mut panels: PanelParams,
 ) {
     let Ok(ctx) = contexts.ctx_mut() else {
         return;
     };
-    ctx.set_visuals(egui::style::Visuals::light());
 
-    let Ok((mut camera_projection, camera_transform)) = q_camera.single_mut() else {
-        eprintln!("Erreur camera: impossible d'obtenir une seule caméra");
+    // The camera is spawned at startup, so it is only missing before then
+    let Ok((mut camera_projection, mut camera_transform)) = q_camera.single_mut() else {
         return;
     };
 
@@ -46,41 +117,111 @@ pub fn control_panel_system(
         match camera_projection.as_mut() {
             Projection::Orthographic(orthographic) => {
                 let speed_slider = period_to_slider(simulation_config.period.as_secs_f32());
-                let scale_slider = scale_to_slider(orthographic.scale);
+                let scale_slider = scale_to_slider(
+                    orthographic.scale,
+                    camera_config.min_scale,
+                    camera_config.max_scale,
+                );
                 (speed_slider, scale_slider, scale_slider)
             }
             _ => return,
         };
 
     let mut speed_slider = speed_slider_init;
+    let current_rule = RuleSet::parse(&simulation_config.rule).unwrap_or_default();
+    // The simulation falls back to the sparse backend on its own otherwise
+    let hashlife_supported = hashlife_supports(&current_rule.0, &simulation_config);
+    let living_states = current_rule.states.living_states();
+    if display_config.paint_state > living_states {
+        display_config.paint_state = 1;
+    }
     let separator = |ui: &mut egui::Ui| ui.add(egui::Separator::default());
 
-    egui::Window::new("Game of Life")
+    egui::Window::new(tr("controls-title"))
+        .id(egui::Id::new("controls"))
         .resizable(false)
         .show(ctx, |ui| {
             ui.horizontal(|ui| {
-                if ui.button("Clear Grid").clicked() {
-                    simulation_config.running = false;
-                    clear_cells(&mut commands, &q_cells, &mut dead_pool);
+                if ui.button(tr("controls-clear-grid")).clicked() {
+                    panels.modals.show_reset = true;
+                }
+                if ui.button(tr("controls-random")).clicked() {
+                    panels.modals.show_random = true;
+                }
+                if ui.button(tr("controls-soup-search")).clicked() {
+                    panels.soup.open = true;
+                }
+                if ui.button(tr("controls-analyze")).clicked() {
+                    panels.analysis.requested = Some(AnalysisScope::Pattern);
+                }
+                if ui.button(tr("controls-statistics")).clicked() {
+                    panels.stats.open = true;
+                }
+                if ui.button(tr("controls-save-slots")).clicked() {
+                    panels.slots.open = true;
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button(tr("controls-save-session")).clicked() {
+                    panels.session.save_requested = true;
+                }
+                if ui.button(tr("controls-open-session")).clicked() {
+                    panels.session.open_requested = true;
+                }
+                // The browser storage stands in for the files on the web
+                if storage_available() {
+                    if ui.button(tr("controls-store-session")).clicked() {
+                        panels.session.store_requested = true;
+                    }
+                    if ui.button(tr("controls-restore-session")).clicked() {
+                        panels.session.restore_requested = true;
+                    }
+                }
+                if ui.button(tr("controls-screenshot")).clicked() {
+                    panels.screenshot.requested = true;
+                }
+                if ui.button(tr("controls-export-image")).clicked() {
+                    panels.image_export.open = true;
+                }
+                if ui.button(tr("controls-export-svg")).clicked() {
+                    panels.svg_export.requested = true;
+                }
+                if ui.button(tr("controls-import-image")).clicked() {
+                    panels.image_import.open = true;
+                }
+                if ui.button(tr("controls-record")).clicked() {
+                    panels.recording.open = true;
+                }
+                if ui.button(tr("controls-settings")).clicked() {
+                    panels.settings.open = true;
                 }
             });
 
             ui.horizontal(|ui| {
-                ui.add(
-                    egui::DragValue::new(&mut display_config.random_grid_width).suffix(" width"),
-                );
-                if ui.button("Random Cells").clicked() {
-                    let offset = -(display_config.random_grid_width as isize) / 2;
-                    let width = display_config.random_grid_width as usize;
-                    clear_cells(&mut commands, &q_cells, &mut dead_pool);
-                    generate_random_cells(
-                        &mut commands,
-                        &color_config,
-                        offset,
-                        offset,
-                        width,
-                        width,
-                    );
+                ui.label(tr("controls-draw"));
+                ui.radio_value(draw_tool.as_mut(), DrawTool::Paint, tr("controls-paint"));
+                ui.radio_value(draw_tool.as_mut(), DrawTool::Erase, tr("controls-erase"));
+                if living_states > 1 {
+                    // Generations rules have dying states rather than colors
+                    let noun = match current_rule.states {
+                        StateModel::Generations(_) => tr("controls-state"),
+                        _ => tr("controls-color"),
+                    };
+                    let mut paint_state = display_config.paint_state;
+                    egui::ComboBox::from_id_salt("paint_state")
+                        .selected_text(format!("{noun} {paint_state}"))
+                        .show_ui(ui, |ui| {
+                            for state in 1..=living_states {
+                                let [r, g, b, _] =
+                                    color_config.state_color(state).to_srgba().to_u8_array();
+                                let label = egui::RichText::new(format!("{noun} {state}"))
+                                    .color(egui::Color32::from_rgb(r, g, b));
+                                ui.selectable_value(&mut paint_state, state, label);
+                            }
+                        });
+                    if paint_state != display_config.paint_state {
+                        display_config.paint_state = paint_state;
+                    }
                 }
             });
 
@@ -88,100 +229,312 @@ pub fn control_panel_system(
             ui.vertical(|ui| {
                 ui.add(
                     egui::Slider::new(&mut speed_slider, 1.0..=100.0)
-                        .text("Speed")
+                        .text(tr_args(
+                            "controls-speed",
+                            &[(
+                                "speed",
+                                &format!("{:.1}", simulation_config.generations_per_second()),
+                            )],
+                        ))
                         .show_value(false),
                 );
                 ui.add(
                     egui::Slider::new(&mut scale_slider_val, 1.0..=100.0)
-                        .text("Camera Distance")
-                        .show_value(false)
-                        .logarithmic(true),
+                        .text(tr("controls-camera-distance"))
+                        .show_value(false),
                 );
             });
 
             separator(ui);
             ui.horizontal(|ui| {
                 let play_text = if simulation_config.running {
-                    "Pause"
+                    tr("controls-pause")
                 } else {
-                    "Start"
+                    tr("controls-start")
                 };
                 if ui.button(play_text).clicked() {
                     simulation_config.running = !simulation_config.running;
                 }
                 let next_step_btn = ui.add_enabled(
                     !simulation_config.running,
-                    egui::Button::new("Next Generation"),
+                    egui::Button::new(tr("controls-next-generation")),
                 );
                 if !simulation_config.running && next_step_btn.clicked() {
                     simulation_config.calculate_next_gen = true;
                 };
+                let step_back_btn = ui.add_enabled(
+                    !simulation_config.running && !history.is_empty(),
+                    egui::Button::new(tr("controls-step-back")),
+                );
+                if step_back_btn.clicked() {
+                    simulation_config.step_back = true;
+                }
+                let reverse_text = if simulation_config.playing_backwards {
+                    tr("controls-stop-backwards")
+                } else {
+                    tr("controls-play-backwards")
+                };
+                let reverse_btn = ui.add_enabled(
+                    !simulation_config.running && !history.is_empty(),
+                    egui::Button::new(reverse_text),
+                );
+                if reverse_btn.clicked() {
+                    simulation_config.playing_backwards = !simulation_config.playing_backwards;
+                }
             });
-
-            separator(ui);
-            ui.vertical(|ui| {
-                ui.checkbox(&mut display_config.grid_visible, "Show Grid");
+            ui.add_enabled_ui(simulation_config.backend == SimulationBackend::Sparse, |ui| {
+                ui.checkbox(&mut simulation_config.explain_steps, tr("controls-explain-steps"))
+                    .on_hover_text(tr("controls-explain-steps-hover"));
             });
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut display_config.warp_generations)
+                        .range(1..=1_000_000_000)
+                        .suffix(tr("controls-gens-suffix")),
+                );
+                if simulation_config.is_warping() {
+                    if ui.button(tr("controls-cancel-warp")).clicked() {
+                        simulation_config.cancel_warp();
+                    }
+                } else if ui.button(tr("controls-warp")).clicked() {
+                    simulation_config.warp(display_config.warp_generations);
+                }
+                let stable_btn = ui.add_enabled(
+                    simulation_config.backend == SimulationBackend::Sparse,
+                    egui::Button::new(tr("controls-run-until-stable")),
+                );
+                if stable_btn.clicked() {
+                    simulation_config.run_until_stable = true;
+                }
+            });
+            if simulation_config.is_warping() {
+                ui.add(
+                    egui::ProgressBar::new(simulation_config.warp_progress())
+                        .show_percentage(),
+                );
+            }
 
             separator(ui);
             ui.vertical(|ui| {
-                ui.label("Colors:");
-
-                // Color picker for cells
+                let rule = &simulation_config.rule;
+                match current_rule.preset_name() {
+                    Some(name) => ui.label(tr_args(
+                        "controls-rule-preset",
+                        &[("rule", rule), ("name", &name)],
+                    )),
+                    None => ui.label(tr_args("controls-rule", &[("rule", rule)])),
+                };
+                let mut preset = None;
+                egui::ComboBox::new("rule_presets", tr("controls-presets"))
+                    .selected_text(current_rule.preset_name().unwrap_or(tr("controls-custom")))
+                    .show_ui(ui, |ui| {
+                        for &(name, rulestring) in RULE_PRESETS {
+                            if ui.selectable_label(false, name).clicked() {
+                                preset = Some(rulestring);
+                            }
+                        }
+                    });
+                if let Some(rulestring) = preset {
+                    simulation_config.rule = rulestring.to_string();
+                    rule_editor.text = rulestring.to_string();
+                    rule_editor.error_message = None;
+                }
                 ui.horizontal(|ui| {
-                    ui.label("Cells:");
-                    let mut cell_color = [
-                        color_config.cell_color.to_srgba().red,
-                        color_config.cell_color.to_srgba().green,
-                        color_config.cell_color.to_srgba().blue,
-                    ];
-                    if ui.color_edit_button_rgb(&mut cell_color).changed() {
-                        color_config.cell_color =
-                            Color::srgb(cell_color[0], cell_color[1], cell_color[2]);
+                    ui.add(
+                        egui::TextEdit::singleline(&mut rule_editor.text)
+                            .desired_width(100.0)
+                            .hint_text("B3/S23"),
+                    );
+                    if ui.button(tr("controls-apply")).clicked() {
+                        match RuleSet::parse(&rule_editor.text) {
+                            Ok(rule) => {
+                                simulation_config.rule = rule.to_string();
+                                rule_editor.error_message = None;
+                            }
+                            Err(e) => rule_editor.error_message = Some(e.to_string()),
+                        }
+                    }
+                    if ui.button(tr("controls-table")).clicked() {
+                        rule_editor.table_open = true;
                     }
                 });
+                if let Some(error) = &rule_editor.error_message {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
 
-                // Color picker for background
-                ui.horizontal(|ui| {
-                    ui.label("Background:");
-                    let mut background_color = [
-                        color_config.background_color.to_srgba().red,
-                        color_config.background_color.to_srgba().green,
-                        color_config.background_color.to_srgba().blue,
-                    ];
-                    if ui.color_edit_button_rgb(&mut background_color).changed() {
-                        color_config.background_color = Color::srgb(
-                            background_color[0],
-                            background_color[1],
-                            background_color[2],
+                let mut neighborhood = current_rule.neighborhood;
+                egui::ComboBox::new("neighborhood", tr("controls-neighborhood"))
+                    .selected_text(neighborhood.to_string())
+                    .show_ui(ui, |ui| {
+                        for option in Neighborhood::ALL {
+                            ui.selectable_value(&mut neighborhood, option, option.to_string());
+                        }
+                    });
+                if neighborhood != current_rule.neighborhood {
+                    simulation_config.rule =
+                        current_rule.0.with_neighborhood(neighborhood).to_string();
+                    rule_editor.text = simulation_config.rule.clone();
+                }
+
+                egui::CollapsingHeader::new(tr("controls-noise"))
+                    .id_salt("noise")
+                    .show(ui, |ui| {
+                        let mut noise = simulation_config.noise;
+                        ui.add(
+                            egui::Slider::new(&mut noise.birth, 0.0..=1.0)
+                                .fixed_decimals(2)
+                                .text(tr("controls-birth-chance")),
+                        );
+                        ui.add(
+                            egui::Slider::new(&mut noise.survival, 0.0..=1.0)
+                                .fixed_decimals(2)
+                                .text(tr("controls-survival-chance")),
+                        );
+                        let deterministic = egui::Button::new(tr("controls-deterministic"));
+                        if ui.add_enabled(noise.is_active(), deterministic).clicked() {
+                            noise = Noise::NONE;
+                        }
+                        if noise != simulation_config.noise {
+                            simulation_config.noise = noise;
+                        }
+                    });
+            });
+
+            separator(ui);
+            ui.vertical(|ui| {
+                egui::ComboBox::new("backend", tr("controls-backend"))
+                    .selected_text(format!("{:?}", simulation_config.backend))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut simulation_config.backend,
+                            SimulationBackend::Sparse,
+                            "Sparse",
+                        );
+                        ui.add_enabled_ui(hashlife_supported, |ui| {
+                            ui.selectable_value(
+                                &mut simulation_config.backend,
+                                SimulationBackend::HashLife,
+                                "HashLife",
+                            )
+                            .on_disabled_hover_text(tr("controls-hashlife-unsupported"));
+                        });
+                        ui.selectable_value(
+                            &mut simulation_config.backend,
+                            SimulationBackend::Elementary,
+                            tr("controls-backend-elementary"),
                         );
+                        ui.selectable_value(
+                            &mut simulation_config.backend,
+                            SimulationBackend::Margolus,
+                            tr("controls-backend-margolus"),
+                        );
+                    });
+                match simulation_config.backend {
+                    SimulationBackend::HashLife => {
+                        let mut step_log2 = simulation_config.hashlife_step_log2;
+                        ui.add(egui::Slider::new(&mut step_log2, 0..=20).text(tr("controls-hashlife-step")));
+                        if step_log2 != simulation_config.hashlife_step_log2 {
+                            simulation_config.hashlife_step_log2 = step_log2;
+                        }
                     }
-                });
+                    SimulationBackend::Elementary => {
+                        ui.horizontal(|ui| {
+                            let mut rule = simulation_config.elementary_rule;
+                            ui.add(egui::DragValue::new(&mut rule).prefix(tr("controls-elementary-rule")));
+                            if rule != simulation_config.elementary_rule {
+                                simulation_config.elementary_rule = rule;
+                            }
+                            // The classic start: a single living cell
+                            if ui.button(tr("controls-seed")).clicked() {
+                                board.0.clear();
+                                board.0.set(Cell::new(0, 0), true);
+                                stats.reset();
+                            }
+                        });
+                    }
+                    SimulationBackend::Margolus => {
+                        let current = simulation_config.margolus_rule;
+                        let mut rule = current;
+                        let name = MARGOLUS_PRESETS
+                            .iter()
+                            .find(|(_, preset)| *preset == current)
+                            .map_or(tr("controls-custom"), |(name, _)| *name);
+                        egui::ComboBox::new("block_rule", tr("controls-block-rule"))
+                            .selected_text(name)
+                            .show_ui(ui, |ui| {
+                                for &(name, preset) in MARGOLUS_PRESETS {
+                                    ui.selectable_value(&mut rule, preset, name)
+                                        .on_hover_text(preset.to_string());
+                                }
+                            });
+                        if rule != current {
+                            simulation_config.margolus_rule = rule;
+                        }
+                        let partition = if margolus_parity_is_odd(stats.generation) {
+                            tr("controls-partition-odd")
+                        } else {
+                            tr("controls-partition-even")
+                        };
+                        ui.label(partition);
+                    }
+                    SimulationBackend::Sparse => topology_editor(ui, &mut simulation_config),
+                }
             });
 
             // Add pattern section
             pattern_system(
                 ui,
-                &mut placement_mode,
+                &mut patterns.placement_mode,
                 &mut simulation_config,
-                &mut rle_loader,
+                &mut patterns.rle_loader,
+                &mut patterns.browser,
+                &patterns.registry,
             );
 
             separator(ui);
             ui.vertical(|ui| {
-                let x = camera_transform.translation().x;
-                let y = camera_transform.translation().y;
-                ui.label(format!("Current Position: x: {x}, y: {y}"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("controls-go-to"));
+                    ui.add(egui::DragValue::new(&mut panels.jump_target.x).prefix("x: "));
+                    ui.add(egui::DragValue::new(&mut panels.jump_target.y).prefix("y: "));
+                    if ui.button(tr("controls-go")).clicked() {
+                        panels.jump_target.apply(&mut camera_transform);
+                    }
+                });
                 ui.add_space(5.);
-                ui.label("Click on the grid when simulation is paused!");
-                ui.label("Use arrow keys to move the camera!");
+                let keys = &panels.bindings;
+                ui.label(tr("controls-help-click"));
+                ui.label(tr_args(
+                    "controls-help-camera",
+                    &[("jump", &KeyName(keys.jump_to))],
+                ));
+                ui.label(tr("controls-help-drag"));
+                let cursor_keys = format!(
+                    "{}{}{}{}",
+                    KeyName(keys.cursor_up),
+                    KeyName(keys.cursor_left),
+                    KeyName(keys.cursor_down),
+                    KeyName(keys.cursor_right)
+                );
+                ui.label(tr_args(
+                    "controls-help-cursor",
+                    &[("keys", &cursor_keys), ("toggle", &KeyName(keys.toggle_cell))],
+                ));
+                ui.label(tr("controls-help-shortcuts"));
             });
         });
 
     // Apply camera scale changes
     if let Projection::Orthographic(orthographic) = camera_projection.as_mut() {
         if scale_slider_init != scale_slider_val {
-            orthographic.scale = slider_to_scale(scale_slider_val);
+            orthographic.scale = slider_to_scale(
+                scale_slider_val,
+                camera_config.min_scale,
+                camera_config.max_scale,
+            );
+        } else {
+            // The limits may have been tightened around the current zoom
+            orthographic.scale = camera_config.clamp_scale(orthographic.scale);
         }
     }
 
@@ -193,57 +546,56 @@ pub fn control_panel_system(
     // Handle RLE loader modal
     rle_loader_modal(
         ctx,
-        &mut rle_loader,
-        &mut placement_mode,
+        &mut patterns.rle_loader,
+        &mut patterns.placement_mode,
         &mut simulation_config,
+        &mut patterns.registry,
     );
 }
 
-/// Removes all living cells from the simulation
-fn clear_cells(
-    commands: &mut Commands,
-    q_cells: &Query<Entity, With<Alive>>,
-    dead_pool: &mut ResMut<DeadCellPool>,
-) {
-    for entity in q_cells.iter() {
-        commands
-            .entity(entity)
-            .remove::<Alive>()
-            .insert(Visibility::Hidden);
-        dead_pool.entities.push(entity);
-    }
-}
-
-/// Generates random cells in a rectangular area
-fn generate_random_cells(
-    commands: &mut Commands,
-    color_config: &ColorConfig,
-    x: isize,
-    y: isize,
-    width: usize,
-    height: usize,
-) {
-    use gol_simulation::CellPosition;
-    use rand::Rng;
-
-    let mut rng = rand::rng();
-    for coord_x in x..(x + width as isize) {
-        for coord_y in y..(y + height as isize) {
-            if rng.random_range(0..10) > 7 {
-                commands.spawn((
-                    CellPosition {
-                        x: coord_x,
-                        y: coord_y,
-                    },
-                    Alive,
-                    Sprite {
-                        color: color_config.cell_color,
-                        custom_size: Some(Vec2::new(1.0, 1.0)),
-                        ..Default::default()
-                    },
-                    Transform::from_xyz(coord_x as f32, coord_y as f32, 0.0),
-                ));
-            }
+/// Topology selector with the size of finite grids
+fn topology_editor(ui: &mut egui::Ui, simulation_config: &mut SimulationConfig) {
+    let mut topology = simulation_config.topology;
+    let (mut width, mut height) = match topology {
+        GridTopology::Infinite => (DEFAULT_GRID_SIZE, DEFAULT_GRID_SIZE),
+        GridTopology::Bounded { width, height } | GridTopology::Torus { width, height } => {
+            (width, height)
         }
+    };
+    egui::ComboBox::new("topology", tr("controls-topology"))
+        .selected_text(match topology {
+            GridTopology::Infinite => tr("controls-topology-infinite"),
+            GridTopology::Bounded { .. } => tr("controls-topology-bounded"),
+            GridTopology::Torus { .. } => tr("controls-topology-torus"),
+        })
+        .show_ui(ui, |ui| {
+            ui.selectable_value(
+                &mut topology,
+                GridTopology::Infinite,
+                tr("controls-topology-infinite"),
+            );
+            ui.selectable_value(
+                &mut topology,
+                GridTopology::Bounded { width, height },
+                tr("controls-topology-bounded"),
+            );
+            ui.selectable_value(
+                &mut topology,
+                GridTopology::Torus { width, height },
+                tr("controls-topology-torus"),
+            );
+        });
+    if topology != GridTopology::Infinite {
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut width).prefix("w: ").range(1..=100_000));
+            ui.add(egui::DragValue::new(&mut height).prefix("h: ").range(1..=100_000));
+        });
+        topology = match topology {
+            GridTopology::Torus { .. } => GridTopology::Torus { width, height },
+            _ => GridTopology::Bounded { width, height },
+        };
+    }
+    if topology != simulation_config.topology {
+        simulation_config.topology = topology;
     }
 }
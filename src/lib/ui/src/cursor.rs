@@ -0,0 +1,158 @@
+//! # Cursor Module
+//!
+//! Keyboard-only cell editing: an on-grid cursor moved with WASD and toggling
+//! the cell under it with Enter, drawn as a highlighted cell outline.
+
+use bevy::prelude::{
+    App, ButtonInput, Camera, Color, Commands, Component, DetectChanges, GlobalTransform, KeyCode,
+    Plugin, Query, Res, ResMut, Resource, Single, Sprite, Startup, Transform, Update, Vec2,
+    Visibility, With,
+};
+use bevy_egui::EguiContexts;
+use gol_config::{KeyBindings, SimulationConfig};
+use gol_core::Cell;
+use gol_simulation::{BoardState, CellPosition};
+
+/// Width of the cursor outline, in cells
+const OUTLINE_WIDTH: f32 = 0.15;
+
+/// Cell targeted by the keyboard cursor
+#[derive(Resource, Default)]
+pub struct KeyboardCursor {
+    pub position: CellPosition,
+    /// Hidden until a cursor key is pressed
+    pub visible: bool,
+}
+
+/// Marker for the outline drawn around the cursor cell
+#[derive(Component)]
+pub struct CursorOutline;
+
+/// Plugin for the keyboard cursor
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KeyboardCursor>()
+            .add_systems(Startup, spawn_cursor_outline)
+            .add_systems(Update, (keyboard_cursor_system, update_cursor_outline));
+    }
+}
+
+fn spawn_cursor_outline(mut commands: Commands) {
+    let color = Color::srgb(1.0, 0.5, 0.0);
+    let edges = [
+        (
+            Vec2::new(0.0, 0.5),
+            Vec2::new(1.0 + OUTLINE_WIDTH, OUTLINE_WIDTH),
+        ),
+        (
+            Vec2::new(0.0, -0.5),
+            Vec2::new(1.0 + OUTLINE_WIDTH, OUTLINE_WIDTH),
+        ),
+        (
+            Vec2::new(-0.5, 0.0),
+            Vec2::new(OUTLINE_WIDTH, 1.0 + OUTLINE_WIDTH),
+        ),
+        (
+            Vec2::new(0.5, 0.0),
+            Vec2::new(OUTLINE_WIDTH, 1.0 + OUTLINE_WIDTH),
+        ),
+    ];
+    commands
+        .spawn((
+            CursorOutline,
+            // Drawn above the cells and the selection
+            Transform::from_xyz(0.0, 0.0, 3.0),
+            Visibility::Hidden,
+        ))
+        .with_children(|outline| {
+            for (offset, size) in edges {
+                outline.spawn((
+                    Sprite {
+                        color,
+                        custom_size: Some(size),
+                        ..Default::default()
+                    },
+                    Transform::from_translation(offset.extend(0.0)),
+                ));
+            }
+        });
+}
+
+/// Moves the cursor with WASD and toggles the cell under it with Enter, or
+/// the keys bound to these actions
+pub fn keyboard_cursor_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    simulation_config: Res<SimulationConfig>,
+    q_camera: Query<&GlobalTransform, With<Camera>>,
+    mut board: ResMut<BoardState>,
+    mut cursor: ResMut<KeyboardCursor>,
+    mut egui_contexts: EguiContexts,
+) {
+    // Typing in a text field must not move the cursor
+    if egui_contexts
+        .ctx_mut()
+        .is_ok_and(|ctx| ctx.wants_keyboard_input())
+    {
+        return;
+    }
+    if keys.just_pressed(KeyCode::Escape) && cursor.visible {
+        cursor.visible = false;
+        return;
+    }
+
+    let mut delta = (0, 0);
+    if keys.just_pressed(bindings.cursor_up) {
+        delta.1 += 1;
+    }
+    if keys.just_pressed(bindings.cursor_down) {
+        delta.1 -= 1;
+    }
+    if keys.just_pressed(bindings.cursor_left) {
+        delta.0 -= 1;
+    }
+    if keys.just_pressed(bindings.cursor_right) {
+        delta.0 += 1;
+    }
+
+    if delta != (0, 0) {
+        if cursor.visible {
+            cursor.position.x += delta.0;
+            cursor.position.y += delta.1;
+        } else if let Ok(camera_transform) = q_camera.single() {
+            // Appear at the center of the view on the first key press
+            let center = camera_transform.translation().truncate().round();
+            cursor.position = CellPosition {
+                x: center.x as isize,
+                y: center.y as isize,
+            };
+            cursor.visible = true;
+        }
+    }
+
+    if cursor.visible && keys.just_pressed(bindings.toggle_cell) && !simulation_config.running {
+        let target = Cell::from(cursor.position);
+        let alive = board.0.contains(&target);
+        board.0.set(target, !alive);
+    }
+}
+
+/// Moves the outline to the cursor cell
+pub fn update_cursor_outline(
+    cursor: Res<KeyboardCursor>,
+    outline: Single<(&mut Transform, &mut Visibility), With<CursorOutline>>,
+) {
+    if !cursor.is_changed() {
+        return;
+    }
+    let (mut transform, mut visibility) = outline.into_inner();
+    transform.translation.x = cursor.position.x as f32;
+    transform.translation.y = cursor.position.y as f32;
+    *visibility = if cursor.visible {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+}
@@ -0,0 +1,51 @@
+//! # Focus Module
+//!
+//! Pauses the automaton while the window is unfocused or minimized, when
+//! [`SimulationConfig::pause_when_unfocused`] is set, and resumes it when
+//! the window comes back. Saves the battery of a forgotten browser tab.
+
+use bevy::prelude::{App, MessageReader, Plugin, ResMut, Resource, Update};
+use bevy::window::{WindowFocused, WindowOccluded};
+use gol_config::SimulationConfig;
+
+/// Whether the simulation was paused by the window losing focus, and so
+/// should resume with it
+#[derive(Resource, Default)]
+pub struct FocusPause {
+    pub paused: bool,
+}
+
+/// Plugin pausing the simulation in the background
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FocusPause>()
+            .add_systems(Update, focus_pause_system);
+    }
+}
+
+/// Pauses the running simulation when the window is unfocused or occluded,
+/// and resumes it when the window is back in front
+pub fn focus_pause_system(
+    mut focused: MessageReader<WindowFocused>,
+    mut occluded: MessageReader<WindowOccluded>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut focus_pause: ResMut<FocusPause>,
+) {
+    let changes = focused
+        .read()
+        .map(|event| !event.focused)
+        .chain(occluded.read().map(|event| event.occluded));
+    for hidden in changes {
+        if hidden {
+            if simulation_config.pause_when_unfocused && simulation_config.running {
+                simulation_config.running = false;
+                focus_pause.paused = true;
+            }
+        } else if focus_pause.paused {
+            simulation_config.running = true;
+            focus_pause.paused = false;
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! # Help Module
+//!
+//! Cheat sheet of the keyboard shortcuts and mouse controls, opened with `?`
+//! or F1. The shortcuts are read from the key bindings, so the list follows
+//! the settings.
+
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{KeyAction, KeyBindings, KeyName, tr};
+
+/// Controls that cannot be rebound, with their description, as message ids
+const FIXED_CONTROLS: &[(&str, &str)] = &[
+    ("help-arrow-keys", "help-move-camera"),
+    ("help-shift", "help-move-camera-faster"),
+    ("help-left-click", "help-draw-cells"),
+    ("help-right-drag", "help-erase-cells"),
+    ("help-shift-drag", "help-select-cells"),
+    ("help-ctrl-v", "help-paste-pattern"),
+    ("help-escape", "help-close-dialogs"),
+    ("help-help-keys", "help-show-help"),
+];
+
+/// Whether the help window is shown
+#[derive(Resource, Default)]
+pub struct HelpWindow {
+    pub open: bool,
+}
+
+/// Plugin for the keyboard shortcut cheat sheet
+pub struct HelpPlugin;
+
+impl Plugin for HelpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HelpWindow>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, help_window_system);
+    }
+}
+
+/// Toggles the help window with `?` or F1 and renders it while it is open
+pub fn help_window_system(
+    mut contexts: EguiContexts,
+    mut window: ResMut<HelpWindow>,
+    bindings: Res<KeyBindings>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    // `?` is read as text, as its key depends on the keyboard layout
+    let toggle = !ctx.wants_keyboard_input()
+        && ctx.input(|i| {
+            i.key_pressed(egui::Key::F1)
+                || i.events
+                    .iter()
+                    .any(|event| matches!(event, egui::Event::Text(text) if text == "?"))
+        });
+    if toggle {
+        window.open = !window.open;
+    }
+    if !window.open {
+        return;
+    }
+
+    let mut open = true;
+    egui::Window::new(tr("help-title"))
+        .id(egui::Id::new("help"))
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            egui::Grid::new("key_bindings_help")
+                .striped(true)
+                .show(ui, |ui| {
+                    for action in KeyAction::ALL {
+                        ui.strong(KeyName(bindings.key(action)).to_string());
+                        ui.label(action.label());
+                        ui.end_row();
+                    }
+                    for (control, description) in FIXED_CONTROLS {
+                        ui.strong(tr(control));
+                        ui.label(tr(description));
+                        ui.end_row();
+                    }
+                });
+            ui.weak(tr("help-rebind"));
+        });
+    if !open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        window.open = false;
+    }
+}
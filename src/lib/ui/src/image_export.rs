@@ -0,0 +1,186 @@
+//! # Image Export Module
+//!
+//! Writes the board to a PNG file, one square of pixels per cell, in the
+//! colors of the grid. Unlike a screenshot, the whole pattern is exported
+//! whatever the view, cropped to its bounding box.
+
+use bevy::color::ColorToPacked;
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource};
+use bevy::tasks::{IoTaskPool, Task, block_on, futures_lite::future};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{ColorConfig, MAX_EXPORT_SIZE, SimulationBackend, SimulationConfig, tr, tr_args};
+use gol_core::{Board, Cell};
+use gol_simulation::BoardState;
+use std::io::Cursor;
+
+use crate::toast::Toasts;
+
+/// State of the image export window
+#[derive(Resource)]
+pub struct ImageExportWindow {
+    pub open: bool,
+    /// Side of a cell in the image, in pixels
+    pub pixels_per_cell: u32,
+    /// Writes the image, `None` when the dialog was cancelled
+    task: Option<Task<Option<Result<(), String>>>>,
+}
+
+impl Default for ImageExportWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            pixels_per_cell: 1,
+            task: None,
+        }
+    }
+}
+
+/// Plugin for the image export window
+pub struct ImageExportPlugin;
+
+impl Plugin for ImageExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ImageExportWindow>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, image_export_system);
+    }
+}
+
+/// Renders the export window and writes the image when asked
+pub fn image_export_system(
+    mut contexts: EguiContexts,
+    mut window: ResMut<ImageExportWindow>,
+    board: Res<BoardState>,
+    color_config: Res<ColorConfig>,
+    simulation_config: Res<SimulationConfig>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let saved = window
+        .task
+        .as_mut()
+        .and_then(|task| block_on(future::poll_once(task)));
+    if let Some(saved) = saved {
+        window.task = None;
+        match saved {
+            Some(Ok(())) => toasts.info(tr("toast-image-saved")),
+            Some(Err(e)) => toasts.error(tr_args("toast-image-failed", &[("error", &e)])),
+            // The dialog was cancelled
+            None => {}
+        }
+    }
+
+    if !window.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = true;
+    let mut export = false;
+    egui::Window::new(tr("image-export-title"))
+        .id(egui::Id::new("image_export"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            // HashLife only keeps the visible cells on the board
+            let enabled = simulation_config.backend != SimulationBackend::HashLife;
+            if !enabled {
+                ui.label(tr("image-export-hashlife"));
+            }
+            ui.add(
+                egui::DragValue::new(&mut window.pixels_per_cell)
+                    .range(1..=32)
+                    .prefix(tr("image-export-scale"))
+                    .suffix(" px"),
+            );
+            let button = egui::Button::new(tr("image-export-save"));
+            if ui
+                .add_enabled(enabled && window.task.is_none(), button)
+                .clicked()
+            {
+                export = true;
+            }
+        });
+    if !open {
+        window.open = false;
+    }
+    if !export {
+        return;
+    }
+
+    match board_to_png(&board.0, window.pixels_per_cell, &color_config) {
+        Ok(png) => window.task = Some(save_image_file(png)),
+        Err(e) => toasts.error(tr_args("toast-image-failed", &[("error", &e)])),
+    }
+}
+
+/// Draws the bounding box of the board, `pixels_per_cell` pixels per cell,
+/// and encodes it as PNG
+pub fn board_to_png(
+    board: &Board,
+    pixels_per_cell: u32,
+    colors: &ColorConfig,
+) -> Result<Vec<u8>, String> {
+    let Some(area) = board.bounding_box() else {
+        return Err(tr("image-export-empty").to_string());
+    };
+    let pixels = board_image(board, area, pixels_per_cell, colors)?;
+    let mut png = Cursor::new(Vec::new());
+    pixels
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+    Ok(png.into_inner())
+}
+
+/// Draws the cells of the board inside the area given by its (min, max)
+/// corners, `pixels_per_cell` pixels per cell, over the background color
+pub fn board_image(
+    board: &Board,
+    (min, max): (Cell, Cell),
+    pixels_per_cell: u32,
+    colors: &ColorConfig,
+) -> Result<image::RgbaImage, String> {
+    let scale = pixels_per_cell.max(1);
+    let size = |cells: isize| u32::try_from(cells).ok()?.checked_mul(scale);
+    let (Some(width), Some(height)) = (size(max.x - min.x + 1), size(max.y - min.y + 1)) else {
+        return Err(tr("image-export-too-large").to_string());
+    };
+    if width > MAX_EXPORT_SIZE || height > MAX_EXPORT_SIZE {
+        return Err(tr("image-export-too-large").to_string());
+    }
+
+    let background = colors.background_color.to_srgba().to_u8_array();
+    let mut pixels = image::RgbaImage::from_pixel(width, height, image::Rgba(background));
+    let inside =
+        |cell: &&Cell| (min.x..=max.x).contains(&cell.x) && (min.y..=max.y).contains(&cell.y);
+    for cell in board.iter().filter(inside) {
+        let color = image::Rgba(
+            colors
+                .state_color(board.state(cell))
+                .to_srgba()
+                .to_u8_array(),
+        );
+        // Rows go down the image while y goes up the grid
+        let left = (cell.x - min.x) as u32 * scale;
+        let top = (max.y - cell.y) as u32 * scale;
+        for y in top..top + scale {
+            for x in left..left + scale {
+                pixels.put_pixel(x, y, color);
+            }
+        }
+    }
+    Ok(pixels)
+}
+
+/// Asks where to save the image and writes it in the background
+fn save_image_file(png: Vec<u8>) -> Task<Option<Result<(), String>>> {
+    IoTaskPool::get().spawn(async move {
+        let file = rfd::AsyncFileDialog::new()
+            .set_title(tr("image-export-title"))
+            .set_file_name("pattern.png")
+            .add_filter("PNG", &["png"])
+            .save_file()
+            .await?;
+        Some(file.write(&png).await.map_err(|e| e.to_string()))
+    })
+}
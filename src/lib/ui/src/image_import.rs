@@ -0,0 +1,208 @@
+//! # Image Import Module
+//!
+//! Turns a PNG or BMP picture into cells: every pixel darker than a
+//! threshold, or close to a chosen color, becomes a living cell. Logos and
+//! pixel art make starting patterns this way, placed with a click like the
+//! patterns of the library.
+
+use bevy::prelude::{App, Plugin, ResMut, Resource};
+use bevy::tasks::{IoTaskPool, Task, block_on, futures_lite::future};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{MAX_IMPORT_SIZE, SimulationConfig, tr, tr_args};
+use image::{ImageReader, RgbaImage};
+use std::io::Cursor;
+
+use crate::pattern::PlacementMode;
+use crate::toast::Toasts;
+
+/// How the pixels becoming living cells are chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageThreshold {
+    /// Pixels darker than the threshold
+    #[default]
+    Luminance,
+    /// Pixels close to the chosen color
+    Color,
+}
+
+/// State of the image import window
+#[derive(Resource)]
+pub struct ImageImportWindow {
+    pub open: bool,
+    pub mode: ImageThreshold,
+    /// Luminance under which a pixel is alive
+    pub threshold: u8,
+    /// Color of the living pixels, in color mode
+    pub color: [u8; 3],
+    /// Largest difference on each channel still matching the color
+    pub tolerance: u8,
+    /// Picture being picked and decoded, `None` when the dialog was cancelled
+    file_task: Option<Task<Option<Result<RgbaImage, String>>>>,
+}
+
+impl Default for ImageImportWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            mode: ImageThreshold::default(),
+            threshold: 128,
+            color: [0, 0, 0],
+            tolerance: 32,
+            file_task: None,
+        }
+    }
+}
+
+impl ImageImportWindow {
+    /// Whether a pixel becomes a living cell, transparent ones never do
+    pub fn is_alive(&self, [r, g, b, a]: [u8; 4]) -> bool {
+        if a < 128 {
+            return false;
+        }
+        match self.mode {
+            ImageThreshold::Luminance => {
+                let luminance = 0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b);
+                luminance < f32::from(self.threshold)
+            }
+            ImageThreshold::Color => [r, g, b]
+                .into_iter()
+                .zip(self.color)
+                .all(|(channel, target)| channel.abs_diff(target) <= self.tolerance),
+        }
+    }
+
+    /// Living cells of a picture, relative to its bottom left corner
+    pub fn image_to_cells(&self, picture: &RgbaImage) -> Vec<(i32, i32)> {
+        let height = picture.height();
+        // Rows go down the picture while y goes up the grid
+        picture
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| self.is_alive(pixel.0))
+            .map(|(x, y, _)| (x as i32, (height - 1 - y) as i32))
+            .collect()
+    }
+}
+
+/// Decodes a picture, refusing the ones larger than [`MAX_IMPORT_SIZE`]
+/// from their header, before their pixels are allocated
+pub fn decode_image(bytes: &[u8]) -> Result<RgbaImage, String> {
+    let reader = || {
+        ImageReader::new(Cursor::new(bytes))
+            .with_guessed_format()
+            .map_err(|e| e.to_string())
+    };
+    let (width, height) = reader()?.into_dimensions().map_err(|e| e.to_string())?;
+    if width > MAX_IMPORT_SIZE || height > MAX_IMPORT_SIZE {
+        return Err(tr_args(
+            "image-import-too-large",
+            &[("size", &MAX_IMPORT_SIZE)],
+        ));
+    }
+    Ok(reader()?.decode().map_err(|e| e.to_string())?.into_rgba8())
+}
+
+/// Plugin for the image import window
+pub struct ImageImportPlugin;
+
+impl Plugin for ImageImportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ImageImportWindow>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, image_import_system);
+    }
+}
+
+/// Renders the import window, and starts placing the cells of the picked
+/// picture once it is read
+pub fn image_import_system(
+    mut contexts: EguiContexts,
+    mut window: ResMut<ImageImportWindow>,
+    mut placement_mode: ResMut<PlacementMode>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let picked = window
+        .file_task
+        .as_mut()
+        .and_then(|task| block_on(future::poll_once(task)));
+    if let Some(picked) = picked {
+        window.file_task = None;
+        // The dialog was cancelled otherwise
+        if let Some(picture) = picked {
+            match picture.map(|picture| window.image_to_cells(&picture)) {
+                Ok(cells) if cells.is_empty() => toasts.warning(tr("image-import-empty")),
+                Ok(cells) => {
+                    placement_mode.place_cells(tr("image-import-label"), cells);
+                    simulation_config.running = false;
+                    window.open = false;
+                    toasts.info(tr("toast-pattern-loaded"));
+                }
+                Err(e) => toasts.error(tr_args("toast-image-import-failed", &[("error", &e)])),
+            }
+        }
+    }
+
+    if !window.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = true;
+    egui::Window::new(tr("image-import-title"))
+        .id(egui::Id::new("image_import"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut window.mode,
+                    ImageThreshold::Luminance,
+                    tr("image-import-dark"),
+                );
+                ui.radio_value(
+                    &mut window.mode,
+                    ImageThreshold::Color,
+                    tr("image-import-color"),
+                );
+            });
+            match window.mode {
+                ImageThreshold::Luminance => {
+                    ui.add(
+                        egui::Slider::new(&mut window.threshold, 1..=255)
+                            .text(tr("image-import-threshold")),
+                    );
+                }
+                ImageThreshold::Color => {
+                    ui.horizontal(|ui| {
+                        ui.color_edit_button_srgb(&mut window.color);
+                        ui.add(
+                            egui::Slider::new(&mut window.tolerance, 0..=255)
+                                .text(tr("image-import-tolerance")),
+                        );
+                    });
+                }
+            }
+            let button = egui::Button::new(tr("image-import-open"));
+            if ui.add_enabled(window.file_task.is_none(), button).clicked() {
+                window.file_task = Some(open_image_file());
+            }
+        });
+    if !open {
+        window.open = false;
+    }
+}
+
+/// Opens a native file picker, then reads and decodes the chosen picture in
+/// the background
+fn open_image_file() -> Task<Option<Result<RgbaImage, String>>> {
+    IoTaskPool::get().spawn(async {
+        let file = rfd::AsyncFileDialog::new()
+            .set_title(tr("image-import-title"))
+            .add_filter(tr("image-import-filter"), &["png", "bmp"])
+            .add_filter(tr("common-all-files"), &["*"])
+            .pick_file()
+            .await?;
+        Some(decode_image(&file.read().await))
+    })
+}
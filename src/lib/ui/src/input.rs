@@ -2,13 +2,20 @@
 //!
 //! Handles keyboard and mouse input for camera movement and cell interaction.
 
-use crate::pattern::{PlacementMode, RleLoader};
-use bevy::prelude::{Plugin, App, Resource, Update, Vec2, Transform, Visibility, Sprite, ResMut, Commands, Query, Entity, KeyCode, GlobalTransform, Projection, With, Time, Res, Camera, ButtonInput, Window, MouseButton, Without, Vec3};
+use crate::actions::UiAction;
+use crate::clipboard::ClipboardRead;
+use crate::pattern::{PlacementMode, RleLoader, load_pattern, pattern_file_dialog_system};
+use crate::toast::Toasts;
+use bevy::color::Alpha;
+use bevy::prelude::{Plugin, App, Resource, Update, Vec2, Transform, Visibility, Sprite, ResMut, Commands, Query, Entity, KeyCode, GlobalTransform, Projection, With, Time, Res, Camera, ButtonInput, Window, MouseButton, Vec3, Component, DetectChanges, MessageWriter};
 use bevy::window::PrimaryWindow;
+use bevy_egui::egui;
 use gol_config::{
-    BASE_SPEED, CameraConfig, ColorConfig, DEFAULT_SCALE, MAX_SPEED, SimulationConfig, ZOOM_STEP,
+    BASE_SPEED, CameraConfig, ColorConfig, DEFAULT_SCALE, DisplayConfig, KeyBindings, MAX_SPEED,
+    SimulationConfig, ZOOM_STEP, tr, tr_args,
 };
-use gol_simulation::{Alive, CellPosition, DeadCellPool, pattern::Patterns};
+use gol_core::{Board, Cell};
+use gol_simulation::{BoardState, CellPosition, PatternRegistry};
 
 /// Resource to track the last painted position during drag operations
 #[derive(Resource, Default)]
@@ -16,36 +23,51 @@ pub struct LastPaintedPosition {
     pub position: Option<CellPosition>,
 }
 
+/// What dragging with the left mouse button does to cells
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DrawTool {
+    /// Brings cells to life
+    #[default]
+    Paint,
+    /// Kills cells
+    Erase,
+}
+
 /// Plugin for input handling systems
 pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<LastPaintedPosition>()
+            .init_resource::<DrawTool>()
             .init_resource::<PlacementMode>()
             .init_resource::<RleLoader>()
+            .init_resource::<ClipboardRead>()
             .add_systems(
                 Update,
                 (
                     keyboard_input_system,
                     mouse_click_system,
                     reset_paint_position,
+                    pattern_file_dialog_system,
+                    placement_ghost_system,
                 ),
-            );
+            )
+            .add_systems(bevy_egui::EguiPrimaryContextPass, clipboard_paste_system);
     }
 }
 
 /// Handles keyboard input for camera movement and simulation controls
+#[allow(clippy::too_many_arguments)]
 pub fn keyboard_input_system(
     keys: Res<ButtonInput<KeyCode>>,
-    mut commands: Commands,
     mut simulation_config: ResMut<SimulationConfig>,
     mut q_camera_transform: Query<&mut Transform, With<Camera>>,
     mut q_camera: Query<(&mut Projection, &GlobalTransform)>,
-    q_cells: Query<Entity, With<Alive>>,
-    mut dead_pool: ResMut<DeadCellPool>,
     time: Res<Time>,
     mut camera_config: ResMut<CameraConfig>,
+    bindings: Res<KeyBindings>,
+    mut actions: MessageWriter<UiAction>,
 ) {
     let (mut x, mut y) = (0.0, 0.0);
 
@@ -53,16 +75,16 @@ pub fn keyboard_input_system(
         keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight);
 
     // Camera movement
-    if keys.pressed(KeyCode::ArrowLeft) || keys.pressed(KeyCode::KeyH) {
+    if keys.pressed(KeyCode::ArrowLeft) || keys.pressed(bindings.move_left) {
         x -= 1.0;
     }
-    if keys.pressed(KeyCode::ArrowRight) || keys.pressed(KeyCode::KeyL) {
+    if keys.pressed(KeyCode::ArrowRight) || keys.pressed(bindings.move_right) {
         x += 1.0;
     }
-    if keys.pressed(KeyCode::ArrowUp) || keys.pressed(KeyCode::KeyK) {
+    if keys.pressed(KeyCode::ArrowUp) || keys.pressed(bindings.move_up) {
         y += 1.0;
     }
-    if keys.pressed(KeyCode::ArrowDown) || keys.pressed(KeyCode::KeyJ) {
+    if keys.pressed(KeyCode::ArrowDown) || keys.pressed(bindings.move_down) {
         y -= 1.0;
     }
 
@@ -82,7 +104,8 @@ pub fn keyboard_input_system(
         } else {
             BASE_SPEED
         };
-        let scale_factor = (orthographic.scale / DEFAULT_SCALE).clamp(0.1, 10.0);
+        // Keep the on-screen speed roughly constant whatever the zoom level
+        let scale_factor = (orthographic.scale / DEFAULT_SCALE).max(0.1);
         base_speed * scale_factor * time.delta_secs()
     } else {
         30.0 * time.delta_secs()
@@ -94,44 +117,48 @@ pub fn keyboard_input_system(
     }
 
     // Simulation controls
-    if keys.just_pressed(KeyCode::Space) {
+    if keys.just_pressed(bindings.pause) {
         simulation_config.running = !simulation_config.running;
     }
-    if keys.just_pressed(KeyCode::KeyR) {
-        simulation_config.running = false;
-        clear_cells(&mut commands, &q_cells, &mut dead_pool);
+    if keys.just_pressed(bindings.clear) {
+        actions.write(UiAction::Clear);
     }
-    if keys.just_pressed(KeyCode::KeyN) && !simulation_config.running {
+    if keys.just_pressed(bindings.step) && !simulation_config.running {
         simulation_config.calculate_next_gen = true;
     }
+    if keys.just_pressed(bindings.step_back) && !simulation_config.running {
+        simulation_config.step_back = true;
+    }
 
     // Zoom controls
     if let Projection::Orthographic(orthographic) = camera_proj.as_mut() {
-        if keys.just_pressed(KeyCode::KeyI) {
-            orthographic.scale = (orthographic.scale / (1.0 + ZOOM_STEP)).max(DEFAULT_SCALE);
+        if keys.just_pressed(bindings.zoom_in) {
+            orthographic.scale = camera_config.clamp_scale(orthographic.scale / (1.0 + ZOOM_STEP));
         }
-        if keys.just_pressed(KeyCode::KeyO) {
-            orthographic.scale =
-                (orthographic.scale * (1.0 + ZOOM_STEP)).min(gol_config::MAX_SCALE);
+        if keys.just_pressed(bindings.zoom_out) {
+            orthographic.scale = camera_config.clamp_scale(orthographic.scale * (1.0 + ZOOM_STEP));
         }
     }
 }
 
-/// Handles mouse clicks and drag to paint/erase cells
+/// Handles mouse clicks and drag to paint/erase cells.
+///
+/// The left button uses the current [`DrawTool`] and the right button always erases.
+#[allow(clippy::too_many_arguments)]
 pub fn mouse_click_system(
-    mut commands: Commands,
     simulation_config: Res<SimulationConfig>,
-    color_config: Res<ColorConfig>,
+    display_config: Res<DisplayConfig>,
     q_windows: Query<&Window, With<PrimaryWindow>>,
     q_camera: Query<(&Camera, &GlobalTransform)>,
-    q_alive_cells: Query<(Entity, &CellPosition), With<Alive>>,
-    q_dead_cells: Query<(Entity, &CellPosition), Without<Alive>>,
-    mut dead_pool: ResMut<DeadCellPool>,
+    mut board: ResMut<BoardState>,
     buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    draw_tool: Res<DrawTool>,
     mut last_painted: ResMut<LastPaintedPosition>,
     mut placement_mode: ResMut<PlacementMode>,
-    rle_loader: Res<RleLoader>,
+    pattern_registry: Res<PatternRegistry>,
     mut egui_contexts: bevy_egui::EguiContexts,
+    mut toasts: ResMut<Toasts>,
 ) {
     if simulation_config.running {
         return;
@@ -167,109 +194,169 @@ pub fn mouse_click_system(
 
     // Check pattern placement mode FIRST (highest priority)
     if placement_mode.active {
-        if let Some(pattern_name) = &placement_mode.pattern_name {
-            if buttons.just_released(MouseButton::Left) {
-                let cells: &[(i32, i32)] = match pattern_name.as_str() {
-                    "pulsar" => Patterns::demo(),
-                    "pufferfish" => Patterns::pufferfish(),
-                    "traffic-jam" => Patterns::traffic_jam(),
-                    "custom_rle" => {
-                        // Parse the custom RLE and convert to static reference
-                        let parsed_cells = Patterns::from_rle_string(&rle_loader.rle_content);
-                        // For now, we'll need a different approach since we can't return a temporary reference
-                        place_pattern_from_vec(
-                            &mut commands,
-                            &color_config,
-                            &position_cible,
-                            &parsed_cells,
-                            &mut dead_pool,
-                        );
-                        placement_mode.active = false;
-                        placement_mode.pattern_name = None;
-                        return;
-                    }
-                    _ => return,
-                };
-
-                place_pattern(
-                    &mut commands,
-                    &color_config,
-                    &position_cible,
-                    cells,
-                    &mut dead_pool,
-                );
-                placement_mode.active = false;
-                placement_mode.pattern_name = None;
+        if buttons.just_released(MouseButton::Left) {
+            if let Some(cells) = placement_mode.cells(&pattern_registry) {
+                place_pattern(&mut board.0, &position_cible, cells);
+                toasts.info(tr_args(
+                    "toast-pattern-placed",
+                    &[("x", &new_cell.x), ("y", &new_cell.y)],
+                ));
             }
+            placement_mode.cancel();
         }
         return; // Don't allow drawing when in placement mode
     }
 
+    // Shift + left drag draws a selection instead
+    if keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight) {
+        return;
+    }
+
     // Handle both click and drag (pressed instead of just_released)
-    if !buttons.pressed(MouseButton::Left) {
+    // The right button always erases, the left one uses the selected tool
+    let erase = buttons.pressed(MouseButton::Right)
+        || (buttons.pressed(MouseButton::Left) && *draw_tool == DrawTool::Erase);
+    if !erase && !buttons.pressed(MouseButton::Left) {
         return;
     }
 
     // Skip if we already painted this position during the current drag
-    if let Some(last_pos) = last_painted.position {
-        if last_pos == new_cell {
-            return;
-        }
+    if last_painted.position == Some(new_cell) {
+        return;
     }
 
     // Update the last painted position
     last_painted.position = Some(new_cell);
 
-    // Check if there's a living cell at this position
-    for (entity, cell_position) in q_alive_cells.iter() {
-        if cell_position == &new_cell {
-            commands
-                .entity(entity)
-                .remove::<Alive>()
-                .insert(Visibility::Hidden);
-            dead_pool.entities.push(entity);
-            return;
+    // Painting over a living cell keeps its state
+    let cell = Cell::from(new_cell);
+    if erase {
+        if board.0.contains(&cell) {
+            board.0.set(cell, false);
         }
+    } else if !board.0.contains(&cell) {
+        board.0.set_state(cell, display_config.paint_state.max(1));
+    }
+}
+
+/// Loads a pattern pasted with Ctrl+V and enters placement mode, like Golly
+#[allow(clippy::too_many_arguments)]
+pub fn clipboard_paste_system(
+    mut egui_contexts: bevy_egui::EguiContexts,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut clipboard: ResMut<ClipboardRead>,
+    mut rle_loader: ResMut<RleLoader>,
+    mut placement_mode: ResMut<PlacementMode>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut registry: ResMut<PatternRegistry>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Ok(ctx) = egui_contexts.ctx_mut() else {
+        return;
+    };
+    // Pasting into a text field is handled by egui itself
+    if ctx.wants_keyboard_input() {
+        return;
     }
+    let pasted = if cfg!(target_arch = "wasm32") {
+        // The browser only hands the clipboard over through an async read
+        let modifier = keys.any_pressed([
+            KeyCode::ControlLeft,
+            KeyCode::ControlRight,
+            KeyCode::SuperLeft,
+            KeyCode::SuperRight,
+        ]);
+        if modifier && keys.just_pressed(KeyCode::KeyV) {
+            clipboard.request();
+        }
+        clipboard.poll()
+    } else {
+        // bevy_egui reads the system clipboard on Ctrl+V and forwards it as a paste event
+        ctx.input(|input| {
+            input.events.iter().find_map(|event| match event {
+                egui::Event::Paste(text) => Some(text.clone()),
+                _ => None,
+            })
+        })
+    };
+    let Some(content) = pasted else {
+        return;
+    };
 
-    // Check if there's a dead cell at this position to revive
-    for (entity, cell_position) in q_dead_cells.iter() {
-        if cell_position == &new_cell {
+    match load_pattern(
+        &content,
+        &mut placement_mode,
+        &mut simulation_config,
+        &mut registry,
+    ) {
+        Ok(()) => toasts.info(tr("toast-pattern-pasted")),
+        Err(error) => {
+            toasts.error(&error);
+            // Show what was pasted so the user can fix it in the loader
+            rle_loader.show_input = true;
+            rle_loader.rle_content = content;
+            rle_loader.error_message = Some(error);
+        }
+    }
+}
+
+/// Root of the translucent preview following the cursor in placement mode
+#[derive(Component)]
+pub struct PlacementGhost;
+
+/// Shows a ghost of the pattern being placed under the cursor
+pub fn placement_ghost_system(
+    mut commands: Commands,
+    placement_mode: Res<PlacementMode>,
+    pattern_registry: Res<PatternRegistry>,
+    color_config: Res<ColorConfig>,
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform)>,
+    mut q_ghost: Query<(Entity, &mut Transform, &mut Visibility), With<PlacementGhost>>,
+) {
+    // Rebuild the ghost whenever another pattern is picked
+    if placement_mode.is_changed() {
+        for (entity, _, _) in &q_ghost {
+            commands.entity(entity).despawn();
+        }
+        if let Some(cells) = placement_mode.cells(&pattern_registry) {
+            let color = color_config.cell_color.with_alpha(0.4);
             commands
-                .entity(entity)
-                .insert(Alive)
-                .insert(Visibility::Visible);
-            if let Some(index) = dead_pool.entities.iter().position(|&e| e == entity) {
-                dead_pool.entities.swap_remove(index);
-            }
-            return;
+                .spawn((PlacementGhost, Transform::default(), Visibility::Hidden))
+                .with_children(|ghost| {
+                    for &(dx, dy) in cells {
+                        ghost.spawn((
+                            Sprite {
+                                color,
+                                custom_size: Some(Vec2::new(1.0, 1.0)),
+                                ..Default::default()
+                            },
+                            Transform::from_xyz(dx as f32, dy as f32, 0.0),
+                        ));
+                    }
+                });
         }
+        return;
     }
 
-    // No existing cell, try to reuse from pool or create new
-    if let Some(entity) = dead_pool.entities.pop() {
-        commands
-            .entity(entity)
-            .insert(new_cell)
-            .insert(Alive)
-            .insert(Visibility::Visible)
-            .insert(Transform::from_xyz(
-                new_cell.x as f32,
-                new_cell.y as f32,
-                0.0,
-            ));
-    } else {
-        commands.spawn((
-            new_cell,
-            Alive,
-            Sprite {
-                color: color_config.cell_color,
-                custom_size: Some(Vec2::new(1.0, 1.0)),
-                ..Default::default()
-            },
-            Transform::from_xyz(new_cell.x as f32, new_cell.y as f32, 0.0),
-            Visibility::Visible,
-        ));
+    let cursor = q_windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(q_camera.single().ok())
+        .and_then(|(cursor_position, (camera, camera_transform))| {
+            camera
+                .viewport_to_world_2d(camera_transform, cursor_position)
+                .ok()
+        });
+    for (_, mut transform, mut visibility) in &mut q_ghost {
+        let Some(cursor) = cursor else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        // Same rounding as the placement itself, drawn above the cells
+        transform.translation = cursor.round().extend(2.0);
+        *visibility = Visibility::Visible;
     }
 }
 
@@ -278,94 +365,17 @@ pub fn reset_paint_position(
     buttons: Res<ButtonInput<MouseButton>>,
     mut last_painted: ResMut<LastPaintedPosition>,
 ) {
-    if buttons.just_released(MouseButton::Left) {
+    if buttons.just_released(MouseButton::Left) || buttons.just_released(MouseButton::Right) {
         last_painted.position = None;
     }
 }
 
-/// Clear all cells
-fn clear_cells(
-    commands: &mut Commands,
-    q_cells: &Query<Entity, With<Alive>>,
-    dead_pool: &mut ResMut<DeadCellPool>,
-) {
-    for entity in q_cells.iter() {
-        commands
-            .entity(entity)
-            .remove::<Alive>()
-            .insert(Visibility::Hidden);
-        dead_pool.entities.push(entity);
-    }
-}
-
-fn place_pattern(
-    commands: &mut Commands,
-    color_config: &ColorConfig,
-    position: &Vec2,
-    cells: &[(i32, i32)],
-    dead_pool: &mut ResMut<DeadCellPool>,
-) {
-    for (dx, dy) in cells {
-        let pos = CellPosition {
-            x: position.x as isize + *dx as isize,
-            y: position.y as isize + *dy as isize,
-        };
-
-        if let Some(entity) = dead_pool.entities.pop() {
-            commands
-                .entity(entity)
-                .insert(pos)
-                .insert(Alive)
-                .insert(Visibility::Visible)
-                .insert(Transform::from_xyz(pos.x as f32, pos.y as f32, 0.0));
-        } else {
-            commands.spawn((
-                pos,
-                Alive,
-                Sprite {
-                    color: color_config.cell_color,
-                    custom_size: Some(Vec2::new(1.0, 1.0)),
-                    ..Default::default()
-                },
-                Transform::from_xyz(pos.x as f32, pos.y as f32, 0.0),
-                Visibility::Visible,
-            ));
-        }
-    }
-}
-
-fn place_pattern_from_vec(
-    commands: &mut Commands,
-    color_config: &ColorConfig,
-    position: &Vec2,
-    cells: &Vec<(i32, i32)>,
-    dead_pool: &mut ResMut<DeadCellPool>,
-) {
+fn place_pattern(board: &mut Board, position: &Vec2, cells: &[(i32, i32)]) {
     for (dx, dy) in cells {
-        let pos = CellPosition {
-            x: position.x as isize + *dx as isize,
-            y: position.y as isize + *dy as isize,
-        };
-
-        if let Some(entity) = dead_pool.entities.pop() {
-            commands
-                .entity(entity)
-                .insert(pos)
-                .insert(Alive)
-                .insert(Visibility::Visible)
-                .insert(Transform::from_xyz(pos.x as f32, pos.y as f32, 0.0));
-        } else {
-            commands.spawn((
-                pos,
-                Alive,
-                Sprite {
-                    color: color_config.cell_color,
-                    custom_size: Some(Vec2::new(1.0, 1.0)),
-                    ..Default::default()
-                },
-                Transform::from_xyz(pos.x as f32, pos.y as f32, 0.0),
-                Visibility::Visible,
-            ));
-        }
+        let cell = Cell::new(
+            position.x as isize + *dx as isize,
+            position.y as isize + *dy as isize,
+        );
+        board.set(cell, true);
     }
 }
@@ -2,17 +2,73 @@
 //!
 //! User interface components and interaction handling for the Game of Life application.
 
+pub mod actions;
+pub mod analysis;
+pub mod audio;
 pub mod camera;
+pub mod clipboard;
 pub mod controls;
+pub mod cursor;
+pub mod focus;
+pub mod help;
+pub mod image_export;
+pub mod image_import;
 pub mod input;
+pub mod minimap;
 pub mod modals;
+pub mod navigation;
+pub mod neighbors;
 pub mod pattern;
+pub mod recording;
+pub mod rule_table;
+pub mod screenshot;
+pub mod selection;
+pub mod session;
+pub mod settings;
+pub mod slots;
+pub mod soup;
+pub mod stability;
+pub mod stats;
+pub mod status_bar;
+pub mod svg_export;
+pub mod theme;
+pub mod timeline;
+pub mod toast;
+pub mod touch;
 
+pub use actions::*;
+pub use analysis::*;
+pub use audio::*;
 pub use camera::*;
+pub use clipboard::*;
 pub use controls::*;
+pub use cursor::*;
+pub use focus::*;
+pub use help::*;
+pub use image_export::*;
+pub use image_import::*;
 pub use input::*;
+pub use minimap::*;
 pub use modals::*;
+pub use navigation::*;
+pub use neighbors::*;
 pub use pattern::*;
+pub use recording::*;
+pub use rule_table::*;
+pub use screenshot::*;
+pub use selection::*;
+pub use session::*;
+pub use settings::*;
+pub use slots::*;
+pub use soup::*;
+pub use stability::*;
+pub use stats::*;
+pub use status_bar::*;
+pub use svg_export::*;
+pub use theme::*;
+pub use timeline::*;
+pub use toast::*;
+pub use touch::*;
 
 use bevy::prelude::{Plugin, App};
 use bevy_egui::EguiPlugin;
@@ -26,9 +82,36 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(EguiPlugin::default())
+            .add_plugins(ActionsPlugin)
+            .add_plugins(AnalysisPlugin)
             .add_plugins(CameraPlugin)
             .add_plugins(InputPlugin)
             .add_plugins(ControlsPlugin)
-            .add_plugins(ModalsPlugin);
+            .add_plugins(HelpPlugin)
+            .add_plugins(ModalsPlugin)
+            .add_plugins(RuleTablePlugin)
+            .add_plugins(SelectionPlugin)
+            .add_plugins(SessionPlugin)
+            .add_plugins(SettingsPlugin)
+            .add_plugins(SlotsWindowPlugin)
+            .add_plugins(SoupPanelPlugin)
+            .add_plugins(StabilityReportPlugin)
+            .add_plugins(StatsWindowPlugin)
+            .add_plugins(StatusBarPlugin)
+            .add_plugins(ThemePlugin)
+            .add_plugins(ToastPlugin)
+            .add_plugins(CursorPlugin)
+            .add_plugins(NavigationPlugin)
+            .add_plugins(NeighborOverlayPlugin)
+            .add_plugins(MinimapPlugin)
+            .add_plugins(TouchPlugin)
+            .add_plugins(FocusPlugin)
+            .add_plugins(ScreenshotPlugin)
+            .add_plugins(ImageExportPlugin)
+            .add_plugins(ImageImportPlugin)
+            .add_plugins(RecordingPlugin)
+            .add_plugins(SvgExportPlugin)
+            .add_plugins(TimelinePlugin)
+            .add_plugins(AudioPlugin);
     }
 }
@@ -0,0 +1,189 @@
+//! # Minimap Module
+//!
+//! Corner overview of the whole population: the bounding box of alive cells
+//! rendered at low resolution, with the area seen by the camera outlined.
+//! Clicking the minimap centers the camera on that spot.
+
+use bevy::color::ColorToPacked;
+use bevy::prelude::{App, Camera, Plugin, Query, Res, ResMut, Resource, Transform, With};
+use bevy_egui::{
+    EguiContexts,
+    egui::{self, Color32},
+};
+use gol_config::{ColorConfig, DisplayConfig, SimulationBackend, SimulationConfig};
+use gol_simulation::{BoardState, CellPosition, HashLifeUniverse, SimulationViewport};
+
+/// Side length of the minimap, in points
+const MINIMAP_SIZE: f32 = 160.0;
+/// Maximum number of minimap pixels along the longest side
+const MINIMAP_RESOLUTION: usize = 64;
+/// Number of frames between two refreshes of the minimap
+const REFRESH_INTERVAL: u32 = 10;
+
+/// Downsampled picture of the population, refreshed every few frames
+#[derive(Resource, Default)]
+pub struct Minimap {
+    /// Bottom-left and top-right corners of the alive cells, if any
+    bounds: Option<(CellPosition, CellPosition)>,
+    /// Cells covered by one minimap pixel along each axis
+    scale: isize,
+    width: usize,
+    height: usize,
+    /// Row-major occupancy, top row first
+    pixels: Vec<bool>,
+    frames_until_refresh: u32,
+}
+
+impl Minimap {
+    /// Rebuilds the picture from the given alive cells
+    fn rebuild(&mut self, cells: &[(isize, isize)]) {
+        let mut bounds: Option<(CellPosition, CellPosition)> = None;
+        for &(x, y) in cells {
+            let (min, max) = bounds.get_or_insert((CellPosition { x, y }, CellPosition { x, y }));
+            min.x = min.x.min(x);
+            min.y = min.y.min(y);
+            max.x = max.x.max(x);
+            max.y = max.y.max(y);
+        }
+        self.bounds = bounds;
+        let Some((min, max)) = bounds else {
+            self.pixels.clear();
+            return;
+        };
+
+        let span = (max.x - min.x + 1).max(max.y - min.y + 1);
+        self.scale = (span + MINIMAP_RESOLUTION as isize - 1) / MINIMAP_RESOLUTION as isize;
+        self.width = ((max.x - min.x) / self.scale + 1) as usize;
+        self.height = ((max.y - min.y) / self.scale + 1) as usize;
+        self.pixels = vec![false; self.width * self.height];
+        for &(x, y) in cells {
+            let col = ((x - min.x) / self.scale) as usize;
+            let row = ((max.y - y) / self.scale) as usize;
+            self.pixels[row * self.width + col] = true;
+        }
+    }
+}
+
+/// Plugin for the population minimap
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Minimap>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, minimap_system);
+    }
+}
+
+/// Refreshes and draws the minimap in the bottom-right corner
+#[allow(clippy::too_many_arguments)]
+pub fn minimap_system(
+    mut contexts: EguiContexts,
+    display_config: Res<DisplayConfig>,
+    color_config: Res<ColorConfig>,
+    simulation_config: Res<SimulationConfig>,
+    viewport: Res<SimulationViewport>,
+    hashlife: Res<HashLifeUniverse>,
+    board: Res<BoardState>,
+    mut q_camera: Query<&mut Transform, With<Camera>>,
+    mut minimap: ResMut<Minimap>,
+) {
+    if !display_config.minimap_visible {
+        return;
+    }
+
+    if minimap.frames_until_refresh == 0 {
+        minimap.frames_until_refresh = REFRESH_INTERVAL;
+        let cells: Vec<_> = if simulation_config.backend == SimulationBackend::HashLife {
+            // The board only mirrors the viewport, the universe holds everything
+            let cells = hashlife.universe.cells(None);
+            cells
+                .iter()
+                .map(|&(x, y)| (x as isize, y as isize))
+                .collect()
+        } else {
+            board.0.iter().map(|cell| (cell.x, cell.y)).collect()
+        };
+        minimap.rebuild(&cells);
+    } else {
+        minimap.frames_until_refresh -= 1;
+    }
+
+    let Some((min, max)) = minimap.bounds else {
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let pixel_size = MINIMAP_SIZE / minimap.width.max(minimap.height) as f32;
+    let [r, g, b, _] = color_config.cell_color.to_srgba().to_u8_array();
+    let cell_color = Color32::from_rgb(r, g, b);
+    let [r, g, b, _] = color_config.background_color.to_srgba().to_u8_array();
+    let background = Color32::from_rgb(r, g, b);
+
+    egui::Area::new(egui::Id::new("minimap"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0))
+        .show(ctx, |ui| {
+            let (response, painter) = ui.allocate_painter(
+                egui::vec2(
+                    minimap.width as f32 * pixel_size,
+                    minimap.height as f32 * pixel_size,
+                ),
+                egui::Sense::click(),
+            );
+            let origin = response.rect.min;
+            let frame = response.rect.expand(2.0);
+            painter.rect_filled(frame, 2.0, background);
+            painter.rect_stroke(
+                frame,
+                2.0,
+                egui::Stroke::new(1.0_f32, Color32::GRAY),
+                egui::StrokeKind::Outside,
+            );
+            for row in 0..minimap.height {
+                for col in 0..minimap.width {
+                    if minimap.pixels[row * minimap.width + col] {
+                        let pos = origin + egui::vec2(col as f32, row as f32) * pixel_size;
+                        painter.rect_filled(
+                            egui::Rect::from_min_size(pos, egui::Vec2::splat(pixel_size)),
+                            0.0,
+                            cell_color,
+                        );
+                    }
+                }
+            }
+
+            // Cell coordinates to minimap points, the top row holding the largest y
+            let scale = minimap.scale as f32;
+            let to_point = |x: isize, y: isize| {
+                origin
+                    + egui::vec2(
+                        (x - min.x) as f32 / scale * pixel_size,
+                        (max.y - y) as f32 / scale * pixel_size,
+                    )
+            };
+            let view = egui::Rect::from_two_pos(
+                to_point(viewport.min.x, viewport.max.y),
+                to_point(viewport.max.x + 1, viewport.min.y - 1),
+            );
+            if view.intersects(frame) {
+                painter.rect_stroke(
+                    view.intersect(frame),
+                    0.0,
+                    egui::Stroke::new(1.5_f32, Color32::from_rgb(255, 128, 0)),
+                    egui::StrokeKind::Middle,
+                );
+            }
+
+            if let Some(click) = response
+                .interact_pointer_pos()
+                .filter(|_| response.clicked())
+            {
+                let offset = (click - origin) / pixel_size * scale;
+                if let Ok(mut camera_transform) = q_camera.single_mut() {
+                    camera_transform.translation.x = min.x as f32 + offset.x;
+                    camera_transform.translation.y = max.y as f32 - offset.y;
+                }
+            }
+        });
+}
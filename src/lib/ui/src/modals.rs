@@ -1,8 +1,11 @@
 //! # Modals Module
 //!
-//! Modal dialogs for confirmation and input.
-use bevy::prelude::{App, Plugin, ResMut, Resource};
+//! Modal dialogs for confirmation and input. Confirming sends the matching
+//! [`UiAction`].
+use crate::actions::UiAction;
+use bevy::prelude::{App, MessageWriter, Plugin, ResMut, Resource};
 use bevy_egui::{EguiContexts, egui};
+use gol_config::{DisplayConfig, RandomShape, tr};
 
 /// State for managing modal windows
 #[derive(Default, Resource)]
@@ -22,7 +25,12 @@ impl Plugin for ModalsPlugin {
 }
 
 /// System that handles modal dialog rendering and interaction
-pub fn modal_system(mut contexts: EguiContexts, mut modal_state: ResMut<ModalState>) {
+pub fn modal_system(
+    mut contexts: EguiContexts,
+    mut modal_state: ResMut<ModalState>,
+    mut display_config: ResMut<DisplayConfig>,
+    mut actions: MessageWriter<UiAction>,
+) {
     let Ok(ctx) = contexts.ctx_mut() else {
         return;
     };
@@ -31,29 +39,31 @@ pub fn modal_system(mut contexts: EguiContexts, mut modal_state: ResMut<ModalSta
     if modal_state.show_reset {
         render_overlay(ctx);
 
-        egui::Window::new("⚠ Kill all cells!")
+        egui::Window::new(tr("modal-reset-title"))
+            .id(egui::Id::new("reset_modal"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
             .show(ctx, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.add_space(10.0);
-                    ui.label("Are you sure you want to kill all cells?");
+                    ui.label(tr("modal-reset-question"));
                     ui.add_space(15.0);
 
                     ui.horizontal(|ui| {
                         ui.spacing_mut().button_padding = egui::Vec2::new(20.0, 10.0);
 
-                        if ui.button("No").clicked() {
+                        if ui.button(tr("common-no")).clicked() {
                             modal_state.show_reset = false;
                         }
 
                         ui.add_space(10.0);
 
-                        let delete_btn =
-                            egui::Button::new("Yes").fill(egui::Color32::from_rgb(180, 50, 50));
+                        let delete_btn = egui::Button::new(tr("common-yes"))
+                            .fill(egui::Color32::from_rgb(180, 50, 50));
                         if ui.add(delete_btn).clicked() {
                             modal_state.show_reset = false;
+                            actions.write(UiAction::Clear);
                         }
                     });
                     ui.add_space(5.0);
@@ -65,32 +75,59 @@ pub fn modal_system(mut contexts: EguiContexts, mut modal_state: ResMut<ModalSta
     if modal_state.show_random {
         render_overlay(ctx);
 
-        egui::Window::new("Random Generation")
+        egui::Window::new(tr("modal-random-title"))
+            .id(egui::Id::new("random_modal"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
             .show(ctx, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.add_space(10.0);
-                    ui.label("Fill the grid with random cells?");
+                    ui.label(tr("modal-random-question"));
                     ui.add_space(5.0);
-                    ui.label("Grid size: 50×50"); // TODO: Get from config
+                    ui.label(tr("modal-random-centered"));
+                    ui.add(
+                        egui::DragValue::new(&mut display_config.random_size)
+                            .range(1..=1000)
+                            .prefix(tr("modal-random-size"))
+                            .suffix(tr("modal-random-cells")),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut display_config.random_density, 0..=100)
+                            .suffix("%")
+                            .text(tr("random-density")),
+                    );
+                    egui::ComboBox::from_id_salt("random_shape")
+                        .selected_text(display_config.random_shape.to_string())
+                        .show_ui(ui, |ui| {
+                            for shape in RandomShape::ALL {
+                                ui.selectable_value(
+                                    &mut display_config.random_shape,
+                                    shape,
+                                    shape.to_string(),
+                                );
+                            }
+                        });
+                    ui.add(
+                        egui::DragValue::new(&mut display_config.random_seed)
+                            .prefix(tr("random-seed")),
+                    );
                     ui.add_space(15.0);
 
                     ui.horizontal(|ui| {
                         ui.spacing_mut().button_padding = egui::Vec2::new(20.0, 10.0);
 
-                        if ui.button("No").clicked() {
+                        if ui.button(tr("common-no")).clicked() {
                             modal_state.show_random = false;
                         }
 
                         ui.add_space(10.0);
 
-                        let generate_btn =
-                            egui::Button::new("Yes").fill(egui::Color32::from_rgb(50, 100, 180));
+                        let generate_btn = egui::Button::new(tr("common-yes"))
+                            .fill(egui::Color32::from_rgb(50, 100, 180));
                         if ui.add(generate_btn).clicked() {
                             modal_state.show_random = false;
-                            // The actual generation will be handled by the controls module
+                            actions.write(UiAction::Randomize);
                         }
                     });
                     ui.add_space(5.0);
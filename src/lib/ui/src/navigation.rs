@@ -0,0 +1,89 @@
+//! # Navigation Module
+//!
+//! Teleports the camera to given cell coordinates, from the control panel or
+//! from a small dialog opened with `G`.
+
+use bevy::prelude::{
+    App, ButtonInput, Camera, KeyCode, Plugin, Query, Res, ResMut, Resource, Transform, With,
+};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{KeyBindings, tr};
+
+/// Cell coordinates the camera can jump to
+#[derive(Resource, Default)]
+pub struct JumpTarget {
+    pub x: isize,
+    pub y: isize,
+    /// Whether the `G` dialog is open
+    pub dialog_open: bool,
+}
+
+impl JumpTarget {
+    /// Centers the camera on the target cell
+    pub fn apply(&self, camera_transform: &mut Transform) {
+        camera_transform.translation.x = self.x as f32;
+        camera_transform.translation.y = self.y as f32;
+    }
+}
+
+/// Plugin for camera navigation helpers
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<JumpTarget>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, jump_dialog_system);
+    }
+}
+
+/// Opens the jump dialog with `G`, or the key bound to it, and moves the
+/// camera when confirmed
+pub fn jump_dialog_system(
+    mut contexts: EguiContexts,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut jump_target: ResMut<JumpTarget>,
+    mut q_camera: Query<&mut Transform, With<Camera>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if !ctx.wants_keyboard_input() && keys.just_pressed(bindings.jump_to) {
+        jump_target.dialog_open = true;
+    }
+    if !jump_target.dialog_open {
+        return;
+    }
+
+    let mut go = false;
+    let mut close = false;
+    egui::Window::new(tr("navigation-title"))
+        .id(egui::Id::new("navigation"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut jump_target.x).prefix("x: "));
+                ui.add(egui::DragValue::new(&mut jump_target.y).prefix("y: "));
+            });
+            ui.horizontal(|ui| {
+                close = ui.button(tr("common-cancel")).clicked();
+                go = ui.button(tr("controls-go")).clicked()
+                    || ui.input(|i| i.key_pressed(egui::Key::Enter));
+            });
+        });
+    if keys.just_pressed(KeyCode::Escape) {
+        close = true;
+    }
+
+    if go {
+        if let Ok(mut camera_transform) = q_camera.single_mut() {
+            jump_target.apply(&mut camera_transform);
+        }
+        close = true;
+    }
+    if close {
+        jump_target.dialog_open = false;
+    }
+}
@@ -0,0 +1,122 @@
+//! # Neighbors Module
+//!
+//! Teaching overlay writing the number of living neighbors in the cells
+//! around the mouse cursor while the simulation is paused, so the rule
+//! deciding the next generation can be followed cell by cell.
+
+use bevy::prelude::{App, Camera, GlobalTransform, Plugin, Query, Res, Vec3, Window, With};
+use bevy::window::PrimaryWindow;
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{DisplayConfig, SimulationBackend, SimulationConfig};
+use gol_core::{Cell, StateModel};
+use gol_simulation::{BoardState, RuleSet};
+
+/// Cells labeled on each side of the cursor
+const OVERLAY_RADIUS: isize = 4;
+/// Smallest size of a cell on screen, in points, for its label to fit
+const MIN_CELL_SIZE: f32 = 12.0;
+/// Color of the labels, the same as the keyboard cursor outline
+const LABEL_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 128, 0);
+
+/// Plugin for the neighbor count overlay
+pub struct NeighborOverlayPlugin;
+
+impl Plugin for NeighborOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(bevy_egui::EguiPrimaryContextPass, neighbor_counts_system);
+    }
+}
+
+/// Writes the neighbor count of each cell around the cursor, skipping dead
+/// cells without neighbors.
+///
+/// Counts follow the neighborhood and topology of the current rule. Cells
+/// dying through the states of a Generations rule are not counted, as in the
+/// simulation.
+pub fn neighbor_counts_system(
+    mut contexts: EguiContexts,
+    display_config: Res<DisplayConfig>,
+    simulation_config: Res<SimulationConfig>,
+    rule: Res<RuleSet>,
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform)>,
+    board: Res<BoardState>,
+) {
+    let counted_backend = matches!(
+        simulation_config.backend,
+        SimulationBackend::Sparse | SimulationBackend::HashLife
+    );
+    if !display_config.show_neighbor_counts || simulation_config.running || !counted_backend {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if ctx.is_pointer_over_area() {
+        return;
+    }
+    let Some(cursor_position) = q_windows.single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = q_camera.single() else {
+        return;
+    };
+    let Ok(world) = camera.viewport_to_world_2d(camera_transform, cursor_position) else {
+        return;
+    };
+    let to_screen = |cell: Cell| {
+        let world = Vec3::new(cell.x as f32, cell.y as f32, 0.0);
+        camera
+            .world_to_viewport(camera_transform, world)
+            .ok()
+            .map(|pos| egui::pos2(pos.x, pos.y))
+    };
+
+    let center = Cell::new(world.x.round() as isize, world.y.round() as isize);
+    let (Some(origin), Some(next)) = (
+        to_screen(center),
+        to_screen(Cell::new(center.x + 1, center.y)),
+    ) else {
+        return;
+    };
+    let cell_size = origin.distance(next);
+    if cell_size < MIN_CELL_SIZE {
+        return;
+    }
+
+    let generations = matches!(rule.0.states, StateModel::Generations(_));
+    // Dying cells of Generations rules do not count as neighbors
+    let alive = |cell: &Cell| match board.0.state(cell) {
+        0 => false,
+        state => !generations || state <= 1,
+    };
+    let topology = simulation_config.topology;
+    let font = egui::FontId::monospace(cell_size * 0.6);
+    let painter = ctx.layer_painter(egui::LayerId::background());
+    for y in center.y - OVERLAY_RADIUS..=center.y + OVERLAY_RADIUS {
+        for x in center.x - OVERLAY_RADIUS..=center.x + OVERLAY_RADIUS {
+            let cell = Cell::new(x, y);
+            let count = rule
+                .0
+                .neighborhood
+                .offsets()
+                .iter()
+                .filter_map(|&(dx, dy)| topology.wrap(Cell::new(x + dx, y + dy)))
+                .filter(|neighbor| alive(neighbor))
+                .count();
+            if count == 0 && !alive(&cell) {
+                continue;
+            }
+            let Some(pos) = to_screen(cell) else {
+                continue;
+            };
+            painter.text(
+                pos,
+                egui::Align2::CENTER_CENTER,
+                count.to_string(),
+                font.clone(),
+                LABEL_COLOR,
+            );
+        }
+    }
+}
@@ -1,11 +1,57 @@
+use crate::toast::Toasts;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::{ResMut, Resource};
+use bevy::tasks::{IoTaskPool, Task, block_on, futures_lite::future};
 use bevy_egui::egui;
-use gol_config::SimulationConfig;
+use gol_config::{SimulationConfig, tr, tr_args};
+use gol_simulation::{
+    PatternCategory, PatternRegistry, RuleSet, THUMBNAIL_SIZE, Thumbnail, pattern::Patterns,
+};
+
+/// Side of a pattern thumbnail in the browser, in points
+const THUMBNAIL_PIXELS: f32 = 32.0;
 
 #[derive(Resource, Default)]
 pub struct PlacementMode {
     pub active: bool,
     pub pattern_name: Option<String>,
+    /// Cells to place instead of a registered pattern, such as a pasted selection
+    pub cells: Option<Vec<(i32, i32)>>,
+}
+
+impl PlacementMode {
+    /// Starts placing a pattern of the registry
+    pub fn place_pattern(&mut self, name: String) {
+        self.active = true;
+        self.pattern_name = Some(name);
+        self.cells = None;
+    }
+
+    /// Starts placing arbitrary cells, shown under the given label
+    pub fn place_cells(&mut self, label: &str, cells: Vec<(i32, i32)>) {
+        self.active = true;
+        self.pattern_name = Some(label.to_string());
+        self.cells = Some(cells);
+    }
+
+    /// Leaves placement mode
+    pub fn cancel(&mut self) {
+        self.active = false;
+        self.pattern_name = None;
+        self.cells = None;
+    }
+
+    /// Cells of the pattern being placed
+    pub fn cells<'a>(&'a self, registry: &'a PatternRegistry) -> Option<&'a [(i32, i32)]> {
+        if !self.active {
+            return None;
+        }
+        match (&self.cells, &self.pattern_name) {
+            (Some(cells), _) => Some(cells),
+            (None, Some(name)) => registry.get(name).map(|entry| entry.cells.as_slice()),
+            (None, None) => None,
+        }
+    }
 }
 
 #[derive(Resource, Default)]
@@ -13,6 +59,23 @@ pub struct RleLoader {
     pub rle_content: String,
     pub show_input: bool,
     pub error_message: Option<String>,
+    /// File being picked and read through the native file dialog
+    pub file_task: Option<Task<Option<String>>>,
+}
+
+/// Pattern resources used by the control panel
+#[derive(SystemParam)]
+pub struct PatternParams<'w> {
+    pub placement_mode: ResMut<'w, PlacementMode>,
+    pub rle_loader: ResMut<'w, RleLoader>,
+    pub browser: ResMut<'w, PatternBrowser>,
+    pub registry: ResMut<'w, PatternRegistry>,
+}
+
+/// Search state of the pattern browser
+#[derive(Resource, Default)]
+pub struct PatternBrowser {
+    pub search: String,
 }
 
 pub fn pattern_system(
@@ -20,53 +83,105 @@ pub fn pattern_system(
     placement_mode: &mut ResMut<PlacementMode>,
     simulation_config: &mut ResMut<SimulationConfig>,
     rle_loader: &mut ResMut<RleLoader>,
+    browser: &mut ResMut<PatternBrowser>,
+    registry: &PatternRegistry,
 ) {
     ui.separator();
     ui.vertical(|ui| {
-        ui.label("Patterns:");
-        ui.horizontal_wrapped(|ui| {
-            if ui.button("pulsar").clicked() {
-                placement_mode.active = true;
-                placement_mode.pattern_name = Some("pulsar".to_string());
-                simulation_config.running = false;
-            }
-            if ui.button("pufferfish").clicked() {
-                placement_mode.active = true;
-                placement_mode.pattern_name = Some("pufferfish".to_string());
-                simulation_config.running = false;
-            }
-            if ui.button("traffic-jam").clicked() {
-                placement_mode.active = true;
-                placement_mode.pattern_name = Some("traffic-jam".to_string());
-                simulation_config.running = false;
-            }
-            if ui.button("Load RLE").clicked() {
+        ui.horizontal(|ui| {
+            ui.label(tr("pattern-patterns"));
+            ui.add(
+                egui::TextEdit::singleline(&mut browser.search)
+                    .desired_width(120.0)
+                    .hint_text(tr("pattern-search")),
+            );
+            if ui.button(tr("pattern-load")).clicked() {
                 rle_loader.show_input = true;
                 rle_loader.rle_content.clear();
                 rle_loader.error_message = None;
             }
         });
 
+        egui::ScrollArea::vertical()
+            .id_salt("pattern_browser")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                let searching = !browser.search.trim().is_empty();
+                for category in PatternCategory::ALL {
+                    let mut entries = registry.search(category, &browser.search).peekable();
+                    if entries.peek().is_none() {
+                        continue;
+                    }
+                    egui::CollapsingHeader::new(category.to_string())
+                        .id_salt(category)
+                        .default_open(true)
+                        .open(searching.then_some(true))
+                        .show(ui, |ui| {
+                            for entry in entries {
+                                ui.horizontal(|ui| {
+                                    let thumbnail = thumbnail_widget(ui, &entry.thumbnail);
+                                    if ui.button(&entry.name).clicked() || thumbnail.clicked() {
+                                        placement_mode.place_pattern(entry.name.clone());
+                                        simulation_config.running = false;
+                                    }
+                                });
+                            }
+                        });
+                }
+            });
+
         if placement_mode.active {
             ui.colored_label(
                 egui::Color32::GREEN,
-                format!(
-                    "Click to place: {}",
-                    placement_mode.pattern_name.as_ref().unwrap()
+                tr_args(
+                    "pattern-click-to-place",
+                    &[("name", placement_mode.pattern_name.as_ref().unwrap())],
                 ),
             );
-            if ui.button("Cancel").clicked() {
-                placement_mode.active = false;
+            if ui.button(tr("common-cancel")).clicked() {
+                placement_mode.cancel();
             }
         }
     });
 }
 
+/// Paints a pattern thumbnail in a fixed-size square
+fn thumbnail_widget(ui: &mut egui::Ui, thumbnail: &Thumbnail) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(
+        egui::Vec2::splat(THUMBNAIL_PIXELS),
+        egui::Sense::click(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, egui::CornerRadius::same(2), egui::Color32::from_gray(30));
+
+    let pixel = THUMBNAIL_PIXELS / THUMBNAIL_SIZE as f32;
+    // Center the pattern when it is not square
+    let offset = egui::vec2(
+        (THUMBNAIL_SIZE - thumbnail.width) as f32,
+        (THUMBNAIL_SIZE - thumbnail.height) as f32,
+    ) * pixel
+        / 2.0;
+    for row in 0..thumbnail.height {
+        for column in 0..thumbnail.width {
+            if thumbnail.get(column, row) {
+                let min = rect.min + offset + egui::vec2(column as f32, row as f32) * pixel;
+                painter.rect_filled(
+                    egui::Rect::from_min_size(min, egui::Vec2::splat(pixel)),
+                    egui::CornerRadius::ZERO,
+                    egui::Color32::WHITE,
+                );
+            }
+        }
+    }
+    response.on_hover_cursor(egui::CursorIcon::PointingHand)
+}
+
 pub fn rle_loader_modal(
     ctx: &egui::Context,
     rle_loader: &mut ResMut<RleLoader>,
     placement_mode: &mut ResMut<PlacementMode>,
     simulation_config: &mut ResMut<SimulationConfig>,
+    registry: &mut ResMut<PatternRegistry>,
 ) {
     if !rle_loader.show_input {
         return;
@@ -85,7 +200,8 @@ pub fn rle_loader_modal(
             );
         });
 
-    egui::Window::new("Load RLE Pattern")
+    egui::Window::new(tr("pattern-load-title"))
+        .id(egui::Id::new("rle_loader"))
         .collapsible(false)
         .resizable(true)
         .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
@@ -93,7 +209,7 @@ pub fn rle_loader_modal(
         .max_height(ctx.content_rect().height() * 0.8)
         .show(ctx, |ui| {
             ui.vertical(|ui| {
-                ui.label("Paste your RLE pattern content:");
+                ui.label(tr("pattern-paste-prompt"));
                 ui.add_space(10.0);
 
                 // ScrollArea pour gérer le contenu trop grand
@@ -103,7 +219,7 @@ pub fn rle_loader_modal(
                         let text_edit = egui::TextEdit::multiline(&mut rle_loader.rle_content)
                             .desired_width(f32::INFINITY)
                             .desired_rows(12)
-                            .hint_text("Example: bo$2bo$3o!");
+                            .hint_text(tr("pattern-paste-hint"));
 
                         ui.add(text_edit);
                     });
@@ -115,7 +231,8 @@ pub fn rle_loader_modal(
 
                 ui.add_space(10.0);
                 ui.horizontal(|ui| {
-                    if ui.button("Cancel").clicked() {
+                    if ui.button(tr("common-cancel")).clicked() {
+                        rle_loader.file_task = None;
                         rle_loader.show_input = false;
                         rle_loader.rle_content.clear();
                         rle_loader.error_message = None;
@@ -123,24 +240,35 @@ pub fn rle_loader_modal(
 
                     ui.add_space(10.0);
 
-                    let load_btn = egui::Button::new("Load Pattern")
+                    let picking = rle_loader.file_task.is_some();
+                    if ui
+                        .add_enabled(!picking, egui::Button::new(tr("pattern-open-file")))
+                        .clicked()
+                    {
+                        rle_loader.file_task = Some(open_pattern_file());
+                    }
+
+                    ui.add_space(10.0);
+
+                    let load_btn = egui::Button::new(tr("pattern-load"))
                         .fill(egui::Color32::from_rgb(50, 100, 180));
 
                     if ui.add(load_btn).clicked() {
                         if rle_loader.rle_content.trim().is_empty() {
-                            rle_loader.error_message = Some("Please enter RLE content".to_string());
+                            rle_loader.error_message = Some(tr("pattern-empty").to_string());
                         } else {
-                            // Validate RLE format (basic check)
-                            if rle_loader.rle_content.contains('!') {
-                                // Close modal and activate placement mode
-                                rle_loader.show_input = false;
-                                rle_loader.error_message = None;
-                                placement_mode.active = true;
-                                placement_mode.pattern_name = Some("custom_rle".to_string());
-                                simulation_config.running = false;
-                            } else {
-                                rle_loader.error_message =
-                                    Some("Invalid RLE format (missing '!') dumbass !".to_string());
+                            match load_pattern(
+                                &rle_loader.rle_content,
+                                placement_mode,
+                                simulation_config,
+                                registry,
+                            ) {
+                                Ok(()) => {
+                                    // Close modal, placement mode is now active
+                                    rle_loader.show_input = false;
+                                    rle_loader.error_message = None;
+                                }
+                                Err(error) => rle_loader.error_message = Some(error),
                             }
                         }
                     }
@@ -148,3 +276,84 @@ pub fn rle_loader_modal(
             });
         });
 }
+
+/// Parses pattern content, registers it and enters placement mode
+pub fn load_pattern(
+    content: &str,
+    placement_mode: &mut PlacementMode,
+    simulation_config: &mut SimulationConfig,
+    registry: &mut PatternRegistry,
+) -> Result<(), String> {
+    let pattern = Patterns::from_string(content).map_err(|e| tr_args("pattern-invalid", &[("error", &e)]))?;
+    if let Some(rule) = pattern.rule.as_deref() {
+        RuleSet::parse(rule)
+            .map_err(|e| tr_args("pattern-unsupported-rule", &[("rule", &rule), ("error", &e)]))?;
+    }
+
+    if let Some(rule) = pattern.rule {
+        simulation_config.rule = rule;
+    }
+    let name = pattern.name.unwrap_or_else(|| {
+        let loaded = registry
+            .iter()
+            .filter(|entry| entry.category == PatternCategory::Loaded)
+            .count();
+        format!("custom-{}", loaded + 1)
+    });
+    registry.register(name.clone(), PatternCategory::Loaded, pattern.cells);
+    placement_mode.place_pattern(name);
+    simulation_config.running = false;
+    Ok(())
+}
+
+/// Opens a native file picker and reads the chosen pattern file in the background
+fn open_pattern_file() -> Task<Option<String>> {
+    IoTaskPool::get().spawn(async {
+        let file = rfd::AsyncFileDialog::new()
+            .set_title(tr("pattern-open-title"))
+            .add_filter(tr("pattern-filter"), &["rle", "cells", "lif", "life", "mc"])
+            .add_filter(tr("common-all-files"), &["*"])
+            .pick_file()
+            .await?;
+        Some(String::from_utf8_lossy(&file.read().await).into_owned())
+    })
+}
+
+/// Loads the pattern file picked in the loader modal once it has been read
+pub fn pattern_file_dialog_system(
+    mut rle_loader: ResMut<RleLoader>,
+    mut placement_mode: ResMut<PlacementMode>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut registry: ResMut<PatternRegistry>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let Some(task) = rle_loader.file_task.as_mut() else {
+        return;
+    };
+    let Some(content) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    rle_loader.file_task = None;
+
+    // The dialog was cancelled
+    let Some(content) = content else {
+        return;
+    };
+    match load_pattern(
+        &content,
+        &mut placement_mode,
+        &mut simulation_config,
+        &mut registry,
+    ) {
+        Ok(()) => {
+            rle_loader.show_input = false;
+            rle_loader.error_message = None;
+            toasts.info(tr("toast-pattern-loaded"));
+        }
+        Err(error) => {
+            toasts.error(&error);
+            rle_loader.rle_content = content;
+            rle_loader.error_message = Some(error);
+        }
+    }
+}
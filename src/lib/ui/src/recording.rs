@@ -0,0 +1,263 @@
+//! # Recording Module
+//!
+//! Records the board over a number of generations and encodes the frames as
+//! an animated GIF, to share clips of a pattern. Frames are drawn from the
+//! cells rather than the screen, cropped to the area covered by the whole
+//! clip, so the view can move freely while recording.
+
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource};
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{
+    ColorConfig, MAX_RECORDING_FRAMES, SimulationBackend, SimulationConfig, tr, tr_args,
+};
+use gol_core::{Board, Cell};
+use gol_simulation::{BoardState, SimulationStats};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame};
+
+use crate::image_export::board_image;
+use crate::toast::Toasts;
+
+/// Boards captured so far by a running recording
+pub struct Recording {
+    /// Generation of the first frame
+    start: u64,
+    /// Last generation captured, so each is captured once
+    last: u64,
+    boards: Vec<Board>,
+}
+
+/// State of the recording window
+#[derive(Resource)]
+pub struct RecordingWindow {
+    pub open: bool,
+    /// Number of frames of the clip
+    pub frames: u32,
+    /// Generations between two frames
+    pub frame_skip: u32,
+    /// Side of a cell in the clip, in pixels
+    pub pixels_per_cell: u32,
+    /// Time each frame is shown, in milliseconds
+    pub frame_delay: u32,
+    pub recording: Option<Recording>,
+    /// Encodes and writes the clip, `None` when the dialog was cancelled
+    task: Option<Task<Option<Result<(), String>>>>,
+}
+
+impl Default for RecordingWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            frames: 100,
+            frame_skip: 1,
+            pixels_per_cell: 4,
+            frame_delay: 100,
+            recording: None,
+            task: None,
+        }
+    }
+}
+
+/// Plugin for the recording window
+pub struct RecordingPlugin;
+
+impl Plugin for RecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RecordingWindow>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, recording_system);
+    }
+}
+
+/// Captures a frame every few generations while recording, renders the
+/// window and encodes the clip once it is complete or stopped
+pub fn recording_system(
+    mut contexts: EguiContexts,
+    mut window: ResMut<RecordingWindow>,
+    board: Res<BoardState>,
+    stats: Res<SimulationStats>,
+    color_config: Res<ColorConfig>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let saved = window
+        .task
+        .as_mut()
+        .and_then(|task| block_on(future::poll_once(task)));
+    if let Some(saved) = saved {
+        window.task = None;
+        match saved {
+            Some(Ok(())) => toasts.info(tr("toast-recording-saved")),
+            Some(Err(e)) => toasts.error(tr_args("toast-recording-failed", &[("error", &e)])),
+            // The dialog was cancelled
+            None => {}
+        }
+    }
+
+    let frames = window.frames as usize;
+    let frame_skip = u64::from(window.frame_skip.max(1));
+    let mut complete = false;
+    if let Some(recording) = window.recording.as_mut() {
+        let generation = stats.generation;
+        // Going back in time, e.g. when the grid is cleared, ends the clip
+        if generation < recording.last {
+            complete = true;
+        } else if generation != recording.last {
+            recording.last = generation;
+            if (generation - recording.start).is_multiple_of(frame_skip) {
+                recording.boards.push(board.0.clone());
+            }
+        }
+        complete |= recording.boards.len() >= frames;
+    }
+
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    if window.open {
+        let mut open = true;
+        egui::Window::new(tr("recording-title"))
+            .id(egui::Id::new("recording"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                // HashLife only keeps the visible cells on the board
+                let enabled = simulation_config.backend != SimulationBackend::HashLife;
+                if !enabled {
+                    ui.label(tr("recording-hashlife"));
+                }
+                let idle = window.recording.is_none() && window.task.is_none();
+                ui.add_enabled_ui(idle, |ui| {
+                    egui::Grid::new("recording_options").show(ui, |ui| {
+                        ui.label(tr("recording-frames"));
+                        ui.add(
+                            egui::DragValue::new(&mut window.frames)
+                                .range(2..=MAX_RECORDING_FRAMES),
+                        );
+                        ui.end_row();
+                        ui.label(tr("recording-frame-skip"));
+                        ui.add(egui::DragValue::new(&mut window.frame_skip).range(1..=1000));
+                        ui.end_row();
+                        ui.label(tr("recording-scale"));
+                        ui.add(
+                            egui::DragValue::new(&mut window.pixels_per_cell)
+                                .range(1..=16)
+                                .suffix(" px"),
+                        );
+                        ui.end_row();
+                        ui.label(tr("recording-delay"));
+                        ui.add(
+                            egui::DragValue::new(&mut window.frame_delay)
+                                .range(20..=2000)
+                                .suffix(" ms"),
+                        );
+                        ui.end_row();
+                    });
+                });
+                match window.recording.as_ref() {
+                    Some(recording) => {
+                        ui.label(tr_args(
+                            "recording-progress",
+                            &[
+                                ("frames", &recording.boards.len()),
+                                ("total", &window.frames),
+                            ],
+                        ));
+                        if ui.button(tr("recording-stop")).clicked() {
+                            complete = true;
+                        }
+                    }
+                    None => {
+                        let start = egui::Button::new(tr("recording-start"));
+                        if ui.add_enabled(enabled && idle, start).clicked() {
+                            window.recording = Some(Recording {
+                                start: stats.generation,
+                                last: stats.generation,
+                                boards: vec![board.0.clone()],
+                            });
+                            simulation_config.running = true;
+                        }
+                    }
+                }
+            });
+        if !open {
+            window.open = false;
+        }
+    }
+
+    if !complete {
+        return;
+    }
+    let Some(recording) = window.recording.take() else {
+        return;
+    };
+    if recording.boards.len() < 2 {
+        toasts.warning(tr("recording-too-short"));
+        return;
+    }
+    window.task = Some(save_recording(
+        recording.boards,
+        window.pixels_per_cell,
+        window.frame_delay,
+        color_config.clone(),
+    ));
+}
+
+/// Encodes the boards as a looping GIF in the background, then asks where
+/// to save it
+fn save_recording(
+    boards: Vec<Board>,
+    pixels_per_cell: u32,
+    frame_delay: u32,
+    colors: ColorConfig,
+) -> Task<Option<Result<(), String>>> {
+    AsyncComputeTaskPool::get().spawn(async move {
+        let gif = match encode_gif(&boards, pixels_per_cell, frame_delay, &colors) {
+            Ok(gif) => gif,
+            Err(e) => return Some(Err(e)),
+        };
+        let file = rfd::AsyncFileDialog::new()
+            .set_title(tr("recording-title"))
+            .set_file_name("recording.gif")
+            .add_filter("GIF", &["gif"])
+            .save_file()
+            .await?;
+        Some(file.write(&gif).await.map_err(|e| e.to_string()))
+    })
+}
+
+/// Draws every board over the area covered by all of them and encodes the
+/// frames as a looping GIF
+pub fn encode_gif(
+    boards: &[Board],
+    pixels_per_cell: u32,
+    frame_delay: u32,
+    colors: &ColorConfig,
+) -> Result<Vec<u8>, String> {
+    let area = boards
+        .iter()
+        .filter_map(Board::bounding_box)
+        .reduce(|(min, max), (low, high)| {
+            (
+                Cell::new(min.x.min(low.x), min.y.min(low.y)),
+                Cell::new(max.x.max(high.x), max.y.max(high.y)),
+            )
+        })
+        .ok_or_else(|| tr("image-export-empty").to_string())?;
+
+    let mut gif = Vec::new();
+    {
+        let mut encoder = GifEncoder::new(&mut gif);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+        for board in boards {
+            let pixels = board_image(board, area, pixels_per_cell, colors)?;
+            let delay = Delay::from_numer_denom_ms(frame_delay, 1);
+            encoder
+                .encode_frame(Frame::from_parts(pixels, 0, 0, delay))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(gif)
+}
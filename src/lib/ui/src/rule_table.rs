@@ -0,0 +1,93 @@
+//! # Rule Table Module
+//!
+//! Dialog toggling the neighbor counts of the birth and survival conditions
+//! as a grid of checkboxes, applying the resulting rule live.
+
+use bevy::prelude::{App, Plugin, ResMut};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{SimulationConfig, tr, tr_args};
+use gol_core::ConfigSet;
+use gol_simulation::RuleSet;
+
+use crate::controls::RuleEditor;
+
+/// Plugin for the rule table dialog
+pub struct RuleTablePlugin;
+
+impl Plugin for RuleTablePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(bevy_egui::EguiPrimaryContextPass, rule_table_system);
+    }
+}
+
+/// Renders the rule table while it is open.
+///
+/// Counts whose Hensel letters only select some configurations show as
+/// indeterminate, and toggling them includes or excludes the whole count.
+pub fn rule_table_system(
+    mut contexts: EguiContexts,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut rule_editor: ResMut<RuleEditor>,
+) {
+    if !rule_editor.table_open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let Ok(rule) = RuleSet::parse(&simulation_config.rule) else {
+        return;
+    };
+
+    let mut open = true;
+    let mut edited = rule.0;
+    egui::Window::new(tr("rule-table-title"))
+        .id(egui::Id::new("rule_table"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(tr_args("rule-table-rule", &[("rule", &rule)]));
+            egui::Grid::new("rule_table").show(ui, |ui| {
+                ui.label(tr("rule-table-neighbors"));
+                for count in 0..=rule.neighborhood.size() {
+                    ui.label(count.to_string());
+                }
+                ui.end_row();
+
+                for (survival, label) in [
+                    (false, tr("rule-table-birth")),
+                    (true, tr("rule-table-survival")),
+                ] {
+                    ui.label(label);
+                    for count in 0..=rule.neighborhood.size() {
+                        let mask = if survival { rule.survival } else { rule.birth };
+                        let mut enabled = mask & (1 << count) != 0;
+                        let partial = rule.transitions.is_some_and(|transitions| {
+                            let set = if survival {
+                                transitions.survival
+                            } else {
+                                transitions.birth
+                            };
+                            enabled && !ConfigSet::with_count(count).difference(&set).is_empty()
+                        });
+                        let checkbox =
+                            egui::Checkbox::without_text(&mut enabled).indeterminate(partial);
+                        if ui.add(checkbox).changed() {
+                            // Any click on a partial count selects the whole count
+                            edited = edited.with_condition(survival, count, enabled || partial);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+
+    if edited != rule.0 {
+        simulation_config.rule = edited.to_string();
+        rule_editor.text = simulation_config.rule.clone();
+        rule_editor.error_message = None;
+    }
+    if !open {
+        rule_editor.table_open = false;
+    }
+}
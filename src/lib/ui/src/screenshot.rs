@@ -0,0 +1,67 @@
+//! # Screenshot Module
+//!
+//! Captures the window, interface included, to a PNG file named after the
+//! current generation. On the web, the browser downloads the file instead.
+
+use bevy::prelude::{App, ButtonInput, Commands, KeyCode, Plugin, Res, ResMut, Resource, Update};
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+use bevy_egui::EguiContexts;
+use gol_config::{KeyBindings, tr_args};
+use gol_simulation::SimulationStats;
+use std::path::{Path, PathBuf};
+
+use crate::toast::Toasts;
+
+/// Screenshot requests from the control panel
+#[derive(Resource, Default)]
+pub struct ScreenshotRequest {
+    pub requested: bool,
+}
+
+/// Plugin for the screenshots
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScreenshotRequest>()
+            .add_systems(Update, screenshot_system);
+    }
+}
+
+/// Takes a screenshot when requested, or when its key is pressed
+pub fn screenshot_system(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    stats: Res<SimulationStats>,
+    mut request: ResMut<ScreenshotRequest>,
+    mut toasts: ResMut<Toasts>,
+) {
+    let typing = contexts
+        .ctx_mut()
+        .is_ok_and(|ctx| ctx.wants_keyboard_input());
+    let pressed = !typing && keys.just_pressed(bindings.screenshot);
+    if !std::mem::take(&mut request.requested) && !pressed {
+        return;
+    }
+    let path = screenshot_path(stats.generation);
+    toasts.info(tr_args("toast-screenshot", &[("file", &path.display())]));
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(path));
+}
+
+/// First free file name for a screenshot of the given generation, such as
+/// `gol-42.png` then `gol-42-2.png`
+fn screenshot_path(generation: u64) -> PathBuf {
+    let base = format!("gol-{generation}");
+    (1..)
+        .map(|n| match n {
+            1 => PathBuf::from(format!("{base}.png")),
+            _ => PathBuf::from(format!("{base}-{n}.png")),
+        })
+        // The browser names the downloads itself
+        .find(|path| cfg!(target_arch = "wasm32") || !Path::exists(path))
+        .unwrap_or_default()
+}
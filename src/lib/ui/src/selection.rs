@@ -0,0 +1,455 @@
+//! # Selection Module
+//!
+//! Rectangular cell selection drawn with Shift + left drag. Selected cells
+//! can be copied (Ctrl+C) or cut (Ctrl+X) to the clipboard as RLE and to an
+//! internal buffer, then pasted or moved elsewhere through placement mode, and
+//! the selected region can be seeded with a random soup, inverted or cleared.
+
+use crate::analysis::{AnalysisDialog, AnalysisScope};
+use crate::clipboard::copy_text;
+use crate::pattern::PlacementMode;
+use crate::toast::Toasts;
+use bevy::prelude::{
+    App, ButtonInput, Camera, Color, Commands, Component, DetectChanges, GlobalTransform, KeyCode,
+    MouseButton, Plugin, Query, Res, ResMut, Resource, Single, Sprite, Startup, Transform, Update,
+    Vec2, Visibility, Window, With,
+};
+use bevy::window::PrimaryWindow;
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{DisplayConfig, MAX_EDITED_AREA, RandomShape, SimulationConfig, tr, tr_args};
+use gol_core::{Board, Cell, encode_rle};
+use gol_simulation::{BoardState, CellPosition, HashLifeUniverse, RuleSet, SimRng};
+
+/// Cells selected with Shift + left drag
+#[derive(Resource, Default)]
+pub struct Selection {
+    /// Cell where the current drag started
+    anchor: Option<CellPosition>,
+    /// Inclusive bounds of the selection
+    pub rect: Option<(CellPosition, CellPosition)>,
+}
+
+impl Selection {
+    /// Whether a cell lies inside the selection
+    pub fn contains(&self, pos: &CellPosition) -> bool {
+        self.rect.is_some_and(|(min, max)| {
+            (min.x..=max.x).contains(&pos.x) && (min.y..=max.y).contains(&pos.y)
+        })
+    }
+
+    /// Removes the selection
+    pub fn clear(&mut self) {
+        self.anchor = None;
+        self.rect = None;
+    }
+}
+
+/// Cells last copied or cut from a selection, relative to its bottom-left corner
+#[derive(Resource, Default)]
+pub struct SelectionBuffer {
+    pub cells: Vec<(i32, i32)>,
+}
+
+/// Marker for the sprite highlighting the selection
+#[derive(Component)]
+pub struct SelectionOverlay;
+
+/// Plugin for the selection tool
+pub struct SelectionPlugin;
+
+impl Plugin for SelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Selection>()
+            .init_resource::<SelectionBuffer>()
+            .add_systems(Startup, spawn_selection_overlay)
+            .add_systems(Update, (selection_input_system, update_selection_overlay))
+            .add_systems(
+                bevy_egui::EguiPrimaryContextPass,
+                (copy_selection_system, selection_panel_system),
+            );
+    }
+}
+
+fn spawn_selection_overlay(mut commands: Commands) {
+    commands.spawn((
+        SelectionOverlay,
+        Sprite {
+            color: Color::srgba(0.2, 0.5, 1.0, 0.25),
+            custom_size: Some(Vec2::ONE),
+            ..Default::default()
+        },
+        // Drawn above the cells
+        Transform::from_xyz(0.0, 0.0, 1.0),
+        Visibility::Hidden,
+    ));
+}
+
+/// Drags a selection rectangle with Shift + left mouse button, Escape clears it
+pub fn selection_input_system(
+    buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform)>,
+    mut selection: ResMut<Selection>,
+    mut egui_contexts: EguiContexts,
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        selection.clear();
+        return;
+    }
+    let shift = keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight);
+    if !shift || !buttons.pressed(MouseButton::Left) {
+        // Avoid flagging the selection as changed every frame
+        if selection.anchor.is_some() {
+            selection.anchor = None;
+        }
+        return;
+    }
+
+    let Ok(egui_ctx) = egui_contexts.ctx_mut() else {
+        return;
+    };
+    if egui_ctx.wants_pointer_input() || egui_ctx.is_using_pointer() {
+        return;
+    }
+    let Ok(window) = q_windows.single() else {
+        return;
+    };
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = q_camera.single() else {
+        return;
+    };
+    let Ok(world) = camera.viewport_to_world_2d(camera_transform, cursor_position) else {
+        return;
+    };
+    let cursor = CellPosition {
+        x: world.x.round() as isize,
+        y: world.y.round() as isize,
+    };
+
+    let anchor = *selection.anchor.get_or_insert(cursor);
+    selection.rect = Some((
+        CellPosition {
+            x: anchor.x.min(cursor.x),
+            y: anchor.y.min(cursor.y),
+        },
+        CellPosition {
+            x: anchor.x.max(cursor.x),
+            y: anchor.y.max(cursor.y),
+        },
+    ));
+}
+
+/// Moves and resizes the overlay sprite to match the selection
+pub fn update_selection_overlay(
+    selection: Res<Selection>,
+    overlay: Single<(&mut Transform, &mut Sprite, &mut Visibility), With<SelectionOverlay>>,
+) {
+    if !selection.is_changed() {
+        return;
+    }
+    let (mut transform, mut sprite, mut visibility) = overlay.into_inner();
+    let Some((min, max)) = selection.rect else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    // Cells are unit squares centered on their coordinates
+    let size = Vec2::new((max.x - min.x + 1) as f32, (max.y - min.y + 1) as f32);
+    transform.translation.x = (min.x + max.x) as f32 / 2.0;
+    transform.translation.y = (min.y + max.y) as f32 / 2.0;
+    sprite.custom_size = Some(size);
+    *visibility = Visibility::Visible;
+}
+
+/// Copies or cuts the selected cells to the clipboard as RLE on Ctrl+C / Ctrl+X
+pub fn copy_selection_system(
+    mut egui_contexts: EguiContexts,
+    selection: Res<Selection>,
+    mut buffer: ResMut<SelectionBuffer>,
+    mut board: ResMut<BoardState>,
+    rule: Res<RuleSet>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+) {
+    if selection.rect.is_none() {
+        return;
+    }
+    let Ok(ctx) = egui_contexts.ctx_mut() else {
+        return;
+    };
+    // Copying from a text field is handled by egui itself
+    if ctx.wants_keyboard_input() {
+        return;
+    }
+    let (copied, cut) = ctx.input(|input| {
+        input
+            .events
+            .iter()
+            .fold((false, false), |(copied, cut), event| {
+                (
+                    copied || matches!(event, egui::Event::Copy),
+                    cut || matches!(event, egui::Event::Cut),
+                )
+            })
+    });
+    if !copied && !cut {
+        return;
+    }
+
+    copy_selection(&selection, &board.0, &mut hashlife, &mut buffer);
+    let rule = rule.to_string();
+    copy_text(ctx, encode_rle(&buffer.cells, Some(&rule)));
+    if cut {
+        clear_selection(&selection, &mut board.0);
+    }
+}
+
+/// Floating toolbar with the selection operations
+#[allow(clippy::too_many_arguments)]
+pub fn selection_panel_system(
+    mut egui_contexts: EguiContexts,
+    mut selection: ResMut<Selection>,
+    mut buffer: ResMut<SelectionBuffer>,
+    mut board: ResMut<BoardState>,
+    mut placement_mode: ResMut<PlacementMode>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut display_config: ResMut<DisplayConfig>,
+    rule: Res<RuleSet>,
+    mut rng: ResMut<SimRng>,
+    mut analysis: ResMut<AnalysisDialog>,
+    mut toasts: ResMut<Toasts>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+) {
+    if selection.rect.is_none() && buffer.cells.is_empty() {
+        return;
+    }
+    let Ok(ctx) = egui_contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new(tr("selection-title"))
+        .id(egui::Id::new("selection"))
+        .resizable(false)
+        .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-10.0, 10.0))
+        .show(ctx, |ui| {
+            if let Some((min, max)) = selection.rect {
+                ui.label(tr_args(
+                    "selection-size",
+                    &[
+                        ("width", &(max.x - min.x + 1)),
+                        ("height", &(max.y - min.y + 1)),
+                    ],
+                ));
+            } else {
+                ui.label(tr("selection-none"));
+            }
+
+            let selected = selection.rect.is_some();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(selected, egui::Button::new(tr("selection-copy")))
+                    .clicked()
+                {
+                    copy_selection(&selection, &board.0, &mut hashlife, &mut buffer);
+                }
+                if ui
+                    .add_enabled(selected, egui::Button::new(tr("selection-cut")))
+                    .clicked()
+                {
+                    copy_selection(&selection, &board.0, &mut hashlife, &mut buffer);
+                    clear_selection(&selection, &mut board.0);
+                }
+                let paste = egui::Button::new(tr("selection-paste"));
+                if ui.add_enabled(!buffer.cells.is_empty(), paste).clicked() {
+                    placement_mode.place_cells("selection", buffer.cells.clone());
+                    simulation_config.running = false;
+                }
+                // Cut and pick the cells up again, so the ghost follows the cursor
+                if ui
+                    .add_enabled(selected, egui::Button::new(tr("selection-move")))
+                    .clicked()
+                {
+                    copy_selection(&selection, &board.0, &mut hashlife, &mut buffer);
+                    clear_selection(&selection, &mut board.0);
+                    placement_mode.place_cells("selection", buffer.cells.clone());
+                    simulation_config.running = false;
+                    selection.clear();
+                }
+            });
+            if selected {
+                ui.horizontal(|ui| {
+                    if ui.button(tr("selection-invert")).clicked()
+                        && !invert_selection(&selection, &mut board.0)
+                    {
+                        toasts.warning(too_large());
+                    }
+                    if ui.button(tr("selection-clear")).clicked() {
+                        clear_selection(&selection, &mut board.0);
+                    }
+                    if ui.button(tr("selection-analyze")).clicked() {
+                        analysis.requested = Some(AnalysisScope::Selection);
+                    }
+                    if ui.button(tr("selection-clear-outside")).clicked() {
+                        let outside: Vec<Cell> = board
+                            .0
+                            .iter()
+                            .filter(|cell| !selection.contains(&CellPosition::from(**cell)))
+                            .copied()
+                            .collect();
+                        for cell in outside {
+                            board.0.set(cell, false);
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::Slider::new(&mut display_config.random_density, 0..=100)
+                            .suffix("%")
+                            .text(tr("random-density")),
+                    );
+                    egui::ComboBox::from_id_salt("selection_random_shape")
+                        .selected_text(display_config.random_shape.to_string())
+                        .show_ui(ui, |ui| {
+                            for shape in RandomShape::ALL {
+                                ui.selectable_value(
+                                    &mut display_config.random_shape,
+                                    shape,
+                                    shape.to_string(),
+                                );
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::DragValue::new(&mut display_config.random_seed)
+                            .prefix(tr("random-seed")),
+                    );
+                    if ui
+                        .button("🎲")
+                        .on_hover_text(tr("random-new-seed"))
+                        .clicked()
+                    {
+                        display_config.random_seed = rng.next_u64();
+                    }
+                    if ui.button(tr("selection-fill-randomly")).clicked()
+                        && let Some(rect) = selection.rect
+                    {
+                        // Checked first, so a refused fill keeps the selected cells
+                        if area(rect) > MAX_EDITED_AREA {
+                            toasts.warning(too_large());
+                            return;
+                        }
+                        clear_selection(&selection, &mut board.0);
+                        // The same seed gives the same soup, and the same noisy run after it
+                        rng.reseed(display_config.random_seed);
+                        generate_random_cells(
+                            &mut board.0,
+                            rect,
+                            display_config.random_shape,
+                            display_config.random_density,
+                            rule.states.living_states(),
+                            &mut rng,
+                        );
+                    }
+                });
+                if ui.button(tr("selection-deselect")).clicked() {
+                    selection.clear();
+                }
+            }
+        });
+}
+
+/// Number of cells of a rectangle
+fn area((min, max): (CellPosition, CellPosition)) -> u64 {
+    (max.x.abs_diff(min.x) as u64 + 1).saturating_mul(max.y.abs_diff(min.y) as u64 + 1)
+}
+
+/// Warning shown when a selection is too large to be visited cell by cell
+fn too_large() -> String {
+    tr_args("selection-too-large", &[("cells", &MAX_EDITED_AREA)])
+}
+
+/// Turns every living cell of the selection dead and every dead one alive,
+/// returning `false` without any change when it is larger than
+/// [`MAX_EDITED_AREA`]
+fn invert_selection(selection: &Selection, board: &mut Board) -> bool {
+    let Some((min, max)) = selection.rect else {
+        return true;
+    };
+    if area((min, max)) > MAX_EDITED_AREA {
+        return false;
+    }
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            let cell = Cell::new(x, y);
+            let alive = board.contains(&cell);
+            board.set(cell, !alive);
+        }
+    }
+    true
+}
+
+/// Seeds the `shape` inscribed between `min` and `max` with living cells at
+/// the given percentage, each in a random one of the `states` living states,
+/// drawing from the shared generator. Returns `false` without any change
+/// when the area is larger than [`MAX_EDITED_AREA`].
+pub fn generate_random_cells(
+    board: &mut Board,
+    (min, max): (CellPosition, CellPosition),
+    shape: RandomShape,
+    density: u8,
+    states: u8,
+    rng: &mut SimRng,
+) -> bool {
+    if area((min, max)) > MAX_EDITED_AREA {
+        return false;
+    }
+    let center_x = (min.x + max.x) as f64 / 2.0;
+    let center_y = (min.y + max.y) as f64 / 2.0;
+    let half_width = (max.x - min.x + 1) as f64 / 2.0;
+    let half_height = (max.y - min.y + 1) as f64 / 2.0;
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            let u = (x as f64 - center_x) / half_width;
+            let v = (y as f64 - center_y) / half_height;
+            if shape.contains(u, v) && rng.next_f64() * 100.0 < f64::from(density) {
+                let state = 1 + (rng.next_u64() % u64::from(states.max(1))) as u8;
+                board.set_state(Cell::new(x, y), state);
+            }
+        }
+    }
+    true
+}
+
+/// Fills the buffer with the selected cells, relative to the selection
+/// corner, including those outside the view under HashLife
+fn copy_selection(
+    selection: &Selection,
+    board: &Board,
+    hashlife: &mut HashLifeUniverse,
+    buffer: &mut SelectionBuffer,
+) {
+    let Some((min, max)) = selection.rect else {
+        return;
+    };
+    // Same orientation as patterns placed from the browser, so a copy pastes back unchanged
+    buffer.cells = hashlife
+        .cells_in(board, Some((min, max)))
+        .iter()
+        .map(|cell| ((cell.x - min.x) as i32, (cell.y - min.y) as i32))
+        .collect();
+}
+
+/// Kills every cell inside the selection
+fn clear_selection(selection: &Selection, board: &mut Board) {
+    let selected: Vec<Cell> = board
+        .iter()
+        .filter(|cell| selection.contains(&CellPosition::from(**cell)))
+        .copied()
+        .collect();
+    for cell in selected {
+        board.set(cell, false);
+    }
+}
@@ -0,0 +1,374 @@
+//! # Session Module
+//!
+//! Saves the whole session to a RON file and opens it back: the board and
+//! its generation, the rule, the camera and the colors, so that work
+//! survives restarting the application. On the web, where there are no
+//! files to come back to, the session is also kept in the browser storage.
+
+use bevy::color::ColorToComponents;
+use bevy::prelude::{
+    App, Camera2d, Color, DetectChanges, Local, Plugin, Projection, Query, Res, ResMut, Resource,
+    Time, Transform, Update, With, warn,
+};
+use bevy::tasks::{IoTaskPool, Task, block_on, futures_lite::future};
+use gol_config::{
+    AUTOSAVE_PERIOD, ColorConfig, SESSION_KEY, SimulationBackend, SimulationConfig, StartupPattern,
+    read_storage, storage_available, tr, tr_args, write_storage,
+};
+use gol_core::{Board, Cell};
+use gol_simulation::{BoardHistory, BoardState, HashLifeUniverse, RuleSet, SimulationStats};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::controls::RuleEditor;
+use crate::toast::Toasts;
+
+/// Everything saved in a session file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    /// Rulestring of the simulation
+    pub rule: String,
+    /// Generation reached by the board
+    pub generation: u64,
+    /// Living cells, as (x, y, state)
+    pub cells: Vec<(isize, isize, u8)>,
+    pub camera: SessionCamera,
+    pub colors: SessionColors,
+}
+
+/// Position and zoom of the camera
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SessionCamera {
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+}
+
+/// Colors of the session, as sRGBA components
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionColors {
+    pub cell: [f32; 4],
+    pub background: [f32; 4],
+    /// Colors of the living states after the first
+    pub states: Vec<[f32; 4]>,
+    /// Color of the grid lines, missing from older sessions
+    #[serde(default)]
+    pub grid: Option<[f32; 4]>,
+}
+
+impl SessionColors {
+    /// Colors of the given configuration
+    pub fn from_config(config: &ColorConfig) -> Self {
+        let srgba = |color: &Color| color.to_srgba().to_f32_array();
+        Self {
+            cell: srgba(&config.cell_color),
+            background: srgba(&config.background_color),
+            states: config.state_colors.iter().map(srgba).collect(),
+            grid: Some(srgba(&config.grid_color)),
+        }
+    }
+
+    /// Applies the colors to the given configuration
+    pub fn apply(&self, config: &mut ColorConfig) {
+        let color = |[r, g, b, a]: [f32; 4]| Color::srgba(r, g, b, a);
+        config.cell_color = color(self.cell);
+        config.background_color = color(self.background);
+        config.state_colors = self.states.iter().copied().map(color).collect();
+        if let Some(grid) = self.grid {
+            config.grid_color = color(grid);
+        }
+    }
+}
+
+impl Session {
+    /// Board of the session
+    pub fn board(&self) -> Board {
+        let mut board = Board::new();
+        for &(x, y, state) in &self.cells {
+            board.set_state(Cell::new(x, y), state.max(1));
+        }
+        board
+    }
+
+    /// Writes the session as RON
+    pub fn to_ron(&self) -> Result<String, SessionError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| SessionError::Format(e.to_string()))
+    }
+
+    /// Reads a session written by [`Session::to_ron`]
+    pub fn from_ron(content: &str) -> Result<Self, SessionError> {
+        let session: Self =
+            ron::from_str(content).map_err(|e| SessionError::Format(e.to_string()))?;
+        RuleSet::parse(&session.rule)
+            .map_err(|e| SessionError::Rule(format!("{}: {e}", session.rule)))?;
+        Ok(session)
+    }
+}
+
+/// Error returned when a session cannot be written or read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionError {
+    /// The file is not a valid session
+    Format(String),
+    /// The rule of the session is not supported
+    Rule(String),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Format(e) => write!(f, "{}", tr_args("session-invalid", &[("error", e)])),
+            Self::Rule(e) => write!(
+                f,
+                "{}",
+                tr_args("session-unsupported-rule", &[("error", e)])
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+/// Session file requests from the control panel
+#[derive(Resource, Default)]
+pub struct SessionFiles {
+    /// Asks where to save the current session
+    pub save_requested: bool,
+    /// Asks which session to open
+    pub open_requested: bool,
+    /// Saves the current session to the browser storage, on the web
+    pub store_requested: bool,
+    /// Opens the session of the browser storage, on the web
+    pub restore_requested: bool,
+    /// Writes the session, `None` when the dialog was cancelled
+    save_task: Option<Task<Option<Result<(), String>>>>,
+    open_task: Option<Task<Option<String>>>,
+}
+
+/// Plugin for saving and opening sessions
+pub struct SessionPlugin;
+
+impl Plugin for SessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SessionFiles>()
+            .add_systems(Update, session_system);
+    }
+}
+
+/// Saves the session or opens one when requested.
+///
+/// The HashLife backend only mirrors the visible cells on the board, so
+/// sessions are not saved while it is selected. Opening a session pauses
+/// the simulation, replaces the whole universe under HashLife and forgets
+/// the history of the replaced board.
+///
+/// On the web, the session is also saved to the browser storage every
+/// [`AUTOSAVE_PERIOD`] when the board changed, and opened from it at
+/// startup unless the page link gave a pattern.
+#[allow(clippy::too_many_arguments)]
+pub fn session_system(
+    mut board_state: ResMut<BoardState>,
+    mut files: ResMut<SessionFiles>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut rule_editor: ResMut<RuleEditor>,
+    mut color_config: ResMut<ColorConfig>,
+    mut stats: ResMut<SimulationStats>,
+    mut history: ResMut<BoardHistory>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+    mut q_camera: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+    mut toasts: ResMut<Toasts>,
+    time: Res<Time>,
+    mut autosave: Local<Autosave>,
+) {
+    let now = time.elapsed_secs();
+    autosave.board_changed |= board_state.is_changed();
+    let save = std::mem::take(&mut files.save_requested) && files.save_task.is_none();
+    let store = std::mem::take(&mut files.store_requested);
+    let autosave_due = storage_available()
+        && autosave.started
+        && autosave.board_changed
+        && now - autosave.last_save >= AUTOSAVE_PERIOD;
+    if autosave_due {
+        autosave.last_save = now;
+        autosave.board_changed = false;
+    }
+    if save || store || autosave_due {
+        if simulation_config.backend == SimulationBackend::HashLife {
+            if save || store {
+                toasts.warning(tr("toast-session-hashlife"));
+            }
+        } else if let Ok((transform, projection)) = q_camera.single() {
+            let session = current_session(
+                &board_state.0,
+                &simulation_config,
+                &stats,
+                (transform, projection),
+                &color_config,
+            );
+            match session.to_ron() {
+                Ok(content) => {
+                    if store || autosave_due {
+                        match write_storage(SESSION_KEY, &content) {
+                            Ok(()) if store => toasts.info(tr("toast-session-stored")),
+                            Ok(()) => {}
+                            Err(e) if store => {
+                                toasts.error(tr_args("toast-session-save-failed", &[("error", &e)]))
+                            }
+                            Err(e) => warn!("Could not save the session: {e}"),
+                        }
+                    }
+                    if save {
+                        files.save_task = Some(save_session_file(content));
+                    }
+                }
+                Err(e) => toasts.error(tr_args("toast-session-save-failed", &[("error", &e)])),
+            }
+        }
+    }
+    let saved = files
+        .save_task
+        .as_mut()
+        .and_then(|task| block_on(future::poll_once(task)));
+    if let Some(saved) = saved {
+        files.save_task = None;
+        match saved {
+            Some(Ok(())) => toasts.info(tr("toast-session-saved")),
+            Some(Err(e)) => toasts.error(tr_args("toast-session-save-failed", &[("error", &e)])),
+            // The dialog was cancelled
+            None => {}
+        }
+    }
+    if std::mem::take(&mut files.open_requested) && files.open_task.is_none() {
+        files.open_task = Some(open_session_file());
+    }
+
+    // A link opening into a pattern takes precedence over the stored session
+    let startup = !std::mem::replace(&mut autosave.started, true)
+        && storage_available()
+        && !matches!(simulation_config.startup_pattern, StartupPattern::Text(_));
+    let content = if std::mem::take(&mut files.restore_requested) || startup {
+        match read_storage(SESSION_KEY) {
+            Some(content) => content,
+            None if startup => return,
+            None => {
+                toasts.warning(tr("toast-session-not-stored"));
+                return;
+            }
+        }
+    } else {
+        let Some(task) = files.open_task.as_mut() else {
+            return;
+        };
+        let Some(content) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        files.open_task = None;
+        // The dialog was cancelled
+        let Some(content) = content else {
+            return;
+        };
+        content
+    };
+    let session = match Session::from_ron(&content) {
+        Ok(session) => session,
+        Err(e) => {
+            toasts.error(tr_args("toast-session-open-failed", &[("error", &e)]));
+            return;
+        }
+    };
+
+    let board = session.board();
+    simulation_config.running = false;
+    simulation_config.rule = session.rule.clone();
+    rule_editor.text = session.rule;
+    rule_editor.error_message = None;
+    stats.generation = session.generation;
+    stats.births = 0;
+    stats.deaths = 0;
+    stats.population = board.len();
+    board_state.0 = board;
+    hashlife.replace(&board_state.0);
+    history.clear();
+    session.colors.apply(&mut color_config);
+    if let Ok((mut transform, mut projection)) = q_camera.single_mut() {
+        transform.translation.x = session.camera.x;
+        transform.translation.y = session.camera.y;
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scale = session.camera.scale;
+        }
+    }
+    toasts.info(tr_args(
+        "toast-session-opened",
+        &[("generation", &stats.generation)],
+    ));
+}
+
+/// State of the session autosave, on the web
+#[derive(Default)]
+pub struct Autosave {
+    /// Whether the stored session was looked at on startup
+    started: bool,
+    /// Whether the board changed since the last save
+    board_changed: bool,
+    /// Time of the last save, in seconds since startup
+    last_save: f32,
+}
+
+/// Session of the current board, rule, camera and colors
+fn current_session(
+    board: &Board,
+    simulation_config: &SimulationConfig,
+    stats: &SimulationStats,
+    (transform, projection): (&Transform, &Projection),
+    color_config: &ColorConfig,
+) -> Session {
+    let scale = match projection {
+        Projection::Orthographic(ortho) => ortho.scale,
+        _ => 1.0,
+    };
+    Session {
+        rule: simulation_config.rule.clone(),
+        generation: stats.generation,
+        cells: board
+            .iter()
+            .map(|cell| (cell.x, cell.y, board.state(cell)))
+            .collect(),
+        camera: SessionCamera {
+            x: transform.translation.x,
+            y: transform.translation.y,
+            scale,
+        },
+        colors: SessionColors::from_config(color_config),
+    }
+}
+
+/// Asks where to save the session and writes it in the background
+fn save_session_file(content: String) -> Task<Option<Result<(), String>>> {
+    IoTaskPool::get().spawn(async move {
+        let file = rfd::AsyncFileDialog::new()
+            .set_title(tr("session-save-title"))
+            .set_file_name("session.ron")
+            .add_filter(tr("session-filter"), &["ron"])
+            .save_file()
+            .await?;
+        Some(
+            file.write(content.as_bytes())
+                .await
+                .map_err(|e| e.to_string()),
+        )
+    })
+}
+
+/// Opens a native file picker and reads the chosen session in the background
+fn open_session_file() -> Task<Option<String>> {
+    IoTaskPool::get().spawn(async {
+        let file = rfd::AsyncFileDialog::new()
+            .set_title(tr("session-open-title"))
+            .add_filter(tr("session-filter"), &["ron"])
+            .add_filter(tr("common-all-files"), &["*"])
+            .pick_file()
+            .await?;
+        Some(String::from_utf8_lossy(&file.read().await).into_owned())
+    })
+}
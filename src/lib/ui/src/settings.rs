@@ -0,0 +1,494 @@
+//! # Settings Module
+//!
+//! Window grouping the preferences of the application: simulation, display,
+//! colors, camera and key bindings. They are the configuration resources,
+//! saved in the configuration file on exit.
+
+use bevy::color::ColorToComponents;
+use bevy::input::InputSystems;
+use bevy::prelude::{
+    App, ButtonInput, Color, IntoScheduleConfigs, KeyCode, Plugin, PreUpdate, ResMut, Resource,
+};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{
+    CONFIG_FILE, CameraConfig, CellRenderMode, CellShape, ColorConfig, DisplayConfig, KeyAction,
+    KeyBindings, KeyName, Language, MAX_SCALE, MAX_UI_SCALE, MIN_UI_SCALE, SimulationConfig, Theme,
+    tr, tr_args,
+};
+
+/// Page of the settings window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SettingsTab {
+    #[default]
+    Simulation,
+    Display,
+    Colors,
+    Camera,
+    Keys,
+}
+
+/// State of the settings window
+#[derive(Resource, Default)]
+pub struct SettingsWindow {
+    pub open: bool,
+    pub tab: SettingsTab,
+    /// Action waiting for its new key
+    pub rebinding: Option<KeyAction>,
+    /// Interface scale being dragged, applied once released
+    pub ui_scale: Option<f32>,
+}
+
+/// Plugin for the settings window
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsWindow>()
+            .add_systems(PreUpdate, capture_key_binding.after(InputSystems))
+            .add_systems(bevy_egui::EguiPrimaryContextPass, settings_window_system);
+    }
+}
+
+/// Binds the next key pressed to the action being rebound, Escape cancelling.
+///
+/// The key is consumed, so that it does not trigger its old action as well.
+pub fn capture_key_binding(
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut window: ResMut<SettingsWindow>,
+    mut bindings: ResMut<KeyBindings>,
+) {
+    let Some(action) = window.rebinding else {
+        return;
+    };
+    let Some(&key) = keys.get_just_pressed().next() else {
+        return;
+    };
+    keys.clear_just_pressed(key);
+    window.rebinding = None;
+    if key != KeyCode::Escape {
+        bindings.set(action, key);
+    }
+}
+
+/// Renders the settings window while it is open
+pub fn settings_window_system(
+    mut contexts: EguiContexts,
+    mut window: ResMut<SettingsWindow>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut display_config: ResMut<DisplayConfig>,
+    mut color_config: ResMut<ColorConfig>,
+    mut camera_config: ResMut<CameraConfig>,
+    mut bindings: ResMut<KeyBindings>,
+) {
+    if !window.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = true;
+    egui::Window::new(tr("settings-title"))
+        .id(egui::Id::new("settings"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let mut language = display_config.language;
+            egui::ComboBox::new("language", tr("settings-language"))
+                .selected_text(language.to_string())
+                .show_ui(ui, |ui| {
+                    for option in Language::ALL {
+                        ui.selectable_value(&mut language, option, option.to_string());
+                    }
+                });
+            if language != display_config.language {
+                display_config.language = language;
+            }
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                let tabs = [
+                    (SettingsTab::Simulation, "settings-tab-simulation"),
+                    (SettingsTab::Display, "settings-tab-display"),
+                    (SettingsTab::Colors, "settings-tab-colors"),
+                    (SettingsTab::Camera, "settings-tab-camera"),
+                    (SettingsTab::Keys, "settings-tab-keys"),
+                ];
+                for (tab, label) in tabs {
+                    ui.selectable_value(&mut window.tab, tab, tr(label));
+                }
+            });
+            ui.separator();
+
+            match window.tab {
+                SettingsTab::Simulation => simulation_settings(ui, &mut simulation_config),
+                SettingsTab::Display => {
+                    display_settings(ui, &mut display_config, &mut window.ui_scale)
+                }
+                SettingsTab::Colors => color_settings(ui, &mut color_config, &mut display_config),
+                SettingsTab::Camera => camera_settings(ui, &mut camera_config),
+                SettingsTab::Keys => key_settings(ui, &mut window.rebinding, &mut bindings),
+            }
+
+            if !cfg!(target_arch = "wasm32") {
+                ui.separator();
+                ui.weak(tr_args("settings-saved-in", &[("file", &CONFIG_FILE)]));
+            }
+        });
+
+    if !open {
+        window.open = false;
+        window.rebinding = None;
+    }
+}
+
+fn simulation_settings(ui: &mut egui::Ui, simulation_config: &mut ResMut<SimulationConfig>) {
+    let mut tick_rate = simulation_config.tick_rate;
+    ui.add(
+        egui::Slider::new(&mut tick_rate, 10.0..=240.0)
+            .text(tr("settings-tick-rate"))
+            .integer(),
+    );
+    if tick_rate != simulation_config.tick_rate {
+        simulation_config.tick_rate = tick_rate;
+    }
+    let mut history_depth = simulation_config.history_depth;
+    ui.add(
+        egui::DragValue::new(&mut history_depth)
+            .range(0..=1000)
+            .suffix(tr("settings-history-depth")),
+    );
+    if history_depth != simulation_config.history_depth {
+        simulation_config.history_depth = history_depth;
+    }
+    let mut pause_when_unfocused = simulation_config.pause_when_unfocused;
+    ui.checkbox(&mut pause_when_unfocused, tr("settings-pause-unfocused"))
+        .on_hover_text(tr("settings-pause-unfocused-hover"));
+    if pause_when_unfocused != simulation_config.pause_when_unfocused {
+        simulation_config.pause_when_unfocused = pause_when_unfocused;
+    }
+    if ui.button(tr("settings-restore-defaults")).clicked() {
+        let defaults = SimulationConfig::default();
+        simulation_config.tick_rate = defaults.tick_rate;
+        simulation_config.history_depth = defaults.history_depth;
+        simulation_config.pause_when_unfocused = defaults.pause_when_unfocused;
+    }
+}
+
+fn display_settings(
+    ui: &mut egui::Ui,
+    display_config: &mut ResMut<DisplayConfig>,
+    dragged_ui_scale: &mut Option<f32>,
+) {
+    // Rescaling the interface while dragging would move the slider away
+    // from the pointer, so the new scale waits for the release
+    let mut ui_scale = dragged_ui_scale.unwrap_or(display_config.ui_scale);
+    let response = ui.add(
+        egui::Slider::new(&mut ui_scale, MIN_UI_SCALE..=MAX_UI_SCALE)
+            .step_by(0.05)
+            .suffix("×")
+            .text(tr("settings-ui-scale")),
+    );
+    if response.dragged() {
+        *dragged_ui_scale = Some(ui_scale);
+    } else {
+        *dragged_ui_scale = None;
+        if ui_scale != display_config.ui_scale {
+            display_config.ui_scale = ui_scale;
+        }
+    }
+    ui.horizontal(|ui| {
+        ui.label(tr("settings-fps-cap"));
+        let mut fps_cap = display_config.fps_cap;
+        ui.add(
+            egui::DragValue::new(&mut fps_cap)
+                .range(0..=240)
+                .suffix(" fps"),
+        )
+        .on_hover_text(tr("settings-fps-cap-hover"));
+        if fps_cap != display_config.fps_cap {
+            display_config.fps_cap = fps_cap;
+        }
+    });
+    ui.checkbox(
+        &mut display_config.reduce_motion,
+        tr("settings-reduce-motion"),
+    )
+    .on_hover_text(tr("settings-reduce-motion-hover"));
+    ui.checkbox(&mut display_config.grid_visible, tr("settings-show-grid"));
+    ui.add_enabled_ui(display_config.grid_visible, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(tr("settings-grid-major"));
+            let mut spacing = display_config.grid_major_spacing;
+            ui.add(
+                egui::DragValue::new(&mut spacing)
+                    .range(0..=1000)
+                    .suffix(tr("settings-grid-major-cells")),
+            )
+            .on_hover_text(tr("settings-grid-major-hover"));
+            if spacing != display_config.grid_major_spacing {
+                display_config.grid_major_spacing = spacing;
+            }
+        });
+        ui.horizontal(|ui| {
+            // The lines are hidden below the end of the fade, which cannot
+            // exceed its start
+            ui.label(tr("settings-grid-fade"));
+            let fade_end = display_config.grid_fade_end;
+            ui.add(
+                egui::DragValue::new(&mut display_config.grid_fade_start)
+                    .speed(0.1)
+                    .range(fade_end..=64.0)
+                    .suffix(" px"),
+            );
+            ui.label(tr("settings-grid-fade-to"));
+            let fade_start = display_config.grid_fade_start;
+            ui.add(
+                egui::DragValue::new(&mut display_config.grid_fade_end)
+                    .speed(0.1)
+                    .range(0.0..=fade_start)
+                    .suffix(" px"),
+            );
+        })
+        .response
+        .on_hover_text(tr("settings-grid-fade-hover"));
+        ui.checkbox(&mut display_config.grid_axes, tr("settings-grid-axes"));
+        ui.add_enabled(
+            display_config.grid_major_spacing > 0,
+            egui::Checkbox::new(&mut display_config.grid_labels, tr("settings-grid-labels")),
+        );
+    });
+    ui.checkbox(
+        &mut display_config.minimap_visible,
+        tr("settings-show-minimap"),
+    );
+    ui.checkbox(
+        &mut display_config.show_neighbor_counts,
+        tr("settings-neighbor-counts"),
+    )
+    .on_hover_text(tr("settings-neighbor-counts-hover"));
+    ui.checkbox(&mut display_config.onion_skin, tr("settings-onion-skin"))
+        .on_hover_text(tr("settings-onion-skin-hover"));
+    ui.horizontal(|ui| {
+        ui.label(tr("settings-cells"));
+        ui.radio_value(
+            &mut display_config.render_mode,
+            CellRenderMode::Sprites,
+            tr("settings-sprites"),
+        );
+        ui.radio_value(
+            &mut display_config.render_mode,
+            CellRenderMode::Chunks,
+            tr("settings-textures"),
+        )
+        .on_hover_text(tr("settings-textures-hover"));
+    });
+    ui.checkbox(&mut display_config.bloom, tr("settings-bloom"))
+        .on_hover_text(tr("settings-bloom-hover"));
+    ui.add_enabled_ui(display_config.bloom, |ui| {
+        let mut intensity = display_config.bloom_intensity;
+        ui.add(egui::Slider::new(&mut intensity, 0.0..=1.0).text(tr("settings-bloom-intensity")));
+        if intensity != display_config.bloom_intensity {
+            display_config.bloom_intensity = intensity;
+        }
+    });
+    ui.checkbox(
+        &mut display_config.density_view,
+        tr("settings-density-view"),
+    )
+    .on_hover_text(tr("settings-density-view-hover"));
+    ui.horizontal(|ui| {
+        ui.label(tr("settings-cell-shape"));
+        for shape in CellShape::ALL {
+            ui.radio_value(&mut display_config.cell_shape, shape, shape.to_string());
+        }
+    });
+    ui.add_enabled_ui(display_config.cell_shape == CellShape::Square, |ui| {
+        let mut roundness = display_config.cell_roundness;
+        ui.add(egui::Slider::new(&mut roundness, 0.0..=1.0).text(tr("settings-cell-roundness")));
+        if roundness != display_config.cell_roundness {
+            display_config.cell_roundness = roundness;
+        }
+    });
+    let mut cell_size = display_config.cell_size;
+    ui.add(egui::Slider::new(&mut cell_size, 0.5..=1.0).text(tr("settings-cell-size")))
+        .on_hover_text(tr("settings-cell-size-hover"));
+    if cell_size != display_config.cell_size {
+        display_config.cell_size = cell_size;
+    }
+    ui.add_enabled_ui(
+        display_config.render_mode == CellRenderMode::Sprites,
+        |ui| {
+            ui.checkbox(
+                &mut display_config.state_shapes,
+                tr("settings-state-shapes"),
+            )
+            .on_hover_text(tr("settings-state-shapes-hover"));
+            let mut death_trail = display_config.death_trail;
+            ui.add(egui::Slider::new(&mut death_trail, 0..=32).text(tr("settings-death-trail")));
+            if death_trail != display_config.death_trail {
+                display_config.death_trail = death_trail;
+            }
+            ui.checkbox(
+                &mut display_config.animate_cells,
+                tr("settings-animate-cells"),
+            )
+            .on_hover_text(tr("settings-animate-cells-hover"));
+        },
+    );
+    ui.separator();
+    ui.checkbox(&mut display_config.sound_muted, tr("settings-sound-muted"))
+        .on_hover_text(tr("settings-sound-muted-hover"));
+    ui.add_enabled_ui(!display_config.sound_muted, |ui| {
+        let mut volume = display_config.sound_volume;
+        ui.add(egui::Slider::new(&mut volume, 0.0..=1.0).text(tr("settings-sound-volume")));
+        if volume != display_config.sound_volume {
+            display_config.sound_volume = volume;
+        }
+    });
+    if ui.button(tr("settings-restore-defaults")).clicked() {
+        // The random fills, the warp length, the language and the theme
+        // belong to their own controls
+        let defaults = DisplayConfig {
+            random_density: display_config.random_density,
+            random_seed: display_config.random_seed,
+            random_shape: display_config.random_shape,
+            random_size: display_config.random_size,
+            paint_state: display_config.paint_state,
+            warp_generations: display_config.warp_generations,
+            language: display_config.language,
+            theme: display_config.theme,
+            ..Default::default()
+        };
+        **display_config = defaults;
+    }
+}
+
+fn color_settings(
+    ui: &mut egui::Ui,
+    color_config: &mut ResMut<ColorConfig>,
+    display_config: &mut ResMut<DisplayConfig>,
+) {
+    let mut theme = display_config.theme;
+    egui::ComboBox::new("theme", tr("settings-theme"))
+        .selected_text(theme.to_string())
+        .show_ui(ui, |ui| {
+            for option in Theme::ALL {
+                ui.selectable_value(&mut theme, option, option.to_string());
+            }
+        });
+    // The colors follow the theme, and can be tuned from there
+    if theme != display_config.theme {
+        display_config.theme = theme;
+        **color_config = theme.colors();
+    }
+    ui.separator();
+    egui::Grid::new("color_settings").show(ui, |ui| {
+        ui.label(tr("settings-cells"));
+        if let Some(color) = color_edit(ui, color_config.cell_color) {
+            color_config.cell_color = color;
+        }
+        ui.end_row();
+        ui.label(tr("settings-background"));
+        if let Some(color) = color_edit(ui, color_config.background_color) {
+            color_config.background_color = color;
+        }
+        ui.end_row();
+        ui.label(tr("settings-grid-color"))
+            .on_hover_text(tr("settings-grid-color-hover"));
+        if let Some(color) = color_edit_alpha(ui, color_config.grid_color) {
+            color_config.grid_color = color;
+        }
+        ui.end_row();
+        for index in 0..color_config.state_colors.len() {
+            ui.label(tr_args("settings-state-color", &[("state", &(index + 2))]))
+                .on_hover_text(tr("settings-state-color-hover"));
+            if let Some(color) = color_edit(ui, color_config.state_colors[index]) {
+                color_config.state_colors[index] = color;
+            }
+            ui.end_row();
+        }
+    });
+    if ui.button(tr("settings-restore-defaults")).clicked() {
+        **color_config = display_config.theme.colors();
+    }
+}
+
+/// Color picker, returning the new color when it was changed
+fn color_edit(ui: &mut egui::Ui, color: Color) -> Option<Color> {
+    let srgba = color.to_srgba();
+    let mut rgb = [srgba.red, srgba.green, srgba.blue];
+    ui.color_edit_button_rgb(&mut rgb)
+        .changed()
+        .then(|| Color::srgb(rgb[0], rgb[1], rgb[2]))
+}
+
+/// Color picker with an opacity slider, returning the new color when it was
+/// changed
+fn color_edit_alpha(ui: &mut egui::Ui, color: Color) -> Option<Color> {
+    let mut rgba = color.to_srgba().to_f32_array();
+    ui.color_edit_button_rgba_unmultiplied(&mut rgba)
+        .changed()
+        .then(|| Color::srgba(rgba[0], rgba[1], rgba[2], rgba[3]))
+}
+
+fn camera_settings(ui: &mut egui::Ui, camera_config: &mut ResMut<CameraConfig>) {
+    ui.label(tr("settings-zoom-limits"));
+    ui.horizontal(|ui| {
+        // Each limit stays at least a factor 2 away from the other
+        ui.label(tr("settings-zoom-closest"));
+        let max_scale = camera_config.max_scale;
+        ui.add(
+            egui::DragValue::new(&mut camera_config.min_scale)
+                .speed(0.001)
+                .range(0.001..=(max_scale / 2.0).min(MAX_SCALE)),
+        );
+        ui.label(tr("settings-zoom-farthest"));
+        let min_scale = camera_config.min_scale;
+        ui.add(
+            egui::DragValue::new(&mut camera_config.max_scale)
+                .speed(0.5)
+                .range(min_scale * 2.0..=1024.0),
+        );
+    });
+    if ui.button(tr("settings-restore-defaults")).clicked() {
+        **camera_config = CameraConfig::default();
+    }
+}
+
+fn key_settings(
+    ui: &mut egui::Ui,
+    rebinding: &mut Option<KeyAction>,
+    bindings: &mut ResMut<KeyBindings>,
+) {
+    ui.label(tr("settings-keys-help"));
+    let conflicts = bindings.conflicts();
+    egui::Grid::new("key_settings")
+        .striped(true)
+        .show(ui, |ui| {
+            for action in KeyAction::ALL {
+                ui.label(action.label());
+                let text = if *rebinding == Some(action) {
+                    egui::RichText::new(tr("settings-press-key")).italics()
+                } else if conflicts.contains(&action) {
+                    egui::RichText::new(KeyName(bindings.key(action)).to_string())
+                        .color(egui::Color32::RED)
+                } else {
+                    egui::RichText::new(KeyName(bindings.key(action)).to_string())
+                };
+                let mut button = ui.button(text);
+                if conflicts.contains(&action) {
+                    button = button.on_hover_text(tr("settings-key-conflict"));
+                }
+                if button.clicked() {
+                    *rebinding = Some(action);
+                }
+                ui.end_row();
+            }
+        });
+    ui.weak(tr("settings-arrow-keys"));
+    if ui.button(tr("settings-restore-defaults")).clicked() {
+        *rebinding = None;
+        **bindings = KeyBindings::default();
+    }
+}
@@ -0,0 +1,99 @@
+//! # Slots Module
+//!
+//! Window listing the save slots, where the current board can be saved as a
+//! checkpoint and loaded back later.
+
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{SimulationBackend, SimulationConfig, tr, tr_args};
+use gol_simulation::{SaveSlots, SlotRequest};
+
+/// Whether the save slots window is shown
+#[derive(Resource, Default)]
+pub struct SlotsWindow {
+    pub open: bool,
+}
+
+/// Plugin for the save slots window
+pub struct SlotsWindowPlugin;
+
+impl Plugin for SlotsWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SlotsWindow>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, slots_window_system);
+    }
+}
+
+/// Renders the save slots window while it is open
+pub fn slots_window_system(
+    mut contexts: EguiContexts,
+    mut window: ResMut<SlotsWindow>,
+    mut slots: ResMut<SaveSlots>,
+    simulation_config: Res<SimulationConfig>,
+) {
+    if !window.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = true;
+    let mut request = None;
+    egui::Window::new(tr("slots-title"))
+        .id(egui::Id::new("save_slots"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            // HashLife only keeps the visible cells on the board
+            let enabled = simulation_config.backend != SimulationBackend::HashLife;
+            if !enabled {
+                ui.label(tr("slots-hashlife"));
+            }
+            egui::Grid::new("save_slots").striped(true).show(ui, |ui| {
+                for (index, slot) in slots.slots.iter_mut().enumerate() {
+                    ui.add(egui::TextEdit::singleline(&mut slot.name).desired_width(100.0));
+                    match &slot.snapshot {
+                        Some(snapshot) => ui.label(tr_args(
+                            "slots-snapshot",
+                            &[
+                                ("generation", &snapshot.generation),
+                                ("cells", &snapshot.board.len()),
+                            ],
+                        )),
+                        None => ui.weak(tr("slots-empty")),
+                    };
+                    if ui
+                        .add_enabled(enabled, egui::Button::new(tr("slots-save")))
+                        .clicked()
+                    {
+                        request = Some(SlotRequest::Save(index));
+                    }
+                    let load = egui::Button::new(tr("slots-load"));
+                    if ui
+                        .add_enabled(enabled && slot.snapshot.is_some(), load)
+                        .clicked()
+                    {
+                        request = Some(SlotRequest::Load(index));
+                    }
+                    if ui
+                        .add_enabled(
+                            slot.snapshot.is_some(),
+                            egui::Button::new(tr("slots-clear")),
+                        )
+                        .clicked()
+                    {
+                        slot.snapshot = None;
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+
+    if request.is_some() {
+        slots.request = request;
+    }
+    if !open {
+        window.open = false;
+    }
+}
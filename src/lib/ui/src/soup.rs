@@ -0,0 +1,185 @@
+//! # Soup Module
+//!
+//! Panel of the soup search: starts and stops it, shows the census of the
+//! ash and the long-lived soups found, which can be loaded onto the grid or
+//! exported as CSV.
+
+use bevy::prelude::{App, Plugin, ResMut, Resource, warn};
+use bevy::tasks::IoTaskPool;
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{SimulationConfig, tr, tr_args};
+use gol_core::{SoupResult, random_soup};
+use gol_simulation::{BoardHistory, BoardState, HashLifeUniverse, SimulationStats, SoupSearch};
+
+/// Number of census entries listed, the most common first
+const CENSUS_ROWS: usize = 20;
+
+/// Whether the soup search panel is shown
+#[derive(Resource, Default)]
+pub struct SoupPanel {
+    pub open: bool,
+}
+
+/// Plugin for the soup search panel
+pub struct SoupPanelPlugin;
+
+impl Plugin for SoupPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoupPanel>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, soup_panel_system);
+    }
+}
+
+/// Renders the soup search panel while it is open
+#[allow(clippy::too_many_arguments)]
+pub fn soup_panel_system(
+    mut contexts: EguiContexts,
+    mut panel: ResMut<SoupPanel>,
+    mut search: ResMut<SoupSearch>,
+    mut simulation_config: ResMut<SimulationConfig>,
+    mut stats: ResMut<SimulationStats>,
+    mut history: ResMut<BoardHistory>,
+    mut board: ResMut<BoardState>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+) {
+    if !panel.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = true;
+    let mut load = None;
+    egui::Window::new(tr("soup-title"))
+        .id(egui::Id::new("soup_search"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let label = if search.running {
+                    tr("soup-stop")
+                } else {
+                    tr("soup-start")
+                };
+                if ui.button(label).clicked() {
+                    search.running = !search.running;
+                }
+                ui.add_enabled(
+                    !search.running,
+                    egui::DragValue::new(&mut search.next_seed).prefix(tr("soup-seed-prefix")),
+                );
+                if search.is_busy() {
+                    ui.spinner();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut search.max_generations)
+                        .range(100..=1_000_000)
+                        .prefix(tr("soup-give-up")),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut search.methuselah_lifespan)
+                        .range(1..=1_000_000)
+                        .prefix(tr("soup-keep-from")),
+                );
+            });
+            ui.label(tr_args(
+                "soup-progress",
+                &[("soups", &search.soups), ("kept", &search.results.len())],
+            ));
+
+            egui::CollapsingHeader::new(tr("soup-census"))
+                .id_salt("census")
+                .show(ui, |ui| {
+                    let mut census: Vec<(&String, &u64)> = search.census.iter().collect();
+                    census.sort_by(|a, b| b.1.cmp(a.1));
+                    egui::Grid::new("soup_census").striped(true).show(ui, |ui| {
+                        for (name, count) in census.into_iter().take(CENSUS_ROWS) {
+                            ui.label(name);
+                            ui.label(count.to_string());
+                            ui.end_row();
+                        }
+                    });
+                });
+
+            egui::CollapsingHeader::new(tr("soup-results"))
+                .id_salt("results")
+                .default_open(true)
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| load = results_table(ui, &search.results));
+                });
+
+            ui.horizontal(|ui| {
+                let export = egui::Button::new(tr("soup-export"));
+                if ui.add_enabled(!search.results.is_empty(), export).clicked() {
+                    export_csv(search.to_csv());
+                }
+                if ui.button(tr("soup-clear")).clicked() {
+                    search.clear();
+                }
+            });
+        });
+
+    if let Some(seed) = load {
+        simulation_config.running = false;
+        board.0 = random_soup(seed);
+        hashlife.replace(&board.0);
+        stats.reset();
+        history.clear();
+    }
+    if !open {
+        panel.open = false;
+    }
+}
+
+/// Lists the recorded soups, returning the seed of the one to load if any
+fn results_table(ui: &mut egui::Ui, results: &[SoupResult]) -> Option<u64> {
+    let mut load = None;
+    egui::Grid::new("soup_results")
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(tr("soup-seed"));
+            ui.label(tr("soup-lifespan"));
+            ui.label(tr("soup-period"));
+            ui.label(tr("soup-cells"));
+            ui.end_row();
+            for result in results {
+                let period = result
+                    .period
+                    .map_or("-".to_string(), |period| period.to_string());
+                ui.label(result.seed.to_string());
+                ui.label(result.lifespan.to_string());
+                ui.label(period);
+                ui.label(result.final_population.to_string());
+                if ui.button(tr("soup-load")).clicked() {
+                    load = Some(result.seed);
+                }
+                ui.end_row();
+            }
+        });
+    load
+}
+
+/// Asks where to save the results and writes them in the background
+fn export_csv(csv: String) {
+    IoTaskPool::get()
+        .spawn(async move {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .set_title(tr("soup-export-title"))
+                .set_file_name("soups.csv")
+                .add_filter("CSV", &["csv"])
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            if let Err(e) = file.write(csv.as_bytes()).await {
+                warn!("Could not export the soups: {e}");
+            }
+        })
+        .detach();
+}
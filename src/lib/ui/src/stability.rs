@@ -0,0 +1,74 @@
+//! # Stability Module
+//!
+//! Report of the "Run until stable" search: shown while it runs and once it
+//! finished, until dismissed.
+
+use bevy::prelude::{App, Plugin, ResMut};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{tr, tr_args};
+use gol_simulation::{StabilitySearch, StabilityStatus};
+
+/// Plugin for the stability report window
+pub struct StabilityReportPlugin;
+
+impl Plugin for StabilityReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(bevy_egui::EguiPrimaryContextPass, stability_report_system);
+    }
+}
+
+/// Renders the state of the last stability search
+pub fn stability_report_system(mut contexts: EguiContexts, mut search: ResMut<StabilitySearch>) {
+    if search.status == StabilityStatus::Idle {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut close = false;
+    egui::Window::new(tr("stability-title"))
+        .id(egui::Id::new("stability"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 10.0))
+        .show(ctx, |ui| {
+            match search.status {
+                StabilityStatus::Idle => {}
+                StabilityStatus::Searching => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr("stability-searching"));
+                    });
+                }
+                StabilityStatus::Stable { cycle, generation } => {
+                    let kind = match cycle.period {
+                        1 => tr("stability-stable").to_string(),
+                        period => tr_args("stability-cycle", &[("period", &period)]),
+                    };
+                    ui.label(tr_args(
+                        "stability-found",
+                        &[
+                            ("kind", &kind),
+                            ("start", &cycle.start),
+                            ("generation", &generation),
+                        ],
+                    ));
+                    if !cycle.exact {
+                        ui.label(tr("stability-inexact"));
+                    }
+                }
+                StabilityStatus::Unstable { generation } => {
+                    ui.label(tr_args(
+                        "stability-not-found",
+                        &[("generation", &generation)],
+                    ));
+                }
+            }
+            close = search.status != StabilityStatus::Searching
+                && ui.button(tr("common-close")).clicked();
+        });
+    if close {
+        search.status = StabilityStatus::Idle;
+    }
+}
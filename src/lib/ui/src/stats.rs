@@ -0,0 +1,96 @@
+//! # Stats Module
+//!
+//! Window graphing the population, births and deaths over the last
+//! generations. The graph can be paused to look at it closely, zooming and
+//! dragging it around.
+
+use bevy::prelude::{App, Plugin, ResMut, Resource};
+use bevy_egui::{EguiContexts, egui};
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+use gol_config::tr;
+use gol_simulation::{PopulationHistory, PopulationSample};
+
+/// Whether the statistics window is shown
+#[derive(Resource, Default)]
+pub struct StatsWindow {
+    pub open: bool,
+}
+
+/// Plugin for the statistics window
+pub struct StatsWindowPlugin;
+
+impl Plugin for StatsWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StatsWindow>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, stats_window_system);
+    }
+}
+
+/// Renders the statistics window while it is open
+pub fn stats_window_system(
+    mut contexts: EguiContexts,
+    mut window: ResMut<StatsWindow>,
+    mut history: ResMut<PopulationHistory>,
+) {
+    if !window.open {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    let mut open = true;
+    egui::Window::new(tr("stats-title"))
+        .id(egui::Id::new("statistics"))
+        .open(&mut open)
+        .default_width(420.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let label = if history.paused {
+                    tr("stats-resume")
+                } else {
+                    tr("stats-pause")
+                };
+                if ui.button(label).clicked() {
+                    history.paused = !history.paused;
+                }
+                let mut capacity = history.capacity;
+                ui.add(
+                    egui::DragValue::new(&mut capacity)
+                        .range(10..=100_000)
+                        .prefix(tr("stats-last-prefix"))
+                        .suffix(tr("stats-last-suffix")),
+                );
+                if capacity != history.capacity {
+                    history.capacity = capacity;
+                    history.truncate();
+                }
+                if ui.button(tr("stats-clear")).clicked() {
+                    history.clear();
+                }
+            });
+
+            let series = |value: fn(&PopulationSample) -> usize| -> PlotPoints<'static> {
+                history
+                    .samples()
+                    .map(|sample| [sample.generation as f64, value(sample) as f64])
+                    .collect()
+            };
+            Plot::new("population_plot")
+                .legend(Legend::default())
+                .height(220.0)
+                .x_axis_label(tr("stats-generation"))
+                .allow_zoom(true)
+                .allow_drag(true)
+                .show(ui, |plot_ui| {
+                    plot_ui.line(Line::new(tr("stats-population"), series(|s| s.population)));
+                    plot_ui.line(Line::new(tr("stats-births"), series(|s| s.births)));
+                    plot_ui.line(Line::new(tr("stats-deaths"), series(|s| s.deaths)));
+                });
+            ui.weak(tr("stats-plot-help"));
+        });
+
+    if !open {
+        window.open = false;
+    }
+}
@@ -0,0 +1,114 @@
+//! # Status Bar Module
+//!
+//! Thin bar at the bottom of the window showing the cell under the mouse,
+//! the zoom, the population, the generation and the speed actually reached
+//! by the simulation.
+
+use bevy::prelude::{
+    App, Camera, GlobalTransform, Plugin, Projection, Query, Res, ResMut, Resource, Time, Window,
+    With,
+};
+use bevy::window::PrimaryWindow;
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{DEFAULT_SCALE, SimulationBackend, SimulationConfig, tr_args};
+use gol_simulation::{BoardState, SimulationStats};
+
+/// Time over which the generation rate is measured, in seconds
+const RATE_WINDOW: f64 = 1.0;
+
+/// Generations per second actually computed, measured over the last window
+#[derive(Resource, Default)]
+pub struct GenerationRate {
+    /// Start of the current window, in seconds since startup
+    since: f64,
+    /// Generation at the start of the current window
+    generation: u64,
+    /// Rate measured over the previous window
+    pub rate: f64,
+}
+
+impl GenerationRate {
+    /// Counts the generations computed until `now`, closing the window once
+    /// it is over
+    fn update(&mut self, now: f64, generation: u64) {
+        let elapsed = now - self.since;
+        if elapsed < RATE_WINDOW {
+            return;
+        }
+        // The generation restarts from 0 when the board is cleared
+        self.rate = generation.saturating_sub(self.generation) as f64 / elapsed;
+        self.since = now;
+        self.generation = generation;
+    }
+}
+
+/// Plugin for the status bar
+pub struct StatusBarPlugin;
+
+impl Plugin for StatusBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GenerationRate>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, status_bar_system);
+    }
+}
+
+/// Renders the status bar
+#[allow(clippy::too_many_arguments)]
+pub fn status_bar_system(
+    mut contexts: EguiContexts,
+    mut rate: ResMut<GenerationRate>,
+    time: Res<Time>,
+    stats: Res<SimulationStats>,
+    simulation_config: Res<SimulationConfig>,
+    q_windows: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform, &Projection)>,
+    board: Res<BoardState>,
+) {
+    rate.update(time.elapsed_secs_f64(), stats.generation);
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let Ok((camera, camera_transform, projection)) = q_camera.single() else {
+        return;
+    };
+
+    let hovered = q_windows
+        .single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .and_then(|position| camera.viewport_to_world_2d(camera_transform, position).ok());
+    let zoom = match projection {
+        Projection::Orthographic(orthographic) => DEFAULT_SCALE / orthographic.scale * 100.0,
+        _ => 100.0,
+    };
+    // HashLife only mirrors the cells in view
+    let population = match simulation_config.backend {
+        SimulationBackend::HashLife => stats.population,
+        _ => board.0.len(),
+    };
+
+    egui::TopBottomPanel::bottom("status_bar")
+        .exact_height(20.0)
+        .show(ctx, |ui| {
+            ui.horizontal_centered(|ui| {
+                let cell = hovered.map_or("–".to_string(), |world| {
+                    format!("{}, {}", world.x.round() as isize, world.y.round() as isize)
+                });
+                ui.label(tr_args("status-cell", &[("cell", &cell)]));
+                ui.separator();
+                ui.label(tr_args("status-zoom", &[("zoom", &format!("{zoom:.0}"))]));
+                ui.separator();
+                ui.label(tr_args("status-population", &[("population", &population)]));
+                ui.separator();
+                ui.label(tr_args(
+                    "status-generation",
+                    &[("generation", &stats.generation)],
+                ));
+                ui.separator();
+                ui.label(tr_args(
+                    "status-rate",
+                    &[("rate", &format!("{:.1}", rate.rate))],
+                ));
+            });
+        });
+}
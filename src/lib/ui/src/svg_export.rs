@@ -0,0 +1,127 @@
+//! # SVG Export Module
+//!
+//! Writes the cells of the current view to an SVG file, for papers and
+//! slides. The drawing is cropped to the bounding box of the visible cells,
+//! and carries the grid lines when the grid is shown.
+
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource, Update};
+use bevy::tasks::{IoTaskPool, Task, block_on, futures_lite::future};
+use gol_config::{ColorConfig, DisplayConfig, tr, tr_args};
+use gol_core::{Board, Cell};
+use gol_simulation::{BoardState, HashLifeUniverse, SimulationViewport};
+
+use crate::toast::Toasts;
+
+/// Size of a cell in the SVG document, in pixels
+const SVG_CELL_SIZE: isize = 10;
+
+/// SVG export requests from the control panel
+#[derive(Resource, Default)]
+pub struct SvgExport {
+    pub requested: bool,
+    /// Writes the file, `None` when the dialog was cancelled
+    task: Option<Task<Option<Result<(), String>>>>,
+}
+
+/// Plugin for the SVG export
+pub struct SvgExportPlugin;
+
+impl Plugin for SvgExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SvgExport>()
+            .add_systems(Update, svg_export_system);
+    }
+}
+
+/// Draws the visible cells as SVG when requested and saves the document
+pub fn svg_export_system(
+    mut export: ResMut<SvgExport>,
+    board_state: Res<BoardState>,
+    viewport: Res<SimulationViewport>,
+    color_config: Res<ColorConfig>,
+    display_config: Res<DisplayConfig>,
+    mut toasts: ResMut<Toasts>,
+    mut hashlife: ResMut<HashLifeUniverse>,
+) {
+    if std::mem::take(&mut export.requested) && export.task.is_none() {
+        // The HashLife mirror may lag behind a view that just moved
+        let board = hashlife.cells_in(&board_state.0, Some((viewport.min, viewport.max)));
+        match board.bounding_box() {
+            Some(area) => {
+                let svg = board_to_svg(&board, area, &color_config, display_config.grid_visible);
+                export.task = Some(save_svg_file(svg));
+            }
+            None => toasts.warning(tr("svg-export-empty")),
+        }
+    }
+
+    let saved = export
+        .task
+        .as_mut()
+        .and_then(|task| block_on(future::poll_once(task)));
+    if let Some(saved) = saved {
+        export.task = None;
+        match saved {
+            Some(Ok(())) => toasts.info(tr("toast-svg-saved")),
+            Some(Err(e)) => toasts.error(tr_args("toast-svg-failed", &[("error", &e)])),
+            // The dialog was cancelled
+            None => {}
+        }
+    }
+}
+
+/// SVG document of the cells inside the area given by its (min, max)
+/// corners, one unit per cell, optionally with the grid lines
+pub fn board_to_svg(
+    board: &Board,
+    (min, max): (Cell, Cell),
+    colors: &ColorConfig,
+    grid: bool,
+) -> String {
+    let width = max.x - min.x + 1;
+    let height = max.y - min.y + 1;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {width} {height}\" \
+         width=\"{}\" height=\"{}\">\n",
+        width * SVG_CELL_SIZE,
+        height * SVG_CELL_SIZE,
+    );
+    svg.push_str(&format!(
+        "<rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>\n",
+        colors.background_color.to_srgba().to_hex(),
+    ));
+    for cell in board.iter() {
+        // Rows go down the document while y goes up the grid
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>\n",
+            cell.x - min.x,
+            max.y - cell.y,
+            colors.state_color(board.state(cell)).to_srgba().to_hex(),
+        ));
+    }
+    if grid {
+        let columns = (0..=width).map(|x| format!("M{x} 0V{height}"));
+        let rows = (0..=height).map(|y| format!("M0 {y}H{width}"));
+        let path: Vec<String> = columns.chain(rows).collect();
+        svg.push_str(&format!(
+            "<path d=\"{}\" stroke=\"{}\" stroke-width=\"0.05\"/>\n",
+            path.join(""),
+            colors.grid_color.to_srgba().to_hex(),
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Asks where to save the document and writes it in the background
+fn save_svg_file(svg: String) -> Task<Option<Result<(), String>>> {
+    IoTaskPool::get().spawn(async move {
+        let file = rfd::AsyncFileDialog::new()
+            .set_title(tr("svg-export-title"))
+            .set_file_name("view.svg")
+            .add_filter("SVG", &["svg"])
+            .save_file()
+            .await?;
+        Some(file.write(svg.as_bytes()).await.map_err(|e| e.to_string()))
+    })
+}
@@ -0,0 +1,103 @@
+//! # Theme Module
+//!
+//! Applies the egui visuals of the [`Theme`] chosen in the settings. The
+//! colors of the grid are set with the theme, from [`Theme::colors`].
+//!
+//! Also scales the interface by [`DisplayConfig::ui_scale`], for high
+//! resolution screens and readability, and turns the egui animations off
+//! with [`DisplayConfig::reduce_motion`].
+
+use bevy::prelude::{App, Local, Plugin, Query, Res, Update};
+use bevy_egui::{EguiContextSettings, EguiContexts, egui};
+use gol_config::{DisplayConfig, MAX_UI_SCALE, MIN_UI_SCALE, Theme};
+
+/// Plugin applying the interface theme
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(bevy_egui::EguiPrimaryContextPass, apply_theme_system)
+            .add_systems(Update, apply_ui_scale_system);
+    }
+}
+
+/// Sets the egui visuals and animations when the theme or the reduced
+/// motion setting changes
+pub fn apply_theme_system(
+    mut contexts: EguiContexts,
+    display_config: Res<DisplayConfig>,
+    mut applied: Local<Option<(Theme, bool)>>,
+) {
+    let style = (display_config.theme, display_config.reduce_motion);
+    if *applied == Some(style) {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+    let (theme, reduce_motion) = style;
+    ctx.set_visuals(theme_visuals(theme));
+    let animation_time = if reduce_motion {
+        0.0
+    } else {
+        egui::Style::default().animation_time
+    };
+    ctx.style_mut(|style| style.animation_time = animation_time);
+    *applied = Some(style);
+}
+
+/// Scales the egui contexts by the interface scale of the settings, bevy_egui
+/// multiplying it with the scale factor of the window
+pub fn apply_ui_scale_system(
+    display_config: Res<DisplayConfig>,
+    mut context_settings: Query<&mut EguiContextSettings>,
+) {
+    // The configuration file may hold any value
+    let scale = display_config.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    for mut settings in &mut context_settings {
+        if settings.scale_factor != scale {
+            settings.scale_factor = scale;
+        }
+    }
+}
+
+/// egui visuals of a theme
+pub fn theme_visuals(theme: Theme) -> egui::Visuals {
+    match theme {
+        Theme::Classic | Theme::Colorblind => egui::Visuals::light(),
+        Theme::Dark => egui::Visuals::dark(),
+        Theme::Solarized => {
+            let base03 = egui::Color32::from_rgb(0x00, 0x2b, 0x36);
+            let base02 = egui::Color32::from_rgb(0x07, 0x36, 0x42);
+            let mut visuals = egui::Visuals::dark();
+            visuals.panel_fill = base02;
+            visuals.window_fill = base02;
+            visuals.extreme_bg_color = base03;
+            visuals.faint_bg_color = base03;
+            visuals.override_text_color = Some(egui::Color32::from_rgb(0x93, 0xa1, 0xa1));
+            visuals.selection.bg_fill = egui::Color32::from_rgb(0x26, 0x8b, 0xd2);
+            visuals.hyperlink_color = egui::Color32::from_rgb(0x2a, 0xa1, 0x98);
+            visuals
+        }
+        Theme::HighContrast => {
+            let mut visuals = egui::Visuals::dark();
+            visuals.panel_fill = egui::Color32::BLACK;
+            visuals.window_fill = egui::Color32::BLACK;
+            visuals.extreme_bg_color = egui::Color32::BLACK;
+            visuals.override_text_color = Some(egui::Color32::WHITE);
+            visuals.window_stroke = egui::Stroke::new(2.0_f32, egui::Color32::WHITE);
+            visuals.selection.bg_fill = egui::Color32::from_rgb(0, 0, 200);
+            visuals.selection.stroke = egui::Stroke::new(2.0_f32, egui::Color32::YELLOW);
+            for widget in [
+                &mut visuals.widgets.noninteractive,
+                &mut visuals.widgets.inactive,
+                &mut visuals.widgets.hovered,
+                &mut visuals.widgets.active,
+            ] {
+                widget.bg_stroke = egui::Stroke::new(1.0_f32, egui::Color32::WHITE);
+            }
+            visuals.widgets.hovered.bg_stroke = egui::Stroke::new(2.0_f32, egui::Color32::YELLOW);
+            visuals
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! # Timeline Module
+//!
+//! Bar above the status bar for scrubbing through the history: dragging it
+//! shows any recorded generation, the recent ones from the cache and the
+//! older ones replayed from the nearest keyframe.
+
+use bevy::prelude::{App, IntoScheduleConfigs, Plugin, Res, ResMut};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{SimulationBackend, SimulationConfig, tr};
+use gol_simulation::{BoardHistory, SimulationStats};
+
+use crate::status_bar::status_bar_system;
+
+/// Plugin for the timeline
+pub struct TimelinePlugin;
+
+impl Plugin for TimelinePlugin {
+    fn build(&self, app: &mut App) {
+        // Drawn after the status bar, so that it sits above it
+        app.add_systems(
+            bevy_egui::EguiPrimaryContextPass,
+            timeline_system.after(status_bar_system),
+        );
+    }
+}
+
+/// Renders the timeline while some history is recorded
+pub fn timeline_system(
+    mut contexts: EguiContexts,
+    history: Res<BoardHistory>,
+    stats: Res<SimulationStats>,
+    mut simulation_config: ResMut<SimulationConfig>,
+) {
+    // Only the sparse backend records the history
+    if simulation_config.backend != SimulationBackend::Sparse {
+        return;
+    }
+    let Some((oldest, latest)) = history.range() else {
+        return;
+    };
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    // The board on display is one generation past the last snapshot
+    let newest = latest.max(stats.generation);
+    egui::TopBottomPanel::bottom("timeline").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(tr("timeline-label"));
+            ui.label(oldest.to_string());
+            ui.spacing_mut().slider_width = (ui.available_width() - 120.0).max(50.0);
+            let mut generation = stats.generation;
+            let response = ui
+                .add(egui::Slider::new(&mut generation, oldest..=newest).show_value(false))
+                .on_hover_text(tr("timeline-hover"));
+            ui.label(newest.to_string());
+            if response.changed() && generation != stats.generation {
+                simulation_config.scrub_to = Some(generation);
+            }
+        });
+    });
+}
@@ -0,0 +1,132 @@
+//! # Toast Module
+//!
+//! Transient notifications shown in a corner of the window, reporting what
+//! happened after an action: a pattern placed, a session saved, a file that
+//! could not be parsed...
+//!
+//! Warnings and errors are logged as well.
+
+use bevy::prelude::{App, Plugin, Res, ResMut, Resource, Time, info, warn};
+use bevy_egui::{EguiContexts, egui};
+use gol_config::{DisplayConfig, MAX_TOASTS, Seconds, TOAST_DURATION};
+use std::collections::VecDeque;
+
+/// Kind of a notification, giving its color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Notification shown on screen
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub text: String,
+    pub level: ToastLevel,
+    /// Time spent on screen so far
+    pub age: Seconds,
+}
+
+/// Notifications currently shown, oldest first
+#[derive(Resource, Default)]
+pub struct Toasts {
+    toasts: VecDeque<Toast>,
+}
+
+impl Toasts {
+    /// Shows a notification
+    pub fn push(&mut self, level: ToastLevel, text: impl Into<String>) {
+        let text = text.into();
+        match level {
+            ToastLevel::Info => info!("{text}"),
+            ToastLevel::Warning | ToastLevel::Error => warn!("{text}"),
+        }
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            text,
+            level,
+            age: 0.0,
+        });
+    }
+
+    /// Shows an informative notification
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.push(ToastLevel::Info, text);
+    }
+
+    /// Shows a warning
+    pub fn warning(&mut self, text: impl Into<String>) {
+        self.push(ToastLevel::Warning, text);
+    }
+
+    /// Shows an error
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(ToastLevel::Error, text);
+    }
+
+    /// Notifications currently shown, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> + '_ {
+        self.toasts.iter()
+    }
+}
+
+/// Plugin for the notifications
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Toasts>()
+            .add_systems(bevy_egui::EguiPrimaryContextPass, toast_system);
+    }
+}
+
+/// Ages the notifications, dropping the expired ones, and draws the others
+/// in the bottom right corner, fading out at the end of their life unless
+/// motion is reduced
+pub fn toast_system(
+    mut contexts: EguiContexts,
+    mut toasts: ResMut<Toasts>,
+    display_config: Res<DisplayConfig>,
+    time: Res<Time>,
+) {
+    if toasts.toasts.is_empty() {
+        return;
+    }
+    let delta = time.delta_secs();
+    toasts.toasts.retain_mut(|toast| {
+        toast.age += delta;
+        toast.age < TOAST_DURATION
+    });
+    let Ok(ctx) = contexts.ctx_mut() else {
+        return;
+    };
+
+    egui::Area::new(egui::Id::new("toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0))
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .show(ctx, |ui| {
+            for toast in toasts.iter() {
+                let color = match toast.level {
+                    ToastLevel::Info => egui::Color32::from_rgb(40, 40, 40),
+                    ToastLevel::Warning => egui::Color32::from_rgb(180, 110, 0),
+                    ToastLevel::Error => egui::Color32::from_rgb(190, 30, 30),
+                };
+                let opacity = if display_config.reduce_motion {
+                    1.0
+                } else {
+                    ((TOAST_DURATION - toast.age) / 0.5).min(1.0)
+                };
+                egui::Frame::popup(ui.style())
+                    .multiply_with_opacity(opacity)
+                    .show(ui, |ui| {
+                        ui.colored_label(color.gamma_multiply(opacity), &toast.text);
+                    });
+            }
+        });
+    // Keep fading out, or expiring, while nothing else happens
+    ctx.request_repaint();
+}
@@ -0,0 +1,115 @@
+//! # Touch Module
+//!
+//! Touch gestures for tablets and phones: one finger pans the camera, two
+//! fingers pinch to zoom and a short tap toggles the cell under the finger.
+
+use bevy::input::touch::Touches;
+use bevy::prelude::{
+    App, Camera, GlobalTransform, Plugin, Projection, Query, Res, ResMut, Resource, Transform,
+    Update, Vec2,
+};
+use bevy_egui::EguiContexts;
+use gol_config::{CameraConfig, SimulationConfig};
+use gol_core::{Board, Cell};
+use gol_simulation::BoardState;
+
+/// Distance a finger may travel, in pixels, and still count as a tap
+const TAP_SLOP: f32 = 10.0;
+
+/// State of the gesture in progress
+#[derive(Resource, Default)]
+pub struct TouchGesture {
+    /// Whether the gesture moved the camera, so releasing it is not a tap
+    pub moved: bool,
+    /// Whether more than one finger took part in the gesture
+    pub multi_touch: bool,
+}
+
+/// Plugin for touch gestures
+pub struct TouchPlugin;
+
+impl Plugin for TouchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchGesture>()
+            .add_systems(Update, touch_input_system);
+    }
+}
+
+/// Pans, zooms and toggles cells from touch input
+pub fn touch_input_system(
+    touches: Res<Touches>,
+    simulation_config: Res<SimulationConfig>,
+    camera_config: Res<CameraConfig>,
+    mut q_camera: Query<(&Camera, &GlobalTransform, &mut Transform, &mut Projection)>,
+    mut board: ResMut<BoardState>,
+    mut gesture: ResMut<TouchGesture>,
+    mut egui_contexts: EguiContexts,
+) {
+    // Touches on the control windows belong to egui
+    if egui_contexts
+        .ctx_mut()
+        .is_ok_and(|ctx| ctx.wants_pointer_input() || ctx.is_using_pointer())
+    {
+        return;
+    }
+    let Ok((camera, camera_transform, mut transform, mut projection)) = q_camera.single_mut()
+    else {
+        return;
+    };
+    let Projection::Orthographic(orthographic) = projection.as_mut() else {
+        return;
+    };
+
+    let pressed: Vec<_> = touches.iter().collect();
+    match pressed.as_slice() {
+        [touch] => {
+            if touch.distance().length() > TAP_SLOP {
+                gesture.moved = true;
+            }
+            if gesture.moved && !gesture.multi_touch {
+                // Screen y points down while world y points up
+                let delta = touch.delta() * orthographic.scale;
+                transform.translation.x -= delta.x;
+                transform.translation.y += delta.y;
+            }
+        }
+        [first, second, ..] => {
+            gesture.multi_touch = true;
+            gesture.moved = true;
+
+            let previous = first
+                .previous_position()
+                .distance(second.previous_position());
+            let current = first.position().distance(second.position());
+            if current > 0.0 && previous > 0.0 {
+                orthographic.scale =
+                    camera_config.clamp_scale(orthographic.scale * previous / current);
+            }
+
+            let delta = (first.delta() + second.delta()) / 2.0 * orthographic.scale;
+            transform.translation.x -= delta.x;
+            transform.translation.y += delta.y;
+        }
+        [] => {}
+    }
+
+    let is_tap = !gesture.moved && !gesture.multi_touch;
+    if is_tap && !simulation_config.running {
+        for touch in touches.iter_just_released() {
+            if let Ok(world) = camera.viewport_to_world_2d(camera_transform, touch.position()) {
+                toggle_cell(&mut board.0, world.round());
+            }
+        }
+    }
+
+    // The gesture ends once every finger is lifted
+    if pressed.is_empty() {
+        *gesture = TouchGesture::default();
+    }
+}
+
+fn toggle_cell(board: &mut Board, world: Vec2) {
+    let target = Cell::new(world.x as isize, world.y as isize);
+    let alive = board.contains(&target);
+    board.set(target, !alive);
+}
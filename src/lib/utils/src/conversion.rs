@@ -3,25 +3,34 @@
 //! Utility functions for converting between different value ranges,
 //! particularly for UI sliders and simulation parameters.
 
-use gol_config::{DEFAULT_SCALE, MAX_PERIOD, MAX_SCALE, MIN_PERIOD};
+use gol_config::{MAX_PERIOD, MIN_PERIOD};
 
 /// Convert simulation period to slider value (1-100)
+///
+/// The mapping is logarithmic so that high speeds, where many generations
+/// run per frame, keep a usable resolution.
 pub fn period_to_slider(period: f32) -> f32 {
-    (100.0 - 99.0 * (period - MIN_PERIOD) / (MAX_PERIOD - MIN_PERIOD)).clamp(1.0, 100.0)
+    let span = (MAX_PERIOD / MIN_PERIOD).ln();
+    (1.0 + 99.0 * (MAX_PERIOD / period).ln() / span).clamp(1.0, 100.0)
 }
 
 /// Convert slider value (1-100) to simulation period
 pub fn slider_to_period(slider: f32) -> f32 {
-    ((100.0 - slider) * (MAX_PERIOD - MIN_PERIOD) / 99.0 + MIN_PERIOD).clamp(MIN_PERIOD, MAX_PERIOD)
+    let span = (MAX_PERIOD / MIN_PERIOD).ln();
+    (MAX_PERIOD * (-(slider - 1.0) * span / 99.0).exp()).clamp(MIN_PERIOD, MAX_PERIOD)
 }
 
-/// Convert camera scale to slider value (1-100)
-pub fn scale_to_slider(scale: f32) -> f32 {
-    (1.0 + 99.0 * (scale - DEFAULT_SCALE) / (MAX_SCALE - DEFAULT_SCALE)).clamp(1.0, 100.0)
+/// Convert camera scale to slider value (1-100) within the given zoom limits
+///
+/// The mapping is logarithmic so that deep zoom-out stays reachable without
+/// losing precision close to the cells.
+pub fn scale_to_slider(scale: f32, min_scale: f32, max_scale: f32) -> f32 {
+    let span = (max_scale / min_scale).ln();
+    (1.0 + 99.0 * (scale / min_scale).ln() / span).clamp(1.0, 100.0)
 }
 
-/// Convert slider value (1-100) to camera scale
-pub fn slider_to_scale(slider: f32) -> f32 {
-    ((slider - 1.0) * (MAX_SCALE - DEFAULT_SCALE) / 99.0 + DEFAULT_SCALE)
-        .clamp(DEFAULT_SCALE, MAX_SCALE)
+/// Convert slider value (1-100) to camera scale within the given zoom limits
+pub fn slider_to_scale(slider: f32, min_scale: f32, max_scale: f32) -> f32 {
+    let span = (max_scale / min_scale).ln();
+    (min_scale * ((slider - 1.0) * span / 99.0).exp()).clamp(min_scale, max_scale)
 }
@@ -1,41 +1,100 @@
 //! # Diagnostics Module
 //!
 //! FPS display and performance monitoring utilities.
+//!
+//! The simulation statistics are registered as diagnostics too, so they can
+//! be read from the [`DiagnosticsStore`] and logged like the frame time.
 
-use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, DiagnosticsStore};
-use bevy::prelude::{Plugin, App, Update, Res, ButtonInput, KeyCode, ResMut, Query, With};
+use bevy::diagnostic::{
+    Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, FrameTimeDiagnosticsPlugin,
+    RegisterDiagnostic,
+};
+use bevy::prelude::{Plugin, App, Update, Res, ButtonInput, KeyCode, ResMut, DetectChanges};
 use bevy_egui::{EguiContexts, egui};
-use gol_config::FpsConfig;
-use gol_simulation::cell::{Alive, CellPosition};
+use gol_config::{FpsConfig, KeyBindings, SimulationConfig, tr, tr_args};
+use gol_simulation::{BoardState, RuleSet, SimulationStats};
 
 /// Plugin for diagnostic systems
 pub struct DiagnosticsPlugin;
 
+impl DiagnosticsPlugin {
+    /// Number of living cells after the last step
+    pub const POPULATION: DiagnosticPath = DiagnosticPath::const_new("gol/population");
+    /// Cells born during the last step
+    pub const BIRTHS: DiagnosticPath = DiagnosticPath::const_new("gol/births");
+    /// Cells that died during the last step
+    pub const DEATHS: DiagnosticPath = DiagnosticPath::const_new("gol/deaths");
+    /// Time spent computing a generation, in milliseconds
+    pub const GEN_TIME_MS: DiagnosticPath = DiagnosticPath::const_new("gol/gen_time_ms");
+    /// Bitboard chunks stepped during the last generation
+    pub const ACTIVE_CHUNKS: DiagnosticPath = DiagnosticPath::const_new("gol/active_chunks");
+}
+
 impl Plugin for DiagnosticsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(FrameTimeDiagnosticsPlugin::default())
+            .register_diagnostic(Diagnostic::new(Self::POPULATION).with_suffix(" cells"))
+            .register_diagnostic(Diagnostic::new(Self::BIRTHS).with_suffix(" cells"))
+            .register_diagnostic(Diagnostic::new(Self::DEATHS).with_suffix(" cells"))
+            .register_diagnostic(Diagnostic::new(Self::GEN_TIME_MS).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(Self::ACTIVE_CHUNKS).with_suffix(" chunks"))
             .init_resource::<FpsConfig>()
-            .add_systems(Update, toggle_fps_display)
+            .add_systems(Update, (toggle_fps_display, simulation_diagnostics_system))
             .add_systems(bevy_egui::EguiPrimaryContextPass, fps_display_system);
     }
 }
 
-/// Toggle FPS display with F3 key
+/// Toggle FPS display with F3 key, or the key bound to it
 pub fn toggle_fps_display(
     keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     mut fps_config: ResMut<FpsConfig>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::F3) {
+    if keyboard_input.just_pressed(bindings.diagnostics) {
         fps_config.visible = !fps_config.visible;
     }
 }
 
+/// Records the simulation statistics as diagnostics after each step
+pub fn simulation_diagnostics_system(mut diagnostics: Diagnostics, stats: Res<SimulationStats>) {
+    if !stats.is_changed() {
+        return;
+    }
+    diagnostics.add_measurement(&DiagnosticsPlugin::POPULATION, || stats.population as f64);
+    diagnostics.add_measurement(&DiagnosticsPlugin::BIRTHS, || stats.births as f64);
+    diagnostics.add_measurement(&DiagnosticsPlugin::DEATHS, || stats.deaths as f64);
+    diagnostics.add_measurement(&DiagnosticsPlugin::GEN_TIME_MS, || {
+        stats.step_time.as_secs_f64() * 1000.0
+    });
+    if let Some(active_chunks) = stats.active_chunks {
+        diagnostics.add_measurement(&DiagnosticsPlugin::ACTIVE_CHUNKS, || active_chunks as f64);
+    }
+}
+
+/// Smoothed and highest recorded generation times, in milliseconds
+fn generation_time(diagnostics: &DiagnosticsStore) -> Option<(f64, f64)> {
+    let diagnostic = diagnostics.get(&DiagnosticsPlugin::GEN_TIME_MS)?;
+    let max = diagnostic.values().copied().reduce(f64::max)?;
+    Some((diagnostic.smoothed()?, max))
+}
+
+/// Latest value of a diagnostic, formatted with the given precision
+fn latest(diagnostics: &DiagnosticsStore, path: &DiagnosticPath, precision: usize) -> String {
+    diagnostics
+        .get(path)
+        .and_then(|diagnostic| diagnostic.value())
+        .map_or(tr("diagnostics-not-available").to_string(), |value| format!("{value:.precision$}"))
+}
+
 /// System to display FPS and live cell count in an egui window
 pub fn fps_display_system(
     mut contexts: EguiContexts,
     diagnostics: Res<DiagnosticsStore>,
     fps_config: Res<FpsConfig>,
-    alive_cells_query: Query<&CellPosition, With<Alive>>,
+    stats: Res<SimulationStats>,
+    rule: Res<RuleSet>,
+    simulation_config: Res<SimulationConfig>,
+    board: Res<BoardState>,
 ) {
     if !fps_config.visible {
         return;
@@ -49,21 +108,66 @@ pub fn fps_display_system(
         if let Some(value) = fps.smoothed() {
             format!("{:.2}", value)
         } else {
-            "N/A".to_string()
+            tr("diagnostics-not-available").to_string()
         }
     } else {
-        "N/A".to_string()
+        tr("diagnostics-not-available").to_string()
     };
 
-    let alive_count = alive_cells_query.iter().count();
+    let alive_count = board.0.len();
 
-    egui::Window::new("Diagnostics")
+    egui::Window::new(tr("diagnostics-title"))
+        .id(egui::Id::new("diagnostics"))
         .resizable(false)
         .collapsible(false)
         .anchor(egui::Align2::RIGHT_TOP, egui::Vec2::new(-10.00, 10.0))
         .show(ctx, |ui| {
-            ui.label(format!("FPS: {}", fps_value));
-            ui.label(format!("Cellules vivantes: {}", alive_count));
+            ui.label(tr_args("diagnostics-fps", &[("fps", &fps_value)]));
+            ui.label(tr_args("diagnostics-alive-cells", &[("count", &alive_count)]));
+            ui.label(tr_args("diagnostics-generation", &[("generation", &stats.generation)]));
+            match rule.preset_name() {
+                Some(name) => ui.label(tr_args(
+                    "controls-rule-preset",
+                    &[("rule", &*rule), ("name", &name)],
+                )),
+                None => ui.label(tr_args("controls-rule", &[("rule", &*rule)])),
+            };
+            ui.label(tr_args(
+                "diagnostics-population",
+                &[("population", &latest(&diagnostics, &DiagnosticsPlugin::POPULATION, 0))],
+            ));
+            ui.label(tr_args(
+                "diagnostics-births-deaths",
+                &[
+                    ("births", &latest(&diagnostics, &DiagnosticsPlugin::BIRTHS, 0)),
+                    ("deaths", &latest(&diagnostics, &DiagnosticsPlugin::DEATHS, 0)),
+                ],
+            ));
+            // Only rules stepped on a bitboard skip stable chunks
+            if let Some(active_chunks) = stats.active_chunks {
+                ui.label(tr_args("diagnostics-active-chunks", &[("count", &active_chunks)]));
+            }
+            match generation_time(&diagnostics) {
+                Some((smoothed, max)) => {
+                    ui.label(tr_args(
+                        "diagnostics-generation-time",
+                        &[
+                            ("time", &format!("{smoothed:.3}")),
+                            ("max", &format!("{max:.3}")),
+                        ],
+                    ));
+                    // Generations taking longer than their period slow the simulation down
+                    let period = simulation_config.period.as_secs_f64() * 1000.0;
+                    if simulation_config.running && smoothed > period {
+                        ui.colored_label(egui::Color32::DARK_RED, tr("diagnostics-simulation-bound"));
+                    } else {
+                        ui.label(tr("diagnostics-render-bound"));
+                    }
+                }
+                None => {
+                    ui.label(tr("diagnostics-generation-time-unknown"));
+                }
+            }
 
             // if let Some(frame_time) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) {
             //     if let Some(value) = frame_time.smoothed() {
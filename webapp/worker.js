@@ -0,0 +1,12 @@
+// Simulation worker: runs the same module as the page, whose main function
+// answers the stepping requests of the page instead of starting the game.
+import init from "./jeu_de_la_vie.js";
+
+// Requests posted while the module loads are replayed once it handles them
+const pending = [];
+self.onmessage = (event) => pending.push(event);
+
+await init();
+for (const event of pending) {
+    self.onmessage(event);
+}